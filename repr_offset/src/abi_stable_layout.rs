@@ -0,0 +1,262 @@
+//! Functions for checking the offsets of fields against the layout that
+//! [`abi_stable`]'s `StableAbi` trait describes.
+//!
+//! This is for types that derive both `StableAbi` and [`ReprOffset`],
+//! to double check that the offsets that `repr_offset` uses agree with
+//! the sizes and alignments of the fields that `abi_stable` checks at load time.
+//!
+//! `abi_stable` layouts don't store the offsets of fields,
+//! so the expected offsets are calculated from the size and alignment
+//! of every field in the layout, following the rules of the type's `#[repr]` attribute.
+//!
+//! [`abi_stable`]: https://docs.rs/abi_stable/0.11
+//! [`ReprOffset`]: ../derive.ReprOffset.html
+
+use crate::{offset_calc::GetNextFieldOffset, FieldOffsetDyn};
+
+use abi_stable::{
+    type_layout::{ReprAttr, TLData},
+    StableAbi,
+};
+
+use core::fmt::{self, Display};
+
+/// The error returned by [`compare_field_offsets`].
+///
+/// [`compare_field_offsets`]: ./fn.compare_field_offsets.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LayoutMismatch {
+    /// The type isn't a `#[repr(C)]` or `#[repr(transparent)]` struct,
+    /// so the offsets of its fields can't be calculated from its layout.
+    UnsupportedLayout {
+        /// The name of the type.
+        type_name: &'static str,
+    },
+    /// There's no offset for a field that's in the layout.
+    MissingField {
+        /// The name of the field.
+        field: &'static str,
+    },
+    /// The offset of a field isn't the one calculated from the layout.
+    Offset {
+        /// The name of the field.
+        field: &'static str,
+        /// The offset calculated from the layout.
+        expected: usize,
+        /// The offset that `repr_offset` uses.
+        found: usize,
+    },
+    /// The size of a field isn't the one in the layout.
+    Size {
+        /// The name of the field.
+        field: &'static str,
+        /// The size of the field in the layout.
+        expected: usize,
+        /// The size of the field that `repr_offset` uses.
+        found: usize,
+    },
+}
+
+impl Display for LayoutMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            LayoutMismatch::UnsupportedLayout { type_name } => write!(
+                f,
+                "`{}` is not a `#[repr(C)]` or `#[repr(transparent)]` struct",
+                type_name,
+            ),
+            LayoutMismatch::MissingField { field } => {
+                write!(f, "there is no offset for the `{}` field", field)
+            }
+            LayoutMismatch::Offset {
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected the `{}` field to be at offset {}, found offset {}",
+                field, expected, found,
+            ),
+            LayoutMismatch::Size {
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected the `{}` field to be {} bytes large, found {} bytes",
+                field, expected, found,
+            ),
+        }
+    }
+}
+
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl std::error::Error for LayoutMismatch {}
+
+/// Compares the offsets of the fields of `S` that `offsets` returns
+/// with the ones calculated from the `abi_stable` layout of `S`.
+///
+/// `offsets` is called with the name of every field in the layout.
+/// `abi_stable` names tuple struct fields like `field_0`,
+/// so if `offsets` returns `None` for one of those names,
+/// it's called again with the position of the field, eg: `"0"`.
+///
+/// # Errors
+///
+/// This returns an error if `S` isn't a `#[repr(C)]` or `#[repr(transparent)]` struct,
+/// if `offsets` returns `None` for any field,
+/// or if the offset or size of any field doesn't match the layout.
+///
+/// # Example
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+#[cfg_attr(feature = "derive", doc = "use repr_offset::ReprOffset;")]
+#[cfg_attr(not(feature = "derive"), doc = "use repr_offset_derive::ReprOffset;")]
+/// use repr_offset::{
+///     abi_stable_layout::{compare_field_offsets, LayoutMismatch},
+///     Aligned, FieldOffset, FieldOffsetDyn,
+/// };
+///
+/// use abi_stable::StableAbi;
+///
+/// #[repr(C)]
+/// #[derive(StableAbi, ReprOffset)]
+/// pub struct Header {
+///     pub tag: u8,
+///     pub len: u32,
+///     pub flags: u16,
+/// }
+///
+/// let offsets = |name: &str| match name {
+///     "tag" => Some(Header::OFFSET_TAG.to_dyn()),
+///     "len" => Some(Header::OFFSET_LEN.to_dyn()),
+///     "flags" => Some(Header::OFFSET_FLAGS.to_dyn()),
+///     _ => None,
+/// };
+///
+/// assert_eq!( compare_field_offsets(offsets), Ok(()) );
+///
+/// // Offsets that don't agree with the layout are reported.
+/// let wrong_len: FieldOffsetDyn<Header> =
+///     unsafe{ FieldOffset::<Header, u32, Aligned>::new(1) }.to_dyn();
+///
+/// assert_eq!(
+///     compare_field_offsets(|name| match name {
+///         "len" => Some(wrong_len),
+///         _ => offsets(name),
+///     }),
+///     Err(LayoutMismatch::Offset{ field: "len", expected: 4, found: 1 }),
+/// );
+///
+/// ```
+pub fn compare_field_offsets<S, G>(mut offsets: G) -> Result<(), LayoutMismatch>
+where
+    S: StableAbi,
+    G: FnMut(&str) -> Option<FieldOffsetDyn<S>>,
+{
+    let layout = S::LAYOUT;
+
+    let unsupported = LayoutMismatch::UnsupportedLayout {
+        type_name: layout.name(),
+    };
+
+    let fields = match layout.data() {
+        TLData::Struct { fields } => fields,
+        _ => return Err(unsupported),
+    };
+
+    let is_transparent = match layout.repr_attr() {
+        ReprAttr::C => false,
+        ReprAttr::Transparent => true,
+        _ => return Err(unsupported),
+    };
+
+    // The offset and size of the previous field.
+    let mut previous: Option<(usize, usize)> = None;
+
+    for field in fields.iter() {
+        let field_layout = field.layout();
+
+        let expected_offset = match previous {
+            Some((previous_offset, previous_size)) if !is_transparent => GetNextFieldOffset {
+                previous_offset,
+                previous_size,
+                container_alignment: layout.alignment(),
+                next_alignment: field_layout.alignment(),
+            }
+            .call(),
+            _ => 0,
+        };
+
+        let name = field.name();
+        let offset = offsets(name)
+            .or_else(|| offsets(name.strip_prefix("field_")?))
+            .ok_or(LayoutMismatch::MissingField { field: name })?;
+
+        if offset.offset() != expected_offset {
+            return Err(LayoutMismatch::Offset {
+                field: name,
+                expected: expected_offset,
+                found: offset.offset(),
+            });
+        }
+
+        if offset.size() != field_layout.size() {
+            return Err(LayoutMismatch::Size {
+                field: name,
+                expected: field_layout.size(),
+                found: offset.size(),
+            });
+        }
+
+        previous = Some((expected_offset, field_layout.size()));
+    }
+
+    Ok(())
+}
+
+/// Asserts that the offsets of the fields of `S` that `offsets` returns
+/// agree with the `abi_stable` layout of `S`.
+///
+/// This is [`compare_field_offsets`] panicking on errors,
+/// for use in tests.
+///
+/// # Panics
+///
+/// This panics in the same situations that [`compare_field_offsets`] returns an error.
+///
+/// # Example
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+#[cfg_attr(feature = "derive", doc = "use repr_offset::ReprOffset;")]
+#[cfg_attr(not(feature = "derive"), doc = "use repr_offset_derive::ReprOffset;")]
+/// use repr_offset::abi_stable_layout::assert_field_offsets;
+///
+/// use abi_stable::StableAbi;
+///
+/// #[repr(C)]
+/// #[derive(StableAbi, ReprOffset)]
+/// pub struct Rgb(pub u8, pub u16, pub u8);
+///
+/// assert_field_offsets(|name| match name {
+///     "0" => Some(Rgb::OFFSET_0.to_dyn()),
+///     "1" => Some(Rgb::OFFSET_1.to_dyn()),
+///     "2" => Some(Rgb::OFFSET_2.to_dyn()),
+///     _ => None,
+/// });
+///
+/// ```
+///
+/// [`compare_field_offsets`]: ./fn.compare_field_offsets.html
+pub fn assert_field_offsets<S, G>(offsets: G)
+where
+    S: StableAbi,
+    G: FnMut(&str) -> Option<FieldOffsetDyn<S>>,
+{
+    if let Err(e) = compare_field_offsets(offsets) {
+        panic!("the field offsets don't match the abi_stable layout: {}", e);
+    }
+}
@@ -23,10 +23,67 @@ use self::sealed::Sealed;
 ///
 /// [`Aligned`]:  ./struct.Aligned.html
 /// [`Unaligned`]: ./struct.Unaligned.html
-pub trait Alignment: Sealed {}
+pub trait Alignment:
+    Sealed + CombineAlignment<Aligned, Output = Self> + CombineAlignment<Unaligned, Output = Unaligned>
+{
+    /// Whether this is [`Aligned`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use repr_offset::{alignment::Alignment, Aligned, Unaligned};
+    ///
+    /// const ALIGNED: bool = <Aligned as Alignment>::IS_ALIGNED;
+    /// const UNALIGNED: bool = <Unaligned as Alignment>::IS_ALIGNED;
+    ///
+    /// assert!( ALIGNED );
+    /// assert!( !UNALIGNED );
+    ///
+    /// ```
+    ///
+    /// [`Aligned`]:  ./struct.Aligned.html
+    const IS_ALIGNED: bool;
+}
+
+impl Alignment for Aligned {
+    const IS_ALIGNED: bool = true;
+}
+impl Alignment for Unaligned {
+    const IS_ALIGNED: bool = false;
+}
+
+mod align_one {
+    pub trait Sealed {}
+}
 
-impl Alignment for Aligned {}
-impl Alignment for Unaligned {}
+/// Marker trait for types with an alignment of 1,
+/// fields of these types are always aligned, even inside of packed structs.
+///
+/// This trait is sealed, it can't be implemented outside of `repr_offset`.
+///
+/// This is used by the [`FieldOffset::to_aligned_if_align_one`] method.
+///
+/// [`FieldOffset::to_aligned_if_align_one`]:
+/// ../struct.FieldOffset.html#method.to_aligned_if_align_one
+pub trait AlignOne: align_one::Sealed {}
+
+macro_rules! impl_align_one {
+    ($($ty:ty)*) => {
+        $(
+            impl align_one::Sealed for $ty {}
+            impl AlignOne for $ty {}
+        )*
+    };
+}
+
+impl_align_one! {u8 i8 bool}
+
+#[cfg(feature = "min_const_generics")]
+impl<T: AlignOne, const N: usize> align_one::Sealed for [T; N] {}
+
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "min_const_generics")))]
+#[cfg(feature = "min_const_generics")]
+impl<T: AlignOne, const N: usize> AlignOne for [T; N] {}
 
 /// Combines two [`Alignment`] types,
 /// determines the return type of `FieldOffset + FieldOffset`.
@@ -56,38 +113,39 @@ impl<A: Alignment> CombineAlignment<A> for Unaligned {
     type Output = Unaligned;
 }
 
+impl<Carry: Alignment> CombineAlignment<Carry> for () {
+    type Output = Carry;
+}
+
+// These impls are generic over the alignment of every element,
+// so that tuples containing type parameters bounded by `Alignment` can be combined,
+// eg: when getting the offset of a nested field in a field whose type is a type parameter.
 macro_rules! tuple_impls {
-    (small=> $ty:ty = $output:ty ) => {
-        impl<Carry: Alignment> CombineAlignment<Carry> for $ty {
-            type Output = $output;
-        }
-    };
-    (large=>
-        $( ($t0:ident,$t1:ident,$t2:ident,$t3:ident,), )*
-        $($trailing:ident,)*
-    )=>{
-        #[allow(non_camel_case_types)]
-        impl<A: Alignment, $($t0,$t1,$t2,$t3,)* $($trailing,)* CombTuples >
-            CombineAlignment<A>
-        for ($($t0,$t1,$t2,$t3,)* $($trailing,)*)
-        where
-            ($($trailing,)*): CombineAlignment<A>,
-            $( ($t0,$t1,$t2,$t3): CombineAlignment<Aligned>, )*
-            (
-                $( CombineAlignmentOut<($t0,$t1,$t2,$t3), Aligned>, )*
-            ):CombineAlignment<
-                CombineAlignmentOut<($($trailing,)*), A>,
-                Output = CombTuples,
-            >,
-            CombTuples: Alignment,
-        {
-            type Output = CombTuples;
-        }
+    ($( ($first:ident $(,$rest:ident)*) )*) => {
+        $(
+            impl<Carry, $first, $($rest,)*> CombineAlignment<Carry> for ($first, $($rest,)*)
+            where
+                Carry: Alignment,
+                ($($rest,)*): CombineAlignment<Carry>,
+                $first: CombineAlignment<CombineAlignmentOut<($($rest,)*), Carry>>,
+            {
+                type Output = CombineAlignmentOut<$first, CombineAlignmentOut<($($rest,)*), Carry>>;
+            }
+        )*
     };
 }
 
-impl_all_trait_for_tuples! {
-    macro = tuple_impls,
-    true = Aligned,
-    false = Unaligned,
+tuple_impls! {
+    (A0)
+    (A0, A1)
+    (A0, A1, A2)
+    (A0, A1, A2, A3)
+    (A0, A1, A2, A3, A4)
+    (A0, A1, A2, A3, A4, A5)
+    (A0, A1, A2, A3, A4, A5, A6)
+    (A0, A1, A2, A3, A4, A5, A6, A7)
+    (A0, A1, A2, A3, A4, A5, A6, A7, A8)
+    (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9)
+    (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10)
+    (A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11)
 }
@@ -200,6 +200,66 @@ pub unsafe trait ROExtAcc: Sized {
     ///
     fn f_get_mut<F>(&mut self, offset: FieldOffset<Self, F, Aligned>) -> &mut F;
 
+    /// Gets a mutable reference to the field determined by `write_offset`,
+    /// and a copy of the field determined by `read_offset`.
+    ///
+    /// This is for updating a field based on the value of another field,
+    /// which can't be done by calling [`f_get_mut`](#tymethod.f_get_mut)
+    /// and [`f_get`](#tymethod.f_get) while the mutable reference is alive.
+    ///
+    /// The field is copied before the mutable reference is created,
+    /// so this is sound even if the fields overlap.
+    ///
+    /// # Panics
+    ///
+    /// With debug assertions enabled,
+    /// this panics if the fields overlap,
+    /// since that's likely a mistake in passing the offsets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{
+    ///     for_examples::ReprC,
+    ///     Aligned, FieldOffset, ROExtAcc,
+    /// };
+    ///
+    /// type Stats = ReprC<u64, u32, u32, ()>;
+    ///
+    /// let mut stats: Stats = ReprC{ a: 0, b: 0, c: 0, d: () };
+    ///
+    /// for value in [3, 5, 8].iter().copied() {
+    ///     stats.c = value;
+    ///     accumulate(&mut stats, Stats::OFFSET_A, Stats::OFFSET_C);
+    ///     accumulate(&mut stats, Stats::OFFSET_B, Stats::OFFSET_C);
+    /// }
+    ///
+    /// assert_eq!(stats.a, 16);
+    /// assert_eq!(stats.b, 16);
+    ///
+    /// fn accumulate<S, F>(
+    ///     this: &mut S,
+    ///     sum: FieldOffset<S, F, Aligned>,
+    ///     value: FieldOffset<S, u32, Aligned>,
+    /// )
+    /// where
+    ///     F: std::ops::AddAssign<F> + From<u32>,
+    /// {
+    ///     let (sum, value) = this.f_get_mut_and_read(sum, value);
+    ///     *sum += F::from(value);
+    /// }
+    ///
+    /// ```
+    ///
+    fn f_get_mut_and_read<F1, F2>(
+        &mut self,
+        write_offset: FieldOffset<Self, F1, Aligned>,
+        read_offset: FieldOffset<Self, F2, Aligned>,
+    ) -> (&mut F1, F2)
+    where
+        F2: Copy;
+
     /// Gets a const pointer to a field,
     /// the field is determined by `offset`.
     ///
@@ -402,6 +462,76 @@ pub unsafe trait ROExtOps<A>: ROExtAcc {
     fn f_get_copy<F>(&self, offset: FieldOffset<Self, F, A>) -> F
     where
         F: Copy;
+
+    /// Pushes `value` to the end of a `Vec` field (determined by `offset`).
+    ///
+    /// For [`Unaligned`] fields, this moves the `Vec` out of the field,
+    /// pushes to it, and then moves it back into the field,
+    /// leaving an empty `Vec` in the field if pushing panics.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{
+    ///     for_examples::ReprPacked,
+    ///     utils::moved,
+    ///     ROExtOps, off,
+    /// };
+    ///
+    /// let mut value = ReprPacked {
+    ///     a: 3u8,
+    ///     b: vec![5, 8],
+    ///     c: vec!["foo"],
+    ///     d: (),
+    /// };
+    ///
+    /// value.f_vec_push(off!(b), 13);
+    /// value.f_vec_push(off!(c), "bar");
+    ///
+    /// assert_eq!(moved(value.b), vec![5, 8, 13]);
+    /// assert_eq!(moved(value.c), vec!["foo", "bar"]);
+    ///
+    /// ```
+    ///
+    /// [`Unaligned`]: ../alignment/struct.Unaligned.html
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+    fn f_vec_push<T>(&mut self, offset: FieldOffset<Self, std::vec::Vec<T>, A>, value: T);
+
+    /// Removes the last element of a `Vec` field (determined by `offset`),
+    /// returning it, or `None` if the `Vec` is empty.
+    ///
+    /// For [`Unaligned`] fields, this moves the `Vec` out of the field,
+    /// pops from it, and then moves it back into the field.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{
+    ///     for_examples::ReprC,
+    ///     ROExtOps, off,
+    /// };
+    ///
+    /// let mut value = ReprC {
+    ///     a: vec![3, 5],
+    ///     b: Vec::<char>::new(),
+    ///     c: (),
+    ///     d: (),
+    /// };
+    ///
+    /// assert_eq!(value.f_vec_pop(off!(a)), Some(5));
+    /// assert_eq!(value.f_vec_pop(off!(a)), Some(3));
+    /// assert_eq!(value.f_vec_pop(off!(a)), None);
+    /// assert_eq!(value.f_vec_pop(off!(b)), None);
+    ///
+    /// ```
+    ///
+    /// [`Unaligned`]: ../alignment/struct.Unaligned.html
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+    fn f_vec_pop<T>(&mut self, offset: FieldOffset<Self, std::vec::Vec<T>, A>) -> Option<T>;
 }
 
 /////////////////////////////////////////////////////////////////////////////////
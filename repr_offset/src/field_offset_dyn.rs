@@ -0,0 +1,277 @@
+//! A type-erased version of `FieldOffset`.
+
+use crate::FieldOffset;
+
+use core::{
+    any::TypeId,
+    cmp::Ordering,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem,
+};
+
+/// The offset of a field inside the `S` struct, with the type of the field erased.
+///
+/// This stores the offset and size of the field,
+/// which allows offsets for fields of different types to be stored together,
+/// eg: in an array, or returned from a `match` on a field name.
+///
+/// This can be constructed with [`FieldOffset::to_dyn`],
+/// or with the `From<FieldOffset<S, F, A>>` impl.
+///
+/// Constructing it with [`FieldOffset::to_dyn_typed`] also stores the
+/// `TypeId` of the field type and alignment,
+/// which allows converting it back to a `FieldOffset` with the [`downcast`] method.
+///
+/// # Example
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::{
+///     for_examples::{ReprC, ReprPacked},
+///     FieldOffsetDyn,
+/// };
+///
+/// type This = ReprC<u8, u32, u16, u64>;
+///
+/// let offsets: [FieldOffsetDyn<This>; 4] = [
+///     This::OFFSET_A.to_dyn(),
+///     This::OFFSET_B.to_dyn(),
+///     This::OFFSET_C.to_dyn(),
+///     This::OFFSET_D.to_dyn(),
+/// ];
+///
+/// assert_eq!( offsets.iter().map(|x| x.offset() ).collect::<Vec<_>>(), [0, 4, 8, 16] );
+/// assert_eq!( offsets.iter().map(|x| x.size() ).collect::<Vec<_>>(), [1, 4, 2, 8] );
+///
+///
+/// type Packed = ReprPacked<u8, u32, u16, u64>;
+///
+/// let offsets: [FieldOffsetDyn<Packed>; 4] = [
+///     Packed::OFFSET_A.into(),
+///     Packed::OFFSET_B.into(),
+///     Packed::OFFSET_C.into(),
+///     Packed::OFFSET_D.into(),
+/// ];
+///
+/// assert_eq!( offsets.iter().map(|x| x.offset() ).collect::<Vec<_>>(), [0, 1, 5, 7] );
+/// assert_eq!( offsets.iter().map(|x| x.size() ).collect::<Vec<_>>(), [1, 4, 2, 8] );
+///
+/// ```
+///
+/// [`FieldOffset::to_dyn`]: ./struct.FieldOffset.html#method.to_dyn
+/// [`FieldOffset::to_dyn_typed`]: ./struct.FieldOffset.html#method.to_dyn_typed
+/// [`downcast`]: #method.downcast
+pub struct FieldOffsetDyn<S> {
+    offset: usize,
+    size: usize,
+    // The `TypeId` of `(F, A)`,
+    // `None` if this was constructed from a `FieldOffset` without `'static` bounds.
+    type_id: Option<TypeId>,
+    struct_: PhantomData<fn() -> S>,
+}
+
+impl<S> FieldOffsetDyn<S> {
+    #[inline(always)]
+    pub(crate) const fn from_offset<F, A>(offset: FieldOffset<S, F, A>) -> Self {
+        Self {
+            offset: offset.offset(),
+            size: mem::size_of::<F>(),
+            type_id: None,
+            struct_: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn from_offset_typed<F, A>(offset: FieldOffset<S, F, A>) -> Self
+    where
+        F: 'static,
+        A: 'static,
+    {
+        Self {
+            type_id: Some(TypeId::of::<(F, A)>()),
+            ..Self::from_offset(offset)
+        }
+    }
+
+    /// The offset (in bytes) of the field in the `S` struct.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// type This = ReprC<u8, u16, u32, u64>;
+    ///
+    /// assert_eq!( This::OFFSET_A.to_dyn().offset(), 0 );
+    /// assert_eq!( This::OFFSET_B.to_dyn().offset(), 2 );
+    /// assert_eq!( This::OFFSET_C.to_dyn().offset(), 4 );
+    /// assert_eq!( This::OFFSET_D.to_dyn().offset(), 8 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub const fn offset(self) -> usize {
+        self.offset
+    }
+
+    /// The size (in bytes) of the field.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// type This = ReprPacked<u8, u16, [u8; 5], ()>;
+    ///
+    /// assert_eq!( This::OFFSET_A.to_dyn().size(), 1 );
+    /// assert_eq!( This::OFFSET_B.to_dyn().size(), 2 );
+    /// assert_eq!( This::OFFSET_C.to_dyn().size(), 5 );
+    /// assert_eq!( This::OFFSET_D.to_dyn().size(), 0 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub const fn size(self) -> usize {
+        self.size
+    }
+
+    /// Whether this stores the type of the field,
+    /// which is only true when constructed with [`FieldOffset::to_dyn_typed`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// type This = ReprC<u8, u16, u32, u64>;
+    ///
+    /// assert!( This::OFFSET_A.to_dyn_typed().is_typed() );
+    /// assert!( !This::OFFSET_A.to_dyn().is_typed() );
+    ///
+    /// ```
+    ///
+    /// [`FieldOffset::to_dyn_typed`]: ./struct.FieldOffset.html#method.to_dyn_typed
+    #[inline(always)]
+    pub fn is_typed(self) -> bool {
+        self.type_id.is_some()
+    }
+
+    /// Converts this back into a `FieldOffset`,
+    /// returning `None` if `F` and `A` aren't the field type and alignment
+    /// that this was constructed from.
+    ///
+    /// This always returns `None` if this wasn't constructed with
+    /// [`FieldOffset::to_dyn_typed`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{
+    ///     for_examples::{ReprC, ReprPacked},
+    ///     Aligned, FieldOffsetDyn, Unaligned,
+    /// };
+    ///
+    /// type This = ReprC<u8, u32, &'static str, ()>;
+    ///
+    /// let this = ReprC{ a: 3, b: 5, c: "eight", d: () };
+    ///
+    /// let offsets: [FieldOffsetDyn<This>; 3] = [
+    ///     This::OFFSET_A.to_dyn_typed(),
+    ///     This::OFFSET_B.to_dyn_typed(),
+    ///     This::OFFSET_C.to_dyn_typed(),
+    /// ];
+    ///
+    /// let strs = offsets
+    ///     .iter()
+    ///     .filter_map(|offset| offset.downcast::<&'static str, Aligned>() )
+    ///     .map(|offset| *offset.get(&this) )
+    ///     .collect::<Vec<&str>>();
+    ///
+    /// assert_eq!( strs, ["eight"] );
+    ///
+    /// // The field type must be the same
+    /// assert_eq!( offsets[1].downcast::<u32, Aligned>(), Some(This::OFFSET_B) );
+    /// assert_eq!( offsets[1].downcast::<i32, Aligned>(), None );
+    ///
+    /// // The alignment must be the same
+    /// assert_eq!( offsets[1].downcast::<u32, Unaligned>(), None );
+    ///
+    ///
+    /// type Packed = ReprPacked<u8, u32, (), ()>;
+    ///
+    /// let offset_b: FieldOffsetDyn<Packed> = Packed::OFFSET_B.to_dyn_typed();
+    /// assert_eq!( offset_b.downcast::<u32, Unaligned>(), Some(Packed::OFFSET_B) );
+    /// assert_eq!( offset_b.downcast::<u32, Aligned>(), None );
+    ///
+    /// ```
+    ///
+    /// [`FieldOffset::to_dyn_typed`]: ./struct.FieldOffset.html#method.to_dyn_typed
+    pub fn downcast<F, A>(self) -> Option<FieldOffset<S, F, A>>
+    where
+        F: 'static,
+        A: 'static,
+    {
+        if self.type_id == Some(TypeId::of::<(F, A)>()) {
+            // safety: this was constructed from a `FieldOffset<S, F, A>`
+            unsafe { Some(FieldOffset::new(self.offset)) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<S, F, A> From<FieldOffset<S, F, A>> for FieldOffsetDyn<S> {
+    #[inline(always)]
+    fn from(offset: FieldOffset<S, F, A>) -> Self {
+        Self::from_offset(offset)
+    }
+}
+
+impl<S> Debug for FieldOffsetDyn<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldOffsetDyn")
+            .field("offset", &self.offset)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl<S> Copy for FieldOffsetDyn<S> {}
+
+impl<S> Clone for FieldOffsetDyn<S> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> PartialEq for FieldOffsetDyn<S> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.offset, self.size) == (other.offset, other.size)
+    }
+}
+
+impl<S> Eq for FieldOffsetDyn<S> {}
+
+impl<S> PartialOrd for FieldOffsetDyn<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for FieldOffsetDyn<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.offset, self.size).cmp(&(other.offset, other.size))
+    }
+}
+
+impl<S> Hash for FieldOffsetDyn<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.offset.hash(state);
+        self.size.hash(state);
+    }
+}
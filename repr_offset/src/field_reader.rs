@@ -0,0 +1,225 @@
+//! A `FieldOffset` paired with a pointer to the struct, for reading the field.
+
+use crate::{Aligned, FieldOffset, Unaligned};
+
+use core::fmt::{self, Debug};
+
+/// A [`FieldOffset`] together with a pointer to the `S` struct,
+/// to read the field with the [`copy`] and [`read`] methods,
+/// or get a pointer to it with the [`ptr`] method.
+///
+/// This is constructed with the [`FieldOffset::at`] method.
+///
+/// # Example
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::for_examples::{ReprC, ReprPacked};
+///
+/// use std::mem::ManuallyDrop;
+///
+/// let this = ReprC{ a: 3u8, b: 5u32, c: (), d: () };
+/// let packed = ManuallyDrop::new(ReprPacked{ a: 8u8, b: "13".to_string(), c: (), d: () });
+///
+/// unsafe{
+///     assert_eq!( ReprC::OFFSET_A.at(&this).copy(), 3 );
+///     assert_eq!( ReprC::OFFSET_B.at(&this).copy(), 5 );
+///
+///     assert_eq!( ReprPacked::OFFSET_A.at(&*packed).copy(), 8 );
+///     assert_eq!( ReprPacked::OFFSET_B.at(&*packed).read(), "13" );
+/// }
+///
+/// ```
+///
+/// [`FieldOffset`]: ./struct.FieldOffset.html
+/// [`FieldOffset::at`]: ./struct.FieldOffset.html#method.at
+/// [`copy`]: #method.copy
+/// [`read`]: #method.read
+/// [`ptr`]: #method.ptr
+pub struct FieldReader<S, F, A> {
+    offset: FieldOffset<S, F, A>,
+    base: *const S,
+}
+
+impl<S, F, A> FieldReader<S, F, A> {
+    #[inline(always)]
+    pub(crate) const fn new(offset: FieldOffset<S, F, A>, base: *const S) -> Self {
+        Self { offset, base }
+    }
+
+    /// Gets a raw pointer to the field.
+    ///
+    /// # Safety
+    ///
+    /// While calling this method is not by itself unsafe,
+    /// using the pointer returned by this method has the same safety requirements
+    /// as the [`<*const T>::wrapping_offset`] method.
+    ///
+    /// [`<*const T>::wrapping_offset`]:
+    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_offset
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    ///
+    /// let ptr_b = ReprPacked::OFFSET_B.at(&this).ptr();
+    /// // ReprPacked has an alignment of 1,
+    /// // so this u16 field has to be copied with `.read_unaligned()`.
+    /// assert_eq!( unsafe{ ptr_b.read_unaligned() }, 5u16 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn ptr(self) -> *const F {
+        self.offset.wrapping_raw_get(self.base)
+    }
+}
+
+impl<S, F> FieldReader<S, F, Aligned> {
+    /// Copies the aligned field.
+    ///
+    /// # Safety
+    ///
+    /// This has the same safety requirements as [`FieldOffset::read_copy`]
+    /// for the pointer passed to [`FieldOffset::at`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let this = ReprC{ a: 3u8, b: [5u32, 8], c: (), d: () };
+    ///
+    /// unsafe{
+    ///     assert_eq!( ReprC::OFFSET_A.at(&this).copy(), 3 );
+    ///     assert_eq!( ReprC::OFFSET_B.at(&this).copy(), [5, 8] );
+    /// }
+    ///
+    /// ```
+    ///
+    /// [`FieldOffset::read_copy`]: ./struct.FieldOffset.html#method.read_copy
+    /// [`FieldOffset::at`]: ./struct.FieldOffset.html#method.at
+    #[inline(always)]
+    pub unsafe fn copy(self) -> F
+    where
+        F: Copy,
+    {
+        self.offset.read_copy(self.base)
+    }
+
+    /// Reads the aligned field without moving it.
+    ///
+    /// # Safety
+    ///
+    /// This has the same safety requirements as [`FieldOffset::read`]
+    /// for the pointer passed to [`FieldOffset::at`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// use std::mem::ManuallyDrop;
+    ///
+    /// let this = ManuallyDrop::new(ReprC{ a: vec![3, 5], b: (), c: (), d: () });
+    ///
+    /// unsafe{
+    ///     assert_eq!( ReprC::OFFSET_A.at(&*this).read(), vec![3, 5] );
+    /// }
+    ///
+    /// ```
+    ///
+    /// [`FieldOffset::read`]: ./struct.FieldOffset.html#method.read
+    /// [`FieldOffset::at`]: ./struct.FieldOffset.html#method.at
+    #[inline(always)]
+    pub unsafe fn read(self) -> F {
+        self.offset.read(self.base)
+    }
+}
+
+impl<S, F> FieldReader<S, F, Unaligned> {
+    /// Copies the potentially unaligned field.
+    ///
+    /// # Safety
+    ///
+    /// This has the same safety requirements as [`FieldOffset::read_copy`]
+    /// for the pointer passed to [`FieldOffset::at`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let this = ReprPacked{ a: 3u8, b: [5u32, 8], c: (), d: () };
+    ///
+    /// unsafe{
+    ///     assert_eq!( ReprPacked::OFFSET_A.at(&this).copy(), 3 );
+    ///     assert_eq!( ReprPacked::OFFSET_B.at(&this).copy(), [5, 8] );
+    /// }
+    ///
+    /// ```
+    ///
+    /// [`FieldOffset::read_copy`]: ./struct.FieldOffset.html#method.read_copy-1
+    /// [`FieldOffset::at`]: ./struct.FieldOffset.html#method.at
+    #[inline(always)]
+    pub unsafe fn copy(self) -> F
+    where
+        F: Copy,
+    {
+        self.offset.read_copy(self.base)
+    }
+
+    /// Reads the potentially unaligned field without moving it.
+    ///
+    /// # Safety
+    ///
+    /// This has the same safety requirements as [`FieldOffset::read`]
+    /// for the pointer passed to [`FieldOffset::at`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// use std::mem::ManuallyDrop;
+    ///
+    /// let this = ManuallyDrop::new(ReprPacked{ a: 3u8, b: vec![5, 8], c: (), d: () });
+    ///
+    /// unsafe{
+    ///     assert_eq!( ReprPacked::OFFSET_B.at(&*this).read(), vec![5, 8] );
+    /// }
+    ///
+    /// ```
+    ///
+    /// [`FieldOffset::read`]: ./struct.FieldOffset.html#method.read-1
+    /// [`FieldOffset::at`]: ./struct.FieldOffset.html#method.at
+    #[inline(always)]
+    pub unsafe fn read(self) -> F {
+        self.offset.read(self.base)
+    }
+}
+
+impl<S, F, A> Debug for FieldReader<S, F, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldReader")
+            .field("offset", &self.offset.offset())
+            .field("base", &self.base)
+            .finish()
+    }
+}
+
+impl<S, F, A> Copy for FieldReader<S, F, A> {}
+
+impl<S, F, A> Clone for FieldReader<S, F, A> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
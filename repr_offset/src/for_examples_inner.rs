@@ -79,6 +79,16 @@ declare_example_struct! {
     alignment = Aligned,
 }
 
+declare_example_struct! {
+    /// An example `#[repr(C, align(16))]` type
+    ///
+    /// Note that `#[repr(packed)]` types can't contain this type,
+    /// because Rust doesn't allow packed types to contain `#[repr(align(N))]` types.
+    #[repr(C, align(16))]
+    struct ReprAlign16;
+    alignment = Aligned,
+}
+
 declare_example_struct! {
     /// An example `#[repr(C, packed)]` type
     #[repr(C, packed)]
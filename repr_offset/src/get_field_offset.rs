@@ -11,6 +11,7 @@ use crate::{privacy::IsPublic, FieldOffset};
 
 use core::marker::PhantomData;
 
+mod bare_tuple_impls;
 mod tuple_impls;
 
 //////////////////////////////////////////////////////////////////////////////////
@@ -34,6 +35,58 @@ pub struct ImplGetNestedFieldOffset<T>(T);
 
 //////////////////////////////////////////////////////////////////////////////////
 
+/// Marker trait that isn't implemented for any type,
+/// used to produce a helpful error when getting the offset of a field in a tuple.
+///
+/// Tuples don't have a stable layout, so the offsets of their fields can't be known.
+/// You can declare a `#[repr(C)]` tuple struct with the same fields instead,
+/// and use the [`ReprOffset`] derive or the [`unsafe_struct_field_offsets`] macro on it.
+///
+/// This trait is sealed, it can't be implemented outside of `repr_offset`.
+///
+/// # Example
+///
+/// Using the [`off`] macro to get the offset of a field in a tuple
+/// causes a compile-time error saying that
+/// this trait is not implemented for the tuple type.
+///
+/// ```compile_fail,E0277
+/// use repr_offset::off;
+///
+/// let tuple = (3u8, 5u16);
+///
+/// let _ = off!(tuple; 1);
+/// ```
+///
+/// This is how you can use a tuple struct instead:
+///
+/// ```rust
+/// use repr_offset::{off, unsafe_struct_field_offsets, Aligned, ROExtAcc};
+///
+/// #[repr(C)]
+/// struct Pair(u8, u16);
+///
+/// unsafe_struct_field_offsets! {
+///     alignment = Aligned,
+///     impl[] Pair {
+///         const OFFSET_0, 0: u8;
+///         const OFFSET_1, 1: u16;
+///     }
+/// }
+///
+/// let pair = Pair(3, 5);
+///
+/// assert_eq!(pair.f_get(off!(1)), &5);
+/// assert_eq!(off!(pair; 1).offset(), 2);
+/// ```
+///
+/// [`ReprOffset`]: ../derive.ReprOffset.html
+/// [`unsafe_struct_field_offsets`]: ../macro.unsafe_struct_field_offsets.html
+/// [`off`]: ../macro.off.html
+pub trait UseReprCTupleStructInsteadOfTuple: bare_tuple_impls::Sealed {}
+
+//////////////////////////////////////////////////////////////////////////////////
+
 /// For getting the offset of a field given its name.
 ///
 /// This trait exists to make it possible for the
@@ -676,6 +729,15 @@ pub fn loop_create_val<S>(_: PhantomData<fn() -> S>) -> S {
     loop {}
 }
 
+// Asserts that the type of the field that `offset` is for is exactly the type
+// of the field that `getter` returns a pointer to.
+#[doc(hidden)]
+pub fn assert_field_type<S, F, A, G>(_offset: FieldOffset<S, F, A>, _getter: G)
+where
+    G: FnOnce(*const S) -> *const F,
+{
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 #[doc(hidden)]
@@ -0,0 +1,47 @@
+// These impls exist to produce a helpful error message
+// when getting the offset of a field of a tuple,
+// since tuples don't have a stable layout.
+
+use crate::{
+    alignment::Unaligned,
+    get_field_offset::{FieldOffsetWithVis, GetFieldOffset, UseReprCTupleStructInsteadOfTuple},
+    privacy::IsPrivate,
+};
+
+use tstr::TStr;
+
+pub trait Sealed {}
+
+macro_rules! bare_tuple_impl {
+    ($($tp:ident)*) => {
+        unsafe impl<N, $($tp,)*> GetFieldOffset<TStr<N>> for ($($tp,)*)
+        where
+            Self: UseReprCTupleStructInsteadOfTuple,
+        {
+            type Type = ();
+            type Alignment = Unaligned;
+            type Privacy = IsPrivate;
+
+            const OFFSET_WITH_VIS: FieldOffsetWithVis<
+                Self,
+                IsPrivate,
+                TStr<N>,
+                (),
+                Unaligned,
+            > = unsafe { FieldOffsetWithVis::new(0) };
+        }
+    };
+}
+
+bare_tuple_impl! {A}
+bare_tuple_impl! {A B}
+bare_tuple_impl! {A B C}
+bare_tuple_impl! {A B C D}
+bare_tuple_impl! {A B C D E}
+bare_tuple_impl! {A B C D E F}
+bare_tuple_impl! {A B C D E F G}
+bare_tuple_impl! {A B C D E F G H}
+bare_tuple_impl! {A B C D E F G H I}
+bare_tuple_impl! {A B C D E F G H I J}
+bare_tuple_impl! {A B C D E F G H I J K}
+bare_tuple_impl! {A B C D E F G H I J K L}
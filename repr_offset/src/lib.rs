@@ -174,6 +174,48 @@
 //! - `"for_examples"` (disabled by default):
 //! Enables the `for_examples` module, with types used in documentation examples.
 //!
+//! - `"std"` (disabled by default):
+//! Enables items that use the standard library.
+//!
+//! - `"min_const_generics"` (disabled by default):
+//! Enables items that use const generics,
+//! this requires Rust 1.51.0 or later.
+//!
+//! - `"const_fns"` (disabled by default):
+//! Enables const fns that read through raw pointers,
+//! this requires Rust 1.61.0 or later.
+//!
+//! - `"ptr_alignment"` (disabled by default):
+//! Enables the [`FieldOffset::field_alignment`] method,
+//! which returns the unstable `core::mem::Alignment` type,
+//! this requires a nightly compiler.
+//!
+//! - `"bitflags"` (disabled by default):
+//! Enables methods for reading integer fields as [`bitflags`] types,
+//! this requires Rust 1.56.0 or later.
+//!
+//! - `"serde"` (disabled by default):
+//! Enables the `#[roff(serialize_via_fields)]` attribute of the [`ReprOffset`] derive,
+//! which implements [`serde`]'s `Serialize` trait by reading fields through their offsets,
+//! this requires Rust 1.56.0 or later.
+//!
+//! - `"atomic_cas"` (disabled by default):
+//! Enables the [`FieldOffset::compare_exchange`] method,
+//! this requires the target to support atomic compare-and-swap operations,
+//! which targets like `thumbv6m-none-eabi` and `riscv32i-unknown-none-elf` don't.
+//!
+//! - `"abi_stable"` (disabled by default):
+//! Enables the [`abi_stable_layout`] module,
+//! with functions for checking that the offsets of the fields of
+//! types that derive both `StableAbi` and [`ReprOffset`]
+//! agree with their [`abi_stable`] layout,
+//! this requires Rust 1.61.0 or later, and the standard library.
+//!
+//! - `"debug_offsets"` (disabled by default):
+//! Enables runtime checks in [`StructInitializer`]
+//! that no field is written more than once,
+//! and that the unwritten bytes of the struct could be padding.
+//!
 //! Example of using the "derive" feature::
 //! ```toml
 //! repr_offset = { version = "0.2", features = ["derive"] }
@@ -181,7 +223,8 @@
 //!
 //! # no-std support
 //!
-//! This library is unconditionally `#![no_std]`, and that is unlikely to change in the future.
+//! This library is `#![no_std]`,
+//! the items that require the standard library are enabled by the "std" feature.
 //!
 //! # Minimum Rust version
 //!
@@ -198,10 +241,18 @@
 //! [`GetFieldOffset`]: ./get_field_offset/trait.GetFieldOffset.html
 //! [`unsafe_struct_field_offsets`]: ./macro.unsafe_struct_field_offsets.html
 //! [`FieldOffset`]: ./struct.FieldOffset.html
+//! [`FieldOffset::field_alignment`]: ./struct.FieldOffset.html#method.field_alignment
+//! [`FieldOffset::compare_exchange`]: ./struct.FieldOffset.html#method.compare_exchange
+//! [`StructInitializer`]: ./struct.StructInitializer.html
 //! [`ext`]: ./ext/index.html
+//! [`abi_stable_layout`]: ./abi_stable_layout/index.html
+//! [`abi_stable`]: https://docs.rs/abi_stable/0.11
+//! [`bitflags`]: https://docs.rs/bitflags/2
+//! [`serde`]: https://docs.rs/serde/1
 //!
 #![no_std]
 #![cfg_attr(feature = "priv_raw_ref", feature(raw_ref_op))]
+#![cfg_attr(feature = "ptr_alignment", feature(ptr_alignment_type))]
 #![cfg_attr(feature = "docsrs", feature(doc_cfg))]
 #![allow(clippy::empty_loop)]
 #![deny(clippy::missing_safety_doc)]
@@ -209,6 +260,9 @@
 #![deny(clippy::wildcard_imports)]
 #![deny(missing_docs)]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 #[doc(hidden)]
 pub extern crate self as repr_offset;
 
@@ -247,12 +301,24 @@ pub mod for_examples_inner;
 
 mod struct_field_offset;
 
+mod field_offset_dyn;
+
+mod field_reader;
+
+mod struct_initializer;
+
+mod variant_field_offset;
+
 pub mod ext;
 
 pub mod get_field_offset;
 
 pub mod utils;
 
+#[cfg(feature = "abi_stable")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "abi_stable")))]
+pub mod abi_stable_layout;
+
 #[cfg(feature = "testing")]
 pub mod types_for_tests;
 
@@ -263,8 +329,12 @@ include! {"repr_offset_macro.rs"}
 pub use self::{
     alignment::{Aligned, Unaligned},
     ext::{ROExtAcc, ROExtOps, ROExtRawAcc, ROExtRawMutAcc, ROExtRawMutOps, ROExtRawOps},
+    field_offset_dyn::FieldOffsetDyn,
+    field_reader::FieldReader,
     get_field_offset::{FieldType, GetPubFieldOffset},
     struct_field_offset::FieldOffset,
+    struct_initializer::StructInitializer,
+    variant_field_offset::VariantFieldOffset,
 };
 
 #[cfg(all(test, not(feature = "testing")))]
@@ -275,10 +345,18 @@ compile_error! { "tests must be run with the \"testing\" feature" }
 pub mod pmr {
     pub use core::marker::PhantomData;
 
+    pub use core::mem::{ManuallyDrop, MaybeUninit};
+
     pub use crate::struct_field_offset::FOAssertStruct;
 
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    #[cfg(feature = "std")]
+    pub use std::{boxed::Box, vec::Vec};
+
     pub use crate::get_field_offset::{
-        loop_create_fo, loop_create_mutref, loop_create_val, FieldOffsetWithVis, GetFieldOffset,
-        GetPubFieldOffset, ImplsGetFieldOffset,
+        assert_field_type, loop_create_fo, loop_create_mutref, loop_create_val, FieldOffsetWithVis,
+        GetFieldOffset, GetPubFieldOffset, ImplsGetFieldOffset,
     };
 }
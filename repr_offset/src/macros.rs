@@ -6,3 +6,15 @@ mod off_macro;
 
 #[macro_use]
 mod for_boolean_const_enums;
+
+#[macro_use]
+mod assert_c_offset;
+
+#[macro_use]
+mod to_columns;
+
+#[macro_use]
+mod copy_fields;
+
+#[macro_use]
+mod verify_offsets;
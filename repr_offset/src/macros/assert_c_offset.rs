@@ -0,0 +1,77 @@
+/// Asserts at compile-time that the offset of a field is the expected value,
+/// eg: an offset taken from a C header.
+///
+/// This takes an expression that evaluates to a [`FieldOffset`]
+/// (or any type with a `const fn offset(self) -> usize` method),
+/// followed by `==`, and the expected offset.
+///
+/// This macro expands to an item, so it can be used both inside and outside functions.
+///
+/// When the offset is not the expected one, this causes a compile-time error like
+/// `expected an array with a fixed size of 24 elements, found one with 16 elements`,
+/// where `24` is the expected offset, and `16` is the actual offset.
+///
+/// # Example
+///
+/// ```rust
+/// use repr_offset::{assert_c_offset, unsafe_struct_field_offsets, Aligned};
+///
+/// // Equivalent to this C struct:
+/// // struct Foo {
+/// //     uint8_t  x;
+/// //     uint64_t y;
+/// //     uint16_t z;
+/// // };
+/// #[repr(C)]
+/// pub struct Foo {
+///     pub x: u8,
+///     pub y: u64,
+///     pub z: u16,
+/// }
+///
+/// unsafe_struct_field_offsets! {
+///     alignment = Aligned,
+///     impl[] Foo {
+///         pub const OFFSET_X, x: u8;
+///         pub const OFFSET_Y, y: u64;
+///         pub const OFFSET_Z, z: u16;
+///     }
+/// }
+///
+/// // The offsets from `offsetof(struct Foo, <field>)` in C
+/// assert_c_offset!(Foo::OFFSET_X == 0);
+/// assert_c_offset!(Foo::OFFSET_Y == 8);
+/// assert_c_offset!(Foo::OFFSET_Z == 16);
+///
+/// ```
+///
+/// This is an example of an offset that differs from the expected one,
+/// which causes a compile-time error:
+///
+/// ```compile_fail
+/// use repr_offset::{assert_c_offset, for_examples::ReprC};
+///
+/// type This = ReprC<u8, u32, (), ()>;
+///
+/// assert_c_offset!(This::OFFSET_B == 1);
+///
+/// ```
+///
+/// [`FieldOffset`]: ./struct.FieldOffset.html
+#[macro_export]
+macro_rules! assert_c_offset {
+    ( $($tokens:tt)* ) => {
+        $crate::__priv_assert_c_offset!{ () $($tokens)* }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __priv_assert_c_offset {
+    ( ($($offset:tt)*) == $expected:expr $(,)? ) => {
+        const _: [(); $expected] = [(); ($($offset)*).offset()];
+    };
+    ( ($($offset:tt)*) $first:tt $($rest:tt)* ) => {
+        $crate::__priv_assert_c_offset!{ ($($offset)* $first) $($rest)* }
+    };
+}
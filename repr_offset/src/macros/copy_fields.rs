@@ -0,0 +1,71 @@
+/// Copies the passed in (possibly nested) fields from `source` into `destination`,
+/// leaving the other fields of `destination` untouched.
+///
+/// This takes a `*const S` (or `&S`) source, and a `*mut S` (or `&mut S`) destination,
+/// copying each field with [`ROExtRawMutOps::f_copy_from`],
+/// which copies aligned fields with aligned copies,
+/// and unaligned fields (eg: in packed structs) with unaligned copies.
+///
+/// Fields are accessed with the same privacy rules as field expressions
+/// (eg: `value.field`) at the macro call site.
+///
+/// # Safety
+///
+/// This must be called in an `unsafe` block,
+/// and has the same safety requirements as calling
+/// [`ROExtRawMutOps::f_copy_from`] for each field.
+///
+/// The copied fields are not dropped in `destination` before being overwritten,
+/// and the copies in `source` aren't forgotten,
+/// so copying fields that don't implement `Copy` can cause double drops.
+///
+/// # Example
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::{
+///     for_examples::{ReprC, ReprPacked},
+///     copy_fields,
+/// };
+///
+/// let update = ReprC{ a: 3u8, b: 5u32, c: 'x', d: ReprC{ a: 8u16, b: 13u64, c: (), d: () } };
+/// let mut this = ReprC{ a: 0u8, b: 0u32, c: 'a', d: ReprC{ a: 0u16, b: 0u64, c: (), d: () } };
+///
+/// unsafe{ copy_fields!(&update, &mut this; a, c, d.b) };
+///
+/// assert_eq!( this.a, 3 );
+/// assert_eq!( this.b, 0 );
+/// assert_eq!( this.c, 'x' );
+/// assert_eq!( this.d.a, 0 );
+/// assert_eq!( this.d.b, 13 );
+///
+///
+/// // Copying fields of a packed struct through raw pointers
+/// let update = ReprPacked{ a: 21u8, b: 34u64, c: 55u16, d: () };
+/// let mut this = ReprPacked{ a: 0u8, b: 0u64, c: 0u16, d: () };
+///
+/// let source: *const _ = &update;
+/// let destination: *mut _ = &mut this;
+/// unsafe{ copy_fields!(source, destination; b, c) };
+///
+/// assert_eq!( this.a, 0 );
+/// assert_eq!( {this.b}, 34 );
+/// assert_eq!( {this.c}, 55 );
+///
+/// ```
+///
+/// [`ROExtRawMutOps::f_copy_from`]: ./trait.ROExtRawMutOps.html#tymethod.f_copy_from
+#[macro_export]
+macro_rules! copy_fields {
+    ($source:expr, $destination:expr; $( $($fields:tt).+ ),+ $(,)? ) => {{
+        let source: *const _ = $source;
+        let destination: *mut _ = $destination;
+        $(
+            $crate::ROExtRawMutOps::f_copy_from(
+                destination,
+                $crate::off!($($fields).+),
+                source,
+            );
+        )+
+    }};
+}
@@ -17,16 +17,130 @@
 /// ### Caveats with path argument
 ///
 /// With the path way of passing the type,
-/// if the type has all type parameters defaulted or is otherwise generic,
-/// there can be type inference issues.
+/// the generic arguments of the type are inferred from how the
+/// returned [`FieldOffset`] is used,
+/// so defaulted type parameters don't fall back to their default
+/// (Rust doesn't apply defaults in expressions).
 ///
-/// To fix type inference issues with defaulted types,
-/// you can write `<>` (eg: `OFF!(for_examples::ReprC<>; a.b)`).
+/// If nothing constrains the generic arguments,
+/// this causes a "type annotations needed" error mentioning
+/// a type like `FieldOffset<ReprC<_, _, _, _>, _, Aligned>`.
+///
+/// To use the defaulted type parameters,
+/// you can write `<>` (eg: `OFF!(for_examples::ReprC<>; a.b)`),
+/// which passes the type as a type instead of a path.
+///
+/// ```rust
+/// use repr_offset::{for_examples::ReprC, OFF};
+///
+/// // `ReprC<>` is `ReprC<(), (), (), ()>`
+/// assert_eq!(OFF!(ReprC<>; a).offset(), 0);
+/// ```
+///
+/// ```compile_fail
+/// use repr_offset::{for_examples::ReprC, OFF};
+///
+/// // error: type annotations needed
+/// assert_eq!(OFF!(ReprC; a).offset(), 0);
+/// ```
 ///
 /// If a generic type is passed, and its arguments can be inferred from context,
 /// it's only necessary to specify the type of the accessed field,
 /// otherwise you need to write the full type.
 ///
+/// # Const contexts
+///
+/// This macro can be used in `const` items,
+/// since the field access that it checks the field with is never evaluated.
+///
+/// For nested fields, this produces a single [`FieldOffset`] from the outermost struct
+/// to the innermost field,
+/// the same one that adding the `FieldOffset`s of each struct
+/// (with the `FieldOffset::add` method) produces.
+///
+/// ```rust
+/// use repr_offset::{
+///     for_examples::{ReprC, ReprPacked},
+///     FieldOffset, Unaligned, OFF,
+/// };
+///
+/// type Leaf = ReprC<u8, u32, (), ()>;
+/// type Inner = ReprC<u16, Leaf, (), ()>;
+/// type Middle = ReprPacked<u8, Inner, (), ()>;
+/// type Outer = ReprC<u64, Middle, (), ()>;
+///
+/// const NESTED: FieldOffset<Outer, u32, Unaligned> = OFF!(Outer; b.b.b.b);
+///
+/// const ADDED: FieldOffset<Outer, u32, Unaligned> =
+///     Outer::OFFSET_B.add(Middle::OFFSET_B).add(Inner::OFFSET_B).add(Leaf::OFFSET_B);
+///
+/// assert_eq!(NESTED, ADDED);
+/// assert_eq!(NESTED.offset(), 8 + 1 + 4 + 4);
+///
+/// // The offset can be used in other constants, eg: as an array length.
+/// const BYTES: [u8; NESTED.offset()] = [0; NESTED.offset()];
+/// assert_eq!(BYTES.len(), 17);
+/// ```
+///
+/// # Privacy
+///
+/// The fields are accessed with the same privacy rules as a field expression
+/// (eg: `value.field`) at the macro call site,
+/// so a private field can only be accessed from the module that declares the struct,
+/// and its submodules.
+///
+/// ```rust
+#[cfg_attr(feature = "derive", doc = "use repr_offset::ReprOffset;")]
+#[cfg_attr(not(feature = "derive"), doc = "use repr_offset_derive::ReprOffset;")]
+/// use repr_offset::OFF;
+///
+/// mod shapes {
+///     use super::*;
+///
+///     #[repr(C)]
+///     #[derive(ReprOffset)]
+///     pub struct Circle{
+///         pub radius: u32,
+///         pub(crate) center: (u32, u32),
+///         id: u64,
+///     }
+///
+///     pub fn id_offset() -> usize {
+///         OFF!(crate::shapes::Circle; id).offset()
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!( OFF!(shapes::Circle; radius).offset(), 0 );
+///     assert_eq!( OFF!(shapes::Circle; center).offset(), 4 );
+///     assert_eq!( shapes::id_offset(), 16 );
+/// }
+/// ```
+///
+/// Accessing a private field from outside the module that declares the struct
+/// causes a "field is private" error:
+///
+/// ```compile_fail
+#[cfg_attr(feature = "derive", doc = "use repr_offset::ReprOffset;")]
+#[cfg_attr(not(feature = "derive"), doc = "use repr_offset_derive::ReprOffset;")]
+/// use repr_offset::OFF;
+///
+/// mod shapes {
+///     use super::*;
+///
+///     #[repr(C)]
+///     #[derive(ReprOffset)]
+///     pub struct Circle{
+///         pub radius: u32,
+///         id: u64,
+///     }
+/// }
+///
+/// fn main() {
+///     let _ = OFF!(shapes::Circle; id);
+/// }
+/// ```
+///
 /// # Example
 ///
 /// ```rust
@@ -336,6 +450,37 @@ macro_rules! pub_off{
 /// }
 /// ```
 ///
+/// ### Accessing nested fields of type parameters
+///
+/// The fields of a type parameter can be accessed through a field of a struct,
+/// the `Alignment` associated type of the field of the type parameter
+/// must be bounded by the [`Alignment`] trait.
+///
+/// ```rust
+/// use repr_offset::{
+///     alignment::Alignment,
+///     for_examples::ReprC,
+///     tstr::TS,
+///     PUB_OFF,
+///     GetPubFieldOffset, ROExtOps,
+/// };
+///
+/// let inner = ReprC {a: 3u8, b: 5u32, c: (), d: ()};
+/// let this = ReprC {a: 8u16, b: inner, c: (), d: ()};
+///
+/// assert_eq!(get_inner_b(&this), 5);
+///
+/// fn get_inner_b<T, A>(this: &ReprC<u16, T, (), ()>) -> u32
+/// where
+///     T: GetPubFieldOffset<TS!(b), Type = u32, Alignment = A>,
+///     A: Alignment,
+///     ReprC<u16, T, (), ()>: ROExtOps<A>,
+/// {
+///     this.f_get_copy(PUB_OFF!(ReprC<u16, T, (), ()>; b.b))
+/// }
+/// ```
+///
+/// [`Alignment`]: ./alignment/trait.Alignment.html
 /// [`OFF`]: ./macro.OFF.html
 /// [`FieldOffset`]: ./struct.FieldOffset.html
 #[macro_export]
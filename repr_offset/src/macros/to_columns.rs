@@ -0,0 +1,132 @@
+/// Converts a `Vec` of structs into a tuple with a `Vec` for each of the passed in
+/// (possibly nested) fields.
+///
+/// Each element of the `Vec` is consumed,
+/// moving the passed in fields into their columns, and dropping the other fields.
+///
+/// This converts an array of structs into a struct of arrays,
+/// where each column is returned in the same order as the fields were passed.
+///
+/// Fields are accessed with the same privacy rules as field expressions
+/// (eg: `value.field`) at the macro call site.
+///
+/// # Limitations
+///
+/// The fields are moved out of each element with field expressions,
+/// so the struct can't implement `Drop`,
+/// since fields can't be moved out of types that implement `Drop`.
+///
+/// Up to 16 fields can be passed to this macro,
+/// passing more fields causes a compile-time error.
+///
+/// # Example
+///
+/// ```rust
+/// use repr_offset::{
+///     for_examples::{ReprC, ReprPacked},
+///     to_columns,
+/// };
+///
+/// let list = vec![
+///     ReprC{ a: 3u8, b: "foo".to_string(), c: 'x', d: () },
+///     ReprC{ a: 5u8, b: "bar".to_string(), c: 'y', d: () },
+///     ReprC{ a: 8u8, b: "baz".to_string(), c: 'z', d: () },
+/// ];
+///
+/// let (c, a, b) = to_columns!(list; c, a, b);
+///
+/// assert_eq!( a, [3, 5, 8] );
+/// assert_eq!( b, ["foo", "bar", "baz"] );
+/// assert_eq!( c, ['x', 'y', 'z'] );
+///
+///
+/// // Passing a single field returns a single-element tuple
+/// let list = vec![
+///     ReprPacked{ a: 3u8, b: 13u64, c: (), d: () },
+///     ReprPacked{ a: 5u8, b: 21u64, c: (), d: () },
+/// ];
+///
+/// let (b,) = to_columns!(list; b);
+///
+/// assert_eq!( b, [13, 21] );
+///
+/// ```
+///
+/// This is an example of passing a `Vec` of a struct that implements `Drop`,
+/// which causes a compile-time error:
+///
+/// ```compile_fail,E0509
+/// use repr_offset::to_columns;
+///
+/// pub struct Guarded {
+///     pub a: String,
+///     pub b: String,
+/// }
+///
+/// impl Drop for Guarded {
+///     fn drop(&mut self) {}
+/// }
+///
+/// let list = vec![Guarded{ a: "foo".to_string(), b: "bar".to_string() }];
+///
+/// let (a,) = to_columns!(list; a);
+///
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+#[macro_export]
+macro_rules! to_columns {
+    ($vec:expr; $( $($fields:tt).+ ),+ $(,)? ) => {
+        $crate::__priv_to_columns!{
+            vec = $vec;
+            names[
+                column0 column1 column2 column3 column4 column5 column6 column7
+                column8 column9 column10 column11 column12 column13 column14 column15
+            ]
+            fields[$( ($($fields).+) )+]
+            columns[]
+        }
+    };
+}
+
+// Pairs each field with the name of the variable for its column,
+// the amount of names limits the amount of fields that can be passed.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __priv_to_columns {
+    (
+        vec = $vec:expr;
+        names[$name:ident $($rem_names:ident)*]
+        fields[$field:tt $($rem_fields:tt)*]
+        columns[$($columns:tt)*]
+    ) => {
+        $crate::__priv_to_columns!{
+            vec = $vec;
+            names[$($rem_names)*]
+            fields[$($rem_fields)*]
+            columns[$($columns)* ($name $field)]
+        }
+    };
+    (
+        vec = $vec:expr;
+        names[$($rem_names:ident)*]
+        fields[]
+        columns[$( ($name:ident ($($fields:tt).+)) )*]
+    ) => {{
+        let vec: $crate::pmr::Vec<_> = $vec;
+        $( let mut $name = $crate::pmr::Vec::with_capacity(vec.len()); )*
+        for elem in vec {
+            $( $name.push(elem $(.$fields)+); )*
+        }
+        ( $($name,)* )
+    }};
+    (
+        vec = $vec:expr;
+        names[]
+        fields[$($rem_fields:tt)+]
+        columns[$($columns:tt)*]
+    ) => {
+        compile_error!{"`to_columns` can't take more than 16 fields"}
+    };
+}
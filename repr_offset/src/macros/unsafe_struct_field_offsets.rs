@@ -10,7 +10,14 @@
 /// - All field types are listed,in declaration order.
 ///
 /// - The `alignment` parameter is [`Unaligned`] if the struct is `#[repr(C,packed)]`,
-/// and [`Aligned`] if it's not.
+///   and [`Aligned`] if it's not.
+///
+/// - The `packing` parameter (if passed) is the `N` in the `#[repr(C,packed(N))]`
+///   attribute of the struct,
+///   or is at least the alignment of every field if the struct isn't packed.
+///
+/// - The `starting_offset` parameter (if passed) is the offset of
+///   the struct inside the `Self` type.
 ///
 /// # Parameters
 ///
@@ -28,6 +35,28 @@
 /// and describes whether the fields are aligned or potentially unaligned,
 /// changing how fields are accessed in [`FieldOffset`] methods.
 ///
+/// ### `packing`
+///
+/// The optional `packing` parameter is the maximum alignment of the fields,
+/// it's the `N` in `#[repr(C,packed(N))]`.
+///
+/// Without this parameter, the packing of fields is determined by the alignment
+/// of the `Self` type, which is enough when `Self` is the struct itself.
+///
+/// This is useful when the `Self` parameter is a type with a larger alignment than the struct.
+///
+/// ### `starting_offset`
+///
+/// The optional `starting_offset` parameter is the offset of the struct,
+/// from which the offsets of the fields are calculated.
+/// The first field is at `starting_offset`,
+/// rounded up to the (packed) alignment of the field's type.
+///
+/// Without this parameter, the first field is at offset `0`.
+///
+/// This is useful in combination with the `Self` parameter,
+/// for declaring the offsets of a struct that is stored inside of the `Self` type.
+///
 /// ### `usize_offsets`
 ///
 /// The optional `usize_offsets` parameter determines whether type of the
@@ -77,6 +106,12 @@
 ///     alignment =  Aligned,
 ///
 ///     // Optional parameter.
+///     packing = 16,
+///
+///     // Optional parameter.
+///     starting_offset = 0,
+///
+///     // Optional parameter.
 ///     usize_offsets = false,
 ///
 ///     // Optional parameter.
@@ -173,6 +208,8 @@ macro_rules! unsafe_struct_field_offsets{
     (
         $( Self = $Self:ty, )?
         alignment =  $alignment:ty,
+        $( packing = $packing:expr, )?
+        $( starting_offset = $starting_offset:expr, )?
         $( usize_offsets = $usize_offsets:ident,)?
         $( impl_GetFieldOffset = $impl_gfo:ident,)?
 
@@ -196,6 +233,7 @@ macro_rules! unsafe_struct_field_offsets{
                 params(
                     Self( $($Self,)? Self, )
                     alignment =  $alignment,
+                    packing( $($packing)? )
                     usize_offsets($($usize_offsets,)? false,)
                     impl_GetFieldOffset( $(false, $Self:ty )? $($impl_gfo,)? true,)
 
@@ -207,7 +245,8 @@ macro_rules! unsafe_struct_field_offsets{
                     (
                         $crate::_priv_usfoi!(
                             @initial
-                            $($usize_offsets)?, 0,
+                            $($usize_offsets)?,
+                            $crate::_priv_usfoi!(@starting_offset $($starting_offset)?),
                         ),
                         ()
                     ),
@@ -244,6 +283,12 @@ macro_rules! _priv_usfoi{
             offsets( $($offsets)* )
         }
     };
+    (@starting_offset)=>{
+        0
+    };
+    (@starting_offset $starting_offset:expr)=>{
+        $starting_offset
+    };
     (@initial true, $value:expr, )=>{
         $value
     };
@@ -256,12 +301,26 @@ macro_rules! _priv_usfoi{
     (@ty false, $Self:ty, $next_ty:ty, $alignment:ty )=>{
         $crate::FieldOffset<$Self,$next_ty,$alignment>
     };
-    (@val true, $Self:ty, $prev:expr, $prev_ty:ty, $next_ty:ty )=>{
+    (@val true, $Self:ty, $prev:expr, $prev_ty:ty, $next_ty:ty, packing() )=>{
         $crate::offset_calc::next_field_offset::<$Self, $prev_ty, $next_ty>( $prev )
     };
-    (@val false, $Self:ty, $prev:expr, $prev_ty:ty, $next_ty:ty )=>{
+    (@val false, $Self:ty, $prev:expr, $prev_ty:ty, $next_ty:ty, packing() )=>{
         $prev.next_field_offset()
     };
+    (@val true, $Self:ty, $prev:expr, $prev_ty:ty, $next_ty:ty, packing($packing:expr) )=>{
+        $crate::offset_calc::next_field_offset_packed::<$Self, $prev_ty, $next_ty>(
+            $prev,
+            $packing,
+        )
+    };
+    (@val false, $Self:ty, $prev:expr, $prev_ty:ty, $next_ty:ty, packing($packing:expr) )=>{
+        $crate::FieldOffset::new(
+            $crate::offset_calc::next_field_offset_packed::<$Self, $prev_ty, $next_ty>(
+                $crate::FieldOffset::<$Self, $prev_ty, _>::offset($prev),
+                $packing,
+            )
+        )
+    };
     (@FieldOffsetWithVis false, $expr:expr)=>{
         $crate::pmr::FieldOffsetWithVis::from_fieldoffset($expr)
     };
@@ -274,6 +333,7 @@ macro_rules! _priv_usfoi{
         params(
             Self( $Self:ty, $($_ignored_Self:ty,)? )
             alignment =  $alignment:ty,
+            packing $packing:tt
             usize_offsets($usize_offsets:ident, $($_ignored_io:ident,)? )
             impl_GetFieldOffset($impl_gfo:ident, $($_ignored_impl_gfo:tt)*)
 
@@ -309,7 +369,7 @@ macro_rules! _priv_usfoi{
 
             $crate::_priv_usfoi!(
                 @val
-                $usize_offsets, $Self, $prev_offset, $prev_ty, $field_ty
+                $usize_offsets, $Self, $prev_offset, $prev_ty, $field_ty, packing $packing
             )
         };
 
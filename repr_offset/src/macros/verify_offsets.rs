@@ -0,0 +1,135 @@
+/// Asserts at compile-time that the offsets declared for a struct with
+/// [`unsafe_struct_field_offsets`] match its actual layout.
+///
+/// This takes the type of the struct, followed by `;`, and a comma separated list of fields.
+///
+/// For each field, this asserts that:
+///
+/// - The offset in its [`GetFieldOffset`] impl is the one that
+/// [`core::mem::offset_of`] computes.
+///
+/// - The type of the field declared in the [`unsafe_struct_field_offsets`] invocation
+/// is exactly the type of the field.
+///
+/// This is a safety net for the hand-written (and inherently `unsafe`) declarations
+/// of [`unsafe_struct_field_offsets`],
+/// catching mistakes like listing the fields in the wrong order,
+/// or with the wrong types.
+///
+/// This macro expands to an item, so it can be used both inside and outside functions,
+/// and can be put in a `#[cfg(test)]` module to only check the offsets in tests.
+///
+/// When an offset is not the actual one, this causes a compile-time error like
+/// `expected an array with a size of 8, found one with a size of 0`,
+/// where `8` is the actual offset, and `0` is the declared offset.
+///
+/// # Requirements
+///
+/// This macro requires Rust 1.77.0 or later, since it uses [`core::mem::offset_of`].
+///
+/// The struct must implement [`GetFieldOffset`] for the fields,
+/// which [`unsafe_struct_field_offsets`] does by default,
+/// and must not be generic (with generic arguments being allowed).
+///
+/// # Example
+///
+#[cfg_attr(feature = "priv_offset_of", doc = "```rust")]
+#[cfg_attr(not(feature = "priv_offset_of"), doc = "```ignore")]
+/// use repr_offset::{unsafe_struct_field_offsets, verify_offsets, Aligned};
+///
+/// #[repr(C)]
+/// pub struct Foo {
+///     pub x: u8,
+///     pub y: u64,
+///     z: u16,
+/// }
+///
+/// unsafe_struct_field_offsets! {
+///     alignment = Aligned,
+///     impl[] Foo {
+///         pub const OFFSET_X, x: u8;
+///         pub const OFFSET_Y, y: u64;
+///         const OFFSET_Z, z: u16;
+///     }
+/// }
+///
+/// verify_offsets!(Foo; x, y, z);
+///
+/// ```
+///
+/// This is an example of declared offsets that list the fields in the wrong order,
+/// which causes a compile-time error:
+///
+#[cfg_attr(feature = "priv_offset_of", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "priv_offset_of"), doc = "```ignore")]
+/// use repr_offset::{unsafe_struct_field_offsets, verify_offsets, Aligned};
+///
+/// #[repr(C)]
+/// pub struct Foo {
+///     pub x: u8,
+///     pub y: u64,
+/// }
+///
+/// unsafe_struct_field_offsets! {
+///     alignment = Aligned,
+///     impl[] Foo {
+///         pub const OFFSET_Y, y: u64;
+///         pub const OFFSET_X, x: u8;
+///     }
+/// }
+///
+/// verify_offsets!(Foo; x, y);
+///
+/// ```
+///
+/// This is an example of a field declared with the wrong type,
+/// which causes a compile-time error:
+///
+#[cfg_attr(feature = "priv_offset_of", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "priv_offset_of"), doc = "```ignore")]
+/// use repr_offset::{unsafe_struct_field_offsets, verify_offsets, Aligned};
+///
+/// #[repr(C)]
+/// pub struct Foo {
+///     pub x: u32,
+///     pub y: u32,
+/// }
+///
+/// unsafe_struct_field_offsets! {
+///     alignment = Aligned,
+///     impl[] Foo {
+///         pub const OFFSET_X, x: u32;
+///         pub const OFFSET_Y, y: i32;
+///     }
+/// }
+///
+/// verify_offsets!(Foo; x, y);
+///
+/// ```
+///
+/// [`unsafe_struct_field_offsets`]: ./macro.unsafe_struct_field_offsets.html
+/// [`GetFieldOffset`]: ./get_field_offset/trait.GetFieldOffset.html
+/// [`core::mem::offset_of`]: https://doc.rust-lang.org/core/mem/macro.offset_of.html
+#[macro_export]
+macro_rules! verify_offsets {
+    ($type:ty; $($field:tt),+ $(,)? ) => {
+        const _: () = {
+            $(
+                let _: [(); ::core::mem::offset_of!($type, $field)] =
+                    [(); $crate::OFF!($type; $field).offset()];
+            )+
+
+            #[allow(dead_code)]
+            fn __roff_verify_field_types() {
+                $({
+                    // The closure is declared before being passed to `assert_field_type`
+                    // so that its return type isn't inferred from the declared field type.
+                    let getter = |this: *const $type| unsafe {
+                        ::core::ptr::addr_of!((*this).$field)
+                    };
+                    $crate::pmr::assert_field_type($crate::OFF!($type; $field), getter);
+                })+
+            }
+        };
+    };
+}
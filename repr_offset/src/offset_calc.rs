@@ -43,6 +43,59 @@ pub const fn next_field_offset<Struct, Prev, Next>(previous_offset: usize) -> us
     .call()
 }
 
+/// Calculates the offset of a field in bytes,given the previous field,
+/// for a struct with the `#[repr(C, packed(N))]` attribute,
+/// where `N` is the `packing` parameter.
+///
+/// This is equivalent to [`next_field_offset`] when `packing` is
+/// greater than or equal to the alignment of `Struct`.
+///
+/// # Parameters
+///
+/// `Struct` is the struct that contains the field that this calculates the offset for.
+///
+/// `Prev` is the type of the previous field.
+///
+/// `Next` is the type of the field that this calculates the offset for.
+///
+/// `previous_offset` is the offset in bytes of the previous field,of `Prev` type.
+///
+/// `packing` is the maximum alignment of the fields.
+///
+/// # Example
+///
+/// ```
+/// use repr_offset::offset_calc::next_field_offset_packed;
+///
+/// #[repr(C, packed(2))]
+/// struct Foo(u8, u16, u32, u64);
+///
+/// assert_eq!( OFFSET_1, 2 );
+/// assert_eq!( OFFSET_2, 4 );
+/// assert_eq!( OFFSET_3, 8 );
+///
+/// const OFFSET_0: usize = 0;
+/// const OFFSET_1: usize = next_field_offset_packed::<Foo, u8, u16>(OFFSET_0, 2);
+/// const OFFSET_2: usize = next_field_offset_packed::<Foo, u16, u32>(OFFSET_1, 2);
+/// const OFFSET_3: usize = next_field_offset_packed::<Foo, u32, u64>(OFFSET_2, 2);
+///
+/// ```
+///
+/// [`next_field_offset`]: ./fn.next_field_offset.html
+#[inline(always)]
+pub const fn next_field_offset_packed<Struct, Prev, Next>(
+    previous_offset: usize,
+    packing: usize,
+) -> usize {
+    GetNextFieldOffset {
+        previous_offset,
+        previous_size: Mem::<Prev>::SIZE,
+        container_alignment: utils::min_usize(Mem::<Struct>::ALIGN, packing),
+        next_alignment: Mem::<Next>::ALIGN,
+    }
+    .call()
+}
+
 /// Calculates the offset (in bytes) of a field, with the `call` method.
 ///
 /// # Example
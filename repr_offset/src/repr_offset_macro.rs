@@ -91,8 +91,1133 @@
 /// Chooses whether [`GetFieldOffset`] is implemented for all the fields or none of them,
 /// if `true` then [`GetFieldOffset`] is implemented for all the fields,
 /// if `false` then [`GetFieldOffset`] is implemented for none of the fields.
+///
+/// The [`GetFieldOffset`] impls add little to compile times,
+/// about 10 milliseconds for a struct with 50 fields
+/// (measured by the `fifty_fields` example of the `repr_offset_benches` crate in the repository).
+/// Nested field access (eg: `off!(foo; a.b)`) is implemented with blanket impls,
+/// which are only instantiated for the nested fields that are accessed.
 /// 
 ///
+/// ### `#[roff(no_offset_consts)]`
+///
+/// Hides the `OFFSET_*` associated constants,
+/// while still implementing [`GetFieldOffset`] for all the fields.
+///
+/// This is for code that only gets the offsets of fields with the
+/// [`off`], [`pub_off`], [`OFF`], and [`PUB_OFF`] macros,
+/// so that the constants don't add to the associated items of the struct.
+///
+/// The constants are still generated with `#[doc(hidden)]` names that start with `__ROFF_`,
+/// since they're used by the other generated code,
+/// those names are not part of the public API.
+///
+/// This attribute can't be used with `#[roff(impl_GetFieldOffset = false)]`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::{off, Aligned, FieldOffset, ReprOffset};
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(no_offset_consts)]
+/// struct Foo{
+///     x: u8,
+///     y: u64,
+/// }
+///
+/// let foo = Foo{ x: 3, y: 5 };
+///
+/// let _: FieldOffset<Foo, u8, Aligned> = off!(x);
+/// assert_eq!( off!(foo; y).get(&foo), &5 );
+/// assert_eq!( off!(foo; y).offset(), 8 );
+///
+/// ```
+///
+/// The constants aren't accessible with their usual names:
+/// ```compile_fail,E0275
+#[cfg_attr(feature = "derive", doc = "use repr_offset::ReprOffset;")]
+#[cfg_attr(not(feature = "derive"), doc = "use repr_offset_derive::ReprOffset;")]
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(no_offset_consts)]
+/// struct Foo{
+///     x: u8,
+///     y: u64,
+/// }
+///
+/// let _ = Foo::OFFSET_X;
+/// ```
+///
+/// ### `#[roff(field_enum)]`
+///
+/// Generates a `<struct_name>Field` enum, with the same visibility as the struct,
+/// with one variant for each field,
+/// and an `offset_dyn` method to get the [`FieldOffsetDyn`] of that field.
+///
+/// The variants are named after the field in CamelCase,
+/// `foo_bar` becomes `FooBar`, and tuple fields are named `F0`, `F1`, etc.
+///
+/// For structs that aren't packed, this also generates a
+/// `fn get_dyn(&self, field: <struct_name>Field) -> &dyn Any` method,
+/// to access fields at runtime, downcasting them to their type.
+/// The method requires that every field type is `'static`.
+///
+/// This attribute can't be used with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::{ReprOffset, FieldOffsetDyn};
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(field_enum)]
+/// struct Foo{
+///     x: u8,
+///     y: u64,
+///     long_name: u32,
+/// }
+///
+/// let offsets: Vec<FieldOffsetDyn<Foo>> = [FooField::X, FooField::Y, FooField::LongName]
+///     .iter()
+///     .map(|field| field.offset_dyn())
+///     .collect();
+///
+/// assert_eq!( offsets[0], Foo::OFFSET_X.to_dyn() );
+/// assert_eq!( offsets[1], Foo::OFFSET_Y.to_dyn() );
+/// assert_eq!( offsets[2], Foo::OFFSET_LONG_NAME.to_dyn() );
+///
+/// assert_eq!( offsets[2].offset(), 16 );
+/// assert_eq!( offsets[2].size(), 4 );
+///
+/// let foo = Foo{ x: 3, y: 5, long_name: 8 };
+///
+/// assert_eq!( foo.get_dyn(FooField::X).downcast_ref::<u8>(), Some(&3) );
+/// assert_eq!( foo.get_dyn(FooField::Y).downcast_ref::<u64>(), Some(&5) );
+/// assert_eq!( foo.get_dyn(FooField::LongName).downcast_ref::<u32>(), Some(&8) );
+/// assert_eq!( foo.get_dyn(FooField::LongName).downcast_ref::<u64>(), None );
+///
+/// ```
+///
+/// ### `#[roff(const_offset_fn)]`
+///
+/// Generates a `const fn offset_of_field(field: <struct_name>Field) -> usize` function,
+/// with the same visibility as the struct,
+/// which returns the offset of the field that `field` refers to.
+///
+/// This allows selecting the offset of a field at compile-time from a value of
+/// the enum generated by `#[roff(field_enum)]`,
+/// which is required to use this attribute.
+///
+/// Using this attribute on structs with trait bounds requires Rust 1.61.0 or later,
+/// since that's when trait bounds became usable in `const fn`s.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(field_enum, const_offset_fn)]
+/// struct Header{
+///     kind: u8,
+///     flags: u8,
+///     len: u16,
+/// }
+///
+/// const BYTES: [u8; 4] = [3, 5, 8, 13];
+///
+/// const FLAGS: u8 = BYTES[Header::offset_of_field(HeaderField::Flags)];
+/// const LEN_LOW: u8 = BYTES[Header::offset_of_field(HeaderField::Len)];
+///
+/// assert_eq!( FLAGS, 5 );
+/// assert_eq!( LEN_LOW, 8 );
+///
+/// ```
+///
+/// ### `#[roff(refs_view)]`
+///
+/// Generates a `<struct_name>Refs` struct with a reference to each field,
+/// and a `<struct_name>RefsMut` struct with a mutable reference to each field,
+/// returned by the generated `as_refs` and `as_refs_mut` methods respectively.
+///
+/// The generated structs have the same visibility as the derive input,
+/// and each field has the same visibility as the field it references.
+///
+/// For packed structs, `<struct_name>Refs` contains copies of the fields instead
+/// (requiring all fields to be `Copy`),
+/// and neither `<struct_name>RefsMut` nor `as_refs_mut` are generated,
+/// because references to fields of packed structs can be unaligned.
+///
+/// This attribute can't be used with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(refs_view)]
+/// struct Foo{
+///     x: u8,
+///     y: Vec<u64>,
+/// }
+///
+/// let mut foo = Foo{ x: 3, y: vec![5, 8] };
+///
+/// let FooRefs{x, y} = foo.as_refs();
+/// assert_eq!( x, &3 );
+/// assert_eq!( y, &[5, 8] );
+///
+/// let FooRefsMut{x, y} = foo.as_refs_mut();
+/// *x += 10;
+/// y.push(13);
+///
+/// assert_eq!( foo.x, 13 );
+/// assert_eq!( foo.y, [5, 8, 13] );
+///
+///
+/// #[repr(C, packed)]
+/// #[derive(ReprOffset)]
+/// #[roff(refs_view)]
+/// struct Packed{
+///     x: u8,
+///     y: u64,
+/// }
+///
+/// let packed = Packed{ x: 21, y: 34 };
+///
+/// let PackedRefs{x, y} = packed.as_refs();
+/// assert_eq!( x, 21 );
+/// assert_eq!( y, 34 );
+///
+/// ```
+///
+/// ### `#[roff(fields_proxy)]`
+///
+/// Generates a `fields` method, which returns a `<struct_name>Fields` proxy
+/// with a method to access each field.
+///
+/// The accessor methods are named after the field,
+/// and tuple fields are accessed with the `f0`, `f1`, etc. methods.
+/// The accessor methods have the same visibility as the field they access.
+///
+/// For packed structs, the accessor methods return copies of the fields
+/// (only usable when the field is `Copy`),
+/// because references to fields of packed structs can be unaligned.
+///
+/// This attribute can't be used with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(fields_proxy)]
+/// struct Foo{
+///     x: u8,
+///     y: Vec<u64>,
+/// }
+///
+/// let foo = Foo{ x: 3, y: vec![5, 8] };
+///
+/// assert_eq!( foo.fields().x(), &3 );
+/// assert_eq!( foo.fields().y(), &[5, 8] );
+///
+///
+/// #[repr(C, packed)]
+/// #[derive(ReprOffset)]
+/// #[roff(fields_proxy)]
+/// struct Packed(u8, u64, String);
+///
+/// let packed = Packed(21, 34, "55".to_string());
+///
+/// assert_eq!( packed.fields().f0(), 21 );
+/// assert_eq!( packed.fields().f1(), 34 );
+///
+/// ```
+///
+/// ### `#[roff(derive_offset_debug)]`
+///
+/// Generates a `debug_offsets` const function,
+/// which returns a `&'static [(&'static str, usize)]` with the name and offset of every field,
+/// in declaration order.
+///
+/// The function has the same visibility as the struct,
+/// and includes private fields.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(derive_offset_debug)]
+/// struct Foo{
+///     x: u8,
+///     y: u64,
+///     z: u16,
+/// }
+///
+/// assert_eq!( Foo::debug_offsets(), &[("x", 0), ("y", 8), ("z", 16)] );
+///
+/// for (name, offset) in Foo::debug_offsets() {
+///     println!("{}: {}", name, offset);
+/// }
+///
+/// ```
+///
+/// ### `#[roff(clone_via_fields)]`
+///
+/// Generates a `fn clone_via_fields(&self) -> Self` method,
+/// which clones the struct by cloning each field through its `FieldOffset`.
+///
+/// The method requires every field type to implement `Clone`,
+/// unlike `#[derive(Clone)]`, which requires every type parameter to implement `Clone`.
+///
+/// For packed structs, each field is cloned from a copy of it,
+/// since fields of packed structs can't be borrowed,
+/// which requires every field type to also implement `Copy`
+/// (like `#[derive(Clone)]` does for packed structs).
+///
+/// The method has the same visibility as the struct,
+/// and this attribute can't be combined with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// use std::rc::Rc;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(clone_via_fields)]
+/// struct Shared<T>{
+///     value: Rc<T>,
+///     len: usize,
+/// }
+///
+/// // `#[derive(Clone)]` would require this to implement `Clone` to clone a `Shared<NotClone>`
+/// struct NotClone;
+///
+/// let this = Shared{ value: Rc::new(NotClone), len: 3 };
+/// let other = this.clone_via_fields();
+///
+/// assert!( Rc::ptr_eq(&this.value, &other.value) );
+/// assert_eq!( other.len, 3 );
+///
+/// ```
+///
+/// This is an example of a packed struct with a non-`Copy` field,
+/// which causes a compile-time error:
+/// ```compile_fail,E0277
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C, packed)]
+/// #[derive(ReprOffset)]
+/// #[roff(clone_via_fields)]
+/// struct Packed{
+///     name: String,
+///     len: usize,
+/// }
+///
+/// ```
+///
+/// ### `#[roff(read_from_unaligned)]`
+///
+/// Generates an `unsafe fn read_from_unaligned(ptr: *const u8) -> Self` function,
+/// which reads every field from `ptr` plus the offset of the field,
+/// without requiring `ptr` to be aligned.
+///
+/// This is for reading the struct from byte buffers that could be at any alignment,
+/// eg: parsing a `#[repr(C)]` struct from a file or network packet.
+///
+/// The function has the same visibility as the struct,
+/// and this attribute can't be combined with `#[roff(usize_offsets)]`.
+///
+/// Calling the function requires that `ptr` points to at least `size_of::<Self>()` bytes,
+/// and that the bytes of every field are a valid value of the field's type.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(read_from_unaligned)]
+/// struct Header{
+///     kind: u16,
+///     len: u32,
+/// }
+///
+/// let mut buffer = [0u8; 9];
+/// buffer[1..3].copy_from_slice(&7u16.to_ne_bytes());
+/// buffer[5..9].copy_from_slice(&1000u32.to_ne_bytes());
+///
+/// // The `Header` starts at index 1, which could be unaligned.
+/// let header = unsafe{ Header::read_from_unaligned(buffer[1..].as_ptr()) };
+///
+/// assert_eq!( header.kind, 7 );
+/// assert_eq!( header.len, 1000 );
+///
+/// ```
+///
+/// ### `#[roff(write_defaults)]`
+///
+/// Generates an `unsafe fn write_defaults(ptr: *mut Self)` function,
+/// which writes `Default::default()` into every field at its offset,
+/// without reading or dropping the previous values of the fields.
+///
+/// This is for initializing a `MaybeUninit<Self>` when every field implements `Default`,
+/// even if the struct itself doesn't (eg: because the `Default` derive
+/// would add unnecessary bounds on type parameters).
+///
+/// The function has the same visibility as the struct,
+/// and this attribute can't be combined with `#[roff(usize_offsets)]`.
+///
+/// Calling the function requires that `ptr` is valid for writes of `size_of::<Self>()` bytes,
+/// and is aligned to `align_of::<Self>()`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// use std::mem::MaybeUninit;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(write_defaults)]
+/// struct Config{
+///     name: String,
+///     retries: u32,
+///     verbose: bool,
+/// }
+///
+/// let mut config = MaybeUninit::<Config>::uninit();
+///
+/// let config = unsafe{
+///     Config::write_defaults(config.as_mut_ptr());
+///     config.assume_init()
+/// };
+///
+/// assert_eq!( config.name, "" );
+/// assert_eq!( config.retries, 0 );
+/// assert_eq!( config.verbose, false );
+///
+/// ```
+///
+/// ### `#[roff(try_from_bytes)]`
+///
+/// Implements `TryFrom<&[u8]>` for the struct,
+/// which reads every field from the byte slice through its offset,
+/// returning a [`FromBytesError`] if the slice is shorter than the struct,
+/// or if the bytes of any field aren't a valid value of the field's type.
+///
+/// This also implements [`ValidBitPattern`] for the struct,
+/// which requires every field type to implement [`ValidBitPattern`],
+/// so that structs using this attribute can be nested inside each other.
+///
+/// The byte slice doesn't need to be aligned,
+/// since the fields are read with unaligned reads,
+/// and any bytes after the struct are ignored.
+///
+/// This attribute can't be combined with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::{utils::FromBytesError, ReprOffset};
+///
+/// use std::convert::TryFrom;
+///
+/// #[repr(C)]
+/// #[derive(Debug, PartialEq, ReprOffset)]
+/// #[roff(try_from_bytes)]
+/// struct Header{
+///     kind: u16,
+///     is_last: bool,
+///     len: u32,
+/// }
+///
+/// let mut bytes = [0u8; 9];
+/// bytes[1..3].copy_from_slice(&7u16.to_ne_bytes());
+/// bytes[3] = 1;
+/// bytes[5..9].copy_from_slice(&1000u32.to_ne_bytes());
+///
+/// // The `Header` starts at index 1, which could be unaligned.
+/// assert_eq!(
+///     Header::try_from(&bytes[1..]),
+///     Ok(Header{ kind: 7, is_last: true, len: 1000 }),
+/// );
+///
+/// assert_eq!(
+///     Header::try_from(&bytes[2..]),
+///     Err(FromBytesError::TooShort{ expected: 8, found: 7 }),
+/// );
+///
+/// // `2` isn't a valid `bool`
+/// bytes[3] = 2;
+/// assert_eq!( Header::try_from(&bytes[1..]), Err(FromBytesError::InvalidBitPattern) );
+///
+/// ```
+///
+/// ### `#[roff(eq_by_fields)]`
+///
+/// Generates a `fn eq_by_fields(&self, other: &Self) -> bool` method,
+/// which compares the struct with `other` by comparing each field through its `FieldOffset`,
+/// ignoring padding bytes.
+///
+/// The method requires every field type to implement `PartialEq`,
+/// unlike `#[derive(PartialEq)]`, which requires every type parameter to implement `PartialEq`.
+///
+/// For packed structs, each field is compared through a copy of it,
+/// since fields of packed structs can't be borrowed,
+/// which requires every field type to also implement `Copy`.
+///
+/// The method has the same visibility as the struct,
+/// and this attribute can't be combined with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// use std::marker::PhantomData;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(eq_by_fields)]
+/// struct Tagged<T>{
+///     tag: u8,
+///     // There's padding between `tag` and `value`, which is not compared.
+///     value: u32,
+///     _marker: PhantomData<T>,
+/// }
+///
+/// // `#[derive(PartialEq)]` would require this to implement `PartialEq`
+/// // to compare `Tagged<NotEq>`s
+/// struct NotEq;
+///
+/// let tagged = |tag, value| Tagged::<NotEq>{ tag, value, _marker: PhantomData };
+///
+/// assert!( tagged(3, 5).eq_by_fields(&tagged(3, 5)) );
+/// assert!( !tagged(3, 5).eq_by_fields(&tagged(3, 8)) );
+/// assert!( !tagged(3, 5).eq_by_fields(&tagged(4, 5)) );
+///
+/// ```
+///
+/// ### `#[roff(derive_eq)]`
+///
+/// Implements the `PartialEq` trait for the struct,
+/// comparing each field through its `FieldOffset`, ignoring padding bytes.
+///
+/// `#[roff(derive_eq(Eq))]` also implements the `Eq` trait.
+/// `Eq` isn't implemented by default,
+/// because it'd be an error for structs with fields that don't implement `Eq`,
+/// like `f32` and `f64`.
+///
+/// For packed structs, each field is compared through a copy of it,
+/// so unlike `#[derive(PartialEq)]`, this never creates references to packed fields.
+/// This requires every field type of packed structs to implement `Copy`,
+/// like `#[derive(PartialEq)]` does.
+///
+/// For generic structs, `PartialEq` is implemented if every field type implements `PartialEq`,
+/// and with `derive_eq(Eq)`, `Eq` is implemented if every field type implements `Eq`.
+///
+/// This attribute can't be combined with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C, packed)]
+/// #[derive(ReprOffset)]
+/// #[roff(derive_eq(Eq))]
+/// struct Packet{
+///     kind: u8,
+///     len: u32,
+///     checksum: u64,
+/// }
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(derive_eq)]
+/// struct Reading{
+///     sensor: u8,
+///     value: f32,
+/// }
+///
+/// fn assert_eq_impl<T: Eq>(_: &T) {}
+///
+/// let packet = |len, checksum| Packet{ kind: 3, len, checksum };
+///
+/// assert_eq_impl(&packet(5, 8));
+/// assert!( packet(5, 8) == packet(5, 8) );
+/// assert!( packet(5, 8) != packet(5, 13) );
+/// assert!( packet(5, 8) != packet(21, 8) );
+///
+/// let reading = |value| Reading{ sensor: 3, value };
+///
+/// assert!( reading(0.5) == reading(0.5) );
+/// assert!( reading(0.5) != reading(1.5) );
+/// assert!( reading(f32::NAN) != reading(f32::NAN) );
+///
+/// ```
+///
+/// ### `#[roff(derive_hash)]`
+///
+/// Implements the `Hash` trait for the struct,
+/// hashing each field through its `FieldOffset` in declaration order,
+/// ignoring padding bytes.
+///
+/// For packed structs, each field is hashed through a copy of it,
+/// so unlike `#[derive(Hash)]`, this never creates references to packed fields.
+/// This requires every field type of packed structs to implement `Copy`,
+/// like `#[derive(Hash)]` does.
+///
+/// The hash is consistent with the `PartialEq` impl generated by `#[roff(derive_eq)]`,
+/// since both use every field of the struct.
+///
+/// `Hash` is implemented if every field type implements `Hash`.
+///
+/// This attribute can't be combined with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::ReprOffset;
+///
+/// use std::collections::HashSet;
+///
+/// #[repr(C, packed)]
+/// #[derive(ReprOffset)]
+/// #[roff(derive_eq(Eq), derive_hash)]
+/// struct Packet{
+///     kind: u8,
+///     len: u32,
+///     checksum: u64,
+/// }
+///
+/// let packet = |len, checksum| Packet{ kind: 3, len, checksum };
+///
+/// let mut set = HashSet::new();
+/// assert!( set.insert(packet(5, 8)) );
+/// assert!( set.insert(packet(5, 13)) );
+/// assert!( !set.insert(packet(5, 8)) );
+///
+/// assert!( set.contains(&packet(5, 13)) );
+/// assert!( !set.contains(&packet(21, 8)) );
+///
+/// ```
+///
+/// ### `#[roff(into_fields)]`
+///
+/// Generates a `fn into_fields(self) -> (F0, F1, ...)` method,
+/// which moves every field out of the struct by value through its `FieldOffset`,
+/// returning them in a tuple in declaration order.
+///
+/// The destructor of the struct doesn't run,
+/// so this can be used to take apart structs that implement `Drop`.
+///
+/// The method has the same visibility as the struct,
+/// and this attribute can't be combined with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(into_fields)]
+/// struct Message{
+///     id: u32,
+///     text: String,
+///     attachments: Vec<Vec<u8>>,
+/// }
+///
+/// impl Drop for Message {
+///     fn drop(&mut self) {
+///         panic!("the destructor of `Message` must not run");
+///     }
+/// }
+///
+/// let message = Message{ id: 3, text: "hello".to_string(), attachments: vec![vec![5]] };
+///
+/// let (id, text, attachments) = message.into_fields();
+///
+/// assert_eq!( id, 3 );
+/// assert_eq!( text, "hello" );
+/// assert_eq!( attachments, [[5]] );
+///
+/// ```
+///
+/// ### `#[roff(write_all)]`
+///
+/// Generates an `unsafe fn write_all_from_tuple(ptr: *mut Self, values: (F0, F1, ...))`
+/// function, the inverse of `#[roff(into_fields)]`,
+/// which moves every element of the tuple into the field of the same position
+/// (in declaration order) through its `FieldOffset`,
+/// without reading or dropping the previous values of the fields.
+///
+/// This is for initializing a `MaybeUninit<Self>` from the values of all its fields
+/// in one call.
+///
+/// The function has the same visibility as the struct,
+/// and this attribute can't be combined with `#[roff(usize_offsets)]`.
+///
+/// Calling the function requires that `ptr` is valid for writes of `size_of::<Self>()` bytes,
+/// and is aligned to `align_of::<Self>()`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// use std::mem::MaybeUninit;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(write_all)]
+/// struct Message{
+///     id: u32,
+///     text: String,
+///     attachments: Vec<Vec<u8>>,
+/// }
+///
+/// let mut message = MaybeUninit::<Message>::uninit();
+///
+/// let message = unsafe{
+///     Message::write_all_from_tuple(
+///         message.as_mut_ptr(),
+///         (3, "hello".to_string(), vec![vec![5]]),
+///     );
+///     message.assume_init()
+/// };
+///
+/// assert_eq!( message.id, 3 );
+/// assert_eq!( message.text, "hello" );
+/// assert_eq!( message.attachments, [[5]] );
+///
+/// ```
+///
+/// ### `#[roff(dyn_offsets)]`
+///
+/// Generates a `fn field_offset_dyn(name: &str) -> Option<FieldOffsetDyn<Self>>`
+/// associated function, with the same visibility as the struct,
+/// which returns the [`FieldOffsetDyn`] of the field with that name,
+/// or `None` if there's no such field.
+///
+/// Tuple struct fields are named with their position, eg: `"0"`.
+///
+/// This is for reflection code that looks up fields by name at runtime.
+///
+/// This attribute can't be used with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::{ReprOffset, FieldOffsetDyn};
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(dyn_offsets)]
+/// struct Foo{
+///     x: u8,
+///     y: u32,
+/// }
+///
+/// let y: FieldOffsetDyn<Foo> = Foo::field_offset_dyn("y").unwrap();
+/// assert_eq!( y.offset(), 4 );
+/// assert_eq!( y.size(), 4 );
+///
+/// assert!( Foo::field_offset_dyn("z").is_none() );
+///
+/// ```
+///
+/// ### `#[roff(dyn_set)]`
+///
+/// Generates a
+/// `fn set_field_dyn(&mut self, name: &str, value: Box<dyn Any>) -> Result<(), SetFieldError>`
+/// method, with the same visibility as the struct,
+/// which downcasts `value` to the type of the field named `name`,
+/// and then writes it into the field through its offset, dropping the previous value.
+///
+/// This returns a [`SetFieldError`] if there's no field with that name,
+/// or if `value` isn't of the type of the field.
+///
+/// Tuple struct fields are named with their position, eg: `"0"`.
+///
+/// This is for setting fields by name at runtime,
+/// eg: from a configuration file or a scripting language.
+/// The method requires that every field type is `'static`.
+///
+/// This requires the `"std"` feature of `repr_offset` to be enabled,
+/// and can't be used with `#[roff(usize_offsets)]`.
+///
+/// Example:
+#[cfg_attr(feature = "std", doc = "```rust")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+/// use repr_offset::{utils::SetFieldError, ReprOffset};
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(dyn_set)]
+/// struct Config{
+///     retries: u32,
+///     name: String,
+/// }
+///
+/// let mut config = Config{ retries: 3, name: "foo".to_string() };
+///
+/// config.set_field_dyn("retries", Box::new(5u32)).unwrap();
+/// config.set_field_dyn("name", Box::new("bar".to_string())).unwrap();
+///
+/// assert_eq!( config.retries, 5 );
+/// assert_eq!( config.name, "bar" );
+///
+/// match config.set_field_dyn("retries", Box::new("8")) {
+///     Err(SetFieldError::TypeMismatch(value)) => assert_eq!( value.downcast_ref(), Some(&"8") ),
+///     _ => unreachable!(),
+/// }
+///
+/// assert!(matches!(
+///     config.set_field_dyn("timeout", Box::new(8u32)),
+///     Err(SetFieldError::UnknownField),
+/// ));
+///
+/// ```
+///
+/// ### `#[roff(tuple_index)]`
+///
+/// Generates a `fn field_ref(&self, index: usize) -> Option<&dyn Any>` method
+/// for tuple structs, with the same visibility as the struct,
+/// which returns a reference to the field at the `index` position,
+/// or `None` if `index` is out of bounds.
+///
+/// This is for accessing the fields of tuple structs by position at runtime,
+/// eg: in generic serialization code.
+/// The method requires that every field type is `'static`.
+///
+/// This attribute can only be used with tuple structs,
+/// and can't be used with packed structs (since their fields can't be borrowed),
+/// or with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(tuple_index)]
+/// struct Point(u32, u32, &'static str);
+///
+/// let point = Point(3, 5, "origin");
+///
+/// assert_eq!( point.field_ref(1).unwrap().downcast_ref::<u32>(), Some(&5) );
+/// assert_eq!( point.field_ref(2).unwrap().downcast_ref::<&str>(), Some(&"origin") );
+/// assert!( point.field_ref(3).is_none() );
+///
+/// ```
+///
+/// ### `#[roff(nested_consts("a.b", "a.c"))]`
+///
+/// Generates an `OFFSET_A_B` associated constant for every passed in nested field path,
+/// with the [`FieldOffset`] of that nested field,
+/// computed at compile-time by [`add`]ing the offset of the field
+/// to the offset of the nested field inside of it.
+///
+/// The nested fields are separated with `.`,
+/// and can be tuple indices (eg: `"pos.0"`).
+/// The name of the constant is built from the `offset_prefix`
+/// followed by the uppercased field names separated with `_`,
+/// and has the same visibility as the first field in the path.
+///
+/// The type of every field in the path (except the last)
+/// must implement [`GetFieldOffset`] for the nested field,
+/// eg: by also deriving this macro.
+///
+/// This attribute can't be used with `#[roff(usize_offsets)]`.
+///
+/// Example:
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::{ReprOffset, Unaligned};
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// struct Point{
+///     pub x: u16,
+///     pub y: u16,
+/// }
+///
+/// #[repr(C, packed)]
+/// #[derive(ReprOffset)]
+/// #[roff(nested_consts("start.y", "end.x"))]
+/// struct Line{
+///     pub tag: u8,
+///     pub start: Point,
+///     pub end: Point,
+/// }
+///
+/// const START_Y: usize = Line::OFFSET_START_Y.offset();
+/// assert_eq!( START_Y, 3 );
+///
+/// let line = Line{ tag: 0, start: Point{ x: 3, y: 5 }, end: Point{ x: 8, y: 13 } };
+///
+/// assert_eq!( Line::OFFSET_START_Y.get_copy(&line), 5 );
+/// assert_eq!( Line::OFFSET_END_X.get_copy(&line), 8 );
+///
+/// let _: repr_offset::FieldOffset<Line, u16, Unaligned> = Line::OFFSET_END_X;
+///
+/// ```
+///
+/// ### `#[roff(padding_consts)]`
+///
+/// Generates a `PADDING_AFTER_<FIELD_NAME>` associated constant for every field,
+/// with the amount of padding bytes between the end of the field and the start of the next one,
+/// or the end of the struct for the last field.
+///
+/// The constants have the same visibility as the field.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(padding_consts)]
+/// struct Foo{
+///     x: u8,
+///     y: u32,
+///     z: u16,
+/// }
+///
+/// assert_eq!( Foo::PADDING_AFTER_X, 3 );
+/// assert_eq!( Foo::PADDING_AFTER_Y, 0 );
+/// assert_eq!( Foo::PADDING_AFTER_Z, 2 );
+///
+/// ```
+///
+/// ### `#[roff(offsets_array)]`
+///
+/// Generates an `OFFSETS: [usize; N]` associated constant,
+/// with the offsets of all the fields in declaration order.
+///
+/// The constant has the same visibility as the struct.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(offsets_array)]
+/// struct Foo{
+///     x: u8,
+///     y: u32,
+///     z: u16,
+/// }
+///
+/// const OFFSETS: [usize; 3] = Foo::OFFSETS;
+///
+/// assert_eq!( OFFSETS, [0, 4, 8] );
+/// assert_eq!( OFFSETS[1], Foo::OFFSET_Y.offset() );
+///
+/// ```
+///
+/// ### `#[roff(layout_guard)]`
+///
+/// Generates a `const _: () = { ... };` item that asserts that the offset of every field
+/// equals the one that the compiler computes with [`core::mem::offset_of`],
+/// failing compilation otherwise.
+///
+/// This guards against any divergence between the offsets that this crate computes
+/// and the actual layout of the struct,
+/// which would indicate a bug in this crate.
+///
+/// This attribute requires Rust 1.77.0 or later (where `offset_of` is available),
+/// and can't be used with generic structs.
+///
+/// Example:
+/// ```rust
+/// # #[cfg(feature = "priv_offset_of")]
+/// # mod example {
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(layout_guard)]
+/// struct Foo{
+///     x: u8,
+///     y: u64,
+///     z: [u16; 3],
+/// }
+/// # }
+/// ```
+///
+/// ### `#[roff(delegate_fields)]`
+///
+/// For structs with a single field (eg: `#[repr(transparent)]` newtypes),
+/// implements [`GetFieldOffset`] by delegating to the type of that field,
+/// so that the [`pub_off`] and [`PUB_OFF`] macros access the fields of the wrapped type.
+///
+/// The offset constant for the field of the struct itself is still generated,
+/// but the field isn't accessible through the [`GetFieldOffset`] trait.
+///
+/// The fields of the wrapped type are only public
+/// if both the field of the struct and the field of the wrapped type are public.
+///
+/// This attribute causes a compile-time error if the struct doesn't have exactly one field.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::{
+///     for_examples::ReprC,
+///     ReprOffset,
+///     pub_off, Aligned, FieldOffset, ROExtAcc,
+/// };
+///
+/// #[repr(transparent)]
+/// #[derive(ReprOffset)]
+/// #[roff(delegate_fields)]
+/// pub struct Wrapper(pub ReprC<u8, ReprC<u16, u32>>);
+///
+/// let this = Wrapper(ReprC{
+///     a: 3,
+///     b: ReprC{ a: 5, b: 8, c: (), d: () },
+///     c: (),
+///     d: (),
+/// });
+///
+/// let _: FieldOffset<Wrapper, ReprC<u8, ReprC<u16, u32>>, Aligned> = Wrapper::OFFSET_0;
+///
+/// assert_eq!( this.f_get(pub_off!(a)), &3 );
+/// assert_eq!( this.f_get(pub_off!(b.a)), &5 );
+/// assert_eq!( this.f_get(pub_off!(b.b)), &8 );
+///
+/// ```
+///
+///
+/// ### `#[roff(serialize_via_fields)]`
+///
+/// Implements [`serde`]'s `Serialize` trait for the struct,
+/// serializing every field by reading it through its offset,
+/// with the name of the field (as `serde`'s derive does).
+///
+/// Fields of packed structs can't be borrowed,
+/// so they are serialized from a copy of the field,
+/// which requires every field type of packed structs to implement `Copy`.
+///
+/// This requires the `"serde"` feature of `repr_offset` to be enabled.
+///
+/// Example:
+/// ```rust
+/// # #[cfg(feature = "serde")]
+/// # mod example {
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C, packed)]
+/// #[derive(ReprOffset)]
+/// #[roff(serialize_via_fields)]
+/// struct Packet{
+///     tag: u8,
+///     len: u32,
+///     name: [u8; 16],
+/// }
+///
+/// fn assert_serialize<T: serde::Serialize>() {}
+///
+/// fn example() {
+///     assert_serialize::<Packet>();
+/// }
+/// # }
+/// ```
+///
+/// ### `#[roff(c_accessors = "prefix_")]`
+///
+/// Generates an `unsafe extern "C" fn prefix_get_<field_name>(this: *const Self)`
+/// function for every field with an FFI-safe type,
+/// which reads the field from the struct that `this` points to.
+/// Tuple struct fields are named with their position, eg: `prefix_get_0`.
+///
+/// This allows C code to read the fields of the struct,
+/// using the offsets computed by this crate,
+/// by passing the functions as function pointers.
+/// To also export the functions as unmangled symbols,
+/// use the [`#[roff(c_accessors_no_mangle)]`](#roffc_accessors_no_mangle) attribute.
+///
+/// The functions have the same visibility as the struct.
+///
+/// These are the field types that are considered FFI-safe:
+///
+/// - The primitive integer types (except for `u128`/`i128`), `f32`, `f64`, and `bool`.
+///
+/// - The `c_*` types from `std::os::raw`/`core::ffi`, eg: `c_int`, `c_char`.
+///
+/// - Raw pointers.
+///
+/// Fields of any other type (including type aliases of FFI-safe types)
+/// don't get an accessor function, and a warning is emitted for each of them.
+///
+/// This attribute can't be used with generic structs,
+/// nor with the `#[roff(usize_offsets)]` attribute.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(c_accessors = "point_")]
+/// pub struct Point{
+///     pub x: u32,
+///     pub y: f64,
+///     pub next: *const Point,
+/// }
+///
+/// let point = Point{ x: 3, y: 5.0, next: std::ptr::null() };
+///
+/// unsafe{
+///     assert_eq!( point_get_x(&point), 3 );
+///     assert_eq!( point_get_y(&point), 5.0 );
+///     assert!( point_get_next(&point).is_null() );
+/// }
+///
+/// let get_x: unsafe extern "C" fn(*const Point) -> u32 = point_get_x;
+/// assert_eq!( unsafe{ get_x(&point) }, 3 );
+///
+/// ```
+///
+/// ### `#[roff(c_accessors_no_mangle)]`
+///
+/// Adds the `#[no_mangle]` attribute to the functions generated by
+/// `#[roff(c_accessors = "prefix_")]`,
+/// exporting them with the same names as symbols that C code can link to.
+///
+/// Because the exported functions have unmangled names,
+/// the prefix must be unique among all the `#[no_mangle]` functions linked into a program.
+///
+/// This attribute can't be used in crates that use the 2024 edition,
+/// because `#[no_mangle]` must be written as `#[unsafe(no_mangle)]` in that edition.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(c_accessors = "rgb_", c_accessors_no_mangle)]
+/// pub struct Rgb(pub u8, pub u8, pub u8);
+///
+/// let color = Rgb(3, 5, 8);
+///
+/// unsafe{
+///     assert_eq!( rgb_get_0(&color), 3 );
+///     assert_eq!( rgb_get_1(&color), 5 );
+///     assert_eq!( rgb_get_2(&color), 8 );
+/// }
+///
+/// ```
+///
 /// # Field attributes
 ///
 /// ### `#[roff(offset = "fooo")]`
@@ -127,6 +1252,84 @@
 ///
 /// ```
 ///
+/// ### `#[roff(unsafe_offset = 0)]`
+///
+/// Forces the offset constant of the field to the passed value,
+/// instead of the offset that the field actually has in the struct.
+///
+/// The value can be an integer literal,
+/// or a string literal containing a `usize` constant expression.
+///
+/// This is for modeling overlapping fields (eg: union-like views of the same bytes),
+/// by forcing the offset of a field to be the same as the offset of another field.
+///
+/// **Using this attribute is as dangerous as calling [`FieldOffset::new`]**,
+/// the `FieldOffset` methods rely on the offset being correct for their soundness,
+/// with no way for this crate to check it.
+/// Callers must ensure that:
+///
+/// - There are at least `size_of::<FieldType>()` bytes in the struct at the forced offset.
+///
+/// - If the struct isn't packed, the forced offset is a multiple of
+/// `align_of::<FieldType>()`.
+///
+/// - Any bytes read through the forced offset are a valid bit pattern for the field type,
+/// which rules out (among others) field types with padding or invalid bit patterns
+/// overlapping other fields.
+///
+/// Only the offset constant for the field is changed,
+/// the offset used by the [`GetFieldOffset`] impl
+/// (which the [`off`] and [`pub_off`] macros use)
+/// and the offsets of every other field are still computed from the layout of the struct.
+///
+/// This attribute can't be used with the `#[roff(refs_view)]`,
+/// `#[roff(into_fields)]`, or `#[roff(write_all)]` container attributes,
+/// because they would produce aliasing mutable references, or duplicate ownership of fields.
+///
+/// It also can't be used with the `#[roff(clone_via_fields)]` or
+/// `#[roff(write_defaults)]` container attributes,
+/// because they initialize the struct by writing every field through its offset constant,
+/// which would leave the bytes of a field with a forced offset uninitialized.
+///
+/// Example:
+/// ```rust
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// struct Color{
+///     word: u32,
+///     // Forcing the offset of `channels` to be the same as `word`,
+///     // so that `Color::OFFSET_CHANNELS` can be used to access the bytes of `word`.
+///     #[roff(unsafe_offset = 0)]
+///     channels: [u8; 4],
+/// }
+///
+/// let color = Color{ word: u32::from_ne_bytes([3, 5, 8, 13]), channels: [0; 4] };
+///
+/// assert_eq!( Color::OFFSET_WORD.offset(), 0 );
+/// assert_eq!( Color::OFFSET_CHANNELS.offset(), 0 );
+///
+/// assert_eq!( Color::OFFSET_CHANNELS.get(&color), &[3, 5, 8, 13] );
+///
+/// ```
+///
+/// This is an example of combining this attribute with `#[roff(clone_via_fields)]`,
+/// which causes a compile-time error:
+/// ```compile_fail
+/// use repr_offset::ReprOffset;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// #[roff(clone_via_fields)]
+/// struct Color{
+///     word: u32,
+///     #[roff(unsafe_offset = 0)]
+///     channels: [u8; 4],
+/// }
+///
+/// ```
+///
 ///
 /// # Container or Field attributes
 ///
@@ -172,6 +1375,11 @@
 ///
 ///
 /// [`FieldOffset`]: ./struct.FieldOffset.html
+/// [`add`]: ./struct.FieldOffset.html#method.add
+/// [`FieldOffsetDyn`]: ./struct.FieldOffsetDyn.html
+/// [`FromBytesError`]: ./utils/enum.FromBytesError.html
+/// [`SetFieldError`]: ./utils/enum.SetFieldError.html
+/// [`ValidBitPattern`]: ./utils/trait.ValidBitPattern.html
 ///
 ///
 /// # Examples
@@ -262,8 +1470,16 @@
 /// ```
 ///
 ///
+/// [`core::mem::offset_of`]: https://doc.rust-lang.org/core/mem/macro.offset_of.html
+///
+/// [`FieldOffset::new`]: ./struct.FieldOffset.html#method.new
 /// [`GetFieldOffset`]: ./get_field_offset/trait.GetFieldOffset.html
 /// [`ImplsGetFieldOffset`]: ./get_field_offset/trait.ImplsGetFieldOffset.html
+/// [`off`]: ./macro.off.html
+/// [`pub_off`]: ./macro.pub_off.html
+/// [`PUB_OFF`]: ./macro.PUB_OFF.html
+/// [`OFF`]: ./macro.OFF.html
+/// [`serde`]: https://docs.rs/serde/1
 ///
 #[doc(inline)]
 #[cfg(feature = "derive")]
@@ -10,19 +10,32 @@ mod repr_offset_ext_impls;
 ////////////////////////////////////////////////////////////////////////////////
 
 use crate::{
-    alignment::{Aligned, Alignment, CombineAlignment, CombineAlignmentOut, Unaligned},
+    alignment::{AlignOne, Aligned, Alignment, CombineAlignment, CombineAlignmentOut, Unaligned},
     offset_calc::GetNextFieldOffset,
-    utils::Mem,
+    utils::{ByteSwap, IsAtomic, Mem, PrimInt},
+    FieldOffsetDyn, FieldReader,
 };
 
 use crate::get_field_offset::FieldOffsetWithVis;
 
 use core::{
+    alloc::Layout,
+    any::TypeId,
+    cell::{Cell, Ref, RefCell, RefMut, UnsafeCell},
+    cmp::Ordering,
     fmt::{self, Debug},
+    hash::{Hash, Hasher},
     marker::PhantomData,
-    ops::Add,
+    mem::{ManuallyDrop, MaybeUninit},
+    num::NonZeroUsize,
+    ops::{Add, Range},
+    ptr::NonNull,
+    sync::atomic::{AtomicPtr, Ordering as AtomicOrdering},
 };
 
+#[cfg(all(feature = "std", feature = "min_const_generics"))]
+use std::ffi::{CStr, FromBytesWithNulError};
+
 /// Represents the offset of a (potentially nested) field inside a type.
 ///
 /// # Type parameters
@@ -73,6 +86,49 @@ use core::{
 /// nested field (ie: `foo` and `bar` and `baz` in `foo.bar.baz`)
 /// is unaligned according to the rules for non-nested fields described in this section.
 ///
+/// # Unsized structs
+///
+/// The `S` type parameter can be an unsized type,
+/// eg: a `#[repr(C)]` struct whose last field is a slice,
+/// or an `extern type` (which is unstable as of writing this) for structs defined in C.
+///
+/// Only the methods that access a field through a pointer or reference to the struct
+/// (eg: `get`, `get_mut`, `raw_get`, `read`, `write`, and `replace`) support unsized structs,
+/// and only for `Sized` fields.
+/// Methods that need the size or alignment of the struct
+/// (eg: `trailing_padding`, `next_field_offset`) require `S` to be `Sized`.
+///
+/// Since there is no way to compute the offsets of fields in an unsized struct
+/// with this crate's macros,
+/// these `FieldOffset`s must be constructed with [`FieldOffset::new`],
+/// based on the caller's knowledge of the layout of the struct.
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::{Aligned, FieldOffset};
+///
+/// #[repr(C)]
+/// struct Message<T: ?Sized> {
+///     kind: u16,
+///     len: u32,
+///     bytes: T,
+/// }
+///
+/// // safety: `Message` is a `#[repr(C)]` struct, with the `kind` field at offset 0,
+/// // and the `len` field at offset 4 (because `u32` is 4-aligned on every platform).
+/// const OFFSET_KIND: FieldOffset<Message<[u8]>, u16, Aligned> = unsafe{ FieldOffset::new(0) };
+/// const OFFSET_LEN: FieldOffset<Message<[u8]>, u32, Aligned> = unsafe{ FieldOffset::new(4) };
+///
+/// let mut message = Message{ kind: 3, len: 5, bytes: [8u8, 13, 21, 34, 55] };
+/// let message: &mut Message<[u8]> = &mut message;
+///
+/// assert_eq!( OFFSET_KIND.get(message), &3 );
+///
+/// *OFFSET_LEN.get_mut(message) += 1;
+/// assert_eq!( unsafe{ OFFSET_LEN.read(message) }, 6 );
+/// assert_eq!( message.bytes.len(), 5 );
+///
+/// ```
 ///
 /// # Examples
 ///
@@ -190,15 +246,101 @@ use core::{
 /// }
 /// ```
 ///
+/// <span id="tagged-structs"></span>
+/// ### Tagged Structs
+///
+/// This example demonstrates how to access the payload of a "tagged struct",
+/// a `#[repr(C)]` struct with a tag field followed by a union,
+/// where the tag determines which field of the union is initialized.
+///
+/// Since all fields of a union are at offset 0,
+/// the `FieldOffset`s for the union fields are constructed with [`FieldOffset::new`],
+/// and then combined with the offsets of the other structs with [`add`](#method.add).
+///
+/// The tag must be checked before reading the payload,
+/// since reading the wrong union field can be undefined behavior.
+///
+/// For Rust enums, there's also the [`VariantFieldOffset`](./struct.VariantFieldOffset.html) type.
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+#[cfg_attr(feature = "derive", doc = "use repr_offset::ReprOffset;")]
+#[cfg_attr(not(feature = "derive"), doc = "use repr_offset_derive::ReprOffset;")]
+/// use repr_offset::{Aligned, FieldOffset};
+///
+/// const CIRCLE_TAG: u8 = 0;
+/// const RECT_TAG: u8 = 1;
+///
+/// #[repr(C)]
+/// #[derive(ReprOffset)]
+/// struct Shape{
+///     tag: u8,
+///     payload: ShapePayload,
+/// }
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone)]
+/// union ShapePayload{
+///     circle: Circle,
+///     rect: Rect,
+/// }
+///
+/// impl ShapePayload {
+///     const OFFSET_CIRCLE: FieldOffset<Self, Circle, Aligned> = unsafe{ FieldOffset::new(0) };
+///     const OFFSET_RECT: FieldOffset<Self, Rect, Aligned> = unsafe{ FieldOffset::new(0) };
+/// }
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, ReprOffset)]
+/// struct Circle{
+///     radius: u32,
+/// }
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, ReprOffset)]
+/// struct Rect{
+///     width: u16,
+///     height: u16,
+/// }
+///
+/// const RECT_HEIGHT: FieldOffset<Shape, u16, Aligned> =
+///     Shape::OFFSET_PAYLOAD
+///         .add(ShapePayload::OFFSET_RECT)
+///         .add(Rect::OFFSET_HEIGHT);
+///
+/// fn rect_height(shape: &Shape) -> Option<u16> {
+///     if Shape::OFFSET_TAG.get_copy(shape) == RECT_TAG {
+///         // Safety: the tag says that the `rect` field of the payload is initialized.
+///         Some(unsafe{ RECT_HEIGHT.read(shape) })
+///     } else {
+///         None
+///     }
+/// }
+///
+/// let rect = Shape{
+///     tag: RECT_TAG,
+///     payload: ShapePayload{ rect: Rect{ width: 3, height: 5 } },
+/// };
+/// let circle = Shape{
+///     tag: CIRCLE_TAG,
+///     payload: ShapePayload{ circle: Circle{ radius: 8 } },
+/// };
+///
+/// assert_eq!( rect_height(&rect), Some(5) );
+/// assert_eq!( rect_height(&circle), None );
+///
+/// ```
+///
 /// [`Aligned`]: ./alignment/struct.Aligned.html
 /// [`Unaligned`]: ./alignment/struct.Unaligned.html
 ///
 /// [`ReprOffset`]: ./derive.ReprOffset.html
 /// [`unsafe_struct_field_offsets`]: ./macro.unsafe_struct_field_offsets.html
 /// [`GetFieldOffset`]: ./get_field_offset/trait.GetFieldOffset.html
+/// [`FieldOffset::new`]: #method.new
 ///
 #[repr(transparent)]
-pub struct FieldOffset<S, F, A> {
+pub struct FieldOffset<S: ?Sized, F, A> {
     offset: usize,
     #[doc(hidden)]
     pub tys: FOGhosts<S, F, A>,
@@ -207,22 +349,22 @@ pub struct FieldOffset<S, F, A> {
 //////////////////////
 
 #[doc(hidden)]
-pub struct FOGhosts<S, F, A> {
+pub struct FOGhosts<S: ?Sized, F, A> {
     pub struct_: PhantomData<fn() -> S>,
     pub field: PhantomData<fn() -> F>,
     pub alignment: PhantomData<fn() -> A>,
 }
 
-impl<S, F, A> Copy for FOGhosts<S, F, A> {}
+impl<S: ?Sized, F, A> Copy for FOGhosts<S, F, A> {}
 
-impl<S, F, A> Clone for FOGhosts<S, F, A> {
+impl<S: ?Sized, F, A> Clone for FOGhosts<S, F, A> {
     #[inline(always)]
     fn clone(&self) -> Self {
         *self
     }
 }
 
-impl<S, F, A> FOGhosts<S, F, A> {
+impl<S: ?Sized, F, A> FOGhosts<S, F, A> {
     const NEW: Self = Self {
         struct_: PhantomData,
         field: PhantomData,
@@ -234,7 +376,7 @@ impl<S, F, A> FOGhosts<S, F, A> {
 
 #[doc(hidden)]
 #[repr(transparent)]
-pub struct FOAssertStruct<S, F, A> {
+pub struct FOAssertStruct<S: ?Sized, F, A> {
     pub offset: FieldOffset<S, F, A>,
     pub struct_: PhantomData<fn() -> S>,
 }
@@ -242,10 +384,10 @@ pub struct FOAssertStruct<S, F, A> {
 //////////////////////
 
 impl_cmp_traits_for_offset! {
-    impl[S, F, A] FieldOffset<S, F, A>
+    impl[S: ?Sized, F, A] FieldOffset<S, F, A>
 }
 
-impl<S, F, A> Debug for FieldOffset<S, F, A> {
+impl<S: ?Sized, F, A> Debug for FieldOffset<S, F, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("FieldOffset")
             .field("offset", &self.offset)
@@ -253,16 +395,16 @@ impl<S, F, A> Debug for FieldOffset<S, F, A> {
     }
 }
 
-impl<S, F, A> Copy for FieldOffset<S, F, A> {}
+impl<S: ?Sized, F, A> Copy for FieldOffset<S, F, A> {}
 
-impl<S, F, A> Clone for FieldOffset<S, F, A> {
+impl<S: ?Sized, F, A> Clone for FieldOffset<S, F, A> {
     #[inline(always)]
     fn clone(&self) -> Self {
         *self
     }
 }
 
-impl<S, F, A> FieldOffset<S, F, A> {
+impl<S: ?Sized, F, A> FieldOffset<S, F, A> {
     /// Constructs this `FieldOffset` from the offset of the field.
     ///
     /// # Safety
@@ -377,7 +519,10 @@ impl<S, F, A> FieldOffset<S, F, A> {
     ///
     /// [`Aligned`]: ./alignment/struct.Aligned.html
     /// [`Unaligned`]: ./alignment/struct.Unaligned.html
-    pub const unsafe fn next_field_offset<Next, NextA>(self) -> FieldOffset<S, Next, NextA> {
+    pub const unsafe fn next_field_offset<Next, NextA>(self) -> FieldOffset<S, Next, NextA>
+    where
+        S: Sized,
+    {
         let offset = GetNextFieldOffset {
             previous_offset: self.offset,
             previous_size: Mem::<F>::SIZE,
@@ -403,7 +548,7 @@ impl FieldOffset<(), (), Aligned> {
     }
 }
 
-impl<S, F> FieldOffset<S, F, Aligned> {
+impl<S: ?Sized, F> FieldOffset<S, F, Aligned> {
     /// Combines this `FieldOffset` with another one, to access a nested field.
     ///
     /// Note that the resulting `FieldOffset` has the
@@ -447,7 +592,7 @@ impl<S, F> FieldOffset<S, F, Aligned> {
     }
 }
 
-impl<S, F> FieldOffset<S, F, Unaligned> {
+impl<S: ?Sized, F> FieldOffset<S, F, Unaligned> {
     /// Combines this `FieldOffset` with another one, to access a nested field.
     ///
     /// # Example
@@ -528,7 +673,7 @@ where
     }
 }
 
-impl<S, F, A> FieldOffset<S, F, A> {
+impl<S: ?Sized, F, A> FieldOffset<S, F, A> {
     /// The offset (in bytes) of the `F` field in the `S` struct.
     ///
     /// # Example
@@ -558,205 +703,172 @@ impl<S, F, A> FieldOffset<S, F, A> {
     pub const fn offset(self) -> usize {
         self.offset
     }
-}
 
-impl<S, F, A> FieldOffset<S, F, A> {
-    /// Converts this FieldOffset into a [`FieldOffsetWithVis`].
+    /// Gets how many bytes past an `align`-byte boundary the field starts,
+    /// relative to the start of the `S` struct.
     ///
-    /// # Safety
+    /// If this returns 0 and `S` is aligned to at least `align` bytes,
+    /// then the field is aligned to `align` bytes,
+    /// eg: for checking that a field can be used for aligned SIMD loads.
     ///
-    /// The `V` type parameter must be:
-    /// - `[`IsPublic`]`: When the field is `pub`.
+    /// # Panics
     ///
-    /// - [`IsPrivate`]: When the field has the default (private) visibility,
-    /// or has a visibility smaller or equal to `pub(crate)`.
+    /// This panics if `align` is zero.
     ///
-    /// The `FN` type parameter must be the name of the field using the
-    /// `repr_offset::tstr::TS` macro,
-    /// eg: `TS!(foo)` for the `foo` field.
+    /// # Example
     ///
-    /// [`IsPublic`]: ./privacy/struct.IsPublic.html
-    /// [`IsPrivate`]: ./privacy/struct.IsPrivate.html
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
     ///
-    /// [`FieldOffsetWithVis`] ./get_field_offset/struct.FieldOffsetWithVis.html
+    /// type This = ReprC<u8, u32, [u64; 2], ()>;
     ///
-    #[inline(always)]
-    pub const unsafe fn with_vis<V, FN>(self) -> FieldOffsetWithVis<S, V, FN, F, A> {
-        FieldOffsetWithVis::from_fieldoffset(self)
-    }
-}
-
-impl<S, F, A> FieldOffset<S, F, A> {
-    /// Changes the `S` type parameter, most useful for `#[repr(transparent)]` wrappers.
+    /// assert_eq!( This::OFFSET_B.offset_misalignment(4), 0 );
+    /// assert_eq!( This::OFFSET_B.offset_misalignment(16), 4 );
+    /// assert_eq!( This::OFFSET_C.offset_misalignment(8), 0 );
     ///
-    /// # Safety
+    /// type Packed = ReprPacked<u8, u32, (), ()>;
     ///
-    /// Callers must ensure that there is a field of type `F` at the same offset
-    /// inside the `S2` type,
-    /// and is at least as public as this `FieldOffset`.
+    /// assert_eq!( Packed::OFFSET_B.offset_misalignment(4), 1 );
     ///
-    /// If the `A` type parameter is [`Aligned`],
-    /// then the field [must be aligned](#alignment-guidelines)
+    /// ```
+    #[inline(always)]
+    pub const fn offset_misalignment(self, align: usize) -> usize {
+        self.offset % align
+    }
+
+    /// Whether the field is at the start of the `S` struct (at offset 0).
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::FieldOffset;
     /// use repr_offset::for_examples::ReprC;
     ///
-    /// let this = Wrapper(ReprC{
-    ///     a: false,
-    ///     b: 3u8,
-    ///     c: Some('5'),
-    ///     d: [8u32, 13u32],
-    /// });
-    ///
-    /// assert_eq!( cast_offset(ReprC::OFFSET_A).get(&this), &false );
-    /// assert_eq!( cast_offset(ReprC::OFFSET_B).get(&this), &3u8 );
-    /// assert_eq!( cast_offset(ReprC::OFFSET_C).get(&this), &Some('5') );
-    /// assert_eq!( cast_offset(ReprC::OFFSET_D).get(&this), &[8u32, 13u32] );
-    ///
-    ///
-    /// #[repr(transparent)]
-    /// pub struct Wrapper<T>(pub T);
+    /// type This = ReprC<u8, u16, u32, u64>;
     ///
-    /// pub const fn cast_offset<T,F,A>(offset: FieldOffset<T,F,A>) -> FieldOffset<Wrapper<T>,F,A>{
-    ///     // safety: This case is safe because this is a
-    ///     // `#[repr(transparent)]` wrapper around `T`
-    ///     // where `T` is a public field in the wrapper
-    ///     unsafe{ offset.cast_struct() }
-    /// }
+    /// assert!( This::OFFSET_A.is_first() );
+    /// assert!( !This::OFFSET_B.is_first() );
+    /// assert!( !This::OFFSET_C.is_first() );
+    /// assert!( !This::OFFSET_D.is_first() );
     ///
+    /// // Zero-sized fields at the start of the struct are also at offset 0.
+    /// type Zsts = ReprC<(), (), u32, ()>;
     ///
+    /// assert!( Zsts::OFFSET_A.is_first() );
+    /// assert!( Zsts::OFFSET_B.is_first() );
+    /// assert!( Zsts::OFFSET_C.is_first() );
+    /// assert!( !Zsts::OFFSET_D.is_first() );
     ///
     /// ```
-    ///
-    /// [`Aligned`]: ./alignment/struct.Aligned.html
-    /// [`Unaligned`]: ./alignment/struct.Unaligned.html
     #[inline(always)]
-    pub const unsafe fn cast_struct<S2>(self) -> FieldOffset<S2, F, A> {
-        FieldOffset::new(self.offset)
+    pub const fn is_first(self) -> bool {
+        self.offset == 0
     }
 
-    /// Changes the `F` type parameter.
-    ///
-    /// # Safety
+    /// Gets the offset of the field as a `NonZeroUsize`,
+    /// returning `None` for fields at the start of the struct.
     ///
-    /// Callers must ensure that the `F2` type is compatible with the `F` type,
-    /// including size,alignment, and internal layout.
-    ///
-    /// If the `F` type encodes an invariant,
-    /// then callers must ensure that if the field is used as the `F` type
-    /// (including the destructor for the type)
-    /// that the invariants for that type must be upheld.
+    /// This is useful for storing offsets in an `Option<NonZeroUsize>`,
+    /// which is the same size as a `usize`.
     ///
-    /// The same applies if the field is used as the `F2` type
-    /// (if the returned FieldOffset isn't used,then it would not be used as the `F2` type)
+    /// This isn't a `const fn` because `NonZeroUsize::new`
+    /// can't be called in `const fn`s in the minimum supported Rust version.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    ///
-    /// use repr_offset::{Aligned, FieldOffset};
     /// use repr_offset::for_examples::ReprC;
     ///
-    /// type This = ReprC<u8, u64, (), ()>;
+    /// use std::num::NonZeroUsize;
     ///
-    /// let this: This = ReprC{ a: 3, b: 5, c: (), d: () };
+    /// type This = ReprC<u8, u16, u32, u64>;
     ///
-    /// unsafe{
-    ///     assert_eq!( This::OFFSET_A.cast_field::<i8>().get(&this), &3i8 );
-    ///     assert_eq!( This::OFFSET_B.cast_field::<i64>().get(&this), &5i64 );
-    /// }
+    /// assert_eq!( This::OFFSET_A.offset_nonzero(), None );
+    /// assert_eq!( This::OFFSET_B.offset_nonzero(), NonZeroUsize::new(2) );
+    /// assert_eq!( This::OFFSET_C.offset_nonzero(), NonZeroUsize::new(4) );
+    /// assert_eq!( This::OFFSET_D.offset_nonzero(), NonZeroUsize::new(8) );
     ///
     /// ```
-    /// [safe and valid]:
-    /// https://rust-lang.github.io/unsafe-code-guidelines/glossary.html#validity-and-safety-invariant
     #[inline(always)]
-    pub const unsafe fn cast_field<F2>(self) -> FieldOffset<S, F2, A> {
-        FieldOffset::new(self.offset)
+    pub fn offset_nonzero(self) -> Option<NonZeroUsize> {
+        NonZeroUsize::new(self.offset)
     }
 
-    /// Changes this `FieldOffset` to be for a (potentially) unaligned field.
+    /// Gets the offset (in bytes) of this field in the `index`th element of
+    /// an array of `S`, relative to the start of the array.
     ///
-    /// This is useful if you want to get a nested field from an unaligned pointer to a
-    /// `#[repr(C)]`/`#[repr(C,align())]` struct.
+    /// This returns a plain byte offset instead of a `FieldOffset`,
+    /// since the container is the array rather than an `S` struct.
     ///
-    /// # Example
+    /// This is useful for computing offsets into flat arrays of structs,
+    /// eg: arrays of descriptors shared with a GPU or a DMA controller.
+    /// [`flat_offset`](#method.flat_offset) is an alias for this method.
     ///
-    /// This example demonstrates how you can copy a field
-    /// from an unaligned pointer to a `#[repr(C)]` struct.
+    /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
     /// use repr_offset::for_examples::{ReprC, ReprPacked};
     ///
-    /// type Inner = ReprC<usize, &'static str>;
-    /// type Outer = ReprPacked<u8, Inner>;
+    /// type This = ReprC<u32, u64, u16, ()>;
     ///
-    /// let inner = ReprC { a: 3, b: "5", c: (), d: () };
-    /// let outer: Outer = ReprPacked{ a: 21, b: inner, c: (), d: () };
+    /// assert_eq!( This::OFFSET_B.in_array_element(0), 8 );
+    /// assert_eq!( This::OFFSET_B.in_array_element(1), 32 );
+    /// assert_eq!( This::OFFSET_C.in_array_element(2), 64 );
     ///
-    /// let inner_ptr: *const Inner = Outer::OFFSET_B.get_ptr(&outer);
-    /// unsafe{
-    ///     assert_eq!( Inner::OFFSET_A.to_unaligned().read_copy(inner_ptr), 3 );
-    ///     assert_eq!( Inner::OFFSET_B.to_unaligned().read_copy(inner_ptr), "5" );
+    /// type Packed = ReprPacked<u32, u64, u16, ()>;
     ///
-    ///     // This is undefined behavior,
-    ///     // because ReprC's FieldOFfsets require the pointer to be aligned.
-    ///     //
-    ///     // assert_eq!( Inner::OFFSET_A.read_copy(inner_ptr), 3 );
-    ///     // assert_eq!( Inner::OFFSET_B.read_copy(inner_ptr), "5" );
-    /// }
+    /// assert_eq!( Packed::OFFSET_B.in_array_element(0), 4 );
+    /// assert_eq!( Packed::OFFSET_B.in_array_element(1), 18 );
+    /// assert_eq!( Packed::OFFSET_C.in_array_element(2), 40 );
     ///
     /// ```
-    ///
     #[inline(always)]
-    pub const fn to_unaligned(self) -> FieldOffset<S, F, Unaligned> {
-        FieldOffset {
-            offset: self.offset,
-            tys: FOGhosts::NEW,
-        }
+    pub const fn in_array_element(self, index: usize) -> usize
+    where
+        S: Sized,
+    {
+        index * Mem::<S>::SIZE + self.offset
     }
 
-    /// Changes this `FieldOffset` to be for an aligned field.
-    ///
-    /// # Safety
+    /// An alias for [`in_array_element`](#method.in_array_element),
+    /// named after the flat offsets that DMA descriptors and GPU buffers are indexed with.
     ///
-    /// Callers must ensure that [the field is aligned](#alignment-guidelines)
-    /// within the `S` type.
+    /// [`flat_offset_checked`](#method.flat_offset_checked) is the bounds-checked version
+    /// of this method.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::{Aligned, FieldOffset, Unaligned};
-    ///
-    /// // ReprPacked2 is aligned to 2 bytes.
-    /// use repr_offset::for_examples::ReprPacked2;
+    /// use repr_offset::for_examples::ReprC;
     ///
-    /// type This = ReprPacked2<u8, u16, (), ()>;
+    /// type Descriptor = ReprC<u64, u32, u16, u16>;
     ///
-    /// let _: FieldOffset<This, u8, Unaligned> = This::OFFSET_A;
-    /// let _: FieldOffset<This, u16, Unaligned> = This::OFFSET_B;
+    /// const SECOND_LEN: usize = Descriptor::OFFSET_B.flat_offset(1);
     ///
-    /// let this: This = ReprPacked2{ a: 89, b: 144, c: (), d: () };
+    /// assert_eq!( SECOND_LEN, 24 );
+    /// assert_eq!( Descriptor::OFFSET_C.flat_offset(3), 60 );
     ///
-    /// unsafe{
-    ///     assert_eq!( This::OFFSET_A.to_aligned().get(&this), &89 );
-    ///     assert_eq!( This::OFFSET_B.to_aligned().get(&this), &144 );
-    /// }
     /// ```
     #[inline(always)]
-    pub const unsafe fn to_aligned(self) -> FieldOffset<S, F, Aligned> {
-        FieldOffset::new(self.offset)
+    pub const fn flat_offset(self, element_index: usize) -> usize
+    where
+        S: Sized,
+    {
+        self.in_array_element(element_index)
     }
-}
 
-impl<S, F> FieldOffset<S, F, Aligned> {
-    /// Gets a reference to the field that this is an offset for.
+    /// Gets the flat offset (in bytes) of this field in the `element_index`th element of
+    /// a buffer laid out as an `[S; n]` array.
+    ///
+    /// This returns `None` if `element_index` is out of bounds (`element_index >= n`),
+    /// or if the offset doesn't fit in a `usize`.
+    ///
+    /// This isn't a `const fn` because the checked arithmetic methods
+    /// can't be called in `const fn`s in the minimum supported Rust version.
     ///
     /// # Example
     ///
@@ -764,386 +876,2786 @@ impl<S, F> FieldOffset<S, F, Aligned> {
     /// # #![deny(safe_packed_borrows)]
     /// use repr_offset::for_examples::ReprC;
     ///
-    /// let this = ReprC{ a: '@', b: 21u8, c: (), d: () };
+    /// type Descriptor = ReprC<u64, u32, u16, u16>;
     ///
-    /// assert_eq!( ReprC::OFFSET_A.get(&this), &'@' );
-    /// assert_eq!( ReprC::OFFSET_B.get(&this), &21u8 );
+    /// let buffer = [0u8; 64];
+    /// let len = buffer.len() / std::mem::size_of::<Descriptor>();
+    ///
+    /// assert_eq!( Descriptor::OFFSET_B.flat_offset_checked(0, len), Some(8) );
+    /// assert_eq!( Descriptor::OFFSET_B.flat_offset_checked(3, len), Some(56) );
+    /// assert_eq!( Descriptor::OFFSET_B.flat_offset_checked(4, len), None );
     ///
     /// ```
-    #[inline(always)]
-    pub fn get(self, base: &S) -> &F {
-        unsafe { impl_fo!(fn get<S, F, Aligned>(self, base)) }
+    #[inline]
+    pub fn flat_offset_checked(self, element_index: usize, n: usize) -> Option<usize>
+    where
+        S: Sized,
+    {
+        if element_index >= n {
+            return None;
+        }
+        element_index
+            .checked_mul(Mem::<S>::SIZE)?
+            .checked_add(self.offset)
     }
 
-    /// Gets a mutable reference to the field that this is an offset for.
+    /// Gets the amount of bytes between the end of the field and the end of the `S` struct.
+    ///
+    /// For the last field of a struct, this is the trailing padding of the struct.
+    /// For other fields, this includes the fields after this one,
+    /// since a `FieldOffset` doesn't know what the next field is.
+    ///
+    /// The [`ReprOffset`] derive can generate constants with the padding after every field,
+    /// with the `#[roff(padding_consts)]` attribute.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprC;
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
     ///
-    /// let mut this = ReprC{ a: "what", b: '?', c: (), d: () };
+    /// type This = ReprC<u64, u16, u8, ()>;
     ///
-    /// assert_eq!( ReprC::OFFSET_A.get_mut(&mut this), &mut "what" );
-    /// assert_eq!( ReprC::OFFSET_B.get_mut(&mut this), &mut '?' );
+    /// // `c` ends at offset 11, and the struct is 16 bytes large.
+    /// assert_eq!( This::OFFSET_C.trailing_padding(), 5 );
+    ///
+    /// // This includes the `c` field.
+    /// assert_eq!( This::OFFSET_B.trailing_padding(), 6 );
+    ///
+    /// type Packed = ReprPacked<u64, u16, u8, ()>;
+    ///
+    /// assert_eq!( Packed::OFFSET_C.trailing_padding(), 0 );
     ///
     /// ```
+    ///
+    /// [`ReprOffset`]: ./derive.ReprOffset.html
     #[inline(always)]
-    pub fn get_mut(self, base: &mut S) -> &mut F {
-        unsafe { impl_fo!(fn get_mut<S, F, Aligned>(self, base)) }
+    pub const fn trailing_padding(self) -> usize
+    where
+        S: Sized,
+    {
+        Mem::<S>::SIZE - (self.offset + Mem::<F>::SIZE)
     }
 
-    /// Copies the aligned field that this is an offset for.
+    /// The `Layout` of the type of the field,
+    /// for allocating storage for the field elsewhere.
+    ///
+    /// This is equivalent to `Layout::new::<F>()`,
+    /// without having to name the `F` type.
+    ///
+    /// This is not a `const fn` because `Layout::new` is only a `const fn` since Rust 1.42.0.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprC;
+    /// use repr_offset::for_examples::ReprPacked;
     ///
-    /// let this = ReprC{ a: Some(false), b: [8i32, 13, 21], c: (), d: () };
+    /// use std::alloc::{self, Layout};
     ///
-    /// assert_eq!( ReprC::OFFSET_A.get_copy(&this), Some(false) );
-    /// assert_eq!( ReprC::OFFSET_B.get_copy(&this), [8i32, 13, 21] );
+    /// type This = ReprPacked<u8, u64, [u16; 3], ()>;
     ///
-    /// ```
+    /// // The layout of the field type, not of its (unaligned) position in the struct.
+    /// assert_eq!( This::OFFSET_B.field_layout(), Layout::new::<u64>() );
+    /// assert_eq!( This::OFFSET_C.field_layout(), Layout::from_size_align(6, 2).unwrap() );
     ///
-    /// This method can't be called for non-Copy fields.
-    /// ```compile_fail
-    /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprC;
+    /// let this = ReprPacked{ a: 3u8, b: 5u64, c: [8u16, 13, 21], d: () };
     ///
-    /// let this = ReprC{ a: vec![0, 1, 2, 3], b: (), c: (), d: () };
+    /// unsafe{
+    ///     let layout = This::OFFSET_B.field_layout();
+    ///     let ptr = alloc::alloc(layout) as *mut u64;
+    ///     assert!( !ptr.is_null() );
+    ///
+    ///     ptr.write(This::OFFSET_B.read(&this));
+    ///     assert_eq!( *ptr, 5 );
+    ///
+    ///     alloc::dealloc(ptr as *mut u8, layout);
+    /// }
     ///
-    /// let _ = ReprC::OFFSET_A.get_copy(&this);
     /// ```
     #[inline(always)]
-    pub fn get_copy(self, base: &S) -> F
-    where
-        F: Copy,
-    {
-        unsafe { impl_fo!(fn get_copy<S, F, Aligned>(self, base)) }
+    pub fn field_layout(self) -> Layout {
+        Layout::new::<F>()
     }
-}
 
-impl<S, F, A> FieldOffset<S, F, A> {
-    /// Gets a raw pointer to a field from a reference to the `S` struct.
+    /// The alignment of the type of the field.
+    ///
+    /// This is equivalent to `align_of::<F>()`,
+    /// without having to name the `F` type.
+    ///
+    /// With the `"ptr_alignment"` feature, the [`field_alignment`](#method.field_alignment)
+    /// method returns the alignment as the standard library's `Alignment` type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
+    ///
+    /// type This = ReprC<u8, u64, [u16; 3], ()>;
+    ///
+    /// assert_eq!( This::OFFSET_A.field_align(), 1 );
+    /// assert_eq!( This::OFFSET_B.field_align(), std::mem::align_of::<u64>() );
+    /// assert_eq!( This::OFFSET_C.field_align(), 2 );
+    ///
+    /// // The alignment of the field type, not of its (unaligned) position in the struct.
+    /// type Packed = ReprPacked<u8, u64, [u16; 3], ()>;
+    ///
+    /// assert_eq!( Packed::OFFSET_C.field_align(), 2 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub const fn field_align(self) -> usize {
+        Mem::<F>::ALIGN
+    }
+}
+
+impl<S: ?Sized, F, A: Alignment> FieldOffset<S, F, A> {
+    /// Whether this is an offset for an aligned field,
+    /// `true` if `A` is [`Aligned`], `false` if `A` is [`Unaligned`].
+    ///
+    /// This allows code that is generic over the alignment of a `FieldOffset`
+    /// to branch on it.
+    /// In const contexts, the equivalent [`Alignment::IS_ALIGNED`]
+    /// associated constant can be used instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{
+    ///     alignment::Alignment,
+    ///     for_examples::{ReprC, ReprPacked},
+    ///     FieldOffset,
+    /// };
+    ///
+    /// assert!( ReprC::<u8, u32>::OFFSET_B.is_aligned_offset() );
+    /// assert!( !ReprPacked::<u8, u32>::OFFSET_B.is_aligned_offset() );
+    ///
+    /// assert_eq!( describe(ReprC::<u8, u32>::OFFSET_B), "aligned field at offset 4" );
+    /// assert_eq!( describe(ReprPacked::<u8, u32>::OFFSET_B), "unaligned field at offset 1" );
+    ///
+    /// fn describe<S, F, A: Alignment>(offset: FieldOffset<S, F, A>) -> String {
+    ///     let alignment = if offset.is_aligned_offset() { "aligned" } else { "unaligned" };
+    ///     format!("{} field at offset {}", alignment, offset.offset())
+    /// }
+    ///
+    /// ```
+    ///
+    /// [`Aligned`]: ./alignment/struct.Aligned.html
+    /// [`Unaligned`]: ./alignment/struct.Unaligned.html
+    /// [`Alignment::IS_ALIGNED`]: ./alignment/trait.Alignment.html#associatedconstant.IS_ALIGNED
+    #[inline(always)]
+    pub fn is_aligned_offset(self) -> bool {
+        A::IS_ALIGNED
+    }
+}
+
+impl<S, F, A> FieldOffset<S, F, A> {
+    /// Converts this FieldOffset into a [`FieldOffsetWithVis`].
+    ///
+    /// # Safety
+    ///
+    /// The `V` type parameter must be:
+    /// - `[`IsPublic`]`: When the field is `pub`.
+    ///
+    /// - [`IsPrivate`]: When the field has the default (private) visibility,
+    /// or has a visibility smaller or equal to `pub(crate)`.
+    ///
+    /// The `FN` type parameter must be the name of the field using the
+    /// `repr_offset::tstr::TS` macro,
+    /// eg: `TS!(foo)` for the `foo` field.
+    ///
+    /// [`IsPublic`]: ./privacy/struct.IsPublic.html
+    /// [`IsPrivate`]: ./privacy/struct.IsPrivate.html
+    ///
+    /// [`FieldOffsetWithVis`] ./get_field_offset/struct.FieldOffsetWithVis.html
+    ///
+    #[inline(always)]
+    pub const unsafe fn with_vis<V, FN>(self) -> FieldOffsetWithVis<S, V, FN, F, A> {
+        FieldOffsetWithVis::from_fieldoffset(self)
+    }
+}
+
+impl<S, F, A> FieldOffset<S, F, A> {
+    /// Changes the `S` type parameter, most useful for `#[repr(transparent)]` wrappers.
+    ///
+    /// The `F` and `A` type parameters are preserved exactly,
+    /// which makes this usable in code that is generic over the container type,
+    /// converting offsets for any field of a `T` into offsets for the same field
+    /// in a container of `T` (see the second example).
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that there is a field of type `F` at the same offset
+    /// inside the `S2` type,
+    /// and is at least as public as this `FieldOffset`.
+    ///
+    /// If the `A` type parameter is [`Aligned`],
+    /// then the field [must be aligned](#alignment-guidelines)
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
     /// use repr_offset::FieldOffset;
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let this = Wrapper(ReprC{
+    ///     a: false,
+    ///     b: 3u8,
+    ///     c: Some('5'),
+    ///     d: [8u32, 13u32],
+    /// });
+    ///
+    /// assert_eq!( cast_offset(ReprC::OFFSET_A).get(&this), &false );
+    /// assert_eq!( cast_offset(ReprC::OFFSET_B).get(&this), &3u8 );
+    /// assert_eq!( cast_offset(ReprC::OFFSET_C).get(&this), &Some('5') );
+    /// assert_eq!( cast_offset(ReprC::OFFSET_D).get(&this), &[8u32, 13u32] );
+    ///
+    ///
+    /// #[repr(transparent)]
+    /// pub struct Wrapper<T>(pub T);
+    ///
+    /// pub const fn cast_offset<T,F,A>(offset: FieldOffset<T,F,A>) -> FieldOffset<Wrapper<T>,F,A>{
+    ///     // safety: This case is safe because this is a
+    ///     // `#[repr(transparent)]` wrapper around `T`
+    ///     // where `T` is a public field in the wrapper
+    ///     unsafe{ offset.cast_struct() }
+    /// }
+    ///
+    ///
+    ///
+    /// ```
+    ///
+    /// # Generic container example
+    ///
+    /// This example converts an offset into `T` into an offset into a
+    /// `ManuallyDrop<T>`, for any field type and alignment.
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{FieldOffset, Unaligned};
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
+    ///
+    /// use std::mem::ManuallyDrop;
+    ///
+    /// type ThisC = ReprC<&'static str, u64, char, u8>;
+    /// type ThisPacked = ReprPacked<&'static str, u64, char, u8>;
+    ///
+    /// let this = ManuallyDrop::new(ReprC{ a: "foo", b: 3, c: 'X', d: 5 });
+    /// let packed = ManuallyDrop::new(ReprPacked{ a: "bar", b: 8, c: 'Y', d: 13 });
+    ///
+    /// assert_eq!( in_manually_drop(ThisC::OFFSET_A).get(&this), &"foo" );
+    /// assert_eq!( in_manually_drop(ThisC::OFFSET_C).get(&this), &'X' );
+    ///
+    /// // The `Unaligned` type parameter is kept,
+    /// // so the field can only be copied out.
+    /// let offset_b: FieldOffset<ManuallyDrop<ThisPacked>, u64, Unaligned> =
+    ///     in_manually_drop(ThisPacked::OFFSET_B);
+    /// assert_eq!( offset_b.get_copy(&packed), 8 );
+    /// assert_eq!( in_manually_drop(ThisPacked::OFFSET_D).get_copy(&packed), 13 );
+    ///
+    ///
+    /// fn in_manually_drop<S, F, A>(offset: FieldOffset<S, F, A>) -> FieldOffset<ManuallyDrop<S>, F, A> {
+    ///     // safety: `ManuallyDrop<S>` has the same layout as `S`,
+    ///     // and dereferences to it, so all of its fields are at the same offsets.
+    ///     unsafe{ offset.cast_struct() }
+    /// }
+    ///
+    /// ```
+    ///
+    /// [`Aligned`]: ./alignment/struct.Aligned.html
+    /// [`Unaligned`]: ./alignment/struct.Unaligned.html
+    #[inline(always)]
+    pub const unsafe fn cast_struct<S2>(self) -> FieldOffset<S2, F, A> {
+        FieldOffset::new(self.offset)
+    }
+
+    /// Changes the `F` type parameter.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that the `F2` type is compatible with the `F` type,
+    /// including size,alignment, and internal layout.
+    ///
+    /// If the `F` type encodes an invariant,
+    /// then callers must ensure that if the field is used as the `F` type
+    /// (including the destructor for the type)
+    /// that the invariants for that type must be upheld.
+    ///
+    /// The same applies if the field is used as the `F2` type
+    /// (if the returned FieldOffset isn't used,then it would not be used as the `F2` type)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    ///
+    /// use repr_offset::{Aligned, FieldOffset};
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// type This = ReprC<u8, u64, (), ()>;
+    ///
+    /// let this: This = ReprC{ a: 3, b: 5, c: (), d: () };
+    ///
+    /// unsafe{
+    ///     assert_eq!( This::OFFSET_A.cast_field::<i8>().get(&this), &3i8 );
+    ///     assert_eq!( This::OFFSET_B.cast_field::<i64>().get(&this), &5i64 );
+    /// }
+    ///
+    /// ```
+    /// [safe and valid]:
+    /// https://rust-lang.github.io/unsafe-code-guidelines/glossary.html#validity-and-safety-invariant
+    #[inline(always)]
+    pub const unsafe fn cast_field<F2>(self) -> FieldOffset<S, F2, A> {
+        FieldOffset::new(self.offset)
+    }
+
+    /// Changes the `F` type parameter to a type that is at most as large as `F`,
+    /// to access a prefix of the field,
+    /// eg: reading the header at the start of a buffer field.
+    ///
+    /// This causes a compile-time error if `F2` is larger than `F`,
+    /// unlike [`cast_field`](#method.cast_field), which doesn't check the size.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that the first `size_of::<F2>()` bytes of the field
+    /// are compatible with the `F2` type, including internal layout.
+    ///
+    /// If `A` is [`Aligned`], callers must also ensure that the field is
+    /// sufficiently aligned for `F2`,
+    /// otherwise [`to_unaligned`](#method.to_unaligned) can be called on the returned offset.
+    ///
+    /// If the `F` type encodes an invariant,
+    /// then callers must ensure that the invariant is upheld
+    /// when the field is written as the `F2` type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
+    ///
+    /// let mut bytes = [0u8; 16];
+    /// bytes[..4].copy_from_slice(&0x0102_0304u32.to_ne_bytes());
+    ///
+    /// let this = ReprC{ a: 3u32, b: bytes, c: (), d: () };
+    ///
+    /// let header = unsafe{ ReprC::OFFSET_B.cast_field_prefix::<u32>() };
+    /// assert_eq!( header.get(&this), &0x0102_0304 );
+    ///
+    ///
+    /// let this = ReprPacked{ a: 3u8, b: bytes, c: (), d: () };
+    ///
+    /// let header = unsafe{ ReprPacked::OFFSET_B.cast_field_prefix::<u32>() };
+    /// assert_eq!( header.get_copy(&this), 0x0102_0304 );
+    ///
+    /// ```
+    ///
+    /// Casting to a larger type causes a compile-time error:
+    ///
+    /// ```compile_fail
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let this = ReprC{ a: 3u32, b: [0u8; 2], c: (), d: () };
+    ///
+    /// let header = unsafe{ ReprC::OFFSET_B.cast_field_prefix::<u32>() };
+    /// assert_eq!( header.get(&this), &0 );
+    /// ```
+    ///
+    /// [`Aligned`]: ./alignment/struct.Aligned.html
+    #[inline(always)]
+    pub const unsafe fn cast_field_prefix<F2>(self) -> FieldOffset<S, F2, A> {
+        let _: () = AssertFitsIn::<F2, F>::FITS;
+        FieldOffset::new(self.offset)
+    }
+
+    /// Changes the `F` type parameter to `Cell<F>`,
+    /// which has the same layout as `F`.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that the field is not mutated through the `Cell`
+    /// while there are shared references to the `S` struct (or the field),
+    /// unless the field is already inside an `UnsafeCell`.
+    /// Mutating it through a `&mut S` (eg: with [`get_mut`](#method.get_mut)) is fine.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// use std::cell::Cell;
+    ///
+    /// type This = ReprC<u8, u32, (), ()>;
+    ///
+    /// let mut this: This = ReprC{ a: 3, b: 5, c: (), d: () };
+    ///
+    /// {
+    ///     let cell: &Cell<u32> = unsafe{ This::OFFSET_B.as_cell_field().get_mut(&mut this) };
+    ///     let cell_copy = cell;
+    ///     cell.set(8);
+    ///     cell_copy.set(cell_copy.get() + 5);
+    /// }
+    /// assert_eq!( this.b, 13 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub const unsafe fn as_cell_field(self) -> FieldOffset<S, Cell<F>, A> {
+        FieldOffset::new(self.offset)
+    }
+
+    /// Changes the `F` type parameter to `UnsafeCell<F>`,
+    /// which has the same layout as `F`.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that the field is not mutated through the `UnsafeCell`
+    /// while there are shared references to the `S` struct (or the field),
+    /// unless the field is already inside an `UnsafeCell`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// type This = ReprC<u8, u32, (), ()>;
+    ///
+    /// let mut this: This = ReprC{ a: 3, b: 5, c: (), d: () };
+    ///
+    /// unsafe{
+    ///     let cell = This::OFFSET_B.as_unsafe_cell_field().get_mut(&mut this);
+    ///     *cell.get() += 8;
+    /// }
+    /// assert_eq!( this.b, 13 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub const unsafe fn as_unsafe_cell_field(self) -> FieldOffset<S, UnsafeCell<F>, A> {
+        FieldOffset::new(self.offset)
+    }
+
+    /// Adds `bytes` to the offset, returning `None` if that overflows a `usize`.
+    ///
+    /// `Option::unwrap` can only be called in constants since Rust 1.83.0,
+    /// so to check offsets computed in const code,
+    /// match on the returned `Option` and panic on `None` (this requires Rust 1.57.0),
+    /// causing a compile-time error when the addition overflows
+    /// ([example below](#const-example)).
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that there is a field of type `F` at the returned offset
+    /// inside the `S` type,
+    /// and is at least as public as this `FieldOffset`.
+    ///
+    /// If the `A` type parameter is [`Aligned`],
+    /// then the field [must be aligned](#alignment-guidelines)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{Aligned, FieldOffset};
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// type This = ReprC<u32, [u32; 4], (), ()>;
+    ///
+    /// let this: This = ReprC{ a: 3, b: [5, 8, 13, 21], c: (), d: () };
+    ///
+    /// unsafe{
+    ///     // The offset of the third element of the `b` field.
+    ///     let elem_2 = This::OFFSET_B.cast_field::<u32>().checked_add_bytes(8).unwrap();
+    ///     assert_eq!( elem_2.offset(), 12 );
+    ///     assert_eq!( elem_2.get(&this), &13 );
+    ///
+    ///     assert_eq!( This::OFFSET_B.checked_add_bytes(usize::max_value()), None );
+    /// }
+    ///
+    /// ```
+    ///
+    /// # Const Example
+    ///
+    /// This example requires Rust 1.57.0 or later.
+    ///
+    #[cfg_attr(feature = "const_fns", doc = "```rust")]
+    #[cfg_attr(not(feature = "const_fns"), doc = "```ignore")]
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{Aligned, FieldOffset};
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// type This = ReprC<u32, [u32; 4], (), ()>;
+    ///
+    /// // The offset of the third element of the `b` field.
+    /// const ELEM_2: FieldOffset<This, u32, Aligned> =
+    ///     match unsafe{ This::OFFSET_B.cast_field::<u32>().checked_add_bytes(8) } {
+    ///         Some(x) => x,
+    ///         None => panic!("the offset overflowed"),
+    ///     };
+    ///
+    /// let this: This = ReprC{ a: 3, b: [5, 8, 13, 21], c: (), d: () };
+    ///
+    /// assert_eq!( ELEM_2.offset(), 12 );
+    /// assert_eq!( ELEM_2.get(&this), &13 );
+    ///
+    /// ```
+    ///
+    /// [`Aligned`]: ./alignment/struct.Aligned.html
+    #[inline(always)]
+    pub const unsafe fn checked_add_bytes(self, bytes: usize) -> Option<FieldOffset<S, F, A>> {
+        let (offset, overflowed) = self.offset.overflowing_add(bytes);
+        [Some(FieldOffset::new(offset)), None][overflowed as usize]
+    }
+
+    /// Changes this `FieldOffset` to be for a (potentially) unaligned field.
+    ///
+    /// This is useful if you want to get a nested field from an unaligned pointer to a
+    /// `#[repr(C)]`/`#[repr(C,align())]` struct.
+    ///
+    /// # Example
+    ///
+    /// This example demonstrates how you can copy a field
+    /// from an unaligned pointer to a `#[repr(C)]` struct.
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
+    ///
+    /// type Inner = ReprC<usize, &'static str>;
+    /// type Outer = ReprPacked<u8, Inner>;
+    ///
+    /// let inner = ReprC { a: 3, b: "5", c: (), d: () };
+    /// let outer: Outer = ReprPacked{ a: 21, b: inner, c: (), d: () };
+    ///
+    /// let inner_ptr: *const Inner = Outer::OFFSET_B.get_ptr(&outer);
+    /// unsafe{
+    ///     assert_eq!( Inner::OFFSET_A.to_unaligned().read_copy(inner_ptr), 3 );
+    ///     assert_eq!( Inner::OFFSET_B.to_unaligned().read_copy(inner_ptr), "5" );
+    ///
+    ///     // This is undefined behavior,
+    ///     // because ReprC's FieldOFfsets require the pointer to be aligned.
+    ///     //
+    ///     // assert_eq!( Inner::OFFSET_A.read_copy(inner_ptr), 3 );
+    ///     // assert_eq!( Inner::OFFSET_B.read_copy(inner_ptr), "5" );
+    /// }
+    ///
+    /// ```
+    ///
+    #[inline(always)]
+    pub const fn to_unaligned(self) -> FieldOffset<S, F, Unaligned> {
+        FieldOffset {
+            offset: self.offset,
+            tys: FOGhosts::NEW,
+        }
+    }
+
+    /// Changes this `FieldOffset` to be for an aligned field.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that [the field is aligned](#alignment-guidelines)
+    /// within the `S` type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{Aligned, FieldOffset, Unaligned};
+    ///
+    /// // ReprPacked2 is aligned to 2 bytes.
+    /// use repr_offset::for_examples::ReprPacked2;
+    ///
+    /// type This = ReprPacked2<u8, u16, (), ()>;
+    ///
+    /// let _: FieldOffset<This, u8, Unaligned> = This::OFFSET_A;
+    /// let _: FieldOffset<This, u16, Unaligned> = This::OFFSET_B;
+    ///
+    /// let this: This = ReprPacked2{ a: 89, b: 144, c: (), d: () };
+    ///
+    /// unsafe{
+    ///     assert_eq!( This::OFFSET_A.to_aligned().get(&this), &89 );
+    ///     assert_eq!( This::OFFSET_B.to_aligned().get(&this), &144 );
+    /// }
+    /// ```
+    #[inline(always)]
+    pub const unsafe fn to_aligned(self) -> FieldOffset<S, F, Aligned> {
+        FieldOffset::new(self.offset)
+    }
+
+    /// Changes this `FieldOffset` to be for an aligned field,
+    /// which is always safe for fields of types with an alignment of 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{Aligned, FieldOffset, Unaligned};
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// type This = ReprPacked<u32, u8, bool, ()>;
+    ///
+    /// let _: FieldOffset<This, u8, Unaligned> = This::OFFSET_B;
+    ///
+    /// let mut this: This = ReprPacked{ a: 3, b: 5, c: false, d: () };
+    ///
+    /// let offset_b: FieldOffset<This, u8, Aligned> = This::OFFSET_B.to_aligned_if_align_one();
+    /// let offset_c: FieldOffset<This, bool, Aligned> = This::OFFSET_C.to_aligned_if_align_one();
+    ///
+    /// assert_eq!( offset_b.get(&this), &5 );
+    ///
+    /// *offset_c.get_mut(&mut this) = true;
+    /// assert_eq!( offset_c.get(&this), &true );
+    ///
+    /// ```
+    ///
+    /// Fields with a larger alignment can't be converted:
+    ///
+    /// ```compile_fail
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// type This = ReprPacked<u8, u32, (), ()>;
+    ///
+    /// let _ = This::OFFSET_B.to_aligned_if_align_one();
+    /// ```
+    #[inline(always)]
+    pub fn to_aligned_if_align_one(self) -> FieldOffset<S, F, Aligned>
+    where
+        F: AlignOne,
+    {
+        // safety: fields with an alignment of 1 are always aligned
+        unsafe { FieldOffset::new(self.offset) }
+    }
+
+    /// Changes the `S` type parameter to `MaybeUninit<S>`,
+    /// for accessing the field through a pointer to a `MaybeUninit<S>`.
+    ///
+    /// This is sound because `MaybeUninit<S>` has the same layout as `S`.
+    ///
+    /// The extension traits in the [`ext`] module can't be implemented for
+    /// `*mut MaybeUninit<S>` with `S` as the struct type,
+    /// since they're already implemented for `*mut T` with `T` as the struct type,
+    /// this method is used instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{
+    ///     for_examples::ReprC,
+    ///     ROExtRawMutOps,
+    /// };
+    ///
+    /// use std::mem::MaybeUninit;
+    ///
+    /// type This = ReprC<u8, &'static str, (), ()>;
+    ///
+    /// let mut this = MaybeUninit::<This>::uninit();
+    /// let ptr: *mut MaybeUninit<This> = &mut this;
+    ///
+    /// let this = unsafe{
+    ///     ptr.f_write(This::OFFSET_A.in_maybe_uninit(), 3);
+    ///     ptr.f_write(This::OFFSET_B.in_maybe_uninit(), "5");
+    ///     ptr.f_write(This::OFFSET_C.in_maybe_uninit(), ());
+    ///     ptr.f_write(This::OFFSET_D.in_maybe_uninit(), ());
+    ///     this.assume_init()
+    /// };
+    ///
+    /// assert_eq!( this.a, 3 );
+    /// assert_eq!( this.b, "5" );
+    ///
+    /// ```
+    ///
+    /// [`ext`]: ./ext/index.html
+    #[inline(always)]
+    pub const fn in_maybe_uninit(self) -> FieldOffset<MaybeUninit<S>, F, A> {
+        FieldOffset {
+            offset: self.offset,
+            tys: FOGhosts::NEW,
+        }
+    }
+
+    /// Erases the type of the field, returning a [`FieldOffsetDyn`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{for_examples::ReprC, FieldOffsetDyn};
+    ///
+    /// type This = ReprC<u8, u64, [u16; 3], ()>;
+    ///
+    /// let offsets: [FieldOffsetDyn<This>; 3] = [
+    ///     This::OFFSET_A.to_dyn(),
+    ///     This::OFFSET_B.to_dyn(),
+    ///     This::OFFSET_C.to_dyn(),
+    /// ];
+    ///
+    /// assert_eq!( offsets[0].offset(), 0 );
+    /// assert_eq!( offsets[0].size(), 1 );
+    ///
+    /// assert_eq!( offsets[1].offset(), 8 );
+    /// assert_eq!( offsets[1].size(), 8 );
+    ///
+    /// assert_eq!( offsets[2].offset(), 16 );
+    /// assert_eq!( offsets[2].size(), 6 );
+    ///
+    /// ```
+    ///
+    /// [`FieldOffsetDyn`]: ./struct.FieldOffsetDyn.html
+    #[inline(always)]
+    pub const fn to_dyn(self) -> FieldOffsetDyn<S> {
+        FieldOffsetDyn::from_offset(self)
+    }
+
+    /// Erases the type of the field, returning a [`FieldOffsetDyn`] that
+    /// remembers the field type and alignment,
+    /// so that it can be converted back with [`FieldOffsetDyn::downcast`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{for_examples::ReprC, Aligned, FieldOffsetDyn};
+    ///
+    /// type This = ReprC<u8, u64, [u16; 3], ()>;
+    ///
+    /// let this = ReprC{ a: 3, b: 5, c: [8, 13, 21], d: () };
+    ///
+    /// let offset: FieldOffsetDyn<This> = This::OFFSET_C.to_dyn_typed();
+    ///
+    /// assert_eq!( offset.offset(), 16 );
+    /// assert_eq!( offset.size(), 6 );
+    ///
+    /// let offset = offset.downcast::<[u16; 3], Aligned>().unwrap();
+    /// assert_eq!( offset.get(&this), &[8, 13, 21] );
+    ///
+    /// ```
+    ///
+    /// [`FieldOffsetDyn`]: ./struct.FieldOffsetDyn.html
+    /// [`FieldOffsetDyn::downcast`]: ./struct.FieldOffsetDyn.html#method.downcast
+    #[inline]
+    pub fn to_dyn_typed(self) -> FieldOffsetDyn<S>
+    where
+        F: 'static,
+        A: 'static,
+    {
+        FieldOffsetDyn::from_offset_typed(self)
+    }
+
+    /// Gets the `TypeId` of the type of the field.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// use std::any::TypeId;
+    ///
+    /// type This = ReprC<u8, u64, [u16; 3], ()>;
+    ///
+    /// assert_eq!( This::OFFSET_A.field_type_id(), TypeId::of::<u8>() );
+    /// assert_eq!( This::OFFSET_B.field_type_id(), TypeId::of::<u64>() );
+    /// assert_eq!( This::OFFSET_C.field_type_id(), TypeId::of::<[u16; 3]>() );
+    /// assert_ne!( This::OFFSET_D.field_type_id(), TypeId::of::<u8>() );
+    ///
+    /// ```
+    #[inline]
+    pub fn field_type_id(self) -> TypeId
+    where
+        F: 'static,
+    {
+        TypeId::of::<F>()
+    }
+
+    /// Returns the identity `FieldOffset` of the `F` type,
+    /// for starting a new chain of offsets from the type of this field.
+    ///
+    /// This is equivalent to `FieldOffset::identity::<F>()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::{for_examples::ReprC, Aligned, FieldOffset};
+    ///
+    /// type Inner = ReprC<u8, u16, u32, u64>;
+    /// type Outer = ReprC<(), Inner, (), ()>;
+    ///
+    /// let inner = ReprC{ a: 3, b: 5, c: 8, d: 13 };
+    /// let outer: Outer = ReprC{ a: (), b: inner, c: (), d: () };
+    ///
+    /// // The offsets of the fields of `Inner`, starting from a rebased `Outer::OFFSET_B`.
+    /// let in_inner: FieldOffset<Inner, Inner, Aligned> = Outer::OFFSET_B.rebase_to_field();
+    /// let inner_c: FieldOffset<Inner, u32, Aligned> = in_inner + Inner::OFFSET_C;
+    /// let inner_d: FieldOffset<Inner, u64, Aligned> = in_inner + Inner::OFFSET_D;
+    ///
+    /// assert_eq!( inner_c.get(&outer.b), &8 );
+    /// assert_eq!( inner_d.get(&outer.b), &13 );
+    ///
+    /// // Composing the rebased offsets with the offset of the field in `Outer`
+    /// assert_eq!( (Outer::OFFSET_B + inner_c).get(&outer), &8 );
+    /// assert_eq!( (Outer::OFFSET_B + inner_d).get(&outer), &13 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub const fn rebase_to_field(self) -> FieldOffset<F, F, Aligned> {
+        FieldOffset::identity::<F>()
+    }
+
+    /// Pairs this offset with a pointer to the `S` struct,
+    /// returning a [`FieldReader`] to read the field or get a pointer to it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
+    ///
+    /// let this = ReprC{ a: 3u8, b: 5u64, c: (), d: () };
+    /// let packed = ReprPacked{ a: 8u8, b: 13u64, c: (), d: () };
+    ///
+    /// let reader = ReprC::OFFSET_B.at(&this);
+    /// assert_eq!( reader.ptr(), &this.b as *const u64 );
+    /// assert_eq!( unsafe{ reader.copy() }, 5 );
+    ///
+    /// assert_eq!( unsafe{ ReprPacked::OFFSET_B.at(&packed).copy() }, 13 );
+    ///
+    /// ```
+    ///
+    /// [`FieldReader`]: ./struct.FieldReader.html
+    #[inline(always)]
+    pub const fn at(self, base: *const S) -> FieldReader<S, F, A> {
+        FieldReader::new(self, base)
+    }
+}
+
+impl<S: ?Sized, F> FieldOffset<S, F, Aligned> {
+    /// Gets a reference to the field that this is an offset for.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let this = ReprC{ a: '@', b: 21u8, c: (), d: () };
+    ///
+    /// assert_eq!( ReprC::OFFSET_A.get(&this), &'@' );
+    /// assert_eq!( ReprC::OFFSET_B.get(&this), &21u8 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn get(self, base: &S) -> &F {
+        unsafe { impl_fo!(fn get<S, F, Aligned>(self, base)) }
+    }
+
+    /// Gets a mutable reference to the field that this is an offset for.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let mut this = ReprC{ a: "what", b: '?', c: (), d: () };
+    ///
+    /// assert_eq!( ReprC::OFFSET_A.get_mut(&mut this), &mut "what" );
+    /// assert_eq!( ReprC::OFFSET_B.get_mut(&mut this), &mut '?' );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn get_mut(self, base: &mut S) -> &mut F {
+        unsafe { impl_fo!(fn get_mut<S, F, Aligned>(self, base)) }
+    }
+
+    /// Immutably borrows the struct in the `RefCell`,
+    /// returning a borrow of only the field that this is an offset for.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `RefCell` is currently mutably borrowed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// use std::cell::{Ref, RefCell};
+    ///
+    /// let cell = RefCell::new(ReprC{ a: "what", b: 3u32, c: (), d: () });
+    ///
+    /// let a: Ref<'_, &str> = ReprC::OFFSET_A.borrow_field(&cell);
+    /// let b: Ref<'_, u32> = ReprC::OFFSET_B.borrow_field(&cell);
+    ///
+    /// assert_eq!( *a, "what" );
+    /// assert_eq!( *b, 3 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn borrow_field(self, cell: &RefCell<S>) -> Ref<'_, F> {
+        Ref::map(cell.borrow(), |base| self.get(base))
+    }
+
+    /// Mutably borrows the struct in the `RefCell`,
+    /// returning a mutable borrow of only the field that this is an offset for.
+    ///
+    /// The entire struct stays mutably borrowed until the returned `RefMut` is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `RefCell` is currently borrowed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// use std::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(ReprC{ a: String::from("what"), b: 3u32, c: (), d: () });
+    ///
+    /// *ReprC::OFFSET_B.borrow_field_mut(&cell) += 5;
+    /// ReprC::OFFSET_A.borrow_field_mut(&cell).push_str("ever");
+    ///
+    /// let this = cell.into_inner();
+    /// assert_eq!( this.a, "whatever" );
+    /// assert_eq!( this.b, 8 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn borrow_field_mut(self, cell: &RefCell<S>) -> RefMut<'_, F> {
+        RefMut::map(cell.borrow_mut(), |base| self.get_mut(base))
+    }
+
+    /// Gets a `NonNull` pointer to the field that this is an offset for,
+    /// derived from the mutable reference to the struct.
+    ///
+    /// This is for storing pointers to fields for later use,
+    /// eg: when building self-referential structures
+    /// (which require the struct to be pinned, or otherwise not moved).
+    ///
+    /// # Provenance and aliasing
+    ///
+    /// The returned pointer is derived from a `&mut F` to the field,
+    /// so it's only valid to access the field through it,
+    /// accessing other fields through it is Undefined Behavior.
+    ///
+    /// Using `base` (or any pointer derived from it) to access the field
+    /// invalidates the returned pointer,
+    /// and the pointer also becomes dangling once the struct is moved or dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let mut this = ReprC{ a: "what", b: 3u32, c: (), d: () };
+    ///
+    /// let ptr = ReprC::OFFSET_B.field_non_null_mut(&mut this);
+    ///
+    /// unsafe{
+    ///     *ptr.as_ptr() += 5;
+    ///     assert_eq!( *ptr.as_ref(), 8 );
+    /// }
+    ///
+    /// assert_eq!( this.b, 8 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn field_non_null_mut(self, base: &mut S) -> NonNull<F> {
+        NonNull::from(self.get_mut(base))
+    }
+
+    /// Copies the aligned field that this is an offset for.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let this = ReprC{ a: Some(false), b: [8i32, 13, 21], c: (), d: () };
+    ///
+    /// assert_eq!( ReprC::OFFSET_A.get_copy(&this), Some(false) );
+    /// assert_eq!( ReprC::OFFSET_B.get_copy(&this), [8i32, 13, 21] );
+    ///
+    /// ```
+    ///
+    /// This method can't be called for non-Copy fields.
+    /// ```compile_fail
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let this = ReprC{ a: vec![0, 1, 2, 3], b: (), c: (), d: () };
+    ///
+    /// let _ = ReprC::OFFSET_A.get_copy(&this);
+    /// ```
+    #[inline(always)]
+    pub fn get_copy(self, base: &S) -> F
+    where
+        F: Copy,
+    {
+        unsafe { impl_fo!(fn get_copy<S, F, Aligned>(self, base)) }
+    }
+}
+
+impl<S: ?Sized, F, A> FieldOffset<S, F, A> {
+    /// Gets a raw pointer to a field from a reference to the `S` struct.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::FieldOffset;
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    ///
+    /// let ptr_a = ReprPacked::OFFSET_A.get_ptr(&this);
+    /// // A `u8` is always aligned,so a `.read()` is fine.
+    /// assert_eq!( unsafe{ ptr_a.read() }, 3u8 );
+    ///
+    /// let ptr_b = ReprPacked::OFFSET_B.get_ptr(&this);
+    /// // ReprPacked has an alignment of 1,
+    /// // so this u16 field has to be copied with `.read_unaligned()`.
+    /// assert_eq!( unsafe{ ptr_b.read_unaligned() }, 5u16 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn get_ptr(self, base: &S) -> *const F {
+        unsafe { impl_fo!(fn get_ptr<S, F, A>(self, base)) }
+    }
+
+    /// Gets a mutable raw pointer to a field from a mutable reference to the `S` struct.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::FieldOffset;
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let mut this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    ///
+    /// let ptr_a = ReprPacked::OFFSET_A.get_mut_ptr(&mut this);
+    /// unsafe{
+    ///     // A `u8` is always aligned,so a `.read()` is fine.
+    ///     assert_eq!( ptr_a.read(), 3u8 );
+    ///     ptr_a.write(103);
+    ///     assert_eq!( ptr_a.read(), 103 );
+    /// }
+    ///
+    /// let ptr_b = ReprPacked::OFFSET_B.get_mut_ptr(&mut this);
+    /// unsafe{
+    ///     // ReprPacked has an alignment of 1,
+    ///     // so this u16 field has to be read with `.read_unaligned()`.
+    ///     assert_eq!( ptr_b.read_unaligned(), 5u16 );
+    ///     ptr_b.write_unaligned(105);
+    ///     assert_eq!( ptr_b.read_unaligned(), 105 );
+    /// }
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn get_mut_ptr(self, base: &mut S) -> *mut F {
+        unsafe { impl_fo!(fn get_mut_ptr<S, F, A>(self, base)) }
+    }
+
+    /// Gets a raw pointer to a field from a pointer to the `S` struct.
+    ///
+    /// # Safety
+    ///
+    /// This has the same safety requirements as the [`<*const T>::offset`] method.
+    ///
+    /// [`<*const T>::offset`]:
+    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.offset
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::FieldOffset;
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    ///
+    /// let ptr: *const _ = &this;
+    ///
+    /// unsafe{
+    ///     // A `u8` is always aligned,so a `.read()` is fine.
+    ///     assert_eq!( ReprPacked::OFFSET_A.raw_get(ptr).read(), 3u8 );
+    ///     
+    ///     // ReprPacked has an alignment of 1,
+    ///     // so this u16 field has to be copied with `.read_unaligned()`.
+    ///     assert_eq!( ReprPacked::OFFSET_B.raw_get(ptr).read_unaligned(), 5u16 );
+    /// }
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn raw_get(self, base: *const S) -> *const F {
+        impl_fo!(fn raw_get<S, F, A>(self, base))
+    }
+
+    /// Gets a mutable raw pointer to a field from a pointer to the `S` struct.
+    ///
+    /// # Safety
+    ///
+    /// This has the same safety requirements as the [`<*mut T>::offset`] method.
+    ///
+    /// [`<*mut T>::offset`]:
+    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.offset-1
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::FieldOffset;
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let mut this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    ///
+    /// let ptr: *mut _ = &mut this;
+    ///
+    /// unsafe{
+    ///     let ptr_a = ReprPacked::OFFSET_A.raw_get_mut(ptr);
+    ///
+    ///     // A `u8` is always aligned,so a `.read()` is fine.
+    ///     assert_eq!( ptr_a.read(), 3u8 );
+    ///     ptr_a.write(103);
+    ///     assert_eq!( ptr_a.read(), 103 );
+    ///
+    ///
+    ///     let ptr_b = ReprPacked::OFFSET_B.raw_get_mut(ptr);
+    ///
+    ///     // ReprPacked has an alignment of 1,
+    ///     // so this u16 field has to be read with `.read_unaligned()`.
+    ///     assert_eq!( ptr_b.read_unaligned(), 5u16 );
+    ///     ptr_b.write_unaligned(105);
+    ///     assert_eq!( ptr_b.read_unaligned(), 105 );
+    /// }
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn raw_get_mut(self, base: *mut S) -> *mut F {
+        impl_fo!(fn raw_get_mut<S, F, A>(self, base))
+    }
+
+    /// Gets a raw pointer to a field from a pointer to the `S` struct.
+    ///
+    /// # Safety
+    ///
+    /// While calling this method is not by itself unsafe,
+    /// using the pointer returned by this method has the same safety requirements
+    /// as the [`<*const T>::wrapping_offset`] method.
+    ///
+    /// [`<*const T>::wrapping_offset`]:
+    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_offset
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::FieldOffset;
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    ///
+    /// let ptr_a = ReprPacked::OFFSET_A.wrapping_raw_get(&this);
+    /// // A `u8` is always aligned,so a `.read()` is fine.
+    /// assert_eq!( unsafe{ ptr_a.read() }, 3u8 );
+    ///
+    /// let ptr_b = ReprPacked::OFFSET_B.wrapping_raw_get(&this);
+    /// // ReprPacked has an alignment of 1,
+    /// // so this u16 field has to be copied with `.read_unaligned()`.
+    /// assert_eq!( unsafe{ ptr_b.read_unaligned() }, 5u16 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn wrapping_raw_get(self, base: *const S) -> *const F {
+        (base as *const u8).wrapping_offset(self.offset as isize) as *const F
+    }
+
+    /// Gets a mutable raw pointer to a field from a pointer to the `S` struct.
+    ///
+    /// # Safety
+    ///
+    /// While calling this method is not by itself unsafe,
+    /// using the pointer returned by this method has the same safety requirements
+    /// as the [`<*mut T>::wrapping_offset`] method.
+    ///
+    /// [`<*mut T>::wrapping_offset`]:
+    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_offset-1
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::FieldOffset;
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let mut this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    ///
+    /// let ptr: *mut _ = &mut this;
+    ///
+    /// let ptr_a = ReprPacked::OFFSET_A.wrapping_raw_get_mut(ptr);
+    /// unsafe{
+    ///
+    ///     // A `u8` is always aligned,so a `.read()` is fine.
+    ///     assert_eq!( ptr_a.read(), 3u8 );
+    ///     ptr_a.write(103);
+    ///     assert_eq!( ptr_a.read(), 103 );
+    /// }
+    ///
+    /// let ptr_b = ReprPacked::OFFSET_B.wrapping_raw_get_mut(ptr);
+    /// unsafe{
+    ///
+    ///     // ReprPacked has an alignment of 1,
+    ///     // so this u16 field has to be read with `.read_unaligned()`.
+    ///     assert_eq!( ptr_b.read_unaligned(), 5u16 );
+    ///     ptr_b.write_unaligned(105);
+    ///     assert_eq!( ptr_b.read_unaligned(), 105 );
+    /// }
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn wrapping_raw_get_mut(self, base: *mut S) -> *mut F {
+        (base as *mut u8).wrapping_offset(self.offset as isize) as *mut F
+    }
+
+    /// Gets a raw pointer to a field from a pointer to the `S` struct,
+    /// asserting in debug builds that the field is within the bounds of `S`.
+    ///
+    /// This catches offsets that were constructed incorrectly
+    /// when the pointer to the field is created,
+    /// rather than when the pointer is dereferenced.
+    ///
+    /// In release builds this is equivalent to [`wrapping_raw_get`](#method.wrapping_raw_get).
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, this panics if the field ends past the end of the `S` struct,
+    /// ie: if `self.offset() + size_of::<F>() > size_of::<S>()`.
+    ///
+    /// # Safety
+    ///
+    /// While calling this method is not by itself unsafe,
+    /// using the pointer returned by this method has the same safety requirements
+    /// as the [`<*const T>::wrapping_offset`] method.
+    ///
+    /// [`<*const T>::wrapping_offset`]:
+    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_offset
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    ///
+    /// let ptr_b = ReprPacked::OFFSET_B.raw_get_checked(&this);
+    /// // ReprPacked has an alignment of 1,
+    /// // so this u16 field has to be copied with `.read_unaligned()`.
+    /// assert_eq!( unsafe{ ptr_b.read_unaligned() }, 5u16 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn raw_get_checked(self, base: *const S) -> *const F
+    where
+        S: Sized,
+    {
+        self.debug_assert_in_bounds();
+        self.wrapping_raw_get(base)
+    }
+
+    /// Gets a mutable raw pointer to a field from a pointer to the `S` struct,
+    /// asserting in debug builds that the field is within the bounds of `S`.
+    ///
+    /// This catches offsets that were constructed incorrectly
+    /// when the pointer to the field is created,
+    /// rather than when the pointer is dereferenced.
+    ///
+    /// In release builds this is equivalent to
+    /// [`wrapping_raw_get_mut`](#method.wrapping_raw_get_mut).
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, this panics if the field ends past the end of the `S` struct,
+    /// ie: if `self.offset() + size_of::<F>() > size_of::<S>()`.
+    ///
+    /// # Safety
+    ///
+    /// While calling this method is not by itself unsafe,
+    /// using the pointer returned by this method has the same safety requirements
+    /// as the [`<*mut T>::wrapping_offset`] method.
+    ///
+    /// [`<*mut T>::wrapping_offset`]:
+    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_offset-1
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
+    ///
+    /// let mut this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    ///
+    /// let ptr_b = ReprPacked::OFFSET_B.raw_get_checked_mut(&mut this);
+    /// unsafe{ ptr_b.write_unaligned(105); }
+    /// assert_eq!( moved(this.b), 105 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn raw_get_checked_mut(self, base: *mut S) -> *mut F
+    where
+        S: Sized,
+    {
+        self.debug_assert_in_bounds();
+        self.wrapping_raw_get_mut(base)
+    }
+
+    #[inline(always)]
+    fn debug_assert_in_bounds(self)
+    where
+        S: Sized,
+    {
+        debug_assert!(
+            self.offset.saturating_add(Mem::<F>::SIZE) <= Mem::<S>::SIZE,
+            "the field at offset {} with size {} is out of bounds of a struct of size {}",
+            self.offset,
+            Mem::<F>::SIZE,
+            Mem::<S>::SIZE,
+        );
+    }
+
+    /// Gets the range of addresses that the field occupies,
+    /// given a pointer to the `S` struct.
+    ///
+    /// This is useful for checking whether a field crosses a
+    /// cache line or page boundary.
+    ///
+    /// This uses wrapping pointer arithmetic, so `base` doesn't need to be valid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// type This = ReprC<u8, u32, [u64; 8], ()>;
+    ///
+    /// let this: This = ReprC{ a: 3, b: 5, c: [8; 8], d: () };
+    /// let base = &this as *const This as *const u8;
+    ///
+    /// let range = This::OFFSET_C.addr_range(&this);
+    /// assert_eq!( range.start, base.wrapping_add(8) );
+    /// assert_eq!( range.end, base.wrapping_add(72) );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn addr_range(self, base: *const S) -> Range<*const u8> {
+        let start = (base as *const u8).wrapping_add(self.offset);
+        start..start.wrapping_add(Mem::<F>::SIZE)
+    }
+
+    /// Gets the range of addresses that the field occupies,
+    /// given a mutable pointer to the `S` struct.
+    ///
+    /// This uses wrapping pointer arithmetic, so `base` doesn't need to be valid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// type This = ReprPacked<u8, u32, [u8; 3], ()>;
+    ///
+    /// let mut this: This = ReprPacked{ a: 3, b: 5, c: [0; 3], d: () };
+    /// let base = &mut this as *mut This as *mut u8;
+    ///
+    /// let range = This::OFFSET_C.addr_range_mut(&mut this);
+    /// assert_eq!( range.start, base.wrapping_add(5) );
+    /// assert_eq!( range.end, base.wrapping_add(8) );
+    ///
+    /// unsafe{ range.start.write_bytes(13, 3); }
+    /// assert_eq!( this.c, [13; 3] );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn addr_range_mut(self, base: *mut S) -> Range<*mut u8> {
+        let start = (base as *mut u8).wrapping_add(self.offset);
+        start..start.wrapping_add(Mem::<F>::SIZE)
+    }
+
+    /// Gets a pointer to the field, and a pointer to the byte right after the field,
+    /// given a pointer to the `S` struct.
+    ///
+    /// The second pointer is where the next field starts if there's no padding after this one,
+    /// which is useful for cursor-style parsers of `#[repr(C)]` layouts.
+    ///
+    /// # Safety
+    ///
+    /// This has the same safety requirements as the [`<*const T>::offset`] method.
+    ///
+    /// [`<*const T>::offset`]:
+    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.offset
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// type Header = ReprPacked<u8, u16, (), ()>;
+    ///
+    /// let buffer = [3u8, 5, 0, 8, 13];
+    /// let ptr = buffer.as_ptr() as *const Header;
+    ///
+    /// unsafe{
+    ///     let (len, rest) = Header::OFFSET_B.split_field_ptr(ptr);
+    ///     assert_eq!( len.read_unaligned(), u16::from_ne_bytes([5, 0]) );
+    ///     assert_eq!( rest, buffer.as_ptr().add(3) );
+    ///     assert_eq!( *rest, 8 );
+    /// }
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn split_field_ptr(self, base: *const S) -> (*const F, *const u8) {
+        let field = self.raw_get(base);
+        (field, (field as *const u8).add(Mem::<F>::SIZE))
+    }
+}
+
+impl<S: ?Sized, F> FieldOffset<S, F, Aligned> {
+    /// Copies the aligned field that this is an offset for.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::read`](https://doc.rust-lang.org/std/ptr/fn.read.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let this = ReprC{ a: 10u8, b: "20", c: (), d: () };
+    ///
+    /// let ptr: *const _ = &this;
+    /// unsafe{
+    ///     assert_eq!( ReprC::OFFSET_A.read_copy(ptr), 10u8 );
+    ///     assert_eq!( ReprC::OFFSET_B.read_copy(ptr), "20" );
+    /// }
+    /// ```
+    ///
+    /// This method can't be called for non-Copy fields.
+    /// ```compile_fail
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let this = ReprC{ a: vec![0, 1, 2, 3], b: (), c: (), d: () };
+    /// unsafe{
+    ///     let _ = ReprC::OFFSET_A.read_copy(&this);
+    /// }
+    /// ```
+    ///
+    #[inline(always)]
+    pub unsafe fn read_copy(self, base: *const S) -> F
+    where
+        F: Copy,
+    {
+        impl_fo!(fn read_copy<S, F, Aligned>(self, base))
+    }
+
+    /// Copies the field in `base`, returning `None` if it's equal to `sentinel`.
+    ///
+    /// This is for fields where a sentinel value means that the field is absent,
+    /// eg: C APIs that use `-1` or `u32::MAX` as a null value.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::read`](https://doc.rust-lang.org/std/ptr/fn.read.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let absent = ReprC{ a: 3u8, b: -1i32, c: (), d: () };
+    /// let present = ReprC{ a: 3u8, b: 5i32, c: (), d: () };
+    ///
+    /// unsafe{
+    ///     assert_eq!( ReprC::OFFSET_B.read_sentinel(&absent, -1), None );
+    ///     assert_eq!( ReprC::OFFSET_B.read_sentinel(&present, -1), Some(5) );
+    /// }
+    /// ```
+    #[inline(always)]
+    pub unsafe fn read_sentinel(self, base: *const S, sentinel: F) -> Option<F>
+    where
+        F: PartialEq + Copy,
+    {
+        let value = self.read_copy(base);
+        if value == sentinel {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Reads the value from the field in `source` without moving it.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::read`](https://doc.rust-lang.org/std/ptr/fn.read.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// use std::mem::ManuallyDrop;
+    ///
+    /// let this = ManuallyDrop::new(ReprC{
+    ///     a: vec![0, 1, 2],
+    ///     b: "20".to_string(),
+    ///     c: (),
+    ///     d: (),
+    /// });
+    ///
+    /// let ptr: *const _ = &*this;
+    /// unsafe{
+    ///     assert_eq!( ReprC::OFFSET_A.read(ptr), vec![0, 1, 2] );
+    ///     assert_eq!( ReprC::OFFSET_B.read(ptr), "20".to_string() );
+    /// }
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn read(self, source: *const S) -> F {
+        impl_fo!(fn read<S, F, Aligned>(self, source))
+    }
+
+    /// Reads the value from the field in `source` without moving it,
+    /// wrapping it in a `ManuallyDrop` so that it's only dropped if the caller does so.
+    ///
+    /// The field in `source` still logically owns the value,
+    /// so the returned copy must not be dropped unless the field is
+    /// overwritten without dropping it, or is otherwise never used again.
+    ///
+    /// This is useful for temporarily duplicating the bits of a field,
+    /// like when swapping fields, where an accidental drop would cause a double free.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::read`](https://doc.rust-lang.org/std/ptr/fn.read.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// use std::mem::ManuallyDrop;
+    ///
+    /// type This = ReprC<String, String, (), ()>;
+    ///
+    /// let mut this: This = ReprC{ a: "foo".to_string(), b: "bar".to_string(), c: (), d: () };
+    ///
+    /// let ptr: *mut This = &mut this;
+    /// unsafe{
+    ///     let a = This::OFFSET_A.read_manually_drop(ptr);
+    ///     let b = This::OFFSET_B.read_manually_drop(ptr);
+    ///     This::OFFSET_A.write(ptr, ManuallyDrop::into_inner(b));
+    ///     This::OFFSET_B.write(ptr, ManuallyDrop::into_inner(a));
+    /// }
+    /// assert_eq!( This::OFFSET_A.replace_mut(&mut this, String::new()), "bar" );
+    /// assert_eq!( This::OFFSET_B.replace_mut(&mut this, String::new()), "foo" );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn read_manually_drop(self, source: *const S) -> ManuallyDrop<F> {
+        ManuallyDrop::new(self.read(source))
+    }
+
+    /// Writes `value` ìnto the field in `destination` without dropping the old value of the field.
+    ///
+    /// This allows uninitialized fields to be initialized,since doing
+    /// `*OFFSET_FOO.raw_get_mut(ptr) = value;` would drop uninitialized memory.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let mut this = ReprC{ a: 10u8, b: "20", c: (), d: () };
+    ///
+    /// let ptr: *mut _ = &mut this;
+    /// unsafe{
+    ///     ReprC::OFFSET_A.write(ptr, 13u8);
+    ///     ReprC::OFFSET_B.write(ptr, "21");
+    /// }
+    /// assert_eq!( this.a, 13u8 );
+    /// assert_eq!( this.b, "21" );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn write(self, destination: *mut S, value: F) {
+        impl_fo!(fn write<S, F, Aligned>(self, destination, value))
+    }
+
+    /// Copies the field in `source` into `destination`.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::copy`](https://doc.rust-lang.org/std/ptr/fn.copy.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let this = ReprC{ a: 10u8, b: "20", c: (), d: () };
+    /// let mut other = ReprC{ a: 0u8, b: "", c: (), d: () };
+    ///
+    /// let this_ptr: *const _ = &this;
+    /// let other_ptr: *mut _ = &mut other;
+    /// unsafe{
+    ///     ReprC::OFFSET_A.copy(this_ptr, other_ptr);
+    ///     ReprC::OFFSET_B.copy(this_ptr, other_ptr);
+    /// }
+    /// assert_eq!( this.a, 10u8 );
+    /// assert_eq!( this.b, "20" );
+    ///
+    /// assert_eq!( other.a, 10u8 );
+    /// assert_eq!( other.b, "20" );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn copy(self, source: *const S, destination: *mut S) {
+        impl_fo!(fn copy<S, F, Aligned>(self, source, destination))
+    }
+
+    /// Copies the field in `source` into `destination`,
+    /// `source` and `destination` must not overlap.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::copy_nonoverlapping`
+    /// ](https://doc.rust-lang.org/std/ptr/fn.copy_nonoverlapping.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let this = ReprC{ a: '#', b: 81, c: (), d: () };
+    /// let mut other = ReprC{ a: '_', b: 0, c: (), d: () };
+    ///
+    /// let this_ptr: *const _ = &this;
+    /// let other_ptr: *mut _ = &mut other;
+    /// unsafe{
+    ///     ReprC::OFFSET_A.copy_nonoverlapping(this_ptr, other_ptr);
+    ///     ReprC::OFFSET_B.copy_nonoverlapping(this_ptr, other_ptr);
+    /// }
+    /// assert_eq!( this.a, '#' );
+    /// assert_eq!( this.b, 81 );
+    ///
+    /// assert_eq!( other.a, '#' );
+    /// assert_eq!( other.b, 81 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn copy_nonoverlapping(self, source: *const S, destination: *mut S) {
+        impl_fo!(fn copy_nonoverlapping<S, F, Aligned>(self, source, destination))
+    }
+
+    /// Replaces the value of a field in `destination` with `value`,
+    /// returning the old value of the field.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::replace`](https://doc.rust-lang.org/std/ptr/fn.replace.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let mut this = ReprC{ a: [0u8, 1], b: false, c: (), d: () };
+    ///
+    /// let ptr: *mut _ = &mut this;
+    /// unsafe{
+    ///     assert_eq!( ReprC::OFFSET_A.replace(ptr, [2, 3]), [0u8, 1] );
+    ///     assert_eq!( ReprC::OFFSET_B.replace(ptr, true), false );
+    /// }
+    ///
+    /// assert_eq!( this.a, [2u8, 3] );
+    /// assert_eq!( this.b, true );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn replace(self, destination: *mut S, value: F) -> F {
+        impl_fo!(fn replace<S, F, Aligned>(self, destination, value))
+    }
+
+    /// Replaces the value of a field in `destination` with `value`,
+    /// returning the old value of the field.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let mut this = ReprC{ a: [0u8, 1], b: false, c: (), d: () };
+    ///
+    /// assert_eq!( ReprC::OFFSET_A.replace_mut(&mut this, [2, 3]), [0u8, 1] );
+    /// assert_eq!( ReprC::OFFSET_B.replace_mut(&mut this, true), false );
+    ///
+    /// assert_eq!( this.a, [2u8, 3] );
+    /// assert_eq!( this.b, true );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn replace_mut(self, destination: &mut S, value: F) -> F {
+        unsafe { impl_fo!(fn replace_mut<S, F, Aligned>(self, destination, value)) }
+    }
+
+    /// Swaps the values of a field between the `left` and `right` pointers.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::swap`](https://doc.rust-lang.org/std/ptr/fn.swap.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let mut this = ReprC{ a: '=', b: 64u16, c: (), d: () };
+    /// let mut other = ReprC{ a: '!', b: 255u16, c: (), d: () };
+    ///
+    /// let this_ptr: *mut _ = &mut this;
+    /// let other_ptr: *mut _ = &mut other;
+    /// unsafe{
+    ///     ReprC::OFFSET_A.swap(this_ptr, other_ptr);
+    ///     ReprC::OFFSET_B.swap(this_ptr, other_ptr);
+    /// }
+    /// assert_eq!( this.a, '!' );
+    /// assert_eq!( this.b, 255 );
+    ///
+    /// assert_eq!( other.a, '=' );
+    /// assert_eq!( other.b, 64 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn swap(self, left: *mut S, right: *mut S) {
+        impl_fo!(fn swap<S, F, Aligned>(self, left, right))
+    }
+
+    /// Swaps the values of a field between the `left` and `right` non-overlapping pointers.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::swap_nonoverlapping`
+    /// ](https://doc.rust-lang.org/std/ptr/fn.swap_nonoverlapping.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let mut this = ReprC{ a: [false, true], b: &27u32, c: (), d: () };
+    /// let mut other = ReprC{ a: [true, false], b: &81u32, c: (), d: () };
+    ///
+    /// let this_ptr: *mut _ = &mut this;
+    /// let other_ptr: *mut _ = &mut other;
+    /// unsafe{
+    ///     ReprC::OFFSET_A.swap_nonoverlapping(this_ptr, other_ptr);
+    ///     ReprC::OFFSET_B.swap_nonoverlapping(this_ptr, other_ptr);
+    /// }
+    /// assert_eq!( this.a, [true, false] );
+    /// assert_eq!( this.b, &81 );
+    ///
+    /// assert_eq!( other.a, [false, true] );
+    /// assert_eq!( other.b, &27 );
+    ///
+    /// ```
+    ///
+    #[inline(always)]
+    pub unsafe fn swap_nonoverlapping(self, left: *mut S, right: *mut S) {
+        impl_fo!(fn swap_nonoverlapping<S, F, Aligned>(self, left, right))
+    }
+
+    /// Swaps the values of a field between `left` and `right`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let mut this = ReprC{ a: [true, true], b: 0x0Fu8, c: (), d: () };
+    /// let mut other = ReprC{ a: [false, false], b: 0xF0u8, c: (), d: () };
+    ///
+    /// ReprC::OFFSET_A.swap_mut(&mut this, &mut other);
+    /// ReprC::OFFSET_B.swap_mut(&mut this, &mut other);
+    ///
+    /// assert_eq!( this.a, [false, false] );
+    /// assert_eq!( this.b, 0xF0u8 );
+    ///
+    /// assert_eq!( other.a, [true, true] );
+    /// assert_eq!( other.b, 0x0Fu8 );
+    ///
+    /// ```
+    ///
+    #[inline(always)]
+    pub fn swap_mut(self, left: &mut S, right: &mut S) {
+        unsafe { impl_fo!(fn swap_mut<S, F, Aligned>(self, left, right)) }
+    }
+
+    /// Compares the values of this field in `left` and `right`.
+    ///
+    /// This is useful for sorting structs by a field,
+    /// and can be combined with `Ordering::then_with` to compare by multiple fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let mut list = vec![
+    ///     ReprC{ a: 3u8, b: "world", c: (), d: () },
+    ///     ReprC{ a: 5u8, b: "hello", c: (), d: () },
+    ///     ReprC{ a: 3u8, b: "hello", c: (), d: () },
+    /// ];
+    ///
+    /// list.sort_by(|l, r| {
+    ///     ReprC::OFFSET_A.compare_field(l, r)
+    ///         .then_with(|| ReprC::OFFSET_B.compare_field(l, r))
+    /// });
+    ///
+    /// let fields = list.iter().map(|x| (x.a, x.b)).collect::<Vec<_>>();
+    /// assert_eq!( fields, vec![(3, "hello"), (3, "world"), (5, "hello")] );
+    ///
+    /// ```
+    ///
+    #[inline(always)]
+    pub fn compare_field(self, left: &S, right: &S) -> Ordering
+    where
+        F: Ord,
+    {
+        self.get(left).cmp(self.get(right))
+    }
+
+    /// Feeds the value of this field in `base` into `state`.
+    ///
+    /// This is useful for implementing `Hash` over a subset of the fields of a struct,
+    /// ignoring fields like caches.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::{Hash, Hasher};
+    ///
+    /// type This = ReprC<u8, u64, &'static str, ()>;
+    ///
+    /// fn hash_ac(this: &This) -> u64 {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     This::OFFSET_A.hash_field(this, &mut hasher);
+    ///     This::OFFSET_C.hash_field(this, &mut hasher);
+    ///     hasher.finish()
+    /// }
+    ///
+    /// let this = ReprC{ a: 3, b: 5, c: "foo", d: () };
+    ///
+    /// assert_eq!( hash_ac(&this), hash_ac(&ReprC{ b: 1000, ..this }) );
+    ///
+    /// ```
+    ///
+    #[inline(always)]
+    pub fn hash_field<H: Hasher>(self, base: &S, state: &mut H)
+    where
+        F: Hash,
+    {
+        self.get(base).hash(state)
+    }
+
+    /// Gets a reference to the smallest value of this field across
+    /// all the structs in `slice`.
+    ///
+    /// If several structs have equally small values, this returns the first one.
+    ///
+    /// Returns `None` if `slice` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let list = [
+    ///     ReprC{ a: "foo", b: 8u32, c: (), d: () },
+    ///     ReprC{ a: "bar", b: 3u32, c: (), d: () },
+    ///     ReprC{ a: "baz", b: 5u32, c: (), d: () },
+    /// ];
+    ///
+    /// assert_eq!( ReprC::OFFSET_A.field_min(&list), Some(&"bar") );
+    /// assert_eq!( ReprC::OFFSET_B.field_min(&list), Some(&3) );
+    /// assert_eq!( ReprC::OFFSET_B.field_min(&list[..0]), None );
+    ///
+    /// ```
+    ///
+    #[inline]
+    pub fn field_min(self, slice: &[S]) -> Option<&F>
+    where
+        S: Sized,
+        F: Ord,
+    {
+        slice.iter().map(|x| self.get(x)).min()
+    }
+
+    /// Gets a reference to the largest value of this field across
+    /// all the structs in `slice`.
+    ///
+    /// If several structs have equally large values, this returns the last one.
+    ///
+    /// Returns `None` if `slice` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let list = [
+    ///     ReprC{ a: "foo", b: 8u32, c: (), d: () },
+    ///     ReprC{ a: "bar", b: 3u32, c: (), d: () },
+    ///     ReprC{ a: "baz", b: 5u32, c: (), d: () },
+    /// ];
+    ///
+    /// assert_eq!( ReprC::OFFSET_A.field_max(&list), Some(&"foo") );
+    /// assert_eq!( ReprC::OFFSET_B.field_max(&list), Some(&8) );
+    /// assert_eq!( ReprC::OFFSET_B.field_max(&list[..0]), None );
+    ///
+    /// ```
+    ///
+    #[inline]
+    pub fn field_max(self, slice: &[S]) -> Option<&F>
+    where
+        S: Sized,
+        F: Ord,
+    {
+        slice.iter().map(|x| self.get(x)).max()
+    }
+
+    /// Gets a closure that assigns its second argument to this field of its first argument,
+    /// dropping the previous value of the field.
+    ///
+    /// This is useful for building tables of field setters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// type This = ReprC<u32, u32, String, ()>;
+    ///
+    /// let mut this = ReprC{ a: 3, b: 5, c: "foo".to_string(), d: () };
+    ///
+    /// let int_setters = [This::OFFSET_A.setter(), This::OFFSET_B.setter()];
+    /// for (i, set) in int_setters.iter().enumerate() {
+    ///     set(&mut this, i as u32 * 10);
+    /// }
+    ///
+    /// let set_c = This::OFFSET_C.setter();
+    /// set_c(&mut this, "bar".to_string());
+    ///
+    /// assert_eq!( this.a, 0 );
+    /// assert_eq!( this.b, 10 );
+    /// assert_eq!( this.c, "bar" );
+    ///
+    /// ```
+    ///
+    #[inline(always)]
+    pub fn setter(self) -> impl Fn(&mut S, F) + Copy {
+        move |base, value| *self.get_mut(base) = value
+    }
+}
+
+impl<S: ?Sized, F> FieldOffset<S, F, Unaligned> {
+    /// Copies the unaligned field that this is an offset for.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let this = ReprPacked{ a: Some(false), b: [8i32, 13, 21], c: (), d: () };
+    ///
+    /// assert_eq!( ReprPacked::OFFSET_A.get_copy(&this), Some(false) );
+    /// assert_eq!( ReprPacked::OFFSET_B.get_copy(&this), [8i32, 13, 21] );
+    ///
+    /// ```
+    ///
+    /// This method can't be called for non-Copy fields.
+    /// ```compile_fail
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let this = ReprPacked{ a: vec![0, 1, 2], b: (), c: (), d: () };
+    ///
+    /// let _ = ReprPacked::OFFSET_A.get_copy(&this);
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn get_copy(self, base: &S) -> F
+    where
+        F: Copy,
+    {
+        unsafe { impl_fo!(fn get_copy<S, F, Unaligned>(self, base)) }
+    }
+
+    /// Gets a `NonNull` pointer to the unaligned field that this is an offset for,
+    /// derived from the mutable reference to the struct.
+    ///
+    /// This is for storing pointers to fields for later use,
+    /// eg: when building self-referential structures
+    /// (which require the struct to be pinned, or otherwise not moved).
+    ///
+    /// Because the field can be unaligned,
+    /// it must only be accessed with unaligned reads and writes through the returned pointer.
+    ///
+    /// # Provenance and aliasing
+    ///
+    /// The returned pointer is derived from a raw pointer to the whole struct
+    /// (since references to unaligned fields can't be created),
+    /// so it keeps the provenance of `base`.
+    ///
+    /// Using `base` (or any pointer derived from it) to access the struct
+    /// invalidates the returned pointer,
+    /// and the pointer also becomes dangling once the struct is moved or dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
+    ///
+    /// let mut this = ReprPacked{ a: "what", b: 3u32, c: (), d: () };
+    ///
+    /// let ptr = ReprPacked::OFFSET_B.field_non_null_mut(&mut this);
+    ///
+    /// unsafe{
+    ///     let value = ptr.as_ptr().read_unaligned();
+    ///     ptr.as_ptr().write_unaligned(value + 5);
+    /// }
+    ///
+    /// assert_eq!( moved(this.b), 8 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn field_non_null_mut(self, base: &mut S) -> NonNull<F> {
+        unsafe { NonNull::new_unchecked(self.raw_get_mut(base)) }
+    }
+
+    /// Copies the unaligned field that this is an offset for.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::read_unaligned`](https://doc.rust-lang.org/std/ptr/fn.read_unaligned.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let this = ReprPacked{ a: 10u8, b: "20", c: (), d: () };
+    ///
+    /// let ptr: *const _ = &this;
+    /// unsafe{
+    ///     assert_eq!( ReprPacked::OFFSET_A.read_copy(ptr), 10u8 );
+    ///     assert_eq!( ReprPacked::OFFSET_B.read_copy(ptr), "20" );
+    /// }
+    /// ```
+    ///
+    /// This method can't be called for non-Copy fields.
+    /// ```compile_fail
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let this = ReprPacked{ a: vec![0, 1, 2], b: "20", c: (), d: () };
+    ///
+    /// unsafe{
+    ///     let _ = ReprPacked::OFFSET_A.read_copy(&this);
+    /// }
+    /// ```
+    #[inline(always)]
+    pub unsafe fn read_copy(self, base: *const S) -> F
+    where
+        F: Copy,
+    {
+        impl_fo!(fn read_copy<S, F, Unaligned>(self, base))
+    }
+
+    /// Copies the field in `base`, returning `None` if it's equal to `sentinel`.
+    ///
+    /// This is for fields where a sentinel value means that the field is absent,
+    /// eg: C APIs that use `-1` or `u32::MAX` as a null value.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::read_unaligned`](https://doc.rust-lang.org/std/ptr/fn.read_unaligned.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let absent = ReprPacked{ a: 3u8, b: -1i32, c: (), d: () };
+    /// let present = ReprPacked{ a: 3u8, b: 5i32, c: (), d: () };
+    ///
+    /// unsafe{
+    ///     assert_eq!( ReprPacked::OFFSET_B.read_sentinel(&absent, -1), None );
+    ///     assert_eq!( ReprPacked::OFFSET_B.read_sentinel(&present, -1), Some(5) );
+    /// }
+    /// ```
+    #[inline(always)]
+    pub unsafe fn read_sentinel(self, base: *const S, sentinel: F) -> Option<F>
+    where
+        F: PartialEq + Copy,
+    {
+        let value = self.read_copy(base);
+        if value == sentinel {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Reads the value from the field in `source` without moving it.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::read_unaligned`](https://doc.rust-lang.org/std/ptr/fn.read_unaligned.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
     /// use repr_offset::for_examples::ReprPacked;
     ///
-    /// let this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    /// use std::mem::ManuallyDrop;
     ///
-    /// let ptr_a = ReprPacked::OFFSET_A.get_ptr(&this);
-    /// // A `u8` is always aligned,so a `.read()` is fine.
-    /// assert_eq!( unsafe{ ptr_a.read() }, 3u8 );
+    /// let this = ManuallyDrop::new(ReprPacked{
+    ///     a: vec![0, 1, 2],
+    ///     b: "20".to_string(),
+    ///     c: (),
+    ///     d: (),
+    /// });
     ///
-    /// let ptr_b = ReprPacked::OFFSET_B.get_ptr(&this);
-    /// // ReprPacked has an alignment of 1,
-    /// // so this u16 field has to be copied with `.read_unaligned()`.
-    /// assert_eq!( unsafe{ ptr_b.read_unaligned() }, 5u16 );
+    /// let ptr: *const _ = &*this;
+    /// unsafe{
+    ///     assert_eq!( ReprPacked::OFFSET_A.read(ptr), vec![0, 1, 2] );
+    ///     assert_eq!( ReprPacked::OFFSET_B.read(ptr), "20".to_string() );
+    /// }
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn read(self, source: *const S) -> F {
+        impl_fo!(fn read<S, F, Unaligned>(self, source))
+    }
+
+    /// Reads the value from the field in `source` without moving it,
+    /// wrapping it in a `ManuallyDrop` so that it's only dropped if the caller does so.
+    ///
+    /// The field in `source` still logically owns the value,
+    /// so the returned copy must not be dropped unless the field is
+    /// overwritten without dropping it, or is otherwise never used again.
+    ///
+    /// This is useful for temporarily duplicating the bits of a field,
+    /// like when swapping fields, where an accidental drop would cause a double free.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::read_unaligned`](https://doc.rust-lang.org/std/ptr/fn.read_unaligned.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// use std::mem::ManuallyDrop;
+    ///
+    /// type This = ReprPacked<String, String, (), ()>;
+    ///
+    /// let mut this: This = ReprPacked{ a: "foo".to_string(), b: "bar".to_string(), c: (), d: () };
+    ///
+    /// let ptr: *mut This = &mut this;
+    /// unsafe{
+    ///     let a = This::OFFSET_A.read_manually_drop(ptr);
+    ///     let b = This::OFFSET_B.read_manually_drop(ptr);
+    ///     This::OFFSET_A.write(ptr, ManuallyDrop::into_inner(b));
+    ///     This::OFFSET_B.write(ptr, ManuallyDrop::into_inner(a));
+    /// }
+    /// assert_eq!( This::OFFSET_A.replace_mut(&mut this, String::new()), "bar" );
+    /// assert_eq!( This::OFFSET_B.replace_mut(&mut this, String::new()), "foo" );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn read_manually_drop(self, source: *const S) -> ManuallyDrop<F> {
+        ManuallyDrop::new(self.read(source))
+    }
+
+    /// Writes `value` ìnto the field in `source` without dropping the old value of the field.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::write_unaligned`](https://doc.rust-lang.org/std/ptr/fn.write_unaligned.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
+    ///
+    /// let mut this = ReprPacked{ a: 10u8, b: "20", c: (), d: () };
+    ///
+    /// let ptr: *mut _ = &mut this;
+    /// unsafe{
+    ///     ReprPacked::OFFSET_A.write(ptr, 13u8);
+    ///     ReprPacked::OFFSET_B.write(ptr, "21");
+    /// }
+    /// assert_eq!( moved(this.a), 13u8 );
+    /// assert_eq!( moved(this.b), "21" );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn write(self, source: *mut S, value: F) {
+        impl_fo!(fn write<S, F, Unaligned>(self, source, value))
+    }
+
+    /// Copies the field in `source` into `destination`.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::copy`](https://doc.rust-lang.org/std/ptr/fn.copy.html),
+    /// except that `source` and `destination` do not need to be properly aligned.
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
+    ///
+    ///
+    /// let this = ReprPacked{ a: 10u8, b: "20", c: (), d: () };
+    /// let mut other = ReprPacked{ a: 0u8, b: "", c: (), d: () };
+    ///
+    /// let this_ptr: *const _ = &this;
+    /// let other_ptr: *mut _ = &mut other;
+    /// unsafe{
+    ///     ReprPacked::OFFSET_A.copy(this_ptr, other_ptr);
+    ///     ReprPacked::OFFSET_B.copy(this_ptr, other_ptr);
+    /// }
+    /// assert_eq!( moved(this.a), 10u8 );
+    /// assert_eq!( moved(this.b), "20" );
+    ///
+    /// assert_eq!( moved(other.a), 10u8 );
+    /// assert_eq!( moved(other.b), "20" );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn copy(self, source: *const S, destination: *mut S) {
+        impl_fo!(fn copy<S, F, Unaligned>(self, source, destination))
+    }
+
+    /// Copies the field in `source` into `destination`,
+    /// `source` and `destination` must not overlap.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::copy_nonoverlapping`
+    /// ](https://doc.rust-lang.org/std/ptr/fn.copy_nonoverlapping.html),
+    /// except that `source` and `destination` do not need to be properly aligned.
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
+    ///
+    /// let this = ReprPacked{ a: '#', b: 81, c: (), d: () };
+    /// let mut other = ReprPacked{ a: '_', b: 0, c: (), d: () };
+    ///
+    /// let this_ptr: *const _ = &this;
+    /// let other_ptr: *mut _ = &mut other;
+    /// unsafe{
+    ///     ReprPacked::OFFSET_A.copy_nonoverlapping(this_ptr, other_ptr);
+    ///     ReprPacked::OFFSET_B.copy_nonoverlapping(this_ptr, other_ptr);
+    /// }
+    /// assert_eq!( moved(this.a), '#' );
+    /// assert_eq!( moved(this.b), 81 );
+    ///
+    /// assert_eq!( moved(other.a), '#' );
+    /// assert_eq!( moved(other.b), 81 );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn copy_nonoverlapping(self, source: *const S, destination: *mut S) {
+        impl_fo!(fn copy_nonoverlapping<S, F, Unaligned>(self, source, destination))
+    }
+}
+
+impl<S: ?Sized, F> FieldOffset<S, F, Unaligned> {
+    /// Replaces the value of a field in `dest` with `value`,
+    /// returning the old value of the field.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::replace`](https://doc.rust-lang.org/std/ptr/fn.replace.html),
+    /// except that `dest` does not need to be properly aligned.
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
+    ///
+    /// let mut this = ReprPacked{ a: [0u8, 1], b: false, c: (), d: () };
+    ///
+    /// let ptr: *mut _ = &mut this;
+    /// unsafe{
+    ///     assert_eq!( ReprPacked::OFFSET_A.replace(ptr, [2, 3]), [0u8, 1] );
+    ///     assert_eq!( ReprPacked::OFFSET_B.replace(ptr, true), false );
+    /// }
+    ///
+    /// assert_eq!( moved(this.a), [2u8, 3] );
+    /// assert_eq!( moved(this.b), true );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn replace(self, dest: *mut S, value: F) -> F {
+        impl_fo!(fn replace<S, F, Unaligned>(self, dest, value))
+    }
+
+    /// Replaces the value of a field in `dest` with `value`,
+    /// returning the old value of the field.
+    ///
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
+    ///
+    /// let mut this = ReprPacked{ a: [0u8, 1], b: false, c: (), d: () };
+    ///
+    /// assert_eq!( ReprPacked::OFFSET_A.replace_mut(&mut this, [2, 3]), [0u8, 1] );
+    /// assert_eq!( ReprPacked::OFFSET_B.replace_mut(&mut this, true), false );
+    ///
+    /// assert_eq!( moved(this.a), [2u8, 3] );
+    /// assert_eq!( moved(this.b), true );
+    ///
+    /// ```
+    pub fn replace_mut(self, dest: &mut S, value: F) -> F {
+        unsafe { impl_fo!(fn replace_mut<S, F, Unaligned>(self, dest, value)) }
+    }
+}
+
+impl<S: ?Sized, F> FieldOffset<S, F, Unaligned> {
+    /// Swaps the values of a field between the `left` and `right` pointers.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::swap`](https://doc.rust-lang.org/std/ptr/fn.swap.html),
+    /// except that it does not require aligned pointers.
     ///
-    /// ```
-    #[inline(always)]
-    pub fn get_ptr(self, base: &S) -> *const F {
-        unsafe { impl_fo!(fn get_ptr<S, F, A>(self, base)) }
-    }
-
-    /// Gets a mutable raw pointer to a field from a mutable reference to the `S` struct.
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::FieldOffset;
     /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
     ///
-    /// let mut this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    /// let mut this = ReprPacked{ a: '=', b: 64u16, c: (), d: () };
+    /// let mut other = ReprPacked{ a: '!', b: 255u16, c: (), d: () };
     ///
-    /// let ptr_a = ReprPacked::OFFSET_A.get_mut_ptr(&mut this);
+    /// let this_ptr: *mut _ = &mut this;
+    /// let other_ptr: *mut _ = &mut other;
     /// unsafe{
-    ///     // A `u8` is always aligned,so a `.read()` is fine.
-    ///     assert_eq!( ptr_a.read(), 3u8 );
-    ///     ptr_a.write(103);
-    ///     assert_eq!( ptr_a.read(), 103 );
+    ///     ReprPacked::OFFSET_A.swap(this_ptr, other_ptr);
+    ///     ReprPacked::OFFSET_B.swap(this_ptr, other_ptr);
     /// }
+    /// assert_eq!( moved(this.a), '!' );
+    /// assert_eq!( moved(this.b), 255 );
     ///
-    /// let ptr_b = ReprPacked::OFFSET_B.get_mut_ptr(&mut this);
-    /// unsafe{
-    ///     // ReprPacked has an alignment of 1,
-    ///     // so this u16 field has to be read with `.read_unaligned()`.
-    ///     assert_eq!( ptr_b.read_unaligned(), 5u16 );
-    ///     ptr_b.write_unaligned(105);
-    ///     assert_eq!( ptr_b.read_unaligned(), 105 );
-    /// }
+    /// assert_eq!( moved(other.a), '=' );
+    /// assert_eq!( moved(other.b), 64 );
     ///
     /// ```
     #[inline(always)]
-    pub fn get_mut_ptr(self, base: &mut S) -> *mut F {
-        unsafe { impl_fo!(fn get_mut_ptr<S, F, A>(self, base)) }
+    pub unsafe fn swap(self, left: *mut S, right: *mut S) {
+        impl_fo!(fn swap<S, F, Unaligned>(self, left, right))
     }
 
-    /// Gets a raw pointer to a field from a pointer to the `S` struct.
+    /// Swaps the values of a field between the non-overlapping `left` and `right` pointers.
     ///
     /// # Safety
     ///
-    /// This has the same safety requirements as the [`<*const T>::offset`] method.
+    /// This function has the same safety requirements as
+    /// [`std::ptr::swap_nonoverlapping`
+    /// ](https://doc.rust-lang.org/std/ptr/fn.swap_nonoverlapping.html)
+    /// except that it does not require aligned pointers.
     ///
-    /// [`<*const T>::offset`]:
-    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.offset
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::FieldOffset;
     /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
     ///
-    /// let this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
-    ///
-    /// let ptr: *const _ = &this;
+    /// let mut this = ReprPacked{ a: [false, true], b: &27u32, c: (), d: () };
+    /// let mut other = ReprPacked{ a: [true, false], b: &81u32, c: (), d: () };
     ///
+    /// let this_ptr: *mut _ = &mut this;
+    /// let other_ptr: *mut _ = &mut other;
     /// unsafe{
-    ///     // A `u8` is always aligned,so a `.read()` is fine.
-    ///     assert_eq!( ReprPacked::OFFSET_A.raw_get(ptr).read(), 3u8 );
-    ///     
-    ///     // ReprPacked has an alignment of 1,
-    ///     // so this u16 field has to be copied with `.read_unaligned()`.
-    ///     assert_eq!( ReprPacked::OFFSET_B.raw_get(ptr).read_unaligned(), 5u16 );
+    ///     ReprPacked::OFFSET_A.swap_nonoverlapping(this_ptr, other_ptr);
+    ///     ReprPacked::OFFSET_B.swap_nonoverlapping(this_ptr, other_ptr);
     /// }
+    /// assert_eq!( moved(this.a), [true, false] );
+    /// assert_eq!( moved(this.b), &81 );
+    ///
+    /// assert_eq!( moved(other.a), [false, true] );
+    /// assert_eq!( moved(other.b), &27 );
     ///
     /// ```
+    ///
     #[inline(always)]
-    pub unsafe fn raw_get(self, base: *const S) -> *const F {
-        impl_fo!(fn raw_get<S, F, A>(self, base))
+    pub unsafe fn swap_nonoverlapping(self, left: *mut S, right: *mut S) {
+        impl_fo!(fn swap_nonoverlapping<S, F, Unaligned>(self, left, right))
     }
 
-    /// Gets a mutable raw pointer to a field from a pointer to the `S` struct.
-    ///
-    /// # Safety
-    ///
-    /// This has the same safety requirements as the [`<*mut T>::offset`] method.
-    ///
-    /// [`<*mut T>::offset`]:
-    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.offset-1
+    /// Swaps the values of a field between `left` and `right`.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::FieldOffset;
     /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
     ///
-    /// let mut this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
-    ///
-    /// let ptr: *mut _ = &mut this;
-    ///
-    /// unsafe{
-    ///     let ptr_a = ReprPacked::OFFSET_A.raw_get_mut(ptr);
-    ///
-    ///     // A `u8` is always aligned,so a `.read()` is fine.
-    ///     assert_eq!( ptr_a.read(), 3u8 );
-    ///     ptr_a.write(103);
-    ///     assert_eq!( ptr_a.read(), 103 );
+    /// let mut this = ReprPacked{ a: [true, true], b: 0x0Fu8, c: (), d: () };
+    /// let mut other = ReprPacked{ a: [false, false], b: 0xF0u8, c: (), d: () };
     ///
+    /// ReprPacked::OFFSET_A.swap_mut(&mut this, &mut other);
+    /// ReprPacked::OFFSET_B.swap_mut(&mut this, &mut other);
     ///
-    ///     let ptr_b = ReprPacked::OFFSET_B.raw_get_mut(ptr);
+    /// assert_eq!( moved(this.a), [false, false] );
+    /// assert_eq!( moved(this.b), 0xF0u8 );
     ///
-    ///     // ReprPacked has an alignment of 1,
-    ///     // so this u16 field has to be read with `.read_unaligned()`.
-    ///     assert_eq!( ptr_b.read_unaligned(), 5u16 );
-    ///     ptr_b.write_unaligned(105);
-    ///     assert_eq!( ptr_b.read_unaligned(), 105 );
-    /// }
+    /// assert_eq!( moved(other.a), [true, true] );
+    /// assert_eq!( moved(other.b), 0x0Fu8 );
     ///
     /// ```
+    ///
     #[inline(always)]
-    pub unsafe fn raw_get_mut(self, base: *mut S) -> *mut F {
-        impl_fo!(fn raw_get_mut<S, F, A>(self, base))
+    pub fn swap_mut(self, left: &mut S, right: &mut S) {
+        unsafe { impl_fo!(fn swap_mut<S, F, Unaligned>(self, left, right)) }
     }
 
-    /// Gets a raw pointer to a field from a pointer to the `S` struct.
-    ///
-    /// # Safety
-    ///
-    /// While calling this method is not by itself unsafe,
-    /// using the pointer returned by this method has the same safety requirements
-    /// as the [`<*const T>::wrapping_offset`] method.
+    /// Compares copies of the values of this field in `left` and `right`.
     ///
-    /// [`<*const T>::wrapping_offset`]:
-    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_offset
+    /// This is useful for sorting structs by a field,
+    /// and can be combined with `Ordering::then_with` to compare by multiple fields.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::FieldOffset;
     /// use repr_offset::for_examples::ReprPacked;
     ///
-    /// let this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
+    /// let mut list = vec![
+    ///     ReprPacked{ a: 3u8, b: 8u64, c: (), d: () },
+    ///     ReprPacked{ a: 5u8, b: 2u64, c: (), d: () },
+    ///     ReprPacked{ a: 3u8, b: 1u64, c: (), d: () },
+    /// ];
     ///
-    /// let ptr_a = ReprPacked::OFFSET_A.wrapping_raw_get(&this);
-    /// // A `u8` is always aligned,so a `.read()` is fine.
-    /// assert_eq!( unsafe{ ptr_a.read() }, 3u8 );
+    /// list.sort_by(|l, r| {
+    ///     ReprPacked::OFFSET_A.compare_field(l, r)
+    ///         .then_with(|| ReprPacked::OFFSET_B.compare_field(l, r))
+    /// });
     ///
-    /// let ptr_b = ReprPacked::OFFSET_B.wrapping_raw_get(&this);
-    /// // ReprPacked has an alignment of 1,
-    /// // so this u16 field has to be copied with `.read_unaligned()`.
-    /// assert_eq!( unsafe{ ptr_b.read_unaligned() }, 5u16 );
+    /// let fields = list.iter().map(|x| (x.a, x.b)).collect::<Vec<_>>();
+    /// assert_eq!( fields, vec![(3, 1), (3, 8), (5, 2)] );
     ///
     /// ```
+    ///
     #[inline(always)]
-    pub fn wrapping_raw_get(self, base: *const S) -> *const F {
-        (base as *const u8).wrapping_offset(self.offset as isize) as *const F
+    pub fn compare_field(self, left: &S, right: &S) -> Ordering
+    where
+        F: Ord + Copy,
+    {
+        self.get_copy(left).cmp(&self.get_copy(right))
     }
 
-    /// Gets a mutable raw pointer to a field from a pointer to the `S` struct.
-    ///
-    /// # Safety
-    ///
-    /// While calling this method is not by itself unsafe,
-    /// using the pointer returned by this method has the same safety requirements
-    /// as the [`<*mut T>::wrapping_offset`] method.
+    /// Feeds a copy of the value of this field in `base` into `state`.
     ///
-    /// [`<*mut T>::wrapping_offset`]:
-    /// https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_offset-1
+    /// This is useful for implementing `Hash` over a subset of the fields of a struct,
+    /// ignoring fields like caches.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::FieldOffset;
     /// use repr_offset::for_examples::ReprPacked;
     ///
-    /// let mut this = ReprPacked{ a: 3u8, b: 5u16, c: (), d: () };
-    ///
-    /// let ptr: *mut _ = &mut this;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::{Hash, Hasher};
     ///
-    /// let ptr_a = ReprPacked::OFFSET_A.wrapping_raw_get_mut(ptr);
-    /// unsafe{
+    /// type This = ReprPacked<u8, u64, u16, ()>;
     ///
-    ///     // A `u8` is always aligned,so a `.read()` is fine.
-    ///     assert_eq!( ptr_a.read(), 3u8 );
-    ///     ptr_a.write(103);
-    ///     assert_eq!( ptr_a.read(), 103 );
+    /// fn hash_ac(this: &This) -> u64 {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     This::OFFSET_A.hash_field(this, &mut hasher);
+    ///     This::OFFSET_C.hash_field(this, &mut hasher);
+    ///     hasher.finish()
     /// }
     ///
-    /// let ptr_b = ReprPacked::OFFSET_B.wrapping_raw_get_mut(ptr);
-    /// unsafe{
+    /// let this = ReprPacked{ a: 3, b: 5, c: 8, d: () };
     ///
-    ///     // ReprPacked has an alignment of 1,
-    ///     // so this u16 field has to be read with `.read_unaligned()`.
-    ///     assert_eq!( ptr_b.read_unaligned(), 5u16 );
-    ///     ptr_b.write_unaligned(105);
-    ///     assert_eq!( ptr_b.read_unaligned(), 105 );
-    /// }
+    /// assert_eq!( hash_ac(&this), hash_ac(&ReprPacked{ b: 1000, ..this }) );
     ///
     /// ```
+    ///
     #[inline(always)]
-    pub fn wrapping_raw_get_mut(self, base: *mut S) -> *mut F {
-        (base as *mut u8).wrapping_offset(self.offset as isize) as *mut F
+    pub fn hash_field<H: Hasher>(self, base: &S, state: &mut H)
+    where
+        F: Hash + Copy,
+    {
+        self.get_copy(base).hash(state)
     }
-}
 
-impl<S, F> FieldOffset<S, F, Aligned> {
-    /// Copies the aligned field that this is an offset for.
-    ///
-    /// # Safety
-    ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::read`](https://doc.rust-lang.org/std/ptr/fn.read.html).
+    /// Gets a copy of the smallest value of this field across
+    /// all the structs in `slice`.
     ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+    /// Returns `None` if `slice` is empty.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprC;
+    /// use repr_offset::for_examples::ReprPacked;
     ///
-    /// let this = ReprC{ a: 10u8, b: "20", c: (), d: () };
+    /// let list = [
+    ///     ReprPacked{ a: 3u8, b: 8u64, c: (), d: () },
+    ///     ReprPacked{ a: 5u8, b: 2u64, c: (), d: () },
+    ///     ReprPacked{ a: 1u8, b: 5u64, c: (), d: () },
+    /// ];
+    ///
+    /// assert_eq!( ReprPacked::OFFSET_A.field_min(&list), Some(1) );
+    /// assert_eq!( ReprPacked::OFFSET_B.field_min(&list), Some(2) );
+    /// assert_eq!( ReprPacked::OFFSET_B.field_min(&list[..0]), None );
     ///
-    /// let ptr: *const _ = &this;
-    /// unsafe{
-    ///     assert_eq!( ReprC::OFFSET_A.read_copy(ptr), 10u8 );
-    ///     assert_eq!( ReprC::OFFSET_B.read_copy(ptr), "20" );
-    /// }
     /// ```
     ///
-    /// This method can't be called for non-Copy fields.
-    /// ```compile_fail
+    #[inline]
+    pub fn field_min(self, slice: &[S]) -> Option<F>
+    where
+        S: Sized,
+        F: Ord + Copy,
+    {
+        slice.iter().map(|x| self.get_copy(x)).min()
+    }
+
+    /// Gets a copy of the largest value of this field across
+    /// all the structs in `slice`.
+    ///
+    /// Returns `None` if `slice` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprC;
+    /// use repr_offset::for_examples::ReprPacked;
+    ///
+    /// let list = [
+    ///     ReprPacked{ a: 3u8, b: 8u64, c: (), d: () },
+    ///     ReprPacked{ a: 5u8, b: 2u64, c: (), d: () },
+    ///     ReprPacked{ a: 1u8, b: 5u64, c: (), d: () },
+    /// ];
+    ///
+    /// assert_eq!( ReprPacked::OFFSET_A.field_max(&list), Some(5) );
+    /// assert_eq!( ReprPacked::OFFSET_B.field_max(&list), Some(8) );
+    /// assert_eq!( ReprPacked::OFFSET_B.field_max(&list[..0]), None );
     ///
-    /// let this = ReprC{ a: vec![0, 1, 2, 3], b: (), c: (), d: () };
-    /// unsafe{
-    ///     let _ = ReprC::OFFSET_A.read_copy(&this);
-    /// }
     /// ```
     ///
-    #[inline(always)]
-    pub unsafe fn read_copy(self, base: *const S) -> F
+    #[inline]
+    pub fn field_max(self, slice: &[S]) -> Option<F>
     where
-        F: Copy,
+        S: Sized,
+        F: Ord + Copy,
     {
-        impl_fo!(fn read_copy<S, F, Aligned>(self, base))
+        slice.iter().map(|x| self.get_copy(x)).max()
     }
 
-    /// Reads the value from the field in `source` without moving it.
-    ///
-    /// # Safety
-    ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::read`](https://doc.rust-lang.org/std/ptr/fn.read.html).
+    /// Gets a closure that assigns its second argument to this unaligned field
+    /// of its first argument, dropping the previous value of the field.
     ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+    /// This is useful for building tables of field setters.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprC;
+    /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
     ///
-    /// use std::mem::ManuallyDrop;
+    /// type This = ReprPacked<u8, u32, String, ()>;
     ///
-    /// let this = ManuallyDrop::new(ReprC{
-    ///     a: vec![0, 1, 2],
-    ///     b: "20".to_string(),
-    ///     c: (),
-    ///     d: (),
-    /// });
+    /// let mut this = ReprPacked{ a: 3, b: 5, c: "foo".to_string(), d: () };
     ///
-    /// let ptr: *const _ = &*this;
-    /// unsafe{
-    ///     assert_eq!( ReprC::OFFSET_A.read(ptr), vec![0, 1, 2] );
-    ///     assert_eq!( ReprC::OFFSET_B.read(ptr), "20".to_string() );
-    /// }
+    /// let set_b = This::OFFSET_B.setter();
+    /// set_b(&mut this, 8);
     ///
-    /// ```
-    #[inline(always)]
-    pub unsafe fn read(self, source: *const S) -> F {
-        impl_fo!(fn read<S, F, Aligned>(self, source))
-    }
-
-    /// Writes `value` ìnto the field in `destination` without dropping the old value of the field.
+    /// let set_c = This::OFFSET_C.setter();
+    /// set_c(&mut this, "bar".to_string());
     ///
-    /// This allows uninitialized fields to be initialized,since doing
-    /// `*OFFSET_FOO.raw_get_mut(ptr) = value;` would drop uninitialized memory.
+    /// assert_eq!( moved(this.b), 8 );
+    /// assert_eq!( This::OFFSET_C.replace_mut(&mut this, String::new()), "bar" );
     ///
-    /// # Safety
+    /// ```
     ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    #[inline(always)]
+    pub fn setter(self) -> impl Fn(&mut S, F) + Copy {
+        move |base, value| drop(self.replace_mut(base, value))
+    }
+}
+
+impl<S, F: PrimInt, A> FieldOffset<S, F, A> {
+    /// Reads this integer field from `bytes`, converting it from little-endian,
+    /// returning `None` if `bytes` is too short to contain the field.
     ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+    /// This is for parsing serialized structs described with a `#[repr(C)]` struct,
+    /// where `bytes` starts at the start of the serialized struct.
     ///
     /// # Example
     ///
@@ -1151,31 +3663,28 @@ impl<S, F> FieldOffset<S, F, Aligned> {
     /// # #![deny(safe_packed_borrows)]
     /// use repr_offset::for_examples::ReprC;
     ///
-    /// let mut this = ReprC{ a: 10u8, b: "20", c: (), d: () };
+    /// type Header = ReprC<u16, u16, u32, ()>;
     ///
-    /// let ptr: *mut _ = &mut this;
-    /// unsafe{
-    ///     ReprC::OFFSET_A.write(ptr, 13u8);
-    ///     ReprC::OFFSET_B.write(ptr, "21");
-    /// }
-    /// assert_eq!( this.a, 13u8 );
-    /// assert_eq!( this.b, "21" );
+    /// let bytes = [3, 0, 5, 0, 0x78, 0x56, 0x34, 0x12];
+    ///
+    /// assert_eq!( Header::OFFSET_A.read_int_le(&bytes), Some(3) );
+    /// assert_eq!( Header::OFFSET_B.read_int_le(&bytes), Some(5) );
+    /// assert_eq!( Header::OFFSET_C.read_int_le(&bytes), Some(0x1234_5678) );
+    ///
+    /// assert_eq!( Header::OFFSET_C.read_int_le(&bytes[..7]), None );
     ///
     /// ```
-    #[inline(always)]
-    pub unsafe fn write(self, destination: *mut S, value: F) {
-        impl_fo!(fn write<S, F, Aligned>(self, destination, value))
+    #[inline]
+    pub fn read_int_le(self, bytes: &[u8]) -> Option<F> {
+        self.field_bytes(bytes).map(F::__from_le_slice)
     }
 
-    /// Copies the field in `source` into `destination`.
-    ///
-    /// # Safety
+    /// Reads this integer field from `bytes`, converting it from big-endian,
+    /// returning `None` if `bytes` is too short to contain the field.
     ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::copy`](https://doc.rust-lang.org/std/ptr/fn.copy.html).
-    ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+    /// This is for parsing serialized structs described with a `#[repr(C)]` struct,
+    /// eg: the headers of network protocols,
+    /// where `bytes` starts at the start of the serialized struct.
     ///
     /// # Example
     ///
@@ -1183,35 +3692,38 @@ impl<S, F> FieldOffset<S, F, Aligned> {
     /// # #![deny(safe_packed_borrows)]
     /// use repr_offset::for_examples::ReprC;
     ///
-    /// let this = ReprC{ a: 10u8, b: "20", c: (), d: () };
-    /// let mut other = ReprC{ a: 0u8, b: "", c: (), d: () };
+    /// type Header = ReprC<u16, u16, u32, ()>;
     ///
-    /// let this_ptr: *const _ = &this;
-    /// let other_ptr: *mut _ = &mut other;
-    /// unsafe{
-    ///     ReprC::OFFSET_A.copy(this_ptr, other_ptr);
-    ///     ReprC::OFFSET_B.copy(this_ptr, other_ptr);
-    /// }
-    /// assert_eq!( this.a, 10u8 );
-    /// assert_eq!( this.b, "20" );
+    /// let bytes = [0, 3, 0, 5, 0x12, 0x34, 0x56, 0x78];
     ///
-    /// assert_eq!( other.a, 10u8 );
-    /// assert_eq!( other.b, "20" );
+    /// assert_eq!( Header::OFFSET_A.read_int_be(&bytes), Some(3) );
+    /// assert_eq!( Header::OFFSET_B.read_int_be(&bytes), Some(5) );
+    /// assert_eq!( Header::OFFSET_C.read_int_be(&bytes), Some(0x1234_5678) );
+    ///
+    /// assert_eq!( Header::OFFSET_C.read_int_be(&bytes[..4]), None );
     ///
     /// ```
-    #[inline(always)]
-    pub unsafe fn copy(self, source: *const S, destination: *mut S) {
-        impl_fo!(fn copy<S, F, Aligned>(self, source, destination))
+    #[inline]
+    pub fn read_int_be(self, bytes: &[u8]) -> Option<F> {
+        self.field_bytes(bytes).map(F::__from_be_slice)
     }
 
-    /// Copies the field in `source` into `destination`,
-    /// `source` and `destination` must not overlap.
+    fn field_bytes(self, bytes: &[u8]) -> Option<&[u8]> {
+        let end = self.offset.checked_add(Mem::<F>::SIZE)?;
+        bytes.get(self.offset..end)
+    }
+}
+
+impl<S, F: ByteSwap, A> FieldOffset<S, F, A> {
+    /// Reads this integer field from `base`, returning it with its bytes reversed.
+    ///
+    /// This is useful for emitting a struct stored in native endianness
+    /// into a wire format with the opposite endianness, one field at a time.
     ///
     /// # Safety
     ///
     /// This function has the same safety requirements as
-    /// [`std::ptr::copy_nonoverlapping`
-    /// ](https://doc.rust-lang.org/std/ptr/fn.copy_nonoverlapping.html).
+    /// [`std::ptr::read_unaligned`](https://doc.rust-lang.org/std/ptr/fn.read_unaligned.html).
     ///
     /// Those safety requirements only apply to the field that this is an offset for,
     /// fields after it or before it don't need to be valid to call this method.
@@ -1220,36 +3732,34 @@ impl<S, F> FieldOffset<S, F, Aligned> {
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprC;
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
     ///
-    /// let this = ReprC{ a: '#', b: 81, c: (), d: () };
-    /// let mut other = ReprC{ a: '_', b: 0, c: (), d: () };
+    /// let this = ReprC{ a: 0x1234u16, b: 0x1122_3344u32, c: (), d: () };
+    /// let packed = ReprPacked{ a: 0x1234u16, b: 0x1122_3344u32, c: (), d: () };
     ///
-    /// let this_ptr: *const _ = &this;
-    /// let other_ptr: *mut _ = &mut other;
     /// unsafe{
-    ///     ReprC::OFFSET_A.copy_nonoverlapping(this_ptr, other_ptr);
-    ///     ReprC::OFFSET_B.copy_nonoverlapping(this_ptr, other_ptr);
-    /// }
-    /// assert_eq!( this.a, '#' );
-    /// assert_eq!( this.b, 81 );
+    ///     assert_eq!( ReprC::OFFSET_A.read_swapped(&this), 0x3412 );
+    ///     assert_eq!( ReprC::OFFSET_B.read_swapped(&this), 0x4433_2211 );
     ///
-    /// assert_eq!( other.a, '#' );
-    /// assert_eq!( other.b, 81 );
+    ///     assert_eq!( ReprPacked::OFFSET_A.read_swapped(&packed), 0x3412 );
+    ///     assert_eq!( ReprPacked::OFFSET_B.read_swapped(&packed), 0x4433_2211 );
+    /// }
     ///
     /// ```
     #[inline(always)]
-    pub unsafe fn copy_nonoverlapping(self, source: *const S, destination: *mut S) {
-        impl_fo!(fn copy_nonoverlapping<S, F, Aligned>(self, source, destination))
+    pub unsafe fn read_swapped(self, base: *const S) -> F {
+        self.raw_get(base).read_unaligned().__swap_bytes()
     }
 
-    /// Replaces the value of a field in `destination` with `value`,
-    /// returning the old value of the field.
+    /// Writes `value` with its bytes reversed into this integer field in `base`.
+    ///
+    /// This is useful for loading a struct from a wire format with
+    /// the opposite endianness to the native one, one field at a time.
     ///
     /// # Safety
     ///
     /// This function has the same safety requirements as
-    /// [`std::ptr::replace`](https://doc.rust-lang.org/std/ptr/fn.replace.html).
+    /// [`std::ptr::write_unaligned`](https://doc.rust-lang.org/std/ptr/fn.write_unaligned.html).
     ///
     /// Those safety requirements only apply to the field that this is an offset for,
     /// fields after it or before it don't need to be valid to call this method.
@@ -1258,27 +3768,36 @@ impl<S, F> FieldOffset<S, F, Aligned> {
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprC;
+    /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
     ///
-    /// let mut this = ReprC{ a: [0u8, 1], b: false, c: (), d: () };
+    /// let mut this = ReprPacked{ a: 0u16, b: 0u32, c: (), d: () };
     ///
     /// let ptr: *mut _ = &mut this;
     /// unsafe{
-    ///     assert_eq!( ReprC::OFFSET_A.replace(ptr, [2, 3]), [0u8, 1] );
-    ///     assert_eq!( ReprC::OFFSET_B.replace(ptr, true), false );
+    ///     ReprPacked::OFFSET_A.write_swapped(ptr, 0x1234);
+    ///     ReprPacked::OFFSET_B.write_swapped(ptr, 0x1122_3344);
     /// }
-    ///
-    /// assert_eq!( this.a, [2u8, 3] );
-    /// assert_eq!( this.b, true );
+    /// assert_eq!( moved(this.a), 0x3412 );
+    /// assert_eq!( moved(this.b), 0x4433_2211 );
     ///
     /// ```
     #[inline(always)]
-    pub unsafe fn replace(self, destination: *mut S, value: F) -> F {
-        impl_fo!(fn replace<S, F, Aligned>(self, destination, value))
+    pub unsafe fn write_swapped(self, base: *mut S, value: F) {
+        self.raw_get_mut(base).write_unaligned(value.__swap_bytes())
     }
+}
 
-    /// Replaces the value of a field in `destination` with `value`,
-    /// returning the old value of the field.
+impl<S, F: IsAtomic> FieldOffset<S, F, Aligned> {
+    /// Atomically loads the value of this atomic field in `base`.
+    ///
+    /// # Safety
+    ///
+    /// `base` must point to an `S` which is valid for reads,
+    /// and must stay alive for the duration of this call.
+    ///
+    /// Those requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
     ///
     /// # Example
     ///
@@ -1286,28 +3805,40 @@ impl<S, F> FieldOffset<S, F, Aligned> {
     /// # #![deny(safe_packed_borrows)]
     /// use repr_offset::for_examples::ReprC;
     ///
-    /// let mut this = ReprC{ a: [0u8, 1], b: false, c: (), d: () };
+    /// use std::sync::atomic::{AtomicBool, Ordering};
     ///
-    /// assert_eq!( ReprC::OFFSET_A.replace_mut(&mut this, [2, 3]), [0u8, 1] );
-    /// assert_eq!( ReprC::OFFSET_B.replace_mut(&mut this, true), false );
+    /// let this = ReprC{ a: 3u8, b: AtomicBool::new(true), c: (), d: () };
     ///
-    /// assert_eq!( this.a, [2u8, 3] );
-    /// assert_eq!( this.b, true );
+    /// unsafe{
+    ///     assert_eq!( ReprC::OFFSET_B.load(&this, Ordering::Relaxed), true );
+    /// }
     ///
     /// ```
     #[inline(always)]
-    pub fn replace_mut(self, destination: &mut S, value: F) -> F {
-        unsafe { impl_fo!(fn replace_mut<S, F, Aligned>(self, destination, value)) }
+    pub unsafe fn load(self, base: *const S, order: AtomicOrdering) -> F::Value {
+        (*self.raw_get(base)).__load(order)
     }
 
-    /// Swaps the values of a field between the `left` and `right` pointers.
+    /// Stores `new` into this atomic field in `base`
+    /// if its current value is the same as `current`.
+    ///
+    /// This delegates to the `compare_exchange` method of the atomic type,
+    /// returning `Ok` with the previous value if it was replaced,
+    /// and `Err` with the current value if it wasn't.
+    ///
+    /// This is useful for lock-free algorithms that operate on the atomic fields of
+    /// `#[repr(C)]` structs that are only reachable through raw pointers.
+    ///
+    /// This requires the `"atomic_cas"` feature,
+    /// because some targets (eg: `thumbv6m-none-eabi`, `riscv32i-unknown-none-elf`)
+    /// have atomic loads and stores, but not compare-and-swap operations.
     ///
     /// # Safety
     ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::swap`](https://doc.rust-lang.org/std/ptr/fn.swap.html).
+    /// `base` must point to an `S` which is valid for reads,
+    /// and must stay alive for the duration of this call.
     ///
-    /// Those safety requirements only apply to the field that this is an offset for,
+    /// Those requirements only apply to the field that this is an offset for,
     /// fields after it or before it don't need to be valid to call this method.
     ///
     /// # Example
@@ -1316,67 +3847,74 @@ impl<S, F> FieldOffset<S, F, Aligned> {
     /// # #![deny(safe_packed_borrows)]
     /// use repr_offset::for_examples::ReprC;
     ///
-    /// let mut this = ReprC{ a: '=', b: 64u16, c: (), d: () };
-    /// let mut other = ReprC{ a: '!', b: 255u16, c: (), d: () };
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// type Node = ReprC<u32, AtomicUsize, (), ()>;
+    ///
+    /// let node = Node{ a: 3, b: AtomicUsize::new(5), c: (), d: () };
+    /// let ptr: *const Node = &node;
     ///
-    /// let this_ptr: *mut _ = &mut this;
-    /// let other_ptr: *mut _ = &mut other;
     /// unsafe{
-    ///     ReprC::OFFSET_A.swap(this_ptr, other_ptr);
-    ///     ReprC::OFFSET_B.swap(this_ptr, other_ptr);
+    ///     assert_eq!(
+    ///         Node::OFFSET_B.compare_exchange(ptr, 5, 8, Ordering::AcqRel, Ordering::Acquire),
+    ///         Ok(5),
+    ///     );
+    ///     assert_eq!(
+    ///         Node::OFFSET_B.compare_exchange(ptr, 5, 13, Ordering::AcqRel, Ordering::Acquire),
+    ///         Err(8),
+    ///     );
     /// }
-    /// assert_eq!( this.a, '!' );
-    /// assert_eq!( this.b, 255 );
     ///
-    /// assert_eq!( other.a, '=' );
-    /// assert_eq!( other.b, 64 );
+    /// assert_eq!( node.b.load(Ordering::Relaxed), 8 );
     ///
     /// ```
+    #[cfg(feature = "atomic_cas")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "atomic_cas")))]
     #[inline(always)]
-    pub unsafe fn swap(self, left: *mut S, right: *mut S) {
-        impl_fo!(fn swap<S, F, Aligned>(self, left, right))
+    pub unsafe fn compare_exchange(
+        self,
+        base: *const S,
+        current: F::Value,
+        new: F::Value,
+        success: AtomicOrdering,
+        failure: AtomicOrdering,
+    ) -> Result<F::Value, F::Value> {
+        (*self.raw_get(base)).__compare_exchange(current, new, success, failure)
     }
+}
 
-    /// Swaps the values of a field between the `left` and `right` non-overlapping pointers.
-    ///
-    /// # Safety
+impl<S, F, A> FieldOffset<S, F, A> {
+    /// Loads the pointer in `atomic` with the `order` ordering,
+    /// and if it's non-null, calls `f` with a pointer to this field in the pointed-to `S`.
     ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::swap_nonoverlapping`
-    /// ](https://doc.rust-lang.org/std/ptr/fn.swap_nonoverlapping.html).
+    /// Returns `None` if the loaded pointer is null,
+    /// otherwise returns what `f` returned.
     ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+    /// If this is an `Unaligned` offset,
+    /// the field pointer passed to `f` can be unaligned.
     ///
-    /// # Example
+    /// This is useful for accessing the fields of structs that are
+    /// published to other threads through an `AtomicPtr`.
     ///
-    /// ```rust
-    /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprC;
+    /// # Memory ordering
     ///
-    /// let mut this = ReprC{ a: [false, true], b: &27u32, c: (), d: () };
-    /// let mut other = ReprC{ a: [true, false], b: &81u32, c: (), d: () };
+    /// To see the writes that initialized the `S` before it was published,
+    /// `order` must be `Acquire` (or stronger),
+    /// and the pointer must have been stored with a `Release` (or stronger) ordering.
     ///
-    /// let this_ptr: *mut _ = &mut this;
-    /// let other_ptr: *mut _ = &mut other;
-    /// unsafe{
-    ///     ReprC::OFFSET_A.swap_nonoverlapping(this_ptr, other_ptr);
-    ///     ReprC::OFFSET_B.swap_nonoverlapping(this_ptr, other_ptr);
-    /// }
-    /// assert_eq!( this.a, [true, false] );
-    /// assert_eq!( this.b, &81 );
+    /// # Lifetime
     ///
-    /// assert_eq!( other.a, [false, true] );
-    /// assert_eq!( other.b, &27 );
+    /// The field pointer passed to `f` is only valid for as long as
+    /// the pointed-to `S` isn't deallocated,
+    /// which another thread could do right after the pointer is loaded.
+    /// It's up to the caller to ensure that this doesn't happen while the pointer is used,
+    /// eg: by never deallocating the `S`,
+    /// or through a memory reclamation scheme like hazard pointers or epochs.
     ///
-    /// ```
+    /// # Safety
     ///
-    #[inline(always)]
-    pub unsafe fn swap_nonoverlapping(self, left: *mut S, right: *mut S) {
-        impl_fo!(fn swap_nonoverlapping<S, F, Aligned>(self, left, right))
-    }
-
-    /// Swaps the values of a field between `left` and `right`.
+    /// Any non-null pointer loaded from `atomic` must point to an `S` that is
+    /// alive at the time it's loaded.
     ///
     /// # Example
     ///
@@ -1384,426 +3922,577 @@ impl<S, F> FieldOffset<S, F, Aligned> {
     /// # #![deny(safe_packed_borrows)]
     /// use repr_offset::for_examples::ReprC;
     ///
-    /// let mut this = ReprC{ a: [true, true], b: 0x0Fu8, c: (), d: () };
-    /// let mut other = ReprC{ a: [false, false], b: 0xF0u8, c: (), d: () };
+    /// use std::sync::atomic::{AtomicPtr, Ordering};
     ///
-    /// ReprC::OFFSET_A.swap_mut(&mut this, &mut other);
-    /// ReprC::OFFSET_B.swap_mut(&mut this, &mut other);
+    /// type Config = ReprC<u32, &'static str, (), ()>;
     ///
-    /// assert_eq!( this.a, [false, false] );
-    /// assert_eq!( this.b, 0xF0u8 );
+    /// let atomic = AtomicPtr::new(std::ptr::null_mut::<Config>());
     ///
-    /// assert_eq!( other.a, [true, true] );
-    /// assert_eq!( other.b, 0x0Fu8 );
+    /// unsafe{
+    ///     let read_name = |ptr: *const &'static str| *ptr;
+    ///     assert_eq!( Config::OFFSET_B.with_loaded(&atomic, Ordering::Acquire, read_name), None );
     ///
-    /// ```
+    ///     // Leaking the config so that it's never deallocated.
+    ///     let config = Box::leak(Box::new(ReprC{ a: 3, b: "hello", c: (), d: () }));
+    ///     atomic.store(config, Ordering::Release);
     ///
+    ///     assert_eq!(
+    ///         Config::OFFSET_B.with_loaded(&atomic, Ordering::Acquire, read_name),
+    ///         Some("hello"),
+    ///     );
+    /// }
+    ///
+    /// ```
     #[inline(always)]
-    pub fn swap_mut(self, left: &mut S, right: &mut S) {
-        unsafe { impl_fo!(fn swap_mut<S, F, Aligned>(self, left, right)) }
+    pub unsafe fn with_loaded<R>(
+        self,
+        atomic: &AtomicPtr<S>,
+        order: AtomicOrdering,
+        f: impl FnOnce(*const F) -> R,
+    ) -> Option<R> {
+        let ptr = atomic.load(order);
+        if ptr.is_null() {
+            None
+        } else {
+            Some(f(self.raw_get(ptr)))
+        }
     }
 }
 
-impl<S, F> FieldOffset<S, F, Unaligned> {
-    /// Copies the unaligned field that this is an offset for.
+impl<S, F, A> FieldOffset<S, F, A> {
+    /// Reads this field out of a buffer of potentially uninitialized bytes,
+    /// where `bytes` starts at the start of the `S` struct.
+    ///
+    /// This does an unaligned read, so `bytes` can be at any alignment.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be at least `self.offset() + size_of::<F>()` bytes long,
+    /// this is checked with a `debug_assert`.
+    ///
+    /// The bytes of the field must be initialized to a valid value of type `F`.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprPacked;
-    ///
-    /// let this = ReprPacked{ a: Some(false), b: [8i32, 13, 21], c: (), d: () };
+    /// use repr_offset::for_examples::ReprC;
     ///
-    /// assert_eq!( ReprPacked::OFFSET_A.get_copy(&this), Some(false) );
-    /// assert_eq!( ReprPacked::OFFSET_B.get_copy(&this), [8i32, 13, 21] );
+    /// use std::mem::MaybeUninit;
     ///
-    /// ```
+    /// type This = ReprC<u8, u32, (), ()>;
     ///
-    /// This method can't be called for non-Copy fields.
-    /// ```compile_fail
-    /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprPacked;
+    /// let mut buffer = [MaybeUninit::<u8>::uninit(); 8];
     ///
-    /// let this = ReprPacked{ a: vec![0, 1, 2], b: (), c: (), d: () };
+    /// // Only initializing the bytes of the `b` field
+    /// for (dst, src) in buffer[4..].iter_mut().zip(&0xAABB_CCDDu32.to_ne_bytes()) {
+    ///     *dst = MaybeUninit::new(*src);
+    /// }
     ///
-    /// let _ = ReprPacked::OFFSET_A.get_copy(&this);
+    /// unsafe{
+    ///     assert_eq!( This::OFFSET_B.read_from_uninit_bytes(&buffer), 0xAABB_CCDD );
+    /// }
     ///
     /// ```
     #[inline(always)]
-    pub fn get_copy(self, base: &S) -> F
-    where
-        F: Copy,
-    {
-        unsafe { impl_fo!(fn get_copy<S, F, Unaligned>(self, base)) }
+    pub unsafe fn read_from_uninit_bytes(self, bytes: &[MaybeUninit<u8>]) -> F {
+        debug_assert!(
+            self.offset + Mem::<F>::SIZE <= bytes.len(),
+            "the field ends at {}, but the buffer is only {} bytes long",
+            self.offset + Mem::<F>::SIZE,
+            bytes.len(),
+        );
+        read_zst_or!(
+            F,
+            (bytes.as_ptr().add(self.offset) as *const F).read_unaligned()
+        )
     }
 
-    /// Copies the unaligned field that this is an offset for.
+    /// Replaces the value of a field in `dest` with `value`,
+    /// moving the old value of the field into `out`.
+    ///
+    /// The old value is copied straight from `dest` into `out`,
+    /// which can avoid an intermediate copy for large field types.
+    ///
+    /// This does unaligned reads and writes of the field,
+    /// so `dest` does not need to be properly aligned.
     ///
     /// # Safety
     ///
     /// This function has the same safety requirements as
-    /// [`std::ptr::read_unaligned`](https://doc.rust-lang.org/std/ptr/fn.read_unaligned.html).
+    /// [`std::ptr::replace`](https://doc.rust-lang.org/std/ptr/fn.replace.html),
+    /// except that `dest` does not need to be properly aligned.
     ///
     /// Those safety requirements only apply to the field that this is an offset for,
     /// fields after it or before it don't need to be valid to call this method.
     ///
+    /// Any value that was previously in `out` is overwritten without being dropped.
+    ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
     /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::utils::moved;
     ///
-    /// let this = ReprPacked{ a: 10u8, b: "20", c: (), d: () };
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut this = ReprPacked{ a: [0u8, 1], b: String::from("hello"), c: (), d: () };
+    ///
+    /// let mut old = MaybeUninit::<String>::uninit();
+    ///
+    /// let old = unsafe{
+    ///     ReprPacked::OFFSET_B.replace_into(&mut this, String::from("world"), &mut old);
+    ///     old.assume_init()
+    /// };
+    ///
+    /// assert_eq!( old, "hello" );
+    /// assert_eq!( moved(this.b), "world" );
     ///
-    /// let ptr: *const _ = &this;
-    /// unsafe{
-    ///     assert_eq!( ReprPacked::OFFSET_A.read_copy(ptr), 10u8 );
-    ///     assert_eq!( ReprPacked::OFFSET_B.read_copy(ptr), "20" );
-    /// }
     /// ```
+    #[inline(always)]
+    pub unsafe fn replace_into(self, dest: *mut S, value: F, out: &mut MaybeUninit<F>) {
+        let field = self.raw_get_mut(dest);
+        core::ptr::copy_nonoverlapping(
+            field as *const u8,
+            out.as_mut_ptr() as *mut u8,
+            Mem::<F>::SIZE,
+        );
+        field.write_unaligned(value);
+    }
+}
+
+#[cfg(all(feature = "std", feature = "min_const_generics"))]
+#[cfg_attr(
+    feature = "docsrs",
+    doc(cfg(all(feature = "std", feature = "min_const_generics")))
+)]
+impl<S, A, const N: usize> FieldOffset<S, [u8; N], A> {
+    /// Gets the nul-terminated C string stored in this `[u8; N]` field.
     ///
-    /// This method can't be called for non-Copy fields.
-    /// ```compile_fail
+    /// The string ends at the first nul byte in the field,
+    /// the bytes after it are ignored.
+    ///
+    /// Because `[u8; N]` has an alignment of 1,
+    /// this can be called for both `Aligned` and `Unaligned` offsets.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if there's no nul byte in the field.
+    ///
+    /// # Example
+    ///
+    /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprPacked;
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
     ///
-    /// let this = ReprPacked{ a: vec![0, 1, 2], b: "20", c: (), d: () };
+    /// let this = ReprC{ a: 3u32, b: *b"hello\0world", c: (), d: () };
+    /// assert_eq!( ReprC::OFFSET_B.get_cstr(&this).unwrap().to_str(), Ok("hello") );
+    ///
+    /// let this = ReprPacked{ a: 3u8, b: *b"unterminated", c: (), d: () };
+    /// assert!( ReprPacked::OFFSET_B.get_cstr(&this).is_err() );
     ///
-    /// unsafe{
-    ///     let _ = ReprPacked::OFFSET_A.read_copy(&this);
-    /// }
     /// ```
-    #[inline(always)]
-    pub unsafe fn read_copy(self, base: *const S) -> F
-    where
-        F: Copy,
-    {
-        impl_fo!(fn read_copy<S, F, Unaligned>(self, base))
+    pub fn get_cstr(self, base: &S) -> Result<&CStr, FromBytesWithNulError> {
+        let bytes: &[u8; N] = unsafe { &*impl_fo!(fn get_ptr<S, [u8; N], A>(self, base)) };
+
+        match bytes.iter().position(|&b| b == 0) {
+            Some(nul_pos) => CStr::from_bytes_with_nul(&bytes[..=nul_pos]),
+            // There's no nul byte, so this always returns an error
+            None => CStr::from_bytes_with_nul(bytes),
+        }
     }
+}
 
-    /// Reads the value from the field in `source` without moving it.
+#[cfg(feature = "min_const_generics")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "min_const_generics")))]
+impl<S, A, const N: usize> FieldOffset<S, [u8; N], A> {
+    /// Finds the index of the first `needle` byte in this `[u8; N]` field.
+    ///
+    /// Because `[u8; N]` has an alignment of 1,
+    /// this can be called for both `Aligned` and `Unaligned` offsets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
+    ///
+    /// let this = ReprC{ a: 3u32, b: *b"key=value=", c: (), d: () };
+    /// assert_eq!( ReprC::OFFSET_B.find_byte(&this, b'='), Some(3) );
+    /// assert_eq!( ReprC::OFFSET_B.find_byte(&this, b';'), None );
     ///
-    /// # Safety
+    /// let this = ReprPacked{ a: 3u8, b: *b"a,b,c", c: (), d: () };
+    /// assert_eq!( ReprPacked::OFFSET_B.find_byte(&this, b','), Some(1) );
     ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::read_unaligned`](https://doc.rust-lang.org/std/ptr/fn.read_unaligned.html).
+    /// ```
+    #[inline]
+    pub fn find_byte(self, base: &S, needle: u8) -> Option<usize> {
+        let bytes: &[u8; N] = unsafe { &*impl_fo!(fn get_ptr<S, [u8; N], A>(self, base)) };
+
+        bytes.iter().position(|&b| b == needle)
+    }
+
+    /// Finds the index of the last `needle` byte in this `[u8; N]` field.
     ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+    /// Because `[u8; N]` has an alignment of 1,
+    /// this can be called for both `Aligned` and `Unaligned` offsets.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprPacked;
-    ///
-    /// use std::mem::ManuallyDrop;
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
     ///
-    /// let this = ManuallyDrop::new(ReprPacked{
-    ///     a: vec![0, 1, 2],
-    ///     b: "20".to_string(),
-    ///     c: (),
-    ///     d: (),
-    /// });
+    /// let this = ReprC{ a: 3u32, b: *b"key=value=", c: (), d: () };
+    /// assert_eq!( ReprC::OFFSET_B.rfind_byte(&this, b'='), Some(9) );
+    /// assert_eq!( ReprC::OFFSET_B.rfind_byte(&this, b';'), None );
     ///
-    /// let ptr: *const _ = &*this;
-    /// unsafe{
-    ///     assert_eq!( ReprPacked::OFFSET_A.read(ptr), vec![0, 1, 2] );
-    ///     assert_eq!( ReprPacked::OFFSET_B.read(ptr), "20".to_string() );
-    /// }
+    /// let this = ReprPacked{ a: 3u8, b: *b"a,b,c", c: (), d: () };
+    /// assert_eq!( ReprPacked::OFFSET_B.rfind_byte(&this, b','), Some(3) );
     ///
     /// ```
-    #[inline(always)]
-    pub unsafe fn read(self, source: *const S) -> F {
-        impl_fo!(fn read<S, F, Unaligned>(self, source))
+    #[inline]
+    pub fn rfind_byte(self, base: &S, needle: u8) -> Option<usize> {
+        let bytes: &[u8; N] = unsafe { &*impl_fo!(fn get_ptr<S, [u8; N], A>(self, base)) };
+
+        bytes.iter().rposition(|&b| b == needle)
     }
+}
 
-    /// Writes `value` ìnto the field in `source` without dropping the old value of the field.
+#[cfg(feature = "min_const_generics")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "min_const_generics")))]
+impl<S, F, A> FieldOffset<S, F, A> {
+    /// Changes the type of the field to a byte array of the same size.
     ///
-    /// # Safety
+    /// This causes a compile-time error if `N` is not the size of `F`.
     ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::write_unaligned`](https://doc.rust-lang.org/std/ptr/fn.write_unaligned.html).
+    /// Because `[u8; N]` has an alignment of 1,
+    /// the returned `FieldOffset` keeps the `A` type parameter.
     ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+    /// # Safety
+    ///
+    /// Callers must ensure that every byte of the field is initialized
+    /// (eg: the field contains no padding bytes),
+    /// whenever the field is read as a byte array.
     ///
+    /// If the `F` type encodes an invariant,
+    /// then callers must ensure that the invariant is upheld
+    /// when the field is written as a byte array.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprPacked;
-    /// use repr_offset::utils::moved;
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
     ///
-    /// let mut this = ReprPacked{ a: 10u8, b: "20", c: (), d: () };
+    /// let mut this = ReprC{ a: 3u8, b: 0x1234_5678u32, c: (), d: () };
     ///
-    /// let ptr: *mut _ = &mut this;
-    /// unsafe{
-    ///     ReprPacked::OFFSET_A.write(ptr, 13u8);
-    ///     ReprPacked::OFFSET_B.write(ptr, "21");
-    /// }
-    /// assert_eq!( moved(this.a), 13u8 );
-    /// assert_eq!( moved(this.b), "21" );
+    /// let bytes = unsafe{ ReprC::OFFSET_B.as_byte_field::<4>() };
+    /// assert_eq!( bytes.get(&this), &0x1234_5678u32.to_ne_bytes() );
+    ///
+    /// *bytes.get_mut(&mut this) = 0xABCD_EF01u32.to_ne_bytes();
+    /// assert_eq!( this.b, 0xABCD_EF01 );
+    ///
+    ///
+    /// let mut this = ReprPacked{ a: 3u8, b: 0x0102_0304u32, c: (), d: () };
+    ///
+    /// let bytes = unsafe{ ReprPacked::OFFSET_B.as_byte_field::<4>() };
+    /// assert_eq!( bytes.get_copy(&this), 0x0102_0304u32.to_ne_bytes() );
+    ///
+    /// ```
     ///
+    /// Passing the wrong size causes a compile-time error:
+    ///
+    /// ```compile_fail
+    /// use repr_offset::for_examples::ReprC;
+    ///
+    /// let this = ReprC{ a: 3u8, b: 5u32, c: (), d: () };
+    ///
+    /// let bytes = unsafe{ ReprC::OFFSET_B.as_byte_field::<2>() };
+    /// assert_eq!( bytes.get(&this), &[0, 5] );
     /// ```
     #[inline(always)]
-    pub unsafe fn write(self, source: *mut S, value: F) {
-        impl_fo!(fn write<S, F, Unaligned>(self, source, value))
+    pub const unsafe fn as_byte_field<const N: usize>(self) -> FieldOffset<S, [u8; N], A> {
+        let _: () = AssertByteSize::<F, N>::SAME_SIZE;
+        FieldOffset::new(self.offset)
     }
+}
 
-    /// Copies the field in `source` into `destination`.
-    ///
-    /// # Safety
+struct AssertFitsIn<F2, F>(PhantomData<fn() -> (F2, F)>);
+
+impl<F2, F> AssertFitsIn<F2, F> {
+    // Indexing out of bounds when `F2` is larger than `F`,
+    // causing a compile-time error when this constant is used.
+    const FITS: () = [()][(Mem::<F2>::SIZE > Mem::<F>::SIZE) as usize];
+}
+
+#[cfg(feature = "min_const_generics")]
+struct AssertByteSize<F, const N: usize>(PhantomData<fn() -> F>);
+
+#[cfg(feature = "min_const_generics")]
+impl<F, const N: usize> AssertByteSize<F, N> {
+    // Indexing out of bounds when `N` isn't the size of `F`,
+    // causing a compile-time error when this constant is used.
+    const SAME_SIZE: () = [()][(N != Mem::<F>::SIZE) as usize];
+}
+
+#[cfg(feature = "min_const_generics")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "min_const_generics")))]
+impl<S, F> FieldOffset<S, F, Aligned> {
+    /// Gets a reference to the field of the `index`th element of the `base` array.
     ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::copy`](https://doc.rust-lang.org/std/ptr/fn.copy.html),
-    /// except that `source` and `destination` do not need to be properly aligned.
+    /// # Panics
     ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+    /// This panics if `index` is out of bounds for the array, ie: `index >= N`.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprPacked;
-    /// use repr_offset::utils::moved;
-    ///
-    ///
-    /// let this = ReprPacked{ a: 10u8, b: "20", c: (), d: () };
-    /// let mut other = ReprPacked{ a: 0u8, b: "", c: (), d: () };
+    /// use repr_offset::for_examples::ReprC;
     ///
-    /// let this_ptr: *const _ = &this;
-    /// let other_ptr: *mut _ = &mut other;
-    /// unsafe{
-    ///     ReprPacked::OFFSET_A.copy(this_ptr, other_ptr);
-    ///     ReprPacked::OFFSET_B.copy(this_ptr, other_ptr);
-    /// }
-    /// assert_eq!( moved(this.a), 10u8 );
-    /// assert_eq!( moved(this.b), "20" );
+    /// let array = [
+    ///     ReprC{ a: 3u8, b: "foo", c: (), d: () },
+    ///     ReprC{ a: 5u8, b: "bar", c: (), d: () },
+    /// ];
     ///
-    /// assert_eq!( moved(other.a), 10u8 );
-    /// assert_eq!( moved(other.b), "20" );
+    /// assert_eq!( ReprC::OFFSET_A.get_in_array(&array, 0), &3 );
+    /// assert_eq!( ReprC::OFFSET_B.get_in_array(&array, 1), &"bar" );
     ///
     /// ```
     #[inline(always)]
-    pub unsafe fn copy(self, source: *const S, destination: *mut S) {
-        impl_fo!(fn copy<S, F, Unaligned>(self, source, destination))
+    pub fn get_in_array<const N: usize>(self, base: &[S; N], index: usize) -> &F {
+        self.get(&base[index])
     }
 
-    /// Copies the field in `source` into `destination`,
-    /// `source` and `destination` must not overlap.
-    ///
-    /// # Safety
+    /// Gets a mutable reference to the field of the `index`th element of the `base` array.
     ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::copy_nonoverlapping`
-    /// ](https://doc.rust-lang.org/std/ptr/fn.copy_nonoverlapping.html),
-    /// except that `source` and `destination` do not need to be properly aligned.
+    /// # Panics
     ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+    /// This panics if `index` is out of bounds for the array, ie: `index >= N`.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprPacked;
-    /// use repr_offset::utils::moved;
+    /// use repr_offset::for_examples::ReprC;
     ///
-    /// let this = ReprPacked{ a: '#', b: 81, c: (), d: () };
-    /// let mut other = ReprPacked{ a: '_', b: 0, c: (), d: () };
+    /// let mut array = [
+    ///     ReprC{ a: 3u8, b: "foo", c: (), d: () },
+    ///     ReprC{ a: 5u8, b: "bar", c: (), d: () },
+    /// ];
     ///
-    /// let this_ptr: *const _ = &this;
-    /// let other_ptr: *mut _ = &mut other;
-    /// unsafe{
-    ///     ReprPacked::OFFSET_A.copy_nonoverlapping(this_ptr, other_ptr);
-    ///     ReprPacked::OFFSET_B.copy_nonoverlapping(this_ptr, other_ptr);
+    /// for i in 0..array.len() {
+    ///     *ReprC::OFFSET_A.get_mut_in_array(&mut array, i) *= 2;
     /// }
-    /// assert_eq!( moved(this.a), '#' );
-    /// assert_eq!( moved(this.b), 81 );
     ///
-    /// assert_eq!( moved(other.a), '#' );
-    /// assert_eq!( moved(other.b), 81 );
+    /// assert_eq!( array[0].a, 6 );
+    /// assert_eq!( array[1].a, 10 );
     ///
     /// ```
     #[inline(always)]
-    pub unsafe fn copy_nonoverlapping(self, source: *const S, destination: *mut S) {
-        impl_fo!(fn copy_nonoverlapping<S, F, Unaligned>(self, source, destination))
+    pub fn get_mut_in_array<const N: usize>(self, base: &mut [S; N], index: usize) -> &mut F {
+        self.get_mut(&mut base[index])
     }
 }
 
-impl<S, F> FieldOffset<S, F, Unaligned> {
-    /// Replaces the value of a field in `dest` with `value`,
-    /// returning the old value of the field.
-    ///
-    /// # Safety
-    ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::replace`](https://doc.rust-lang.org/std/ptr/fn.replace.html),
-    /// except that `dest` does not need to be properly aligned.
-    ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+#[cfg(feature = "min_const_generics")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "min_const_generics")))]
+impl<S, F, const N: usize> FieldOffset<S, [F; N], Aligned> {
+    /// Gets a slice of the elements in this `[F; N]` field,
+    /// with `N` inferred from the type of the field.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprPacked;
-    /// use repr_offset::utils::moved;
-    ///
-    /// let mut this = ReprPacked{ a: [0u8, 1], b: false, c: (), d: () };
+    /// use repr_offset::for_examples::ReprC;
     ///
-    /// let ptr: *mut _ = &mut this;
-    /// unsafe{
-    ///     assert_eq!( ReprPacked::OFFSET_A.replace(ptr, [2, 3]), [0u8, 1] );
-    ///     assert_eq!( ReprPacked::OFFSET_B.replace(ptr, true), false );
-    /// }
+    /// let this = ReprC{ a: 3u8, b: [5u32, 8, 13], c: (), d: () };
     ///
-    /// assert_eq!( moved(this.a), [2u8, 3] );
-    /// assert_eq!( moved(this.b), true );
+    /// let slice: &[u32] = ReprC::OFFSET_B.get_array_slice(&this);
+    /// assert_eq!( slice, &[5, 8, 13][..] );
     ///
     /// ```
     #[inline(always)]
-    pub unsafe fn replace(self, dest: *mut S, value: F) -> F {
-        impl_fo!(fn replace<S, F, Unaligned>(self, dest, value))
+    pub fn get_array_slice(self, base: &S) -> &[F] {
+        self.get(base)
     }
 
-    /// Replaces the value of a field in `dest` with `value`,
-    /// returning the old value of the field.
-    ///
+    /// Gets a mutable slice of the elements in this `[F; N]` field,
+    /// with `N` inferred from the type of the field.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprPacked;
-    /// use repr_offset::utils::moved;
+    /// use repr_offset::for_examples::ReprC;
     ///
-    /// let mut this = ReprPacked{ a: [0u8, 1], b: false, c: (), d: () };
+    /// let mut this = ReprC{ a: 3u8, b: [5u32, 8, 13], c: (), d: () };
     ///
-    /// assert_eq!( ReprPacked::OFFSET_A.replace_mut(&mut this, [2, 3]), [0u8, 1] );
-    /// assert_eq!( ReprPacked::OFFSET_B.replace_mut(&mut this, true), false );
+    /// for elem in ReprC::OFFSET_B.get_array_mut_slice(&mut this) {
+    ///     *elem *= 2;
+    /// }
     ///
-    /// assert_eq!( moved(this.a), [2u8, 3] );
-    /// assert_eq!( moved(this.b), true );
+    /// assert_eq!( this.b, [10, 16, 26] );
     ///
     /// ```
-    pub fn replace_mut(self, dest: &mut S, value: F) -> F {
-        unsafe { impl_fo!(fn replace_mut<S, F, Unaligned>(self, dest, value)) }
+    #[inline(always)]
+    pub fn get_array_mut_slice(self, base: &mut S) -> &mut [F] {
+        self.get_mut(base)
     }
 }
 
-impl<S, F> FieldOffset<S, F, Unaligned> {
-    /// Swaps the values of a field between the `left` and `right` pointers.
+#[cfg(feature = "const_fns")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "const_fns")))]
+impl<S, F> FieldOffset<S, F, Aligned> {
+    /// Copies the aligned field that this is an offset for,
+    /// from a raw pointer to the `S` struct, usable in const contexts.
+    ///
+    /// This allows computing values from the fields of `const` structs at compile-time,
+    /// eg: to build lookup tables keyed by the layout of a struct.
     ///
     /// # Safety
     ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::swap`](https://doc.rust-lang.org/std/ptr/fn.swap.html),
-    /// except that it does not require aligned pointers.
+    /// This has the same safety requirements as
+    /// [`std::ptr::read`](https://doc.rust-lang.org/std/ptr/fn.read.html)
+    /// for the field that this is an offset for.
     ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+    /// In const contexts, `base` must also follow the rules of const evaluation,
+    /// which only allow reading memory that is known at compile-time,
+    /// eg: pointers derived from references to `const` items.
+    /// Pointers to `static`s can only be read in const contexts since Rust 1.83.0,
+    /// and pointers created from integers can't be read in const contexts.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprPacked;
-    /// use repr_offset::utils::moved;
+    /// use repr_offset::for_examples::ReprC;
     ///
-    /// let mut this = ReprPacked{ a: '=', b: 64u16, c: (), d: () };
-    /// let mut other = ReprPacked{ a: '!', b: 255u16, c: (), d: () };
+    /// type This = ReprC<u8, u32, [u16; 2], ()>;
     ///
-    /// let this_ptr: *mut _ = &mut this;
-    /// let other_ptr: *mut _ = &mut other;
-    /// unsafe{
-    ///     ReprPacked::OFFSET_A.swap(this_ptr, other_ptr);
-    ///     ReprPacked::OFFSET_B.swap(this_ptr, other_ptr);
-    /// }
-    /// assert_eq!( moved(this.a), '!' );
-    /// assert_eq!( moved(this.b), 255 );
+    /// const THIS: This = ReprC{ a: 3, b: 5, c: [8, 13], d: () };
     ///
-    /// assert_eq!( moved(other.a), '=' );
-    /// assert_eq!( moved(other.b), 64 );
+    /// const B: u32 = unsafe{ This::OFFSET_B.get_copy_const_ptr(&THIS) };
+    /// const C: [u16; 2] = unsafe{ This::OFFSET_C.get_copy_const_ptr(&THIS) };
+    ///
+    /// assert_eq!( B, 5 );
+    /// assert_eq!( C, [8, 13] );
     ///
     /// ```
     #[inline(always)]
-    pub unsafe fn swap(self, left: *mut S, right: *mut S) {
-        impl_fo!(fn swap<S, F, Unaligned>(self, left, right))
+    pub const unsafe fn get_copy_const_ptr(self, base: *const S) -> F
+    where
+        F: Copy,
+    {
+        *((base as *const u8).add(self.offset) as *const F)
     }
+}
 
-    /// Swaps the values of a field between the non-overlapping `left` and `right` pointers.
-    ///
-    /// # Safety
-    ///
-    /// This function has the same safety requirements as
-    /// [`std::ptr::swap_nonoverlapping`
-    /// ](https://doc.rust-lang.org/std/ptr/fn.swap_nonoverlapping.html)
-    /// except that it does not require aligned pointers.
+#[cfg(feature = "ptr_alignment")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "ptr_alignment")))]
+impl<S: ?Sized, F, A> FieldOffset<S, F, A> {
+    /// The alignment of the type of the field,
+    /// as the standard library's [`Alignment`] type,
+    /// which is guaranteed to be a power of two.
     ///
-    /// Those safety requirements only apply to the field that this is an offset for,
-    /// fields after it or before it don't need to be valid to call this method.
+    /// The [`Alignment`] type is unstable, so this requires a nightly compiler.
+    /// The [`field_align`](#method.field_align) method returns the alignment as a `usize`,
+    /// and is usable on all supported Rust versions.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
-    /// use repr_offset::for_examples::ReprPacked;
-    /// use repr_offset::utils::moved;
+    /// #![feature(ptr_alignment_type)]
     ///
-    /// let mut this = ReprPacked{ a: [false, true], b: &27u32, c: (), d: () };
-    /// let mut other = ReprPacked{ a: [true, false], b: &81u32, c: (), d: () };
+    /// use repr_offset::for_examples::{ReprC, ReprPacked};
     ///
-    /// let this_ptr: *mut _ = &mut this;
-    /// let other_ptr: *mut _ = &mut other;
-    /// unsafe{
-    ///     ReprPacked::OFFSET_A.swap_nonoverlapping(this_ptr, other_ptr);
-    ///     ReprPacked::OFFSET_B.swap_nonoverlapping(this_ptr, other_ptr);
-    /// }
-    /// assert_eq!( moved(this.a), [true, false] );
-    /// assert_eq!( moved(this.b), &81 );
+    /// use std::mem::Alignment;
     ///
-    /// assert_eq!( moved(other.a), [false, true] );
-    /// assert_eq!( moved(other.b), &27 );
+    /// type This = ReprC<u8, u64, [u16; 3], ()>;
+    ///
+    /// assert_eq!( This::OFFSET_A.field_alignment(), Alignment::of::<u8>() );
+    /// assert_eq!( This::OFFSET_B.field_alignment(), Alignment::of::<u64>() );
+    /// assert_eq!( This::OFFSET_C.field_alignment().as_usize(), 2 );
+    ///
+    /// type Packed = ReprPacked<u8, u64, [u16; 3], ()>;
+    ///
+    /// assert_eq!( Packed::OFFSET_B.field_alignment(), Alignment::of::<u64>() );
     ///
     /// ```
     ///
+    /// [`Alignment`]: https://doc.rust-lang.org/nightly/core/mem/struct.Alignment.html
     #[inline(always)]
-    pub unsafe fn swap_nonoverlapping(self, left: *mut S, right: *mut S) {
-        impl_fo!(fn swap_nonoverlapping<S, F, Unaligned>(self, left, right))
+    pub const fn field_alignment(self) -> core::mem::Alignment {
+        core::mem::Alignment::of::<F>()
     }
+}
 
-    /// Swaps the values of a field between `left` and `right`.
+#[cfg(feature = "bitflags")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "bitflags")))]
+impl<S, B, A> FieldOffset<S, B, A>
+where
+    B: bitflags::Bits,
+{
+    /// Reads this integer field as the `Fl` flags type,
+    /// keeping any bits that don't correspond to a defined flag.
+    ///
+    /// This is for structs that store [`bitflags`] types as their underlying integer,
+    /// eg: structs shared with C code.
+    ///
+    /// Fields whose type is a flags type declared with the [`bitflags`] macro
+    /// can be read with the regular methods, like
+    /// [`get_copy`](#method.get_copy) and [`read`](#method.read),
+    /// because those flags types are `Copy` structs wrapping the integer.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same safety requirements as
+    /// [`std::ptr::read_unaligned`](https://doc.rust-lang.org/std/ptr/fn.read_unaligned.html).
+    ///
+    /// Those safety requirements only apply to the field that this is an offset for,
+    /// fields after it or before it don't need to be valid to call this method.
     ///
     /// # Example
     ///
     /// ```rust
     /// # #![deny(safe_packed_borrows)]
     /// use repr_offset::for_examples::ReprPacked;
-    /// use repr_offset::utils::moved;
     ///
-    /// let mut this = ReprPacked{ a: [true, true], b: 0x0Fu8, c: (), d: () };
-    /// let mut other = ReprPacked{ a: [false, false], b: 0xF0u8, c: (), d: () };
-    ///
-    /// ReprPacked::OFFSET_A.swap_mut(&mut this, &mut other);
-    /// ReprPacked::OFFSET_B.swap_mut(&mut this, &mut other);
+    /// bitflags::bitflags! {
+    ///     #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    ///     struct Permissions: u8 {
+    ///         const READ = 1;
+    ///         const WRITE = 2;
+    ///         const EXECUTE = 4;
+    ///     }
+    /// }
     ///
-    /// assert_eq!( moved(this.a), [false, false] );
-    /// assert_eq!( moved(this.b), 0xF0u8 );
+    /// let this = ReprPacked{ a: 0b11u8, b: 0b101u8, c: (), d: () };
     ///
-    /// assert_eq!( moved(other.a), [true, true] );
-    /// assert_eq!( moved(other.b), 0x0Fu8 );
+    /// unsafe{
+    ///     assert_eq!(
+    ///         ReprPacked::OFFSET_A.read_flags::<Permissions>(&this),
+    ///         Permissions::READ | Permissions::WRITE,
+    ///     );
+    ///     assert_eq!(
+    ///         ReprPacked::OFFSET_B.read_flags::<Permissions>(&this),
+    ///         Permissions::READ | Permissions::EXECUTE,
+    ///     );
+    /// }
     ///
     /// ```
     ///
+    /// [`bitflags`]: https://docs.rs/bitflags/2/bitflags/macro.bitflags.html
     #[inline(always)]
-    pub fn swap_mut(self, left: &mut S, right: &mut S) {
-        unsafe { impl_fo!(fn swap_mut<S, F, Unaligned>(self, left, right)) }
+    pub unsafe fn read_flags<Fl>(self, base: *const S) -> Fl
+    where
+        Fl: bitflags::Flags<Bits = B>,
+    {
+        Fl::from_bits_retain(self.raw_get(base).read_unaligned())
     }
 }
 
@@ -1,5 +1,9 @@
 // Defined this macro to reduce the amount of instructions in debug builds
 // caused by delegating to `raw_get`
+//
+// The field pointer is derived from the base pointer with pointer arithmetic,
+// never by casting through `usize`, so that it keeps the provenance of the base pointer
+// (this is checked by running the tests in Miri with `-Zmiri-strict-provenance`).
 macro_rules! get_ptr_method {
     ($self:expr, $base:expr, $S:ty, $F:ty) => {{
         #[cfg(feature = "testing")]
@@ -20,6 +24,19 @@ macro_rules! get_mut_ptr_method {
     }};
 }
 
+// Reading a zero-sized field doesn't access memory,
+// this avoids doing pointer arithmetic on a (potentially dangling) pointer for those fields,
+// since `ptr::read` only requires the pointer to a zero-sized value to be non-null and aligned.
+macro_rules! read_zst_or {
+    ($F:ty, $read:expr) => {
+        if crate::utils::Mem::<$F>::SIZE == 0 {
+            core::ptr::NonNull::<$F>::dangling().as_ptr().read()
+        } else {
+            $read
+        }
+    };
+}
+
 macro_rules! replace_unaligned {
     ($self:expr, $base:expr, $value:expr, $S:ty, $F:ty) => {{
         let ptr = get_mut_ptr_method!($self, $base, $S, $F);
@@ -72,20 +89,26 @@ macro_rules! impl_fo {
         }
     };
     (fn read_copy<$S:ty, $F:ty, $A:ident>($self:expr, $base:expr)) => {
-        if_aligned! {
-            $A {
-                *get_ptr_method!($self, $base, $S, $F)
-            } else {
-                get_ptr_method!($self, $base, $S, $F).read_unaligned()
+        read_zst_or! {
+            $F,
+            if_aligned! {
+                $A {
+                    *get_ptr_method!($self, $base, $S, $F)
+                } else {
+                    get_ptr_method!($self, $base, $S, $F).read_unaligned()
+                }
             }
         }
     };
     (fn read<$S:ty, $F:ty, $A:ident>($self:expr, $source:ident)) => {
-        if_aligned! {
-            $A {
-                get_ptr_method!($self, $source, $S, $F).read()
-            } else {
-                get_ptr_method!($self, $source, $S, $F).read_unaligned()
+        read_zst_or! {
+            $F,
+            if_aligned! {
+                $A {
+                    get_ptr_method!($self, $source, $S, $F).read()
+                } else {
+                    get_ptr_method!($self, $source, $S, $F).read_unaligned()
+                }
             }
         }
     };
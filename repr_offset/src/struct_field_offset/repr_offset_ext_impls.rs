@@ -4,6 +4,9 @@ use crate::{
     FieldOffset,
 };
 
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
 //////////////////////////////////////////////////////////////////////////////
 
 unsafe impl<S> ROExtAcc for S {
@@ -16,6 +19,26 @@ unsafe impl<S> ROExtAcc for S {
         unsafe { impl_fo!(fn get_mut<S, F, Aligned>(offset, self)) }
     }
 
+    #[inline(always)]
+    fn f_get_mut_and_read<F1, F2>(
+        &mut self,
+        write_offset: FieldOffset<Self, F1, Aligned>,
+        read_offset: FieldOffset<Self, F2, Aligned>,
+    ) -> (&mut F1, F2)
+    where
+        F2: Copy,
+    {
+        debug_assert!(
+            write_offset.offset() + core::mem::size_of::<F1>() <= read_offset.offset()
+                || read_offset.offset() + core::mem::size_of::<F2>() <= write_offset.offset(),
+            "the written field (at offset {}) overlaps the read field (at offset {})",
+            write_offset.offset(),
+            read_offset.offset(),
+        );
+        let read = read_offset.get_copy(self);
+        (write_offset.get_mut(self), read)
+    }
+
     #[inline(always)]
     fn f_get_ptr<F, A>(&self, offset: FieldOffset<Self, F, A>) -> *const F {
         unsafe { impl_fo!(fn get_ptr<S, F, A>(offset, self)) }
@@ -49,6 +72,37 @@ macro_rules! impl_ROExtOps {
             {
                 unsafe{ impl_fo!(fn get_copy<S, F, $A>(offset, self)) }
             }
+
+            #[cfg(feature = "std")]
+            #[inline(always)]
+            fn f_vec_push<T>(&mut self, offset: FieldOffset<Self, Vec<T>, $A>, value: T) {
+                if_aligned!{
+                    $A {
+                        offset.get_mut(self).push(value);
+                    } else {
+                        let mut vec = offset.replace_mut(self, Vec::new());
+                        vec.push(value);
+                        // safety: the empty `Vec` left in the field doesn't need to be dropped
+                        unsafe{ offset.write(self, vec); }
+                    }
+                }
+            }
+
+            #[cfg(feature = "std")]
+            #[inline(always)]
+            fn f_vec_pop<T>(&mut self, offset: FieldOffset<Self, Vec<T>, $A>) -> Option<T> {
+                if_aligned!{
+                    $A {
+                        offset.get_mut(self).pop()
+                    } else {
+                        let mut vec = offset.replace_mut(self, Vec::new());
+                        let ret = vec.pop();
+                        // safety: the empty `Vec` left in the field doesn't need to be dropped
+                        unsafe{ offset.write(self, vec); }
+                        ret
+                    }
+                }
+            }
         }
     };
 }
@@ -0,0 +1,406 @@
+//! A builder for initializing a struct field by field.
+
+use crate::FieldOffset;
+
+use core::{
+    mem::{ManuallyDrop, MaybeUninit},
+    ptr,
+};
+
+/// A builder for initializing an `S` one field at a time,
+/// using the [`FieldOffset`]s of its fields.
+///
+/// This wraps the `MaybeUninit` + [`write`](./struct.FieldOffset.html#method.write-1)
+/// pattern for initializing a struct through a raw pointer.
+///
+/// # Checking completeness
+///
+/// Callers are responsible for writing every field of the struct
+/// before calling [`assume_init`](#method.assume_init),
+/// this type can't know where the fields of `S` are.
+///
+/// When the "debug_offsets" feature is enabled,
+/// this records which bytes of the struct were written,
+/// and [`assume_init`](#method.assume_init) panics if
+/// any gap between the written fields is too large to be padding
+/// (padding is always smaller than the alignment of `S`).
+///
+/// This is only a debugging aid, since it can't tell padding apart from unwritten fields:
+/// a missing field that's smaller than the alignment of `S`
+/// (eg: a `u8` or `bool` field in a struct with a `u32` field)
+/// is not detected unless it's next to other unwritten bytes.
+///
+/// Writing to a field more than once also panics with the "debug_offsets" feature,
+/// since overwriting a field leaks its previous value.
+///
+/// # Panic safety
+///
+/// If the `StructInitializer` is dropped without calling
+/// [`assume_init`](#method.assume_init)
+/// (eg: because of a panic in the middle of initializing the struct),
+/// it drops the fields that were written with [`write`](#method.write).
+///
+/// Up to 16 written fields that need dropping can be tracked,
+/// [`write`](#method.write) panics (before writing the field)
+/// if it would need to track more than that,
+/// so that no written field is leaked.
+/// Fields written through [`as_mut_ptr`](#method.as_mut_ptr) aren't tracked,
+/// and are leaked instead of dropped.
+///
+/// # Example
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::{
+///     for_examples::{ReprC, ReprPacked},
+///     StructInitializer,
+/// };
+///
+/// let mut init = StructInitializer::<ReprC<u8, &str, u64, char>>::new();
+/// init.write(ReprC::OFFSET_A, 3)
+///     .write(ReprC::OFFSET_B, "five")
+///     .write(ReprC::OFFSET_C, 8)
+///     .write(ReprC::OFFSET_D, '@');
+///
+/// let this = unsafe{ init.assume_init() };
+///
+/// assert_eq!( this.a, 3 );
+/// assert_eq!( this.b, "five" );
+/// assert_eq!( this.c, 8 );
+/// assert_eq!( this.d, '@' );
+///
+///
+/// // Unaligned fields can also be written with the builder
+/// let mut init = StructInitializer::<ReprPacked<u8, u16, u32, u64>>::new();
+/// init.write(ReprPacked::OFFSET_A, 13)
+///     .write(ReprPacked::OFFSET_B, 21)
+///     .write(ReprPacked::OFFSET_C, 34)
+///     .write(ReprPacked::OFFSET_D, 55);
+///
+/// let this = unsafe{ init.assume_init() };
+///
+/// assert_eq!( ReprPacked::OFFSET_A.get_copy(&this), 13 );
+/// assert_eq!( ReprPacked::OFFSET_B.get_copy(&this), 21 );
+/// assert_eq!( ReprPacked::OFFSET_C.get_copy(&this), 34 );
+/// assert_eq!( ReprPacked::OFFSET_D.get_copy(&this), 55 );
+///
+/// ```
+///
+/// [`FieldOffset`]: ./struct.FieldOffset.html
+pub struct StructInitializer<S> {
+    value: MaybeUninit<S>,
+    written_fields: written_fields::WrittenFields,
+    #[cfg(feature = "debug_offsets")]
+    written: written_ranges::WrittenRanges,
+}
+
+impl<S> StructInitializer<S> {
+    /// Constructs a `StructInitializer` with all the fields uninitialized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use repr_offset::{for_examples::ReprC, StructInitializer};
+    ///
+    /// let mut init = StructInitializer::<ReprC<u8, u16, (), ()>>::new();
+    /// init.write(ReprC::OFFSET_A, 3)
+    ///     .write(ReprC::OFFSET_B, 5)
+    ///     .write(ReprC::OFFSET_C, ())
+    ///     .write(ReprC::OFFSET_D, ());
+    ///
+    /// let this = unsafe{ init.assume_init() };
+    ///
+    /// assert_eq!( (this.a, this.b), (3, 5) );
+    ///
+    /// ```
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            value: MaybeUninit::uninit(),
+            written_fields: written_fields::WrittenFields::NEW,
+            #[cfg(feature = "debug_offsets")]
+            written: written_ranges::WrittenRanges::NEW,
+        }
+    }
+
+    /// Writes `value` into the field that `offset` is for,
+    /// without dropping the previous value of the field.
+    ///
+    /// Writing to the same field twice leaks the first value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if 16 fields that need dropping were already written,
+    /// and `F` also needs dropping
+    /// ([more details](#panic-safety)).
+    ///
+    /// With the "debug_offsets" feature enabled,
+    /// this panics if the field (or part of it) was already written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use repr_offset::{for_examples::ReprC, StructInitializer};
+    ///
+    /// let mut init = StructInitializer::<ReprC<String, Vec<u8>, (), ()>>::new();
+    /// init.write(ReprC::OFFSET_A, "hello".to_string());
+    /// init.write(ReprC::OFFSET_B, vec![3, 5, 8]);
+    /// init.write(ReprC::OFFSET_C, ());
+    /// init.write(ReprC::OFFSET_D, ());
+    ///
+    /// let this = unsafe{ init.assume_init() };
+    ///
+    /// assert_eq!( this.a, "hello" );
+    /// assert_eq!( this.b, [3, 5, 8] );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn write<F, A>(&mut self, offset: FieldOffset<S, F, A>, value: F) -> &mut Self {
+        #[cfg(feature = "debug_offsets")]
+        self.written
+            .record::<S>(offset.offset(), core::mem::size_of::<F>());
+
+        // Recording the field before writing it,
+        // so that `value` is dropped if this panics.
+        self.written_fields.record::<F>(offset.offset());
+
+        unsafe {
+            // Using `write_unaligned` because this method works with both
+            // `Aligned` and `Unaligned` offsets.
+            offset
+                .raw_get_mut(self.value.as_mut_ptr())
+                .write_unaligned(value);
+        }
+
+        self
+    }
+
+    /// Gets a raw pointer to the struct being initialized.
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const S {
+        self.value.as_ptr()
+    }
+
+    /// Gets a mutable raw pointer to the struct being initialized.
+    ///
+    /// Fields written through this pointer are not tracked by the
+    /// "debug_offsets" feature,
+    /// and aren't dropped if the `StructInitializer` is dropped.
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut S {
+        self.value.as_mut_ptr()
+    }
+
+    /// Returns the initialized struct.
+    ///
+    /// # Safety
+    ///
+    /// Every field of the struct must have been initialized,
+    /// the same requirements as `MaybeUninit::assume_init`.
+    ///
+    /// # Panics
+    ///
+    /// With the "debug_offsets" feature enabled,
+    /// this panics when some unwritten bytes of the struct are too many to be padding.
+    /// This doesn't detect every field that wasn't written
+    /// ([more details](#checking-completeness)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use repr_offset::{for_examples::ReprC, StructInitializer};
+    ///
+    /// let mut init = StructInitializer::<ReprC<u32, u32, u32, u32>>::new();
+    /// init.write(ReprC::OFFSET_A, 3)
+    ///     .write(ReprC::OFFSET_B, 5)
+    ///     .write(ReprC::OFFSET_C, 8)
+    ///     .write(ReprC::OFFSET_D, 13);
+    ///
+    /// let this = unsafe{ init.assume_init() };
+    ///
+    /// assert_eq!( (this.a, this.b, this.c, this.d), (3, 5, 8, 13) );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn assume_init(self) -> S {
+        #[cfg(feature = "debug_offsets")]
+        self.written.assert_complete::<S>();
+
+        let this = ManuallyDrop::new(self);
+        ptr::read(&this.value).assume_init()
+    }
+}
+
+impl<S> Drop for StructInitializer<S> {
+    fn drop(&mut self) {
+        unsafe {
+            self.written_fields
+                .drop_fields(self.value.as_mut_ptr() as *mut u8);
+        }
+    }
+}
+
+impl<S> Default for StructInitializer<S> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod written_fields {
+    use core::mem::{needs_drop, size_of};
+
+    // The maximum amount of fields that need dropping that are tracked,
+    // writing more than this many panics.
+    const CAPACITY: usize = 16;
+
+    #[derive(Copy, Clone)]
+    struct WrittenField {
+        // The `start..end` byte range of the field
+        start: usize,
+        end: usize,
+        drop: unsafe fn(*mut u8),
+    }
+
+    // The fields that need dropping which were written with `StructInitializer::write`
+    pub(super) struct WrittenFields {
+        fields: [WrittenField; CAPACITY],
+        len: usize,
+    }
+
+    impl WrittenFields {
+        pub(super) const NEW: Self = Self {
+            fields: [WrittenField {
+                start: 0,
+                end: 0,
+                drop: drop_nothing,
+            }; CAPACITY],
+            len: 0,
+        };
+
+        pub(super) fn record<F>(&mut self, offset: usize) {
+            let (start, end) = (offset, offset + size_of::<F>());
+            let overlaps = |field: &WrittenField| start < field.end && field.start < end;
+
+            if needs_drop::<F>()
+                && self.len == CAPACITY
+                && !self.fields.iter().any(overlaps)
+            {
+                panic!(
+                    "`StructInitializer` can't track more than {} written fields that need dropping",
+                    CAPACITY,
+                );
+            }
+
+            // Forgetting the fields that this write (partially) overwrote,
+            // since they don't hold valid values anymore.
+            let mut i = 0;
+            while i < self.len {
+                if overlaps(&self.fields[i]) {
+                    self.len -= 1;
+                    self.fields[i] = self.fields[self.len];
+                } else {
+                    i += 1;
+                }
+            }
+
+            if needs_drop::<F>() {
+                self.fields[self.len] = WrittenField {
+                    start,
+                    end,
+                    drop: drop_field::<F>,
+                };
+                self.len += 1;
+            }
+        }
+
+        /// # Safety
+        ///
+        /// `base` must point to the struct that the fields were written into.
+        pub(super) unsafe fn drop_fields(&mut self, base: *mut u8) {
+            let len = self.len;
+            self.len = 0;
+            for field in &self.fields[..len] {
+                (field.drop)(base.add(field.start));
+            }
+        }
+    }
+
+    unsafe fn drop_nothing(_: *mut u8) {}
+
+    // Reading the field before dropping it, because it could be unaligned.
+    unsafe fn drop_field<F>(ptr: *mut u8) {
+        drop((ptr as *mut F).read_unaligned());
+    }
+}
+
+#[cfg(feature = "debug_offsets")]
+mod written_ranges {
+    use core::{
+        any::type_name,
+        mem::{align_of, size_of},
+    };
+
+    // The maximum amount of writes that are tracked,
+    // after this many writes the completeness checks are disabled.
+    const CAPACITY: usize = 64;
+
+    pub(super) struct WrittenRanges {
+        // The `start..end` byte ranges of the written fields
+        ranges: [(usize, usize); CAPACITY],
+        len: usize,
+        overflowed: bool,
+    }
+
+    impl WrittenRanges {
+        pub(super) const NEW: Self = Self {
+            ranges: [(0, 0); CAPACITY],
+            len: 0,
+            overflowed: false,
+        };
+
+        pub(super) fn record<S>(&mut self, offset: usize, size: usize) {
+            let (start, end) = (offset, offset + size);
+            for &(w_start, w_end) in &self.ranges[..self.len] {
+                if start < w_end && w_start < end {
+                    panic!(
+                        "The bytes {}..{} of `{}` were written more than once",
+                        start,
+                        end,
+                        type_name::<S>(),
+                    );
+                }
+            }
+
+            if self.len == CAPACITY {
+                self.overflowed = true;
+            } else {
+                self.ranges[self.len] = (start, end);
+                self.len += 1;
+            }
+        }
+
+        pub(super) fn assert_complete<S>(&self) {
+            if self.overflowed {
+                return;
+            }
+            let mut ranges = self.ranges;
+            let ranges = &mut ranges[..self.len];
+            ranges.sort_unstable();
+
+            let max_padding = align_of::<S>() - 1;
+            let mut written_up_to = 0;
+            for &(start, end) in ranges.iter().chain(Some(&(size_of::<S>(), 0))) {
+                if start.saturating_sub(written_up_to) > max_padding {
+                    panic!(
+                        "The bytes {}..{} of `{}` were not written",
+                        written_up_to,
+                        start,
+                        type_name::<S>(),
+                    );
+                }
+                written_up_to = written_up_to.max(end);
+            }
+        }
+    }
+}
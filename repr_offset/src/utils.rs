@@ -1,6 +1,12 @@
 //! Miscelaneous functions.
 
-use core::marker::PhantomData;
+use crate::{Aligned, FieldOffset, FieldOffsetDyn};
+
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::atomic::{self, Ordering as AtomicOrdering},
+};
 
 /// A helper function to force a variable to move (copy if it's a Copy type).
 ///
@@ -36,6 +42,534 @@ pub const fn moved<T>(val: T) -> T {
     val
 }
 
+/// Gets a reference to a field (determined by `offset`) of the struct that `ptr` points to.
+///
+/// This works with any type that dereferences to the struct,
+/// eg: `Box<S>`, `Rc<S>`, `ManuallyDrop<S>`, or a custom smart pointer.
+///
+/// # Example
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::{for_examples::ReprC, utils::project};
+///
+/// use std::{rc::Rc, sync::Arc};
+///
+/// type This = ReprC<u8, &'static str, Vec<u32>, ()>;
+///
+/// let boxed: Box<This> = Box::new(ReprC{ a: 3, b: "5", c: vec![8, 13], d: () });
+/// assert_eq!( project(&boxed, This::OFFSET_A), &3 );
+/// assert_eq!( project(&boxed, This::OFFSET_B), &"5" );
+/// assert_eq!( project(&boxed, This::OFFSET_C), &[8, 13] );
+///
+/// let rc: Rc<This> = Rc::new(ReprC{ a: 21, b: "34", c: vec![55], d: () });
+/// assert_eq!( project(&rc, This::OFFSET_A), &21 );
+/// assert_eq!( project(&rc, This::OFFSET_B), &"34" );
+/// assert_eq!( project(&rc, This::OFFSET_C), &[55] );
+///
+/// let arc: Arc<This> = Arc::new(ReprC{ a: 89, b: "144", c: vec![], d: () });
+/// assert_eq!( project(&arc, This::OFFSET_A), &89 );
+/// assert_eq!( project(&arc, This::OFFSET_B), &"144" );
+/// assert_eq!( project(&arc, This::OFFSET_C), &[] );
+///
+/// ```
+#[inline(always)]
+pub fn project<'a, D, S, F>(ptr: &'a D, offset: FieldOffset<S, F, Aligned>) -> &'a F
+where
+    D: ?Sized + Deref<Target = S>,
+    S: 'a,
+{
+    offset.get(&**ptr)
+}
+
+/// Gets a mutable reference to a field (determined by `offset`)
+/// of the struct that `ptr` points to.
+///
+/// This works with any type that mutably dereferences to the struct,
+/// eg: `Box<S>`, `ManuallyDrop<S>`, or a custom smart pointer.
+///
+/// # Example
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::{for_examples::ReprC, utils::project_mut};
+///
+/// use std::mem::ManuallyDrop;
+///
+/// type This = ReprC<u8, &'static str, Vec<u32>, ()>;
+///
+/// let mut boxed: Box<This> = Box::new(ReprC{ a: 3, b: "5", c: vec![8, 13], d: () });
+/// *project_mut(&mut boxed, This::OFFSET_A) += 10;
+/// project_mut(&mut boxed, This::OFFSET_C).push(21);
+///
+/// assert_eq!( boxed.a, 13 );
+/// assert_eq!( boxed.c, [8, 13, 21] );
+///
+/// let mut md = ManuallyDrop::new(ReprC{ a: 34, b: "55", c: 89, d: () });
+/// *project_mut(&mut md, ReprC::OFFSET_B) = "144";
+///
+/// assert_eq!( md.b, "144" );
+///
+/// ```
+#[inline(always)]
+pub fn project_mut<'a, D, S, F>(ptr: &'a mut D, offset: FieldOffset<S, F, Aligned>) -> &'a mut F
+where
+    D: ?Sized + DerefMut<Target = S>,
+    S: 'a,
+{
+    offset.get_mut(&mut **ptr)
+}
+
+/// Swaps the field (determined by `offset`) of the `i`th and `j`th elements of `slice`,
+/// leaving the rest of the elements unchanged.
+///
+/// This does nothing if `i == j`.
+///
+/// # Panics
+///
+/// This panics if either `i` or `j` are out of bounds for `slice`.
+///
+/// # Example
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::{
+///     for_examples::ReprPacked,
+///     utils::{moved, swap_field_in_slice},
+/// };
+///
+/// let mut list = [
+///     ReprPacked{ a: 'a', b: 3u64, c: (), d: () },
+///     ReprPacked{ a: 'b', b: 5u64, c: (), d: () },
+///     ReprPacked{ a: 'c', b: 8u64, c: (), d: () },
+/// ];
+///
+/// swap_field_in_slice(ReprPacked::OFFSET_B, &mut list, 0, 2);
+///
+/// assert_eq!( list.iter().map(|x| moved(x.a)).collect::<String>(), "abc" );
+/// assert_eq!( list.iter().map(|x| moved(x.b)).collect::<Vec<u64>>(), [8, 5, 3] );
+///
+/// ```
+#[inline]
+pub fn swap_field_in_slice<S, F, A>(
+    offset: FieldOffset<S, F, A>,
+    slice: &mut [S],
+    i: usize,
+    j: usize,
+) {
+    let len = slice.len();
+    assert!(
+        i < len,
+        "index out of bounds: the len is {} but the index is {}",
+        len,
+        i
+    );
+    assert!(
+        j < len,
+        "index out of bounds: the len is {} but the index is {}",
+        len,
+        j
+    );
+
+    if i != j {
+        let ptr = slice.as_mut_ptr();
+        // safety: `i` and `j` are in bounds and different,
+        // so the fields don't overlap.
+        // Swapping the bytes of the fields works for both aligned and unaligned fields.
+        unsafe {
+            let left = offset.raw_get_mut(ptr.add(i)) as *mut u8;
+            let right = offset.raw_get_mut(ptr.add(j)) as *mut u8;
+            core::ptr::swap_nonoverlapping(left, right, Mem::<F>::SIZE);
+        }
+    }
+}
+
+/// Passes the bytes of each field (determined by `offsets`)
+/// of the struct that `base` points to, to `f`,
+/// in the order of the `offsets` slice.
+///
+/// This is for computing checksums (eg: CRCs) over selected fields of a struct,
+/// incrementally feeding the bytes of each field into the checksum.
+///
+/// # Safety
+///
+/// `base` must point to an `S` that is valid for reads of every field in `offsets`.
+///
+/// The fields must not contain any uninitialized bytes (eg: padding bytes),
+/// since `f` receives them as a `&[u8]`.
+///
+/// # Example
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::{
+///     for_examples::ReprC,
+///     utils::accumulate_fields,
+///     FieldOffsetDyn,
+/// };
+///
+/// type Header = ReprC<u16, u16, [u8; 4], u32>;
+///
+/// let header: Header = ReprC{ a: 3, b: 5, c: [8, 13, 21, 34], d: 0 };
+///
+/// let offsets: [FieldOffsetDyn<Header>; 3] = [
+///     Header::OFFSET_A.to_dyn(),
+///     Header::OFFSET_B.to_dyn(),
+///     Header::OFFSET_C.to_dyn(),
+/// ];
+///
+/// let mut checksum = 0u32;
+/// unsafe{
+///     accumulate_fields(&header, &offsets, &mut |bytes| {
+///         for &byte in bytes {
+///             checksum = checksum.rotate_left(5) ^ u32::from(byte);
+///         }
+///     });
+/// }
+///
+/// let mut expected = 0u32;
+/// let mut all_bytes = Vec::new();
+/// all_bytes.extend_from_slice(&3u16.to_ne_bytes());
+/// all_bytes.extend_from_slice(&5u16.to_ne_bytes());
+/// all_bytes.extend_from_slice(&[8, 13, 21, 34]);
+/// for byte in all_bytes {
+///     expected = expected.rotate_left(5) ^ u32::from(byte);
+/// }
+///
+/// assert_eq!( checksum, expected );
+///
+/// ```
+pub unsafe fn accumulate_fields<S>(
+    base: *const S,
+    offsets: &[FieldOffsetDyn<S>],
+    f: &mut dyn FnMut(&[u8]),
+) {
+    let base = base as *const u8;
+    for offset in offsets {
+        f(core::slice::from_raw_parts(
+            base.add(offset.offset()),
+            offset.size(),
+        ));
+    }
+}
+
+/// Primitive integer types, which can be read from bytes with a specific endianness.
+///
+/// This trait is sealed, it can't be implemented outside of `repr_offset`.
+///
+/// This is used by the [`FieldOffset::read_int_le`] and [`FieldOffset::read_int_be`] methods.
+///
+/// [`FieldOffset::read_int_le`]: ../struct.FieldOffset.html#method.read_int_le
+/// [`FieldOffset::read_int_be`]: ../struct.FieldOffset.html#method.read_int_be
+pub trait PrimInt: Copy + prim_int::Sealed {
+    #[doc(hidden)]
+    fn __from_le_slice(bytes: &[u8]) -> Self;
+
+    #[doc(hidden)]
+    fn __from_be_slice(bytes: &[u8]) -> Self;
+}
+
+mod prim_int {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_prim_int {
+    ($($ty:ident)*) => {
+        $(
+            impl prim_int::Sealed for $ty {}
+
+            impl PrimInt for $ty {
+                #[inline]
+                fn __from_le_slice(bytes: &[u8]) -> Self {
+                    let mut array = [0u8; core::mem::size_of::<$ty>()];
+                    array.copy_from_slice(bytes);
+                    $ty::from_le_bytes(array)
+                }
+
+                #[inline]
+                fn __from_be_slice(bytes: &[u8]) -> Self {
+                    let mut array = [0u8; core::mem::size_of::<$ty>()];
+                    array.copy_from_slice(bytes);
+                    $ty::from_be_bytes(array)
+                }
+            }
+        )*
+    };
+}
+
+impl_prim_int! {u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 usize isize}
+
+/// Primitive integer types, whose bytes can be reversed.
+///
+/// This trait is sealed, it can't be implemented outside of `repr_offset`.
+///
+/// This is used by the [`FieldOffset::read_swapped`] and [`FieldOffset::write_swapped`] methods.
+///
+/// [`FieldOffset::read_swapped`]: ../struct.FieldOffset.html#method.read_swapped
+/// [`FieldOffset::write_swapped`]: ../struct.FieldOffset.html#method.write_swapped
+pub trait ByteSwap: Copy + byte_swap::Sealed {
+    #[doc(hidden)]
+    fn __swap_bytes(self) -> Self;
+}
+
+mod byte_swap {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_byte_swap {
+    ($($ty:ident)*) => {
+        $(
+            impl byte_swap::Sealed for $ty {}
+
+            impl ByteSwap for $ty {
+                #[inline(always)]
+                fn __swap_bytes(self) -> Self {
+                    self.swap_bytes()
+                }
+            }
+        )*
+    };
+}
+
+impl_byte_swap! {u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 usize isize}
+
+/// Atomic types, whose operations can be done through a pointer to the struct containing them.
+///
+/// This trait is sealed, it can't be implemented outside of `repr_offset`.
+///
+/// This is implemented for `AtomicBool`, `AtomicPtr<T>`,
+/// and the atomic integer types that are up to 32 bits large or pointer-sized.
+///
+/// This is used by the [`FieldOffset::load`] and [`FieldOffset::compare_exchange`] methods,
+/// the latter of which requires the `"atomic_cas"` feature.
+///
+/// [`FieldOffset::load`]: ../struct.FieldOffset.html#method.load
+/// [`FieldOffset::compare_exchange`]: ../struct.FieldOffset.html#method.compare_exchange
+pub trait IsAtomic: Sync + is_atomic::Sealed {
+    /// The non-atomic type that this atomic stores.
+    type Value: Copy;
+
+    #[doc(hidden)]
+    fn __load(&self, order: AtomicOrdering) -> Self::Value;
+
+    #[doc(hidden)]
+    #[cfg(feature = "atomic_cas")]
+    fn __compare_exchange(
+        &self,
+        current: Self::Value,
+        new: Self::Value,
+        success: AtomicOrdering,
+        failure: AtomicOrdering,
+    ) -> Result<Self::Value, Self::Value>;
+}
+
+mod is_atomic {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_is_atomic {
+    ($( [$($params:tt)*] $atomic:ty => $value:ty, )*) => {
+        $(
+            impl<$($params)*> is_atomic::Sealed for $atomic {}
+
+            impl<$($params)*> IsAtomic for $atomic {
+                type Value = $value;
+
+                #[inline(always)]
+                fn __load(&self, order: AtomicOrdering) -> Self::Value {
+                    self.load(order)
+                }
+
+                #[cfg(feature = "atomic_cas")]
+                #[inline(always)]
+                fn __compare_exchange(
+                    &self,
+                    current: Self::Value,
+                    new: Self::Value,
+                    success: AtomicOrdering,
+                    failure: AtomicOrdering,
+                ) -> Result<Self::Value, Self::Value> {
+                    self.compare_exchange(current, new, success, failure)
+                }
+            }
+        )*
+    };
+}
+
+impl_is_atomic! {
+    [] atomic::AtomicBool => bool,
+    [] atomic::AtomicU8 => u8,
+    [] atomic::AtomicI8 => i8,
+    [] atomic::AtomicU16 => u16,
+    [] atomic::AtomicI16 => i16,
+    [] atomic::AtomicU32 => u32,
+    [] atomic::AtomicI32 => i32,
+    [] atomic::AtomicUsize => usize,
+    [] atomic::AtomicIsize => isize,
+    [T] atomic::AtomicPtr<T> => *mut T,
+}
+
+/// Types whose validity can be checked from their bytes,
+/// so that they can be read from untrusted byte slices.
+///
+/// This is used by the `TryFrom<&[u8]>` impl that
+/// the [`ReprOffset`] derive generates with the `#[roff(try_from_bytes)]` attribute,
+/// which also implements this trait for the deriving struct.
+///
+/// # Safety
+///
+/// Implementors must ensure that if `is_valid_bit_pattern` returns `true`,
+/// then reading a `Self` from the bytes (with `ptr::read_unaligned`) produces a valid value.
+///
+/// Implementing this trait for types with pointers
+/// (eg: references, `Box`, `Vec`) is almost always unsound,
+/// because there is no way to check that the pointer is valid.
+///
+/// # Example
+///
+/// ```rust
+/// use repr_offset::utils::ValidBitPattern;
+///
+/// assert!( u32::is_valid_bit_pattern(&[0xFF; 4]) );
+///
+/// assert!( bool::is_valid_bit_pattern(&[1]) );
+/// assert!( !bool::is_valid_bit_pattern(&[2]) );
+///
+/// assert!( char::is_valid_bit_pattern(&('a' as u32).to_ne_bytes()) );
+/// assert!( !char::is_valid_bit_pattern(&0xD800u32.to_ne_bytes()) );
+///
+/// ```
+///
+/// [`ReprOffset`]: ../derive.ReprOffset.html
+pub unsafe trait ValidBitPattern {
+    /// Whether `bytes` is the representation of a valid `Self`.
+    ///
+    /// `bytes` is always `size_of::<Self>()` bytes long,
+    /// and is not necessarily aligned to `align_of::<Self>()`.
+    fn is_valid_bit_pattern(bytes: &[u8]) -> bool;
+}
+
+macro_rules! impl_any_bit_pattern {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            unsafe impl ValidBitPattern for $ty {
+                #[inline(always)]
+                fn is_valid_bit_pattern(_: &[u8]) -> bool {
+                    true
+                }
+            }
+        )*
+    };
+}
+
+impl_any_bit_pattern! {
+    u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize,
+    f32, f64,
+    (),
+}
+
+unsafe impl<T: ?Sized> ValidBitPattern for PhantomData<T> {
+    #[inline(always)]
+    fn is_valid_bit_pattern(_: &[u8]) -> bool {
+        true
+    }
+}
+
+unsafe impl ValidBitPattern for bool {
+    #[inline]
+    fn is_valid_bit_pattern(bytes: &[u8]) -> bool {
+        bytes[0] <= 1
+    }
+}
+
+unsafe impl ValidBitPattern for char {
+    #[inline]
+    fn is_valid_bit_pattern(bytes: &[u8]) -> bool {
+        let mut array = [0u8; 4];
+        array.copy_from_slice(bytes);
+        core::char::from_u32(u32::from_ne_bytes(array)).is_some()
+    }
+}
+
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "min_const_generics")))]
+#[cfg(feature = "min_const_generics")]
+unsafe impl<T: ValidBitPattern, const N: usize> ValidBitPattern for [T; N] {
+    fn is_valid_bit_pattern(bytes: &[u8]) -> bool {
+        match Mem::<T>::SIZE {
+            0 => N == 0 || T::is_valid_bit_pattern(bytes),
+            size => bytes.chunks(size).all(T::is_valid_bit_pattern),
+        }
+    }
+}
+
+/// The error returned by the `TryFrom<&[u8]>` impl that
+/// the [`ReprOffset`] derive generates with the `#[roff(try_from_bytes)]` attribute.
+///
+/// [`ReprOffset`]: ../derive.ReprOffset.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FromBytesError {
+    /// The byte slice is shorter than the struct.
+    TooShort {
+        /// The size of the struct.
+        expected: usize,
+        /// The length of the byte slice.
+        found: usize,
+    },
+    /// The bytes of a field aren't a valid value of the field's type.
+    InvalidBitPattern,
+}
+
+impl core::fmt::Display for FromBytesError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            FromBytesError::TooShort { expected, found } => write!(
+                f,
+                "expected at least {} bytes, found {} bytes",
+                expected, found,
+            ),
+            FromBytesError::InvalidBitPattern => {
+                f.write_str("the bytes of a field are not a valid value of its type")
+            }
+        }
+    }
+}
+
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl std::error::Error for FromBytesError {}
+
+/// The error returned by the `set_field_dyn` method that
+/// the [`ReprOffset`] derive generates with the `#[roff(dyn_set)]` attribute.
+///
+/// [`ReprOffset`]: ../derive.ReprOffset.html
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum SetFieldError {
+    /// The struct has no field with the passed in name.
+    UnknownField,
+    /// The value isn't of the type of the field,
+    /// this contains the value that was passed in.
+    TypeMismatch(std::boxed::Box<dyn core::any::Any>),
+}
+
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl core::fmt::Display for SetFieldError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            SetFieldError::UnknownField => f.write_str("there is no field with that name"),
+            SetFieldError::TypeMismatch(_) => {
+                f.write_str("the value is not of the type of the field")
+            }
+        }
+    }
+}
+
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl std::error::Error for SetFieldError {}
+
 /// A const-equivalent of `core::cmp::min::<usize>`
 pub(crate) const fn min_usize(l: usize, r: usize) -> usize {
     let mask_r = ((l < r) as usize).wrapping_sub(1);
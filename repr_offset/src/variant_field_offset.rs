@@ -0,0 +1,213 @@
+//! The offset of a field in one variant of an enum.
+
+use core::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    mem::{self, Discriminant},
+};
+
+/// The offset of a field of type `F` in one variant of the `S` enum.
+///
+/// Unlike [`FieldOffset`], which is for fields that exist in every `S`,
+/// this is for fields that only exist when the enum is a specific variant,
+/// so the field can only be accessed after checking the discriminant of the enum,
+/// with the [`project`] and [`project_mut`] methods.
+///
+/// The offsets of the fields of enum variants are only guaranteed to be stable
+/// for `#[repr(C)]`/`#[repr(<integer>)]` enums.
+///
+/// # Example
+///
+/// ```rust
+/// # #![deny(safe_packed_borrows)]
+/// use repr_offset::VariantFieldOffset;
+///
+/// use std::mem;
+///
+/// // This enum has the same layout as a `#[repr(C)]` union of
+/// // `#[repr(C)]` structs that start with the `u8` tag.
+/// #[repr(u8)]
+/// enum Shape {
+///     Circle(u8, u32),
+///     Square(u16),
+/// }
+///
+/// let circle_1: VariantFieldOffset<Shape, u32> = unsafe {
+///     VariantFieldOffset::new(4, mem::discriminant(&Shape::Circle(0, 0)))
+/// };
+/// let square_0: VariantFieldOffset<Shape, u16> = unsafe {
+///     VariantFieldOffset::new(2, mem::discriminant(&Shape::Square(0)))
+/// };
+///
+/// let mut circle = Shape::Circle(3, 5);
+/// assert_eq!( circle_1.project(&circle), Some(&5) );
+/// assert_eq!( square_0.project(&circle), None );
+///
+/// *circle_1.project_mut(&mut circle).unwrap() += 8;
+/// assert_eq!( circle_1.project(&circle), Some(&13) );
+///
+/// let square = Shape::Square(8);
+/// assert_eq!( circle_1.project(&square), None );
+/// assert_eq!( square_0.project(&square), Some(&8) );
+///
+/// ```
+///
+/// [`FieldOffset`]: ./struct.FieldOffset.html
+/// [`project`]: #method.project
+/// [`project_mut`]: #method.project_mut
+pub struct VariantFieldOffset<S, F> {
+    offset: usize,
+    variant: Discriminant<S>,
+    field: PhantomData<fn() -> F>,
+}
+
+impl<S, F> VariantFieldOffset<S, F> {
+    /// Constructs this `VariantFieldOffset` from the offset of the field,
+    /// and the discriminant of the variant that the field is in.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that the variant of `S` that `variant` is the discriminant of
+    /// has an aligned field of type `F` at the `offset` byte offset.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::VariantFieldOffset;
+    ///
+    /// use std::mem;
+    ///
+    /// #[repr(C)]
+    /// enum Value {
+    ///     Int(u64),
+    ///     Pair(u16, u32),
+    /// }
+    ///
+    /// // `#[repr(C)]` enums are laid out as a `#[repr(C)]` struct containing
+    /// // the tag and a `#[repr(C)]` union of the fields of every variant.
+    /// let int_0: VariantFieldOffset<Value, u64> = unsafe {
+    ///     VariantFieldOffset::new(8, mem::discriminant(&Value::Int(0)))
+    /// };
+    ///
+    /// assert_eq!( int_0.offset(), 8 );
+    /// assert_eq!( int_0.project(&Value::Int(13)), Some(&13) );
+    /// assert_eq!( int_0.project(&Value::Pair(3, 5)), None );
+    ///
+    /// ```
+    #[inline(always)]
+    pub unsafe fn new(offset: usize, variant: Discriminant<S>) -> Self {
+        Self {
+            offset,
+            variant,
+            field: PhantomData,
+        }
+    }
+
+    /// The offset (in bytes) of the field in the `S` enum.
+    #[inline(always)]
+    pub const fn offset(self) -> usize {
+        self.offset
+    }
+
+    /// The discriminant of the variant that the field is in.
+    #[inline(always)]
+    pub const fn variant(self) -> Discriminant<S> {
+        self.variant
+    }
+
+    /// Gets a reference to the field,
+    /// returning `None` if `base` isn't the variant that the field is in.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::VariantFieldOffset;
+    ///
+    /// use std::mem;
+    ///
+    /// #[repr(u8)]
+    /// enum Message {
+    ///     Quit,
+    ///     Move(u8, u16),
+    /// }
+    ///
+    /// let move_1: VariantFieldOffset<Message, u16> = unsafe {
+    ///     VariantFieldOffset::new(2, mem::discriminant(&Message::Move(0, 0)))
+    /// };
+    ///
+    /// assert_eq!( move_1.project(&Message::Move(3, 5)), Some(&5) );
+    /// assert_eq!( move_1.project(&Message::Quit), None );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn project(self, base: &S) -> Option<&F> {
+        if mem::discriminant(base) == self.variant {
+            unsafe {
+                let ptr = (base as *const S as *const u8).add(self.offset) as *const F;
+                Some(&*ptr)
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Gets a mutable reference to the field,
+    /// returning `None` if `base` isn't the variant that the field is in.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![deny(safe_packed_borrows)]
+    /// use repr_offset::VariantFieldOffset;
+    ///
+    /// use std::mem;
+    ///
+    /// #[repr(u8)]
+    /// enum Message {
+    ///     Quit,
+    ///     Move(u8, u16),
+    /// }
+    ///
+    /// let move_1: VariantFieldOffset<Message, u16> = unsafe {
+    ///     VariantFieldOffset::new(2, mem::discriminant(&Message::Move(0, 0)))
+    /// };
+    ///
+    /// let mut message = Message::Move(3, 5);
+    /// *move_1.project_mut(&mut message).unwrap() += 8;
+    /// assert_eq!( move_1.project(&message), Some(&13) );
+    ///
+    /// assert_eq!( move_1.project_mut(&mut Message::Quit), None );
+    ///
+    /// ```
+    #[inline(always)]
+    pub fn project_mut(self, base: &mut S) -> Option<&mut F> {
+        if mem::discriminant(base) == self.variant {
+            unsafe {
+                let ptr = (base as *mut S as *mut u8).add(self.offset) as *mut F;
+                Some(&mut *ptr)
+            }
+        } else {
+            None
+        }
+    }
+}
+
+impl<S, F> Debug for VariantFieldOffset<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VariantFieldOffset")
+            .field("offset", &self.offset)
+            .field("variant", &self.variant)
+            .finish()
+    }
+}
+
+impl<S, F> Copy for VariantFieldOffset<S, F> {}
+
+impl<S, F> Clone for VariantFieldOffset<S, F> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
@@ -1,14 +1,27 @@
 #![cfg_attr(feature = "priv_raw_ref", feature(raw_ref_op))]
+#![cfg_attr(feature = "ptr_alignment", feature(ptr_alignment_type))]
 
 mod misc_tests_submod {
+    #[cfg(feature = "abi_stable")]
+    mod abi_stable_layout;
     mod accessing_struct_fields;
     mod aligned_struct_offsets;
+    mod assert_c_offset_macro;
+    mod copy_fields_macro;
     mod derive_macro;
     mod ext_traits;
     mod from_examples;
     mod get_field_offset_trait;
     mod misc_fieldoffsets_methods;
+    mod nested_alignment_matrix;
     mod off_macros;
     mod packed_struct_offsets;
     mod struct_field_offsets_macro;
+    mod struct_initializer;
+    mod tagged_struct;
+    #[cfg(feature = "std")]
+    mod to_columns_macro;
+    mod utils_fns;
+    #[cfg(feature = "priv_offset_of")]
+    mod verify_offsets_macro;
 }
@@ -0,0 +1,186 @@
+use repr_offset::{
+    abi_stable_layout::{assert_field_offsets, compare_field_offsets, LayoutMismatch},
+    Aligned, FieldOffset, FieldOffsetDyn,
+};
+
+#[cfg(feature = "derive")]
+use repr_offset::ReprOffset;
+
+#[cfg(not(feature = "derive"))]
+use repr_offset_derive::ReprOffset;
+
+use abi_stable::{std_types::RString, StableAbi};
+
+#[repr(C)]
+#[derive(StableAbi, ReprOffset)]
+pub struct Padded {
+    pub a: u8,
+    pub b: u64,
+    pub c: u16,
+    pub d: u32,
+    pub e: u8,
+}
+
+#[repr(C)]
+#[derive(StableAbi, ReprOffset)]
+pub struct Tuple(pub u16, pub [u8; 3], pub u32);
+
+#[repr(C)]
+#[derive(StableAbi, ReprOffset)]
+pub struct Nested {
+    pub tag: u8,
+    pub tuple: Tuple,
+    pub name: RString,
+    pub unit: (),
+    pub last: u8,
+}
+
+#[repr(transparent)]
+#[derive(StableAbi, ReprOffset)]
+pub struct Wrapper {
+    pub inner: u64,
+}
+
+#[allow(dead_code)]
+#[repr(u8)]
+#[derive(StableAbi)]
+pub enum Enum {
+    A,
+    B,
+}
+
+fn padded_offsets(name: &str) -> Option<FieldOffsetDyn<Padded>> {
+    match name {
+        "a" => Some(Padded::OFFSET_A.to_dyn()),
+        "b" => Some(Padded::OFFSET_B.to_dyn()),
+        "c" => Some(Padded::OFFSET_C.to_dyn()),
+        "d" => Some(Padded::OFFSET_D.to_dyn()),
+        "e" => Some(Padded::OFFSET_E.to_dyn()),
+        _ => None,
+    }
+}
+
+fn tuple_offsets(name: &str) -> Option<FieldOffsetDyn<Tuple>> {
+    match name {
+        "0" => Some(Tuple::OFFSET_0.to_dyn()),
+        "1" => Some(Tuple::OFFSET_1.to_dyn()),
+        "2" => Some(Tuple::OFFSET_2.to_dyn()),
+        _ => None,
+    }
+}
+
+fn nested_offsets(name: &str) -> Option<FieldOffsetDyn<Nested>> {
+    match name {
+        "tag" => Some(Nested::OFFSET_TAG.to_dyn()),
+        "tuple" => Some(Nested::OFFSET_TUPLE.to_dyn()),
+        "name" => Some(Nested::OFFSET_NAME.to_dyn()),
+        "unit" => Some(Nested::OFFSET_UNIT.to_dyn()),
+        "last" => Some(Nested::OFFSET_LAST.to_dyn()),
+        _ => None,
+    }
+}
+
+fn wrapper_offsets(name: &str) -> Option<FieldOffsetDyn<Wrapper>> {
+    match name {
+        "inner" => Some(Wrapper::OFFSET_INNER.to_dyn()),
+        _ => None,
+    }
+}
+
+#[test]
+fn matching_offsets() {
+    assert_eq!(compare_field_offsets(padded_offsets), Ok(()));
+    assert_eq!(compare_field_offsets(tuple_offsets), Ok(()));
+    assert_eq!(compare_field_offsets(nested_offsets), Ok(()));
+    assert_eq!(compare_field_offsets(wrapper_offsets), Ok(()));
+
+    assert_field_offsets(padded_offsets);
+    assert_field_offsets(tuple_offsets);
+    assert_field_offsets(nested_offsets);
+    assert_field_offsets(wrapper_offsets);
+}
+
+#[test]
+fn mismatched_offsets() {
+    let wrong_offset = unsafe { FieldOffset::<Padded, u16, Aligned>::new(9) }.to_dyn();
+    assert_eq!(
+        compare_field_offsets(|name| match name {
+            "c" => Some(wrong_offset),
+            _ => padded_offsets(name),
+        }),
+        Err(LayoutMismatch::Offset {
+            field: "c",
+            expected: 16,
+            found: 9,
+        }),
+    );
+
+    let wrong_size = unsafe { FieldOffset::<Tuple, [u8; 2], Aligned>::new(2) }.to_dyn();
+    assert_eq!(
+        compare_field_offsets(|name| match name {
+            "1" => Some(wrong_size),
+            _ => tuple_offsets(name),
+        }),
+        Err(LayoutMismatch::Size {
+            field: "field_1",
+            expected: 3,
+            found: 2,
+        }),
+    );
+
+    assert_eq!(
+        compare_field_offsets(|name| match name {
+            "name" => None,
+            _ => nested_offsets(name),
+        }),
+        Err(LayoutMismatch::MissingField { field: "name" }),
+    );
+}
+
+#[test]
+fn unsupported_layout() {
+    assert_eq!(
+        compare_field_offsets::<Enum, _>(|_| None),
+        Err(LayoutMismatch::UnsupportedLayout { type_name: "Enum" }),
+    );
+}
+
+#[test]
+#[should_panic]
+fn assert_mismatched_offsets() {
+    let wrong_offset = unsafe { FieldOffset::<Wrapper, u64, Aligned>::new(8) }.to_dyn();
+    assert_field_offsets(|_| Some(wrong_offset));
+}
+
+#[test]
+fn mismatch_display() {
+    let cases = [
+        (
+            LayoutMismatch::UnsupportedLayout { type_name: "Enum" },
+            "`Enum` is not a `#[repr(C)]` or `#[repr(transparent)]` struct",
+        ),
+        (
+            LayoutMismatch::MissingField { field: "name" },
+            "there is no offset for the `name` field",
+        ),
+        (
+            LayoutMismatch::Offset {
+                field: "c",
+                expected: 16,
+                found: 9,
+            },
+            "expected the `c` field to be at offset 16, found offset 9",
+        ),
+        (
+            LayoutMismatch::Size {
+                field: "1",
+                expected: 3,
+                found: 2,
+            },
+            "expected the `1` field to be 3 bytes large, found 2 bytes",
+        ),
+    ];
+    for (mismatch, expected) in cases.iter() {
+        assert_eq!(mismatch.to_string(), *expected);
+    }
+}
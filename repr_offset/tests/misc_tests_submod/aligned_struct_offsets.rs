@@ -17,3 +17,77 @@ fn aligned_struct_layouts() {
         struct StructAlign8;
     }
 }
+
+// Tests that the offset of the field after one whose type has an
+// `#[repr(align(N))]` attribute accounts for the trailing padding of that type.
+#[test]
+fn over_aligned_field_type() {
+    use repr_offset::{
+        for_examples::{ReprC, ReprPacked},
+        offset_calc::next_field_offset,
+    };
+
+    use std::mem;
+
+    #[repr(C, align(16))]
+    #[derive(Copy, Clone)]
+    struct OverAligned(u8);
+
+    fn addr_offset<S, F>(base: &S, field: *const F) -> usize {
+        field as usize - (base as *const S as usize)
+    }
+
+    assert_eq!(mem::size_of::<OverAligned>(), 16);
+
+    assert_eq!(
+        next_field_offset::<ReprC<(), (), (), ()>, OverAligned, u8>(0),
+        16
+    );
+    assert_eq!(
+        next_field_offset::<ReprC<(), (), (), ()>, OverAligned, u8>(16),
+        32
+    );
+
+    {
+        type This = ReprC<u8, OverAligned, u8, OverAligned>;
+        let this: This = ReprC {
+            a: 0,
+            b: OverAligned(1),
+            c: 2,
+            d: OverAligned(3),
+        };
+
+        assert_eq!(This::OFFSET_A.offset(), 0);
+        assert_eq!(This::OFFSET_B.offset(), 16);
+        assert_eq!(This::OFFSET_C.offset(), 32);
+        assert_eq!(This::OFFSET_D.offset(), 48);
+        assert_eq!(mem::size_of::<This>(), 64);
+
+        assert_eq!(This::OFFSET_B.offset(), addr_offset(&this, &this.b));
+        assert_eq!(This::OFFSET_C.offset(), addr_offset(&this, &this.c));
+        assert_eq!(This::OFFSET_D.offset(), addr_offset(&this, &this.d));
+
+        assert_eq!(This::OFFSET_B.get(&this).0, 1);
+        assert_eq!(This::OFFSET_C.get_copy(&this), 2);
+        assert_eq!(This::OFFSET_D.get(&this).0, 3);
+    }
+    {
+        type This = ReprPacked<u8, OverAligned, u8, u32>;
+        let this: This = ReprPacked {
+            a: 0,
+            b: OverAligned(1),
+            c: 2,
+            d: 3,
+        };
+
+        assert_eq!(This::OFFSET_A.offset(), 0);
+        assert_eq!(This::OFFSET_B.offset(), 1);
+        assert_eq!(This::OFFSET_C.offset(), 17);
+        assert_eq!(This::OFFSET_D.offset(), 18);
+        assert_eq!(mem::size_of::<This>(), 22);
+
+        assert_eq!(This::OFFSET_B.get_copy(&this).0, 1);
+        assert_eq!(This::OFFSET_C.get_copy(&this), 2);
+        assert_eq!(This::OFFSET_D.get_copy(&this), 3);
+    }
+}
@@ -0,0 +1,30 @@
+use repr_offset::{
+    assert_c_offset,
+    for_examples::{ReprC, ReprPacked},
+    types_for_tests::StructReprC,
+};
+
+type Consts = StructReprC<(), (u8, u64, u16, [u8; 3]), (), ()>;
+
+assert_c_offset!(Consts::OFFSET_A == 0);
+assert_c_offset!(Consts::OFFSET_B == 8);
+assert_c_offset!(Consts::OFFSET_C == 16);
+assert_c_offset!(Consts::OFFSET_D == 18);
+
+type Packed = ReprPacked<u8, u64, u16, [u8; 3]>;
+
+assert_c_offset!(Packed::OFFSET_A == 0);
+assert_c_offset!(Packed::OFFSET_B == 1);
+assert_c_offset!(Packed::OFFSET_C == 9);
+assert_c_offset!(Packed::OFFSET_D == 11);
+
+#[test]
+fn assert_c_offset_in_fn() {
+    type Inner = ReprC<u16, u32, (), ()>;
+    type Outer = ReprC<u8, Inner, (), ()>;
+
+    assert_c_offset!(Outer::OFFSET_B == 4);
+    assert_c_offset!(Outer::OFFSET_B.add(Inner::OFFSET_A) == 4);
+    assert_c_offset!(Outer::OFFSET_B.add(Inner::OFFSET_B) == 2 * 4,);
+    assert_c_offset!(Inner::OFFSET_B.to_dyn() == 4);
+}
@@ -0,0 +1,137 @@
+use repr_offset::{
+    copy_fields,
+    for_examples::{ReprC, ReprPacked},
+    unsafe_struct_field_offsets, Aligned,
+};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Private {
+    a: u8,
+    b: u32,
+}
+
+unsafe_struct_field_offsets! {
+    alignment =  Aligned,
+
+    impl[] Private {
+        const OFFSET_A, a: u8;
+        const OFFSET_B, b: u32;
+    }
+}
+
+#[repr(C)]
+struct Wrapper {
+    x: Private,
+    y: Private,
+}
+
+unsafe_struct_field_offsets! {
+    alignment =  Aligned,
+
+    impl[] Wrapper {
+        const OFFSET_X, x: Private;
+        const OFFSET_Y, y: Private;
+    }
+}
+
+#[test]
+fn copy_subset() {
+    type Inner = ReprPacked<u8, u64, u16, ()>;
+
+    let update: ReprC<u8, u32, char, Inner> = ReprC {
+        a: 3,
+        b: 5,
+        c: 'x',
+        d: ReprPacked {
+            a: 8,
+            b: 13,
+            c: 21,
+            d: (),
+        },
+    };
+    let mut this: ReprC<u8, u32, char, Inner> = ReprC {
+        a: 0,
+        b: 0,
+        c: 'a',
+        d: ReprPacked {
+            a: 0,
+            b: 0,
+            c: 0,
+            d: (),
+        },
+    };
+
+    unsafe {
+        copy_fields!(&update, &mut this; a, c);
+    }
+    assert_eq!((this.a, this.b, this.c), (3, 0, 'x'));
+    assert_eq!(({ this.d.a }, { this.d.b }, { this.d.c }), (0, 0, 0));
+
+    // Nested unaligned fields, and a trailing comma
+    unsafe {
+        copy_fields!(&update, &mut this; d.b, d.c,);
+    }
+    assert_eq!((this.a, this.b, this.c), (3, 0, 'x'));
+    assert_eq!(({ this.d.a }, { this.d.b }, { this.d.c }), (0, 13, 21));
+
+    // Raw pointers
+    let source: *const _ = &update;
+    let destination: *mut _ = &mut this;
+    unsafe {
+        copy_fields!(source, destination; b, d.a);
+    }
+    assert_eq!((this.a, this.b, this.c), (3, 5, 'x'));
+    assert_eq!(({ this.d.a }, { this.d.b }, { this.d.c }), (8, 13, 21));
+}
+
+#[test]
+fn copy_packed_fields() {
+    let update = ReprPacked {
+        a: 3u8,
+        b: 5u64,
+        c: 8u16,
+        d: "hello",
+    };
+    let mut this = ReprPacked {
+        a: 0u8,
+        b: 0u64,
+        c: 0u16,
+        d: "",
+    };
+
+    unsafe {
+        copy_fields!(&update, &mut this; b, d);
+    }
+    assert_eq!(
+        ({ this.a }, { this.b }, { this.c }, { this.d }),
+        (0, 5, 0, "hello")
+    );
+
+    // Copying all the fields
+    unsafe {
+        copy_fields!(&update, &mut this; a, b, c, d);
+    }
+    assert_eq!(
+        ({ this.a }, { this.b }, { this.c }, { this.d }),
+        (3, 5, 8, "hello")
+    );
+}
+
+#[test]
+fn copy_private_fields() {
+    let update = Wrapper {
+        x: Private { a: 3, b: 5 },
+        y: Private { a: 8, b: 13 },
+    };
+    let mut this = Wrapper {
+        x: Private { a: 0, b: 0 },
+        y: Private { a: 0, b: 0 },
+    };
+
+    unsafe {
+        copy_fields!(&update, &mut this; y.b, x);
+    }
+    assert_eq!(this.x, Private { a: 3, b: 5 });
+    assert_eq!(this.y, Private { a: 0, b: 13 });
+}
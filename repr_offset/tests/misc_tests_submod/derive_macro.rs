@@ -424,6 +424,77 @@ mod privacy {
     }
 }
 
+// `OFF!` follows the privacy rules of field expressions at the call site.
+// Accessing fields that aren't visible there is tested in the docs of `OFF!`,
+// as `compile_fail` tests.
+mod off_macro_privacy {
+    use super::*;
+
+    use repr_offset::OFF;
+
+    pub mod outer {
+        use super::*;
+
+        pub mod inner {
+            use super::*;
+
+            #[repr(C)]
+            #[derive(ReprOffset)]
+            pub struct PrivStruct {
+                pub a: u8,
+                pub(crate) b: u16,
+                pub(super) c: u32,
+                d: u64,
+            }
+
+            pub fn offsets_from_inner() -> [usize; 4] {
+                [
+                    OFF!(crate::misc_tests_submod::derive_macro::off_macro_privacy::outer::inner::PrivStruct; a).offset(),
+                    OFF!(self::PrivStruct; b).offset(),
+                    OFF!(PrivStruct; c).offset(),
+                    OFF!(PrivStruct; d).offset(),
+                ]
+            }
+
+            pub mod nested {
+                use super::*;
+
+                // Private fields are visible in submodules of the declaring module
+                pub fn offsets_from_submodule() -> [usize; 2] {
+                    [
+                        OFF!(super::PrivStruct; c).offset(),
+                        OFF!(super::super::inner::PrivStruct; d).offset(),
+                    ]
+                }
+            }
+        }
+
+        // `c` is `pub(super)`, so it's visible here, `d` isn't.
+        pub fn offsets_from_outer() -> [usize; 3] {
+            [
+                OFF!(self::inner::PrivStruct; a).offset(),
+                OFF!(inner::PrivStruct; b).offset(),
+                OFF!(crate::misc_tests_submod::derive_macro::off_macro_privacy::outer::inner::PrivStruct; c).offset(),
+            ]
+        }
+    }
+
+    #[test]
+    fn off_macro_privacy_test() {
+        use self::outer::inner::{self, PrivStruct};
+
+        assert_eq!(inner::offsets_from_inner(), [0, 2, 4, 8]);
+        assert_eq!(inner::nested::offsets_from_submodule(), [4, 8]);
+        assert_eq!(outer::offsets_from_outer(), [0, 2, 4]);
+
+        // Only `a` and `b` are visible here.
+        assert_eq!(OFF!(PrivStruct; a).offset(), 0);
+        assert_eq!(OFF!(outer::inner::PrivStruct; b).offset(), 2);
+
+        assert_eq!(PrivStruct::OFFSET_A, OFF!(PrivStruct; a));
+    }
+}
+
 mod no_getfieldoffset_impls {
     use super::*;
 
@@ -474,3 +545,2600 @@ mod no_getfieldoffset_impls {
         let _: FieldOffset<Struct, ZstZ, Aligned> = PUB_OFF!(Struct; z);
     }
 }
+
+mod no_offset_consts {
+    use super::*;
+
+    use repr_offset::{off, pub_off, FieldOffsetDyn};
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(no_offset_consts, field_enum)]
+    pub struct Struct {
+        pub x: u8,
+        pub y: u64,
+        z: &'static str,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(no_offset_consts, usize_offsets)]
+    pub struct Packed {
+        pub x: u8,
+        pub y: u64,
+    }
+
+    // Inherent associated constants take priority over trait associated constants,
+    // so these are only used if there are no inherent `OFFSET_*` constants.
+    trait FallbackConsts {
+        const OFFSET_X: &'static str = "fallback";
+        const OFFSET_Y: &'static str = "fallback";
+        const OFFSET_Z: &'static str = "fallback";
+    }
+
+    impl FallbackConsts for Struct {}
+    impl FallbackConsts for Packed {}
+
+    #[test]
+    fn no_offset_consts_test() {
+        assert_eq!(Struct::OFFSET_X, "fallback");
+        assert_eq!(Struct::OFFSET_Y, "fallback");
+        assert_eq!(Struct::OFFSET_Z, "fallback");
+        assert_eq!(Packed::OFFSET_X, "fallback");
+        assert_eq!(Packed::OFFSET_Y, "fallback");
+
+        let _: FieldOffset<Struct, u8, Aligned> = off!(x);
+        let _: FieldOffset<Struct, u64, Aligned> = pub_off!(y);
+        let _: FieldOffset<Packed, u64, Unaligned> = PUB_OFF!(Packed; y);
+
+        let mut this = Struct { x: 3, y: 5, z: "8" };
+        assert_eq!(off!(this; x).get_copy(&this), 3);
+        assert_eq!(off!(this; y).get_copy(&this), 5);
+        assert_eq!(off!(this; z).get_copy(&this), "8");
+        *off!(this; y).get_mut(&mut this) = 13;
+        assert_eq!(this.y, 13);
+
+        let packed = Packed { x: 21, y: 34 };
+        assert_eq!(off!(packed; x).get_copy(&packed), 21);
+        assert_eq!(off!(packed; y).get_copy(&packed), 34);
+        assert_eq!(off!(packed; y).offset(), 1);
+
+        let offsets: [FieldOffsetDyn<Struct>; 3] = [
+            StructField::X.offset_dyn(),
+            StructField::Y.offset_dyn(),
+            StructField::Z.offset_dyn(),
+        ];
+        assert_eq!(
+            [
+                offsets[0].offset(),
+                offsets[1].offset(),
+                offsets[2].offset()
+            ],
+            [0, 8, 16]
+        );
+    }
+}
+
+mod field_enum {
+    use super::*;
+
+    use repr_offset::FieldOffsetDyn;
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(field_enum)]
+    pub struct Struct {
+        pub x: u8,
+        pub y_y: u64,
+        #[roff(offset = "OFF_Z")]
+        z: &'static str,
+    }
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(field_enum)]
+    pub struct Tuple(pub u8, pub u16, pub u32);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(field_enum)]
+    #[roff(bound = "T: Copy")]
+    pub struct Generic<T> {
+        pub x: u8,
+        pub y: T,
+    }
+
+    fn field_offset(field: StructField) -> FieldOffsetDyn<Struct> {
+        match field {
+            StructField::X => Struct::OFFSET_X.to_dyn(),
+            StructField::YY => Struct::OFFSET_Y_Y.to_dyn(),
+            StructField::Z => Struct::OFF_Z.to_dyn(),
+        }
+    }
+
+    #[test]
+    fn field_enum_test() {
+        let fields = [StructField::X, StructField::YY, StructField::Z];
+        for &field in &fields {
+            assert_eq!(field.offset_dyn(), field_offset(field));
+        }
+        assert_eq!(StructField::X.offset_dyn().offset(), 0);
+        assert_eq!(StructField::YY.offset_dyn().offset(), 1);
+        assert_eq!(StructField::Z.offset_dyn().offset(), 9);
+        assert_eq!(StructField::YY.offset_dyn().size(), 8);
+
+        assert_eq!(TupleField::F0.offset_dyn(), Tuple::OFFSET_0.to_dyn());
+        assert_eq!(TupleField::F1.offset_dyn(), Tuple::OFFSET_1.to_dyn());
+        assert_eq!(TupleField::F2.offset_dyn(), Tuple::OFFSET_2.to_dyn());
+
+        assert_eq!(GenericField::X.offset_dyn::<u64>().offset(), 0);
+        assert_eq!(GenericField::Y.offset_dyn::<u64>().offset(), 8);
+        assert_eq!(GenericField::Y.offset_dyn::<u16>().offset(), 2);
+        assert_eq!(GenericField::Y.offset_dyn::<u16>().size(), 2);
+    }
+
+    #[test]
+    fn get_dyn_test() {
+        {
+            let this = Tuple(3, 5, 8);
+            assert_eq!(this.get_dyn(TupleField::F0).downcast_ref::<u8>(), Some(&3));
+            assert_eq!(this.get_dyn(TupleField::F1).downcast_ref::<u16>(), Some(&5));
+            assert_eq!(this.get_dyn(TupleField::F2).downcast_ref::<u32>(), Some(&8));
+            assert!(this.get_dyn(TupleField::F2).downcast_ref::<u16>().is_none());
+        }
+        {
+            let this = Generic { x: 13, y: "hello" };
+            assert_eq!(
+                this.get_dyn(GenericField::X).downcast_ref::<u8>(),
+                Some(&13)
+            );
+            assert_eq!(
+                this.get_dyn(GenericField::Y).downcast_ref::<&str>(),
+                Some(&"hello")
+            );
+            assert!(this.get_dyn(GenericField::Y).downcast_ref::<u8>().is_none());
+        }
+    }
+}
+
+mod const_offset_fn {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(field_enum, const_offset_fn)]
+    pub struct Struct {
+        pub x: u8,
+        pub y: u16,
+        #[roff(offset = "OFF_Z")]
+        z: u32,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(field_enum, const_offset_fn)]
+    pub struct Packed(pub u8, pub u32, pub u16);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(field_enum, const_offset_fn)]
+    pub struct Generic<T> {
+        pub x: u8,
+        pub y: T,
+    }
+
+    const BYTES: [u8; 8] = [3, 5, 8, 13, 21, 34, 55, 89];
+
+    const STRUCT_BYTES: [u8; 3] = [
+        BYTES[Struct::offset_of_field(StructField::X)],
+        BYTES[Struct::offset_of_field(StructField::Y)],
+        BYTES[Struct::offset_of_field(StructField::Z)],
+    ];
+
+    const PACKED_OFFSETS: [usize; 3] = [
+        Packed::offset_of_field(PackedField::F0),
+        Packed::offset_of_field(PackedField::F1),
+        Packed::offset_of_field(PackedField::F2),
+    ];
+
+    const GENERIC_Y: [usize; 4] = [
+        Generic::<u64>::offset_of_field(GenericField::X),
+        Generic::<u8>::offset_of_field(GenericField::Y),
+        Generic::<u32>::offset_of_field(GenericField::Y),
+        Generic::<u64>::offset_of_field(GenericField::Y),
+    ];
+
+    #[test]
+    fn const_offset_fn_test() {
+        assert_eq!(STRUCT_BYTES, [3, 8, 21]);
+        assert_eq!(PACKED_OFFSETS, [0, 1, 5]);
+        assert_eq!(GENERIC_Y, [0, 1, 4, 8]);
+
+        for &field in &[StructField::X, StructField::Y, StructField::Z] {
+            assert_eq!(Struct::offset_of_field(field), field.offset_dyn().offset());
+        }
+        assert_eq!(
+            Struct::offset_of_field(StructField::Z),
+            Struct::OFF_Z.offset()
+        );
+    }
+}
+
+mod refs_view {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(refs_view)]
+    pub struct Struct {
+        pub x: u8,
+        pub y: u64,
+        z: String,
+    }
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(refs_view)]
+    pub struct Tuple(pub u8, pub Vec<u16>);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(refs_view)]
+    pub struct Generic<'a, T: Debug> {
+        pub x: &'a str,
+        pub y: T,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(refs_view)]
+    pub struct Packed {
+        pub x: u8,
+        pub y: u64,
+        pub z: &'static str,
+    }
+
+    #[test]
+    fn refs_view_test() {
+        let mut this = Struct {
+            x: 3,
+            y: 5,
+            z: "8".to_string(),
+        };
+        {
+            let StructRefs { x, y, z } = this.as_refs();
+            assert_eq!((x, y, z), (&3, &5, &"8".to_string()));
+        }
+        {
+            let refs = this.as_refs_mut();
+            *refs.x += 10;
+            *refs.y += 20;
+            refs.z.push('9');
+        }
+        assert_eq!((this.x, this.y, &*this.z), (13, 25, "89"));
+
+        let mut tuple = Tuple(3, vec![5, 8]);
+        {
+            let TupleRefs(a, b) = tuple.as_refs();
+            assert_eq!((a, b), (&3, &vec![5, 8]));
+        }
+        {
+            let TupleRefsMut(a, b) = tuple.as_refs_mut();
+            *a = 13;
+            b.push(21);
+        }
+        assert_eq!((tuple.0, tuple.1), (13, vec![5, 8, 21]));
+
+        let mut generic = Generic { x: "3", y: 5u16 };
+        {
+            let refs: GenericRefs<'_, '_, u16> = generic.as_refs();
+            assert_eq!((refs.x, refs.y), (&"3", &5));
+        }
+        {
+            let refs: GenericRefsMut<'_, '_, u16> = generic.as_refs_mut();
+            *refs.x = "8";
+            *refs.y = 13;
+        }
+        assert_eq!((generic.x, generic.y), ("8", 13));
+
+        let packed = Packed { x: 3, y: 5, z: "8" };
+        let PackedRefs { x, y, z } = packed.as_refs();
+        assert_eq!((x, y, z), (3, 5, "8"));
+    }
+}
+
+mod macro_generated {
+    use super::*;
+
+    use repr_offset::{off, pub_off, utils::moved, FieldOffsetDyn, ROExtAcc, ROExtOps, OFF};
+
+    macro_rules! declare_struct {
+        (
+            $(#[$attr:meta])*
+            $vis:vis struct $name:ident {
+                $( $field_vis:vis $field:ident : $field_ty:ty ),* $(,)?
+            }
+        ) => {
+            #[derive(ReprOffset)]
+            $(#[$attr])*
+            $vis struct $name {
+                $( $field_vis $field: $field_ty, )*
+            }
+        };
+    }
+
+    // Declares a struct whose field names are written inside the macro.
+    macro_rules! declare_fixed_struct {
+        ($name:ident, $($repr:tt)*) => {
+            #[repr($($repr)*)]
+            #[derive(ReprOffset)]
+            #[roff(field_enum)]
+            pub struct $name {
+                pub first: u8,
+                pub second: u32,
+                pub third: u16,
+            }
+        };
+    }
+
+    declare_struct! {
+        #[repr(C)]
+        #[roff(field_enum, refs_view)]
+        pub struct WithAttrs {
+            pub x: u8,
+            pub y: u64,
+            z: Vec<u8>,
+        }
+    }
+
+    declare_struct! {
+        #[repr(C, packed)]
+        #[roff(offset_prefix = "OFF_")]
+        pub struct Packed {
+            pub x: u8,
+            pub y: u64,
+        }
+    }
+
+    // The generated code must not be affected by items with common names in scope.
+    #[allow(non_upper_case_globals, dead_code)]
+    const ptr: usize = 0;
+
+    declare_fixed_struct! {FixedAligned, C}
+    declare_fixed_struct! {FixedPacked, C, packed}
+
+    #[test]
+    fn macro_generated_test() {
+        let mut aligned = WithAttrs {
+            x: 3,
+            y: 5,
+            z: vec![8],
+        };
+        assert_eq!(WithAttrs::OFFSET_X.offset(), 0);
+        assert_eq!(WithAttrs::OFFSET_Y.offset(), 8);
+        assert_eq!(WithAttrs::OFFSET_Y, OFF!(WithAttrs; y));
+        assert_eq!(WithAttrs::OFFSET_Z, OFF!(WithAttrs; z));
+        let fields = [WithAttrsField::X, WithAttrsField::Y, WithAttrsField::Z];
+        let offsets = [
+            FieldOffsetDyn::from(WithAttrs::OFFSET_X),
+            FieldOffsetDyn::from(WithAttrs::OFFSET_Y),
+            FieldOffsetDyn::from(WithAttrs::OFFSET_Z),
+        ];
+        for (field, offset) in fields.iter().zip(&offsets) {
+            assert_eq!(field.offset_dyn(), *offset);
+        }
+        assert_eq!(aligned.f_get(off!(x)), &3);
+        assert_eq!(aligned.f_get(pub_off!(y)), &5);
+        assert_eq!(aligned.f_get(off!(z)), &vec![8]);
+        {
+            let WithAttrsRefsMut { x, y, z } = aligned.as_refs_mut();
+            *x += 10;
+            *y += 20;
+            z.push(13);
+        }
+        let WithAttrsRefs { x, y, z } = aligned.as_refs();
+        assert_eq!((x, y, z), (&13, &25, &vec![8, 13]));
+
+        let packed = Packed { x: 3, y: 5 };
+        let _: FieldOffset<Packed, u64, Unaligned> = Packed::OFF_Y;
+        assert_eq!(Packed::OFF_Y.offset(), 1);
+        assert_eq!(Packed::OFF_Y, OFF!(Packed; y));
+        assert_eq!(packed.f_get_copy(off!(x)), 3);
+        assert_eq!(packed.f_get_copy(pub_off!(y)), 5);
+
+        let fixed = FixedAligned {
+            first: 3,
+            second: 5,
+            third: 8,
+        };
+        let _: FieldOffset<FixedAligned, u32, Aligned> = FixedAligned::OFFSET_SECOND;
+        assert_eq!(FixedAligned::OFFSET_THIRD.offset(), 8);
+        assert_eq!(fixed.f_get(off!(first)), &3);
+        assert_eq!(fixed.f_get(PUB_OFF!(FixedAligned; second)), &5);
+        assert_eq!(FixedAlignedField::First.offset_dyn().offset(), 0);
+        assert_eq!(FixedAlignedField::Second.offset_dyn().offset(), 4);
+        assert_eq!(FixedAlignedField::Third.offset_dyn().offset(), 8);
+
+        let fixed = FixedPacked {
+            first: 3,
+            second: 5,
+            third: 8,
+        };
+        assert_eq!(FixedPacked::OFFSET_THIRD.offset(), 5);
+        assert_eq!(moved(fixed.second), 5);
+        assert_eq!(fixed.f_get_copy(off!(third)), 8);
+        assert_eq!(FixedPackedField::First.offset_dyn().offset(), 0);
+        assert_eq!(FixedPackedField::Second.offset_dyn().offset(), 1);
+        assert_eq!(FixedPackedField::Third.offset_dyn().offset(), 5);
+    }
+}
+
+mod fields_proxy {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(fields_proxy)]
+    pub struct Struct {
+        pub x: u8,
+        pub y: u64,
+        z: String,
+    }
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(fields_proxy)]
+    pub struct Generic<'a, T: Debug> {
+        pub x: &'a str,
+        pub y: T,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(fields_proxy)]
+    pub struct Packed {
+        pub x: u8,
+        pub y: u64,
+        pub z: Vec<u8>,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(fields_proxy)]
+    pub struct PackedTuple(pub u8, pub u32, pub u16);
+
+    #[test]
+    fn fields_proxy_test() {
+        let this = Struct {
+            x: 3,
+            y: 5,
+            z: "8".to_string(),
+        };
+        let fields: StructFields<'_> = this.fields();
+        assert_eq!(fields.x(), &3);
+        assert_eq!(fields.y(), &5);
+        assert_eq!(fields.z(), "8");
+        assert_eq!(fields.x() as *const u8, &this.x as *const u8);
+
+        let generic = Generic { x: "3", y: 5u16 };
+        let fields: GenericFields<'_, '_, u16> = generic.fields();
+        assert_eq!((fields.x(), fields.y()), (&"3", &5));
+
+        let packed = Packed {
+            x: 3,
+            y: 5,
+            z: vec![8],
+        };
+        assert_eq!(packed.fields().x(), 3);
+        assert_eq!(packed.fields().y(), 5);
+
+        let tuple = PackedTuple(3, 5, 8);
+        let fields = tuple.fields();
+        assert_eq!((fields.f0(), fields.f1(), fields.f2()), (3, 5, 8));
+    }
+}
+
+mod derive_offset_debug {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(derive_offset_debug)]
+    pub struct Struct {
+        pub x: u8,
+        pub y: u64,
+        z: u16,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(derive_offset_debug, usize_offsets)]
+    pub struct Packed(pub u8, pub u32, u16);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(derive_offset_debug)]
+    pub struct Generic<T> {
+        pub x: u8,
+        #[roff(offset = "OFFSET_TYPE")]
+        pub r#type: T,
+    }
+
+    const STRUCT_OFFSETS: &[(&str, usize)] = Struct::debug_offsets();
+
+    #[test]
+    fn derive_offset_debug_test() {
+        assert_eq!(STRUCT_OFFSETS, &[("x", 0), ("y", 8), ("z", 16)]);
+        assert_eq!(STRUCT_OFFSETS[2].1, Struct::OFFSET_Z.offset());
+
+        assert_eq!(Packed::debug_offsets(), &[("0", 0), ("1", 1), ("2", 5)]);
+
+        assert_eq!(Generic::<u16>::debug_offsets(), &[("x", 0), ("type", 2)]);
+        assert_eq!(Generic::<u32>::debug_offsets(), &[("x", 0), ("type", 4)]);
+    }
+}
+
+mod clone_via_fields {
+    use super::*;
+
+    use std::rc::Rc;
+
+    pub struct NotClone;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(clone_via_fields)]
+    pub struct Shared<T> {
+        pub value: Rc<T>,
+        pub name: String,
+        pub len: u8,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(clone_via_fields)]
+    pub struct Packed<T>(pub u8, pub T, pub &'static str);
+
+    #[test]
+    fn clone_via_fields_test() {
+        {
+            let this = Shared {
+                value: Rc::new(NotClone),
+                name: "hello".to_string(),
+                len: 5,
+            };
+            let other = this.clone_via_fields();
+            assert!(Rc::ptr_eq(&this.value, &other.value));
+            assert_eq!(Rc::strong_count(&this.value), 2);
+            assert_eq!(other.name, "hello");
+            assert_eq!(other.len, 5);
+
+            drop(other);
+            assert_eq!(Rc::strong_count(&this.value), 1);
+        }
+        {
+            let this = Packed(3, 5u64, "world");
+            let other = this.clone_via_fields();
+            assert_eq!(Packed::OFFSET_0.get_copy(&other), 3);
+            assert_eq!(Packed::OFFSET_1.get_copy(&other), 5);
+            assert_eq!(Packed::OFFSET_2.get_copy(&other), "world");
+        }
+    }
+}
+
+mod padding_consts {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(padding_consts)]
+    pub struct Padded {
+        pub a: u8,
+        pub b: u64,
+        pub c: u16,
+        pub d: u16,
+        pub e: (),
+        pub f: u8,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(padding_consts)]
+    pub struct Packed(pub u8, pub u64, pub u16);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(padding_consts, usize_offsets)]
+    pub struct Generic<T>(pub u8, pub T);
+
+    #[test]
+    fn padding_consts_test() {
+        assert_eq!(Padded::PADDING_AFTER_A, 7);
+        assert_eq!(Padded::PADDING_AFTER_B, 0);
+        assert_eq!(Padded::PADDING_AFTER_C, 0);
+        assert_eq!(Padded::PADDING_AFTER_D, 0);
+        assert_eq!(Padded::PADDING_AFTER_E, 0);
+        assert_eq!(Padded::PADDING_AFTER_F, 3);
+        assert_eq!(Padded::OFFSET_F.trailing_padding(), 3);
+
+        assert_eq!(Packed::PADDING_AFTER_0, 0);
+        assert_eq!(Packed::PADDING_AFTER_1, 0);
+        assert_eq!(Packed::PADDING_AFTER_2, 0);
+
+        assert_eq!(Generic::<u8>::PADDING_AFTER_0, 0);
+        assert_eq!(Generic::<u8>::PADDING_AFTER_1, 0);
+        assert_eq!(Generic::<u32>::PADDING_AFTER_0, 3);
+        assert_eq!(Generic::<u32>::PADDING_AFTER_1, 0);
+        assert_eq!(Generic::<(u32, u8)>::PADDING_AFTER_0, 3);
+        assert_eq!(Generic::<(u32, u8)>::PADDING_AFTER_1, 0);
+    }
+}
+
+mod offsets_array {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(offsets_array)]
+    pub struct Struct {
+        pub a: u8,
+        pub b: u64,
+        c: u16,
+        pub d: (),
+        pub e: u8,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(offsets_array)]
+    pub struct Packed(pub u8, pub u64, pub u16);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(offsets_array, usize_offsets)]
+    pub struct Generic<T>(pub u8, pub T);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(offsets_array)]
+    pub struct Empty {}
+
+    // Ensures that the constant is usable in const contexts
+    const STRUCT_OFFSETS: [usize; 5] = Struct::OFFSETS;
+
+    #[test]
+    fn offsets_array_test() {
+        assert_eq!(STRUCT_OFFSETS, [0, 8, 16, 18, 18]);
+        assert_eq!(
+            Struct::OFFSETS,
+            [
+                Struct::OFFSET_A.offset(),
+                Struct::OFFSET_B.offset(),
+                Struct::OFFSET_C.offset(),
+                Struct::OFFSET_D.offset(),
+                Struct::OFFSET_E.offset(),
+            ]
+        );
+
+        assert_eq!(Packed::OFFSETS, [0, 1, 9]);
+        assert_eq!(
+            Packed::OFFSETS,
+            [
+                Packed::OFFSET_0.offset(),
+                Packed::OFFSET_1.offset(),
+                Packed::OFFSET_2.offset(),
+            ]
+        );
+
+        assert_eq!(Generic::<u8>::OFFSETS, [0, 1]);
+        assert_eq!(Generic::<u32>::OFFSETS, [0, 4]);
+        assert_eq!(
+            Generic::<u64>::OFFSETS,
+            [Generic::<u64>::OFFSET_0, Generic::<u64>::OFFSET_1]
+        );
+
+        assert_eq!(Empty::OFFSETS, [0usize; 0]);
+    }
+}
+
+mod into_fields {
+    use super::*;
+
+    use std::{cell::Cell, rc::Rc};
+
+    // Increments the counter when dropped
+    pub struct DropCounter(pub Rc<Cell<u32>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(into_fields)]
+    pub struct WithDrop {
+        pub name: String,
+        pub counters: Vec<DropCounter>,
+        pub counter: DropCounter,
+        pub dropped: Rc<Cell<u32>>,
+    }
+
+    impl Drop for WithDrop {
+        fn drop(&mut self) {
+            self.dropped.set(self.dropped.get() + 1);
+        }
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(into_fields)]
+    pub struct Packed(pub u8, pub DropCounter, pub Vec<String>);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(into_fields)]
+    pub struct Single(pub String);
+
+    #[test]
+    fn into_fields_test() {
+        let drops = Rc::new(Cell::new(0));
+        let struct_drops = Rc::new(Cell::new(0));
+        {
+            let this = WithDrop {
+                name: "hello".to_string(),
+                counters: vec![DropCounter(drops.clone()), DropCounter(drops.clone())],
+                counter: DropCounter(drops.clone()),
+                dropped: struct_drops.clone(),
+            };
+
+            let (name, counters, counter, dropped) = this.into_fields();
+            assert_eq!(struct_drops.get(), 0);
+            assert_eq!(drops.get(), 0);
+            assert_eq!(name, "hello");
+            assert!(Rc::ptr_eq(&dropped, &struct_drops));
+
+            drop(counters);
+            assert_eq!(drops.get(), 2);
+            drop(counter);
+            assert_eq!(drops.get(), 3);
+        }
+        assert_eq!(drops.get(), 3);
+        assert_eq!(struct_drops.get(), 0);
+        assert_eq!(Rc::strong_count(&drops), 1);
+        assert_eq!(Rc::strong_count(&struct_drops), 1);
+
+        {
+            let this = Packed(5, DropCounter(drops.clone()), vec!["world".to_string()]);
+            let (a, counter, strings) = this.into_fields();
+            assert_eq!(a, 5);
+            assert_eq!(strings, ["world"]);
+            assert_eq!(drops.get(), 3);
+            drop(counter);
+            assert_eq!(drops.get(), 4);
+        }
+        assert_eq!(Rc::strong_count(&drops), 1);
+
+        let (text,) = Single("single".to_string()).into_fields();
+        assert_eq!(text, "single");
+    }
+}
+
+mod write_all {
+    use super::*;
+
+    use std::{cell::Cell, mem::MaybeUninit, rc::Rc};
+
+    use super::into_fields::DropCounter;
+
+    #[repr(C)]
+    #[derive(Debug, PartialEq, ReprOffset)]
+    #[roff(write_all, into_fields)]
+    pub struct Struct {
+        pub a: u8,
+        pub b: String,
+        pub c: Option<u16>,
+        pub d: Vec<u64>,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(write_all)]
+    pub struct Packed(pub u8, pub DropCounter, pub Vec<String>);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(write_all)]
+    pub struct Generic<T> {
+        pub a: T,
+        pub b: Option<T>,
+    }
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(write_all)]
+    pub struct Unit;
+
+    #[test]
+    fn write_all_test() {
+        let mut this = MaybeUninit::<Struct>::uninit();
+        let this = unsafe {
+            Struct::write_all_from_tuple(
+                this.as_mut_ptr(),
+                (3, "hello".to_string(), Some(5), vec![8, 13]),
+            );
+            this.assume_init()
+        };
+        assert_eq!(
+            this,
+            Struct {
+                a: 3,
+                b: "hello".to_string(),
+                c: Some(5),
+                d: vec![8, 13],
+            }
+        );
+
+        // Round trip through `into_fields`
+        let fields = this.into_fields();
+        let mut this = MaybeUninit::<Struct>::uninit();
+        let this = unsafe {
+            Struct::write_all_from_tuple(this.as_mut_ptr(), fields);
+            this.assume_init()
+        };
+        assert_eq!(this.b, "hello");
+        assert_eq!(this.d, [8, 13]);
+
+        let drops = Rc::new(Cell::new(0));
+        {
+            let mut packed = MaybeUninit::<Packed>::uninit();
+            let packed = unsafe {
+                Packed::write_all_from_tuple(
+                    packed.as_mut_ptr(),
+                    (5, DropCounter(drops.clone()), vec!["world".to_string()]),
+                );
+                packed.assume_init()
+            };
+            assert_eq!(drops.get(), 0);
+            assert_eq!(Packed::OFFSET_0.get_copy(&packed), 5);
+            let Packed(_, counter, strings) = packed;
+            assert_eq!(strings, ["world"]);
+            drop(counter);
+        }
+        assert_eq!(drops.get(), 1);
+        assert_eq!(Rc::strong_count(&drops), 1);
+
+        let mut generic = MaybeUninit::<Generic<String>>::uninit();
+        let generic = unsafe {
+            Generic::write_all_from_tuple(
+                generic.as_mut_ptr(),
+                ("foo".to_string(), Some("bar".to_string())),
+            );
+            generic.assume_init()
+        };
+        assert_eq!(generic.a, "foo");
+        assert_eq!(generic.b.as_deref(), Some("bar"));
+
+        let mut unit = MaybeUninit::<Unit>::uninit();
+        unsafe { Unit::write_all_from_tuple(unit.as_mut_ptr(), ()) };
+    }
+
+    #[test]
+    fn write_all_overwrites_fields() {
+        let drops = Rc::new(Cell::new(0));
+
+        let mut packed = Packed(0, DropCounter(drops.clone()), Vec::new());
+
+        // The previous values have to be dropped manually,
+        // since `write_all_from_tuple` doesn't drop them.
+        let old_counter = unsafe { Packed::OFFSET_1.read(&packed) };
+        unsafe {
+            Packed::write_all_from_tuple(
+                &mut packed,
+                (8, DropCounter(drops.clone()), vec!["foo".to_string()]),
+            );
+        }
+        assert_eq!(drops.get(), 0);
+        drop(old_counter);
+        assert_eq!(drops.get(), 1);
+
+        assert_eq!(Packed::OFFSET_0.get_copy(&packed), 8);
+        drop(packed);
+        assert_eq!(drops.get(), 2);
+        assert_eq!(Rc::strong_count(&drops), 1);
+    }
+}
+
+mod dyn_offsets {
+    use super::*;
+
+    use repr_offset::{off, FieldOffsetDyn};
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(dyn_offsets)]
+    pub struct Struct {
+        #[roff(offset = "OFFSET_TYPE")]
+        pub r#type: u8,
+        pub y: u32,
+        z: [u8; 3],
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(dyn_offsets)]
+    pub struct Tuple(u8, u32, u16);
+
+    fn field_bytes<S>(this: &S, offset: FieldOffsetDyn<S>) -> &[u8] {
+        unsafe {
+            let ptr = (this as *const S as *const u8).add(offset.offset());
+            std::slice::from_raw_parts(ptr, offset.size())
+        }
+    }
+
+    #[test]
+    fn dyn_offsets_test() {
+        let this = Struct {
+            r#type: 3,
+            y: 0x0505_0505,
+            z: [8, 13, 21],
+        };
+
+        let off_type = Struct::field_offset_dyn("type").unwrap();
+        let off_y = Struct::field_offset_dyn("y").unwrap();
+        let off_z = Struct::field_offset_dyn("z").unwrap();
+        assert_eq!(off_type, off!(this; r#type).to_dyn());
+        assert_eq!(off_y, off!(this; y).to_dyn());
+        assert_eq!(off_z, off!(this; z).to_dyn());
+
+        assert_eq!(field_bytes(&this, off_type), [3]);
+        assert_eq!(field_bytes(&this, off_y), [5, 5, 5, 5]);
+        assert_eq!(field_bytes(&this, off_z), [8, 13, 21]);
+
+        assert!(Struct::field_offset_dyn("r#type").is_none());
+        assert!(Struct::field_offset_dyn("w").is_none());
+        assert!(Struct::field_offset_dyn("").is_none());
+
+        let tuple = Tuple(34, 0x3737_3737, 0x5959);
+        let offsets = ["0", "1", "2"]
+            .iter()
+            .map(|name| Tuple::field_offset_dyn(name).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            offsets
+                .iter()
+                .map(|x| (x.offset(), x.size()))
+                .collect::<Vec<_>>(),
+            [(0, 1), (1, 4), (5, 2)]
+        );
+        assert_eq!(field_bytes(&tuple, offsets[0]), [34]);
+        assert_eq!(field_bytes(&tuple, offsets[1]), [0x37; 4]);
+        assert_eq!(field_bytes(&tuple, offsets[2]), [0x59; 2]);
+        assert!(Tuple::field_offset_dyn("3").is_none());
+    }
+}
+
+#[cfg(feature = "std")]
+mod dyn_set {
+    use super::*;
+
+    use repr_offset::utils::SetFieldError;
+
+    use std::rc::Rc;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(dyn_set)]
+    pub struct Struct {
+        pub x: u8,
+        pub y: String,
+        z: Rc<()>,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(dyn_set)]
+    pub struct Tuple(u8, Vec<u32>);
+
+    fn assert_type_mismatch<T>(result: Result<(), SetFieldError>, expected: T)
+    where
+        T: Debug + PartialEq + 'static,
+    {
+        match result {
+            Err(SetFieldError::TypeMismatch(value)) => {
+                assert_eq!(value.downcast_ref::<T>(), Some(&expected))
+            }
+            x => panic!("expected a TypeMismatch error, found: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn dyn_set_test() {
+        let rc = Rc::new(());
+        let mut this = Struct {
+            x: 3,
+            y: "5".to_string(),
+            z: rc.clone(),
+        };
+
+        this.set_field_dyn("x", Box::new(8u8)).unwrap();
+        this.set_field_dyn("y", Box::new("13".to_string())).unwrap();
+        assert_eq!(Rc::strong_count(&rc), 2);
+        this.set_field_dyn("z", Box::new(Rc::new(()))).unwrap();
+        assert_eq!(
+            Rc::strong_count(&rc),
+            1,
+            "the previous value wasn't dropped"
+        );
+
+        assert_eq!(this.x, 8);
+        assert_eq!(this.y, "13");
+
+        assert_type_mismatch(this.set_field_dyn("x", Box::new(21u32)), 21u32);
+        assert_type_mismatch(this.set_field_dyn("y", Box::new("34")), "34");
+        assert_type_mismatch(this.set_field_dyn("z", Box::new(())), ());
+        assert!(matches!(
+            this.set_field_dyn("w", Box::new(55u8)),
+            Err(SetFieldError::UnknownField)
+        ));
+        assert_eq!(this.x, 8);
+        assert_eq!(this.y, "13");
+
+        let mut tuple = Tuple(3, vec![5]);
+        tuple.set_field_dyn("0", Box::new(8u8)).unwrap();
+        tuple.set_field_dyn("1", Box::new(vec![13u32, 21])).unwrap();
+        assert_type_mismatch(tuple.set_field_dyn("1", Box::new(vec![34u8])), vec![34u8]);
+        assert!(matches!(
+            tuple.set_field_dyn("2", Box::new(8u8)),
+            Err(SetFieldError::UnknownField)
+        ));
+        assert_eq!(tuple.0, 8);
+        assert_eq!({ tuple.1 }, [13, 21]);
+    }
+}
+
+mod tuple_index {
+    use super::*;
+
+    use std::any::Any;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(tuple_index)]
+    pub struct Tuple(pub u8, pub String, [u64; 2]);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(tuple_index)]
+    pub struct Generic<T>(pub u16, pub T);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(tuple_index)]
+    pub struct Unit;
+
+    fn downcast<T: 'static>(field: Option<&dyn Any>) -> Option<&T> {
+        field.unwrap().downcast_ref::<T>()
+    }
+
+    #[test]
+    fn tuple_index_test() {
+        let tuple = Tuple(3, "5".to_string(), [8, 13]);
+        assert_eq!(downcast::<u8>(tuple.field_ref(0)), Some(&3));
+        assert_eq!(downcast::<String>(tuple.field_ref(1)).unwrap(), "5");
+        assert_eq!(downcast::<[u64; 2]>(tuple.field_ref(2)), Some(&[8, 13]));
+        assert_eq!(downcast::<u16>(tuple.field_ref(0)), None);
+        assert!(tuple.field_ref(3).is_none());
+        assert!(tuple.field_ref(usize::max_value()).is_none());
+
+        let generic = Generic(21, 'a');
+        assert_eq!(downcast::<u16>(generic.field_ref(0)), Some(&21));
+        assert_eq!(downcast::<char>(generic.field_ref(1)), Some(&'a'));
+        assert!(generic.field_ref(2).is_none());
+
+        assert!(Unit.field_ref(0).is_none());
+    }
+}
+
+mod nested_consts {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    pub struct Point {
+        pub x: u16,
+        pub y: u32,
+    }
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    pub struct Pair(pub u8, pub u64);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    pub struct Line {
+        pub tag: u8,
+        pub start: Point,
+        pub end: Point,
+    }
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(nested_consts("line.start.y", "line.end.x", "pair.1"))]
+    pub struct Aligned3 {
+        pub flag: bool,
+        pub line: Line,
+        pub pair: Pair,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(nested_consts("line.end.y", "pair.0"), offset_prefix = "OFF_")]
+    pub struct Packed3 {
+        pub flag: bool,
+        pub line: Line,
+        pub pair: Pair,
+    }
+
+    const ALIGNED_START_Y: usize = Aligned3::OFFSET_LINE_START_Y.offset();
+    const ALIGNED_END_X: usize = Aligned3::OFFSET_LINE_END_X.offset();
+    const ALIGNED_PAIR_1: usize = Aligned3::OFFSET_PAIR_1.offset();
+    const PACKED_END_Y: usize = Packed3::OFF_LINE_END_Y.offset();
+    const PACKED_PAIR_0: usize = Packed3::OFF_PAIR_0.offset();
+
+    #[test]
+    fn nested_consts_test() {
+        let _: FieldOffset<Aligned3, u32, Aligned> = Aligned3::OFFSET_LINE_START_Y;
+        let _: FieldOffset<Aligned3, u16, Aligned> = Aligned3::OFFSET_LINE_END_X;
+        let _: FieldOffset<Aligned3, u64, Aligned> = Aligned3::OFFSET_PAIR_1;
+        let _: FieldOffset<Packed3, u32, Unaligned> = Packed3::OFF_LINE_END_Y;
+        let _: FieldOffset<Packed3, u8, Unaligned> = Packed3::OFF_PAIR_0;
+
+        assert_eq!(ALIGNED_START_Y, 4 + 4 + 4);
+        assert_eq!(ALIGNED_END_X, 4 + 12);
+        assert_eq!(ALIGNED_PAIR_1, 24 + 8);
+        assert_eq!(PACKED_END_Y, 1 + 12 + 4);
+        assert_eq!(PACKED_PAIR_0, 1 + 20);
+
+        let point = |x, y| Point { x, y };
+
+        let aligned = Aligned3 {
+            flag: true,
+            line: Line {
+                tag: 0,
+                start: point(3, 5),
+                end: point(8, 13),
+            },
+            pair: Pair(21, 34),
+        };
+        assert_eq!(Aligned3::OFFSET_LINE_START_Y.get_copy(&aligned), 5);
+        assert_eq!(Aligned3::OFFSET_LINE_END_X.get_copy(&aligned), 8);
+        assert_eq!(Aligned3::OFFSET_PAIR_1.get_copy(&aligned), 34);
+
+        let packed = Packed3 {
+            flag: true,
+            line: Line {
+                tag: 0,
+                start: point(3, 5),
+                end: point(8, 13),
+            },
+            pair: Pair(21, 34),
+        };
+        assert_eq!(Packed3::OFF_LINE_END_Y.get_copy(&packed), 13);
+        assert_eq!(Packed3::OFF_PAIR_0.get_copy(&packed), 21);
+    }
+}
+
+mod eq_by_fields {
+    use super::*;
+
+    use std::mem::MaybeUninit;
+
+    pub struct NotEq;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(eq_by_fields)]
+    pub struct Padded<T> {
+        pub a: u8,
+        pub b: u32,
+        pub c: u16,
+        pub d: u64,
+        pub _marker: PhantomData<T>,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(eq_by_fields)]
+    pub struct Packed(pub u8, pub &'static str, pub u32);
+
+    // Constructs a `Padded` whose padding bytes are all `padding`
+    fn padded_with(padding: u8, a: u8, b: u32, c: u16, d: u64) -> Padded<NotEq> {
+        let mut this = MaybeUninit::<Padded<NotEq>>::uninit();
+        let ptr = this.as_mut_ptr();
+        unsafe {
+            ptr.cast::<u8>()
+                .write_bytes(padding, std::mem::size_of::<Padded<NotEq>>());
+            Padded::OFFSET_A.write(ptr, a);
+            Padded::OFFSET_B.write(ptr, b);
+            Padded::OFFSET_C.write(ptr, c);
+            Padded::OFFSET_D.write(ptr, d);
+            Padded::OFFSET__MARKER.write(ptr, PhantomData);
+            this.assume_init()
+        }
+    }
+
+    #[test]
+    fn eq_by_fields_test() {
+        {
+            let left = padded_with(0xAA, 3, 5, 8, 13);
+            let right = padded_with(0x55, 3, 5, 8, 13);
+            assert!(left.eq_by_fields(&right));
+            assert!(right.eq_by_fields(&left));
+
+            assert!(!left.eq_by_fields(&padded_with(0x55, 0, 5, 8, 13)));
+            assert!(!left.eq_by_fields(&padded_with(0x55, 3, 0, 8, 13)));
+            assert!(!left.eq_by_fields(&padded_with(0x55, 3, 5, 0, 13)));
+            assert!(!left.eq_by_fields(&padded_with(0x55, 3, 5, 8, 0)));
+        }
+        {
+            let this = Packed(3, "hello", 5);
+            assert!(this.eq_by_fields(&Packed(3, "hello", 5)));
+            assert!(!this.eq_by_fields(&Packed(0, "hello", 5)));
+            assert!(!this.eq_by_fields(&Packed(3, "world", 5)));
+            assert!(!this.eq_by_fields(&Packed(3, "hello", 0)));
+        }
+    }
+}
+
+// References to fields of packed structs are an error,
+// so this also checks that the generated impls don't create them.
+mod derive_eq {
+    use super::*;
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(derive_eq(Eq))]
+    pub struct Packed {
+        pub a: u8,
+        pub b: u64,
+        pub c: u16,
+        pub d: &'static str,
+    }
+
+    #[repr(C)]
+    #[derive(ReprOffset, Debug)]
+    #[roff(derive_eq(Eq))]
+    pub struct Generic<T> {
+        pub tag: u8,
+        pub value: T,
+    }
+
+    // `Eq` isn't implemented without `derive_eq(Eq)`,
+    // which would be an error for the `f32` field.
+    #[repr(C)]
+    #[derive(ReprOffset, Debug)]
+    #[roff(derive_eq)]
+    pub struct WithFloat {
+        pub a: u32,
+        pub b: f32,
+    }
+
+    fn assert_eq_impl<T: Eq>(_: &T) {}
+
+    #[test]
+    fn derive_eq_test() {
+        let packed = |a, b, c, d| Packed { a, b, c, d };
+        {
+            let this = packed(3, 0x0102_0304_0506_0708, 0x1234, "hello");
+            assert_eq_impl(&this);
+            assert!(this == packed(3, 0x0102_0304_0506_0708, 0x1234, "hello"));
+            assert!(this != packed(4, 0x0102_0304_0506_0708, 0x1234, "hello"));
+            assert!(this != packed(3, 0x0102_0304_0506_0709, 0x1234, "hello"));
+            assert!(this != packed(3, 0x0102_0304_0506_0708, 0x1235, "hello"));
+            assert!(this != packed(3, 0x0102_0304_0506_0708, 0x1234, "world"));
+        }
+        {
+            let this = Generic {
+                tag: 3,
+                value: 5u32,
+            };
+            assert_eq_impl(&this);
+            assert_eq!(this, Generic { tag: 3, value: 5 });
+            assert_ne!(this, Generic { tag: 4, value: 5 });
+            assert_ne!(this, Generic { tag: 3, value: 8 });
+        }
+        {
+            // Only implements `PartialEq`, since `f64` doesn't implement `Eq`
+            let this = Generic {
+                tag: 3,
+                value: std::f64::NAN,
+            };
+            assert_ne!(this, this);
+            assert_eq!(
+                Generic { tag: 3, value: 0.5 },
+                Generic { tag: 3, value: 0.5 }
+            );
+        }
+        {
+            let this = WithFloat { a: 3, b: 0.5 };
+            assert_eq!(this, WithFloat { a: 3, b: 0.5 });
+            assert_ne!(this, WithFloat { a: 4, b: 0.5 });
+            assert_ne!(this, WithFloat { a: 3, b: 1.5 });
+            assert_ne!(
+                WithFloat {
+                    a: 3,
+                    b: std::f32::NAN
+                },
+                WithFloat {
+                    a: 3,
+                    b: std::f32::NAN
+                },
+            );
+        }
+    }
+}
+
+mod derive_hash {
+    use super::*;
+
+    use std::{
+        collections::{hash_map::DefaultHasher, HashSet},
+        hash::{Hash, Hasher},
+    };
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(derive_eq(Eq), derive_hash)]
+    pub struct Packed {
+        pub a: u8,
+        pub b: u64,
+        pub c: u16,
+        pub d: &'static str,
+    }
+
+    #[repr(C)]
+    #[derive(ReprOffset, Debug)]
+    #[roff(derive_eq(Eq), derive_hash)]
+    pub struct Generic<T> {
+        pub tag: u8,
+        pub value: T,
+    }
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(derive_hash)]
+    pub struct Unit;
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // The hash of the fields, in declaration order
+    fn hash_of_fields(a: u8, b: u64, c: u16, d: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        a.hash(&mut hasher);
+        b.hash(&mut hasher);
+        c.hash(&mut hasher);
+        d.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn derive_hash_test() {
+        let packed = |a, b, c, d| Packed { a, b, c, d };
+        {
+            let this = packed(3, 0x0102_0304_0506_0708, 0x1234, "hello");
+            let same = packed(3, 0x0102_0304_0506_0708, 0x1234, "hello");
+            assert!(this == same);
+            assert_eq!(hash_of(&this), hash_of(&same));
+            assert_eq!(
+                hash_of(&this),
+                hash_of_fields(3, 0x0102_0304_0506_0708, 0x1234, "hello")
+            );
+            assert_ne!(
+                hash_of(&this),
+                hash_of(&packed(3, 0x0102_0304_0506_0708, 0x1234, "world"))
+            );
+
+            let mut set = HashSet::new();
+            assert!(set.insert(this));
+            assert!(!set.insert(same));
+            assert!(set.insert(packed(4, 0x0102_0304_0506_0708, 0x1234, "hello")));
+            assert!(set.contains(&packed(3, 0x0102_0304_0506_0708, 0x1234, "hello")));
+            assert!(!set.contains(&packed(3, 0, 0x1234, "hello")));
+            assert_eq!(set.len(), 2);
+        }
+        {
+            let this = Generic {
+                tag: 3,
+                value: "hello",
+            };
+            assert_eq!(
+                hash_of(&this),
+                hash_of(&Generic {
+                    tag: 3,
+                    value: "hello"
+                })
+            );
+            assert_eq!(hash_of(&this), hash_of(&(3u8, "hello")));
+            assert_ne!(
+                hash_of(&this),
+                hash_of(&Generic {
+                    tag: 4,
+                    value: "hello"
+                })
+            );
+        }
+        assert_eq!(hash_of(&Unit), hash_of(&()));
+    }
+}
+
+#[cfg(feature = "priv_offset_of")]
+mod layout_guard {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(layout_guard)]
+    pub struct Named {
+        pub a: u8,
+        pub b: u64,
+        pub c: [u16; 3],
+        pub d: (),
+        pub e: u32,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(layout_guard)]
+    pub struct Packed(pub u8, pub u64, pub u16);
+
+    #[repr(C, packed(4))]
+    #[derive(ReprOffset)]
+    #[roff(layout_guard)]
+    pub struct Packed4(pub u8, pub u64, pub u16);
+
+    #[repr(C, align(16))]
+    #[derive(ReprOffset)]
+    #[roff(layout_guard, usize_offsets)]
+    pub struct AlignedUsize {
+        pub a: u8,
+        pub b: u32,
+        pub c: u8,
+    }
+
+    #[test]
+    fn layout_guard_test() {
+        // The layout guards are checked at compile-time,
+        // these assertions just make sure that the structs above are what they look like.
+        assert_eq!(Named::OFFSET_E.offset(), 24);
+        assert_eq!(Packed::OFFSET_2.offset(), 9);
+        assert_eq!(Packed4::OFFSET_2.offset(), 12);
+        assert_eq!(AlignedUsize::OFFSET_C, 8);
+    }
+}
+
+mod aligned_wrapper_fields {
+    use super::*;
+
+    use repr_offset::{for_examples::ReprPacked, OFF};
+
+    #[repr(C, align(16))]
+    #[derive(ReprOffset)]
+    pub struct Aligned16<T>(pub T);
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    pub struct Outer<T> {
+        pub a: u8,
+        pub b: Aligned16<T>,
+        pub c: u8,
+    }
+
+    fn addr_offset<S, F>(base: &S, field: *const F) -> usize {
+        field as usize - (base as *const S as usize)
+    }
+
+    #[test]
+    fn aligned_wrapper_fields_test() {
+        type Inner = ReprPacked<u8, u32, u16, ()>;
+
+        let this: Outer<Inner> = Outer {
+            a: 3,
+            b: Aligned16(ReprPacked {
+                a: 5,
+                b: 8,
+                c: 13,
+                d: (),
+            }),
+            c: 21,
+        };
+
+        assert_eq!(std::mem::size_of::<Aligned16<Inner>>(), 16);
+        assert_eq!(Outer::<Inner>::OFFSET_B.offset(), 16);
+        assert_eq!(Outer::<Inner>::OFFSET_C.offset(), 32);
+        assert_eq!(
+            Outer::<Inner>::OFFSET_B.offset(),
+            addr_offset(&this, &this.b)
+        );
+        assert_eq!(
+            Outer::<Inner>::OFFSET_C.offset(),
+            addr_offset(&this, &this.c)
+        );
+
+        let to_inner = Outer::<Inner>::OFFSET_B + Aligned16::<Inner>::OFFSET_0;
+        let _: FieldOffset<Outer<Inner>, Inner, Aligned> = to_inner;
+        assert_eq!(to_inner.offset(), 16);
+
+        let inner_c = to_inner + Inner::OFFSET_C;
+        let _: FieldOffset<Outer<Inner>, u16, Unaligned> = inner_c;
+        assert_eq!(inner_c.offset(), 21);
+        assert_eq!(inner_c, OFF!(Outer<Inner>; b.0.c));
+
+        assert_eq!((to_inner + Inner::OFFSET_A).get_copy(&this), 5);
+        assert_eq!((to_inner + Inner::OFFSET_B).get_copy(&this), 8);
+        assert_eq!(inner_c.get_copy(&this), 13);
+        assert_eq!(Outer::<Inner>::OFFSET_C.get_copy(&this), 21);
+    }
+}
+
+mod c_accessors {
+    use super::*;
+
+    use std::ptr;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(c_accessors = "roff_test_point_", c_accessors_no_mangle)]
+    pub struct Point {
+        pub x: u32,
+        pub y: f64,
+        pub visible: bool,
+        pub name: *const u8,
+        pub next: *mut Point,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(c_accessors = "roff_test_packed_")]
+    pub struct Packed(pub u8, pub i64, pub std::os::raw::c_int);
+
+    // The `Vec` field doesn't get an accessor, and emits a deprecation warning.
+    #[allow(deprecated)]
+    mod skipped {
+        use super::*;
+
+        #[repr(C)]
+        #[derive(ReprOffset)]
+        #[roff(c_accessors = "roff_test_skipped_")]
+        pub struct Skipped {
+            pub len: usize,
+            pub list: Vec<u8>,
+        }
+    }
+    use self::skipped::*;
+
+    #[test]
+    fn c_accessors_test() {
+        let name = b"hello\0";
+        let mut next = Point {
+            x: 21,
+            y: 34.0,
+            visible: false,
+            name: ptr::null(),
+            next: ptr::null_mut(),
+        };
+        let point = Point {
+            x: 3,
+            y: 5.5,
+            visible: true,
+            name: name.as_ptr(),
+            next: &mut next,
+        };
+        unsafe {
+            assert_eq!(roff_test_point_get_x(&point), 3);
+            assert_eq!(roff_test_point_get_y(&point), 5.5);
+            assert!(roff_test_point_get_visible(&point));
+            assert_eq!(roff_test_point_get_name(&point), name.as_ptr());
+
+            let next = roff_test_point_get_next(&point);
+            assert_eq!(roff_test_point_get_x(next), 21);
+            assert_eq!(roff_test_point_get_y(next), 34.0);
+            assert!(!roff_test_point_get_visible(next));
+            assert!(roff_test_point_get_name(next).is_null());
+            assert!(roff_test_point_get_next(next).is_null());
+        }
+
+        let packed = Packed(8, -13, 100);
+        unsafe {
+            assert_eq!(roff_test_packed_get_0(&packed), 8);
+            assert_eq!(roff_test_packed_get_1(&packed), -13);
+            assert_eq!(roff_test_packed_get_2(&packed), 100);
+        }
+
+        let skipped = Skipped {
+            len: 2,
+            list: vec![3, 5],
+        };
+        unsafe {
+            assert_eq!(roff_test_skipped_get_len(&skipped), 2);
+        }
+        assert_eq!(skipped.list, [3, 5]);
+    }
+}
+
+mod unsafe_offset {
+    use super::*;
+
+    use repr_offset::{off, ROExtAcc, OFF};
+
+    // Both `lo` and `bytes` are forced to the offset of `word`.
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(padding_consts)]
+    pub struct Overlapping {
+        pub word: u32,
+        #[roff(unsafe_offset = 0)]
+        pub lo: u16,
+        #[roff(unsafe_offset = "Self::OFFSET_WORD.offset()")]
+        pub bytes: [u8; 4],
+        pub after: u64,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(usize_offsets)]
+    pub struct Packed {
+        pub a: u8,
+        pub b: u32,
+        #[roff(unsafe_offset = "1")]
+        pub c: [u8; 4],
+    }
+
+    #[test]
+    fn unsafe_offset_test() {
+        let _: FieldOffset<Overlapping, u16, Aligned> = Overlapping::OFFSET_LO;
+        let _: FieldOffset<Overlapping, [u8; 4], Aligned> = Overlapping::OFFSET_BYTES;
+
+        assert_eq!(Overlapping::OFFSET_WORD.offset(), 0);
+        assert_eq!(Overlapping::OFFSET_LO.offset(), 0);
+        assert_eq!(Overlapping::OFFSET_BYTES.offset(), 0);
+        assert_eq!(Overlapping::OFFSET_AFTER.offset(), 16);
+
+        // The actual offsets of the fields are unaffected
+        assert_eq!(Overlapping::__ROFF_COMPUTED_OFFSET_LO.offset(), 4);
+        assert_eq!(Overlapping::__ROFF_COMPUTED_OFFSET_BYTES.offset(), 6);
+        assert_eq!(OFF!(Overlapping; lo).offset(), 4);
+        assert_eq!(OFF!(Overlapping; bytes).offset(), 6);
+        assert_eq!(Overlapping::PADDING_AFTER_BYTES, 6);
+
+        let mut this = Overlapping {
+            word: u32::from_ne_bytes([3, 5, 8, 13]),
+            lo: 0,
+            bytes: [0; 4],
+            after: 21,
+        };
+
+        assert_eq!(*Overlapping::OFFSET_BYTES.get(&this), [3, 5, 8, 13]);
+        assert_eq!(
+            *Overlapping::OFFSET_LO.get(&this),
+            u16::from_ne_bytes([3, 5])
+        );
+
+        *Overlapping::OFFSET_BYTES.get_mut(&mut this) = [34, 55, 89, 144];
+        assert_eq!(this.word, u32::from_ne_bytes([34, 55, 89, 144]));
+        assert_eq!(
+            *Overlapping::OFFSET_LO.get(&this),
+            u16::from_ne_bytes([34, 55])
+        );
+        assert_eq!(this.lo, 0);
+        assert_eq!(this.bytes, [0; 4]);
+        assert_eq!(this.f_get(off!(bytes)), &[0; 4]);
+        assert_eq!(this.after, 21);
+
+        assert_eq!(Packed::OFFSET_A, 0);
+        assert_eq!(Packed::OFFSET_B, 1);
+        assert_eq!(Packed::OFFSET_C, 1);
+        assert_eq!(Packed::__ROFF_COMPUTED_OFFSET_C, 5);
+
+        let this = Packed {
+            a: 3,
+            b: u32::from_ne_bytes([5, 8, 13, 21]),
+            c: [0; 4],
+        };
+        let bytes = unsafe {
+            ((&this as *const Packed as *const u8).add(Packed::OFFSET_C) as *const [u8; 4])
+                .read_unaligned()
+        };
+        assert_eq!(bytes, [5, 8, 13, 21]);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialize_via_fields {
+    use super::*;
+
+    use serde::ser::{self, Impossible, Serialize, Serializer};
+
+    use std::fmt;
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(serialize_via_fields)]
+    pub struct Packed {
+        pub tag: u8,
+        pub len: u32,
+        pub kind: i16,
+        pub bytes: [u8; 3],
+        pub name: &'static str,
+    }
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(serialize_via_fields)]
+    pub struct Tuple<T>(pub bool, pub T, pub char);
+
+    #[test]
+    fn serialize_via_fields_test() {
+        let this = Packed {
+            tag: 3,
+            len: 0x0102_0304,
+            kind: -5,
+            bytes: [8, 13, 21],
+            name: "hello",
+        };
+        assert_eq!(
+            to_json(&this),
+            r#"{"tag":3,"len":16909060,"kind":-5,"bytes":[8,13,21],"name":"hello"}"#,
+        );
+
+        assert_eq!(to_json(&Tuple(true, 34u64, 'a')), r#"[true,34,"a"]"#);
+        assert_eq!(
+            to_json(&Tuple(false, Tuple(true, 55i8, 'b'), 'c')),
+            r#"[false,[true,55,"b"],"c"]"#
+        );
+    }
+
+    // A minimal JSON serializer, since `serde_json` requires a much newer Rust version
+    // than the ones that this crate is tested with.
+
+    fn to_json<T: Serialize>(value: &T) -> String {
+        let mut json = Json(String::new());
+        value.serialize(&mut json).unwrap();
+        json.0
+    }
+
+    struct Json(String);
+
+    #[derive(Debug)]
+    struct JsonError(String);
+
+    impl fmt::Display for JsonError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl ser::StdError for JsonError {}
+
+    impl ser::Error for JsonError {
+        fn custom<T: fmt::Display>(msg: T) -> Self {
+            JsonError(msg.to_string())
+        }
+    }
+
+    struct Compound<'a> {
+        json: &'a mut Json,
+        first: bool,
+        end: &'static str,
+    }
+
+    impl Compound<'_> {
+        fn separator(&mut self) {
+            if !self.first {
+                self.json.0.push(',');
+            }
+            self.first = false;
+        }
+    }
+
+    macro_rules! serialize_display {
+        ($($method:ident: $ty:ty,)*) => {
+            $(
+                fn $method(self, v: $ty) -> Result<(), JsonError> {
+                    self.0.push_str(&v.to_string());
+                    Ok(())
+                }
+            )*
+        };
+    }
+
+    macro_rules! unsupported {
+        ($($method:ident($($param:ty),*) -> $ret:ty;)*) => {
+            $(
+                fn $method(self, $(_: $param),*) -> Result<$ret, JsonError> {
+                    Err(JsonError(stringify!($method is unsupported).to_string()))
+                }
+            )*
+        };
+    }
+
+    impl<'a> Serializer for &'a mut Json {
+        type Ok = ();
+        type Error = JsonError;
+        type SerializeSeq = Compound<'a>;
+        type SerializeTuple = Compound<'a>;
+        type SerializeTupleStruct = Compound<'a>;
+        type SerializeTupleVariant = Impossible<(), JsonError>;
+        type SerializeMap = Impossible<(), JsonError>;
+        type SerializeStruct = Compound<'a>;
+        type SerializeStructVariant = Impossible<(), JsonError>;
+
+        serialize_display! {
+            serialize_bool: bool,
+            serialize_i8: i8,
+            serialize_i16: i16,
+            serialize_i32: i32,
+            serialize_i64: i64,
+            serialize_u8: u8,
+            serialize_u16: u16,
+            serialize_u32: u32,
+            serialize_u64: u64,
+            serialize_f32: f32,
+            serialize_f64: f64,
+        }
+
+        fn serialize_char(self, v: char) -> Result<(), JsonError> {
+            self.serialize_str(v.encode_utf8(&mut [0; 4]))
+        }
+
+        fn serialize_str(self, v: &str) -> Result<(), JsonError> {
+            self.0.push_str(&format!("{:?}", v));
+            Ok(())
+        }
+
+        fn collect_str<T: ?Sized + fmt::Display>(self, value: &T) -> Result<(), JsonError> {
+            self.serialize_str(&value.to_string())
+        }
+
+        fn serialize_none(self) -> Result<(), JsonError> {
+            self.serialize_unit()
+        }
+
+        fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), JsonError> {
+            value.serialize(self)
+        }
+
+        fn serialize_unit(self) -> Result<(), JsonError> {
+            self.0.push_str("null");
+            Ok(())
+        }
+
+        fn serialize_unit_struct(self, _: &'static str) -> Result<(), JsonError> {
+            self.serialize_unit()
+        }
+
+        fn serialize_newtype_struct<T: ?Sized + Serialize>(
+            self,
+            _: &'static str,
+            value: &T,
+        ) -> Result<(), JsonError> {
+            value.serialize(self)
+        }
+
+        fn serialize_newtype_variant<T: ?Sized + Serialize>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &T,
+        ) -> Result<(), JsonError> {
+            Err(JsonError("enums are unsupported".to_string()))
+        }
+
+        fn serialize_seq(self, _: Option<usize>) -> Result<Compound<'a>, JsonError> {
+            self.0.push('[');
+            Ok(Compound {
+                json: self,
+                first: true,
+                end: "]",
+            })
+        }
+
+        fn serialize_tuple(self, len: usize) -> Result<Compound<'a>, JsonError> {
+            self.serialize_seq(Some(len))
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _: &'static str,
+            len: usize,
+        ) -> Result<Compound<'a>, JsonError> {
+            self.serialize_seq(Some(len))
+        }
+
+        fn serialize_struct(self, _: &'static str, _: usize) -> Result<Compound<'a>, JsonError> {
+            self.0.push('{');
+            Ok(Compound {
+                json: self,
+                first: true,
+                end: "}",
+            })
+        }
+
+        unsupported! {
+            serialize_bytes(&[u8]) -> ();
+            serialize_unit_variant(&'static str, u32, &'static str) -> ();
+            serialize_tuple_variant(&'static str, u32, &'static str, usize)
+                -> Impossible<(), JsonError>;
+            serialize_map(Option<usize>) -> Impossible<(), JsonError>;
+            serialize_struct_variant(&'static str, u32, &'static str, usize)
+                -> Impossible<(), JsonError>;
+        }
+    }
+
+    macro_rules! impl_serialize_elements {
+        ($($trait:ident :: $method:ident,)*) => {
+            $(
+                impl ser::$trait for Compound<'_> {
+                    type Ok = ();
+                    type Error = JsonError;
+
+                    fn $method<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), JsonError> {
+                        self.separator();
+                        value.serialize(&mut *self.json)
+                    }
+
+                    fn end(self) -> Result<(), JsonError> {
+                        self.json.0.push_str(self.end);
+                        Ok(())
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_serialize_elements! {
+        SerializeSeq::serialize_element,
+        SerializeTuple::serialize_element,
+        SerializeTupleStruct::serialize_field,
+    }
+
+    impl ser::SerializeStruct for Compound<'_> {
+        type Ok = ();
+        type Error = JsonError;
+
+        fn serialize_field<T: ?Sized + Serialize>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<(), JsonError> {
+            self.separator();
+            self.json.0.push_str(&format!("{:?}:", key));
+            value.serialize(&mut *self.json)
+        }
+
+        fn end(self) -> Result<(), JsonError> {
+            self.json.0.push_str(self.end);
+            Ok(())
+        }
+    }
+}
+
+// Fields of reference types that carry the lifetime parameters of the struct.
+mod lifetime_reference_fields {
+    use super::*;
+
+    use repr_offset::{off, pub_off, GetPubFieldOffset, ROExtAcc, ROExtOps, OFF};
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    pub struct Refs<'a> {
+        pub r: &'a str,
+        pub m: &'a mut u32,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    pub struct PackedRefs<'a, 'b, T> {
+        pub r: &'a str,
+        pub m: &'b mut T,
+    }
+
+    fn get_str<'a, 'b>(this: &'b Refs<'a>) -> &'b &'a str {
+        this.f_get(pub_off!(r))
+    }
+
+    fn get_str_copy<'a>(this: &Refs<'a>) -> &'a str {
+        this.f_get_copy(off!(*this; r))
+    }
+
+    fn increment<'a>(this: &mut Refs<'a>) {
+        **this.f_get_mut(pub_off!(m)) += 1;
+    }
+
+    fn replace_str<'a>(this: &mut Refs<'a>, other: &'a str) -> &'a str {
+        this.f_replace(PUB_OFF!(Refs<'a>; r), other)
+    }
+
+    fn offsets<'a>() -> (
+        FieldOffset<Refs<'a>, &'a str, Aligned>,
+        FieldOffset<Refs<'a>, &'a mut u32, Aligned>,
+    ) {
+        (OFF!(Refs<'a>; r), PUB_OFF!(Refs<'a>; m))
+    }
+
+    // The `Type` associated type preserves the lifetime of the struct.
+    fn field_type<'a>(this: Refs<'a>) -> <Refs<'a> as GetFieldOffset<TS!(m)>>::Type {
+        this.m
+    }
+
+    fn generic_get_str<'a, T, A>(this: &T) -> &'a str
+    where
+        T: GetPubFieldOffset<TS!(r), Type = &'a str, Alignment = A> + ROExtOps<A>,
+    {
+        this.f_get_copy(PUB_OFF!(T; r))
+    }
+
+    fn packed_replace<'a, 'b, T>(this: &mut PackedRefs<'a, 'b, T>, value: T) -> (&'a str, T) {
+        let offset = pub_off!(*this; m);
+        let m: &'b mut T = unsafe { offset.raw_get(this).read_unaligned() };
+        let old = std::mem::replace(m, value);
+        ((*this).f_get_copy(pub_off!(*this; r)), old)
+    }
+
+    #[test]
+    fn lifetime_reference_fields_test() {
+        let string = String::from("hello");
+        let mut number = 3u32;
+
+        {
+            let mut this = Refs {
+                r: &string[1..],
+                m: &mut number,
+            };
+
+            assert_eq!(*get_str(&this), "ello");
+            assert_eq!(get_str_copy(&this), "ello");
+            assert_eq!(generic_get_str(&this), "ello");
+
+            increment(&mut this);
+            assert_eq!(*this.m, 4);
+
+            assert_eq!(replace_str(&mut this, "world"), "ello");
+            assert_eq!(this.r, "world");
+
+            let (off_r, off_m) = offsets();
+            assert_eq!(off_r, Refs::OFFSET_R);
+            assert_eq!(off_m, Refs::OFFSET_M);
+            assert_eq!(off_m.offset(), std::mem::size_of::<&str>());
+
+            let m: &mut u32 = field_type(this);
+            *m += 10;
+        }
+        assert_eq!(number, 14);
+
+        {
+            let mut this = PackedRefs {
+                r: &string[..2],
+                m: &mut number,
+            };
+            assert_eq!(packed_replace(&mut this, 21), ("he", 14));
+            assert_eq!(generic_get_str(&this), "he");
+        }
+        assert_eq!(number, 21);
+    }
+}
+
+// `#[cfg]`s on fields are evaluated before the derive macro runs,
+// so the derive only sees the fields that are enabled.
+mod cfg_fields {
+    use super::*;
+
+    use repr_offset::off;
+
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    pub struct Struct {
+        pub a: u8,
+        pub b: u32,
+        #[cfg(any())]
+        pub disabled: u64,
+        #[cfg(feature = "derive")]
+        pub with_derive: u64,
+        #[cfg(not(feature = "derive"))]
+        pub without_derive: u16,
+        #[cfg_attr(test, roff(offset = "OFFSET_LAST"))]
+        pub z: u8,
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    pub struct Packed(
+        pub u8,
+        #[cfg(any())] pub u64,
+        #[cfg(feature = "derive")] pub u32,
+        pub u16,
+    );
+
+    fn addr_offset<S, F>(base: &S, field: *const F) -> usize {
+        field as usize - (base as *const S as usize)
+    }
+
+    #[test]
+    fn cfg_fields_test() {
+        #[cfg(feature = "derive")]
+        let this = Struct {
+            a: 3,
+            b: 5,
+            with_derive: 8,
+            z: 13,
+        };
+        #[cfg(not(feature = "derive"))]
+        let this = Struct {
+            a: 3,
+            b: 5,
+            without_derive: 8,
+            z: 13,
+        };
+
+        assert_eq!(Struct::OFFSET_A.offset(), 0);
+        assert_eq!(Struct::OFFSET_B.offset(), 4);
+        assert_eq!(off!(this; b), Struct::OFFSET_B);
+        assert_eq!(Struct::OFFSET_B.get_copy(&this), 5);
+
+        #[cfg(feature = "derive")]
+        {
+            assert_eq!(Struct::OFFSET_WITH_DERIVE.offset(), 8);
+            assert_eq!(off!(this; with_derive), Struct::OFFSET_WITH_DERIVE);
+            assert_eq!(Struct::OFFSET_WITH_DERIVE.get_copy(&this), 8);
+            assert_eq!(Struct::OFFSET_LAST.offset(), 16);
+        }
+        #[cfg(not(feature = "derive"))]
+        {
+            assert_eq!(Struct::OFFSET_WITHOUT_DERIVE.offset(), 8);
+            assert_eq!(off!(this; without_derive), Struct::OFFSET_WITHOUT_DERIVE);
+            assert_eq!(Struct::OFFSET_WITHOUT_DERIVE.get_copy(&this), 8);
+            assert_eq!(Struct::OFFSET_LAST.offset(), 10);
+        }
+
+        assert_eq!(off!(this; z), Struct::OFFSET_LAST);
+        assert_eq!(Struct::OFFSET_LAST.get_copy(&this), 13);
+        assert_eq!(
+            Struct::OFFSET_LAST.offset(),
+            addr_offset(&this, &this.z as *const u8)
+        );
+
+        #[cfg(feature = "derive")]
+        {
+            let this = Packed(3, 5, 8);
+            assert_eq!(Packed::OFFSET_1.offset(), 1);
+            assert_eq!(Packed::OFFSET_2.offset(), 5);
+            assert_eq!(off!(this; 1), Packed::OFFSET_1);
+            assert_eq!(off!(this; 2), Packed::OFFSET_2);
+            assert_eq!(Packed::OFFSET_1.get_copy(&this), 5);
+            assert_eq!(Packed::OFFSET_2.get_copy(&this), 8);
+            assert_eq!(Packed::OFFSET_0.get_copy(&this), 3);
+        }
+        #[cfg(not(feature = "derive"))]
+        {
+            let this = Packed(3, 8);
+            assert_eq!(Packed::OFFSET_1.offset(), 1);
+            assert_eq!(off!(this; 1), Packed::OFFSET_1);
+            assert_eq!(Packed::OFFSET_1.get_copy(&this), 8);
+            assert_eq!(Packed::OFFSET_0.get_copy(&this), 3);
+        }
+    }
+}
+
+mod read_from_unaligned {
+    use super::*;
+
+    use std::mem;
+
+    #[repr(C)]
+    #[derive(Debug, PartialEq, ReprOffset)]
+    #[roff(read_from_unaligned)]
+    pub struct Struct {
+        pub a: u8,
+        pub b: u32,
+        pub c: u16,
+        pub d: u64,
+        pub e: (),
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(read_from_unaligned)]
+    pub struct Packed(pub u8, pub u64, pub char);
+
+    fn write_at<T>(buffer: &mut [u8], offset: usize, value: T) {
+        assert!(offset + mem::size_of::<T>() <= buffer.len());
+        unsafe { (buffer.as_mut_ptr().add(offset) as *mut T).write_unaligned(value) }
+    }
+
+    #[test]
+    fn read_from_unaligned_test() {
+        let size = mem::size_of::<Struct>();
+
+        // Reading the struct from every position of the buffer,
+        // so that it's misaligned in most iterations.
+        let mut buffer = vec![0xFFu8; size + 16];
+        for start in 0..16 {
+            let expected = Struct {
+                a: start as u8,
+                b: 0x0102_0304,
+                c: 0x0506,
+                d: 0x0708_090A_0B0C_0D0E,
+                e: (),
+            };
+            write_at(&mut buffer, start + Struct::OFFSET_A.offset(), expected.a);
+            write_at(&mut buffer, start + Struct::OFFSET_B.offset(), expected.b);
+            write_at(&mut buffer, start + Struct::OFFSET_C.offset(), expected.c);
+            write_at(&mut buffer, start + Struct::OFFSET_D.offset(), expected.d);
+
+            let read = unsafe { Struct::read_from_unaligned(buffer[start..].as_ptr()) };
+            assert_eq!(read, expected);
+        }
+
+        let mut buffer = [0u8; 1 + mem::size_of::<Packed>()];
+        write_at(&mut buffer, 1, 3u8);
+        write_at(&mut buffer, 2, u64::max_value());
+        write_at(&mut buffer, 10, 'ñ');
+
+        let read = unsafe { Packed::read_from_unaligned(buffer[1..].as_ptr()) };
+        assert_eq!(Packed::OFFSET_0.get_copy(&read), 3);
+        assert_eq!(Packed::OFFSET_1.get_copy(&read), u64::max_value());
+        assert_eq!(Packed::OFFSET_2.get_copy(&read), 'ñ');
+    }
+}
+
+mod write_defaults {
+    use super::*;
+
+    use std::{marker::PhantomData, mem::MaybeUninit};
+
+    pub struct NotDefault;
+
+    #[repr(C)]
+    #[derive(Debug, PartialEq, ReprOffset)]
+    #[roff(write_defaults)]
+    pub struct Struct {
+        pub a: u8,
+        pub b: String,
+        pub c: Option<u16>,
+        pub d: Vec<u64>,
+        pub e: (),
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(write_defaults)]
+    pub struct Packed(pub u8, pub u64, pub char);
+
+    // `PhantomData<T>` is `Default` for all `T`,
+    // so this can be initialized even though `NotDefault` isn't `Default`.
+    #[repr(C)]
+    #[derive(ReprOffset)]
+    #[roff(write_defaults)]
+    pub struct Generic<T> {
+        pub a: u32,
+        pub b: PhantomData<T>,
+        pub c: Option<Box<T>>,
+    }
+
+    #[test]
+    fn write_defaults_test() {
+        let mut this = MaybeUninit::<Struct>::uninit();
+        let this = unsafe {
+            Struct::write_defaults(this.as_mut_ptr());
+            this.assume_init()
+        };
+        assert_eq!(
+            this,
+            Struct {
+                a: 0,
+                b: String::new(),
+                c: None,
+                d: Vec::new(),
+                e: (),
+            }
+        );
+
+        let mut packed = MaybeUninit::<Packed>::uninit();
+        let packed = unsafe {
+            Packed::write_defaults(packed.as_mut_ptr());
+            packed.assume_init()
+        };
+        assert_eq!(Packed::OFFSET_0.get_copy(&packed), 0);
+        assert_eq!(Packed::OFFSET_1.get_copy(&packed), 0);
+        assert_eq!(Packed::OFFSET_2.get_copy(&packed), '\0');
+
+        let mut generic = MaybeUninit::<Generic<NotDefault>>::uninit();
+        let generic = unsafe {
+            Generic::write_defaults(generic.as_mut_ptr());
+            generic.assume_init()
+        };
+        assert_eq!(generic.a, 0);
+        assert!(generic.c.is_none());
+    }
+
+    #[test]
+    fn write_defaults_overwrites_fields() {
+        let mut this = Struct {
+            a: 3,
+            b: String::from("hello"),
+            c: Some(5),
+            d: vec![8, 13],
+            e: (),
+        };
+
+        // The previous values have to be dropped manually,
+        // since `write_defaults` doesn't drop them.
+        let old_b = unsafe { Struct::OFFSET_B.read(&this) };
+        let old_d = unsafe { Struct::OFFSET_D.read(&this) };
+        unsafe { Struct::write_defaults(&mut this) };
+
+        assert_eq!(old_b, "hello");
+        assert_eq!(old_d, [8, 13]);
+        assert_eq!(this.a, 0);
+        assert_eq!(this.b, "");
+        assert_eq!(this.c, None);
+        assert!(this.d.is_empty());
+    }
+}
+
+mod try_from_bytes {
+    use super::*;
+
+    use repr_offset::utils::{FromBytesError, ValidBitPattern};
+
+    use std::{convert::TryFrom, mem};
+
+    #[repr(C)]
+    #[derive(Debug, PartialEq, ReprOffset)]
+    #[roff(try_from_bytes)]
+    pub struct Struct {
+        pub a: u8,
+        pub b: u32,
+        pub c: bool,
+        pub d: u64,
+        pub e: (),
+    }
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(try_from_bytes)]
+    pub struct Packed(pub u8, pub u64, pub char);
+
+    #[repr(C)]
+    #[derive(Debug, PartialEq, ReprOffset)]
+    #[roff(try_from_bytes)]
+    pub struct Nested<T> {
+        pub kind: u16,
+        pub inner: T,
+    }
+
+    fn write_at<T>(buffer: &mut [u8], offset: usize, value: T) {
+        assert!(offset + mem::size_of::<T>() <= buffer.len());
+        unsafe { (buffer.as_mut_ptr().add(offset) as *mut T).write_unaligned(value) }
+    }
+
+    fn struct_bytes(start: usize, buffer: &mut [u8], value: &Struct) {
+        write_at(buffer, start + Struct::OFFSET_A.offset(), value.a);
+        write_at(buffer, start + Struct::OFFSET_B.offset(), value.b);
+        write_at(buffer, start + Struct::OFFSET_C.offset(), value.c);
+        write_at(buffer, start + Struct::OFFSET_D.offset(), value.d);
+    }
+
+    #[test]
+    fn valid_bytes() {
+        let size = mem::size_of::<Struct>();
+
+        // Parsing the struct from every position of the buffer,
+        // so that it's misaligned in most iterations.
+        let mut buffer = vec![0xFFu8; size + 16];
+        for start in 0..16 {
+            let expected = Struct {
+                a: start as u8,
+                b: 0x0102_0304,
+                c: start % 2 == 0,
+                d: 0x0708_090A_0B0C_0D0E,
+                e: (),
+            };
+            struct_bytes(start, &mut buffer, &expected);
+
+            assert_eq!(Struct::try_from(&buffer[start..start + size]), Ok(expected));
+        }
+
+        let mut buffer = [0u8; 1 + mem::size_of::<Packed>()];
+        write_at(&mut buffer, 1, 3u8);
+        write_at(&mut buffer, 2, u64::max_value());
+        write_at(&mut buffer, 10, 'ñ');
+
+        let read = Packed::try_from(&buffer[1..]).unwrap();
+        assert_eq!(Packed::OFFSET_0.get_copy(&read), 3);
+        assert_eq!(Packed::OFFSET_1.get_copy(&read), u64::max_value());
+        assert_eq!(Packed::OFFSET_2.get_copy(&read), 'ñ');
+    }
+
+    #[test]
+    fn too_short_bytes() {
+        let size = mem::size_of::<Struct>();
+        let buffer = vec![0u8; size];
+
+        for len in 0..size {
+            assert_eq!(
+                Struct::try_from(&buffer[..len]),
+                Err(FromBytesError::TooShort {
+                    expected: size,
+                    found: len
+                }),
+            );
+        }
+        assert!(Struct::try_from(&buffer[..]).is_ok());
+
+        assert_eq!(
+            Packed::try_from(&[0u8; 12][..]).err(),
+            Some(FromBytesError::TooShort {
+                expected: 13,
+                found: 12
+            }),
+        );
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        let mut buffer = vec![0u8; mem::size_of::<Struct>()];
+        struct_bytes(
+            0,
+            &mut buffer,
+            &Struct {
+                a: 3,
+                b: 5,
+                c: true,
+                d: 8,
+                e: (),
+            },
+        );
+        assert!(Struct::is_valid_bit_pattern(&buffer));
+
+        buffer[Struct::OFFSET_C.offset()] = 2;
+        assert!(!Struct::is_valid_bit_pattern(&buffer));
+        assert_eq!(
+            Struct::try_from(&buffer[..]),
+            Err(FromBytesError::InvalidBitPattern)
+        );
+
+        let mut buffer = [0u8; 13];
+        write_at(&mut buffer, 9, 0xD800u32);
+        assert_eq!(
+            Packed::try_from(&buffer[..]).err(),
+            Some(FromBytesError::InvalidBitPattern)
+        );
+    }
+
+    #[test]
+    fn nested_structs() {
+        type This = Nested<Nested<bool>>;
+
+        let mut buffer = [0u8; mem::size_of::<This>()];
+        write_at(&mut buffer, 0, 3u16);
+        write_at(&mut buffer, 2, 5u16);
+        write_at(&mut buffer, 4, true);
+
+        assert_eq!(
+            This::try_from(&buffer[..]),
+            Ok(Nested {
+                kind: 3,
+                inner: Nested {
+                    kind: 5,
+                    inner: true
+                },
+            }),
+        );
+
+        buffer[4] = 3;
+        assert_eq!(
+            This::try_from(&buffer[..]),
+            Err(FromBytesError::InvalidBitPattern)
+        );
+    }
+}
+
+mod delegate_fields {
+    use super::*;
+
+    use repr_offset::{pub_off, ROExtAcc, ROExtOps};
+
+    pub type Inner = ReprC<u8, ReprC<u16, u32, (), ()>, u64, ()>;
+
+    use repr_offset::for_examples::ReprC;
+
+    #[repr(transparent)]
+    #[derive(ReprOffset)]
+    #[roff(delegate_fields)]
+    pub struct Wrapper(pub Inner);
+
+    #[repr(transparent)]
+    #[derive(ReprOffset)]
+    #[roff(delegate_fields)]
+    pub struct Generic<T> {
+        pub inner: T,
+    }
+
+    #[repr(transparent)]
+    #[derive(ReprOffset)]
+    #[roff(delegate_fields)]
+    pub struct Private(Inner);
+
+    #[repr(C, packed)]
+    #[derive(ReprOffset)]
+    #[roff(delegate_fields)]
+    pub struct Packed(pub Inner);
+
+    fn make_inner() -> Inner {
+        ReprC {
+            a: 3,
+            b: ReprC {
+                a: 5,
+                b: 8,
+                c: (),
+                d: (),
+            },
+            c: 13,
+            d: (),
+        }
+    }
+
+    #[test]
+    fn delegate_fields_test() {
+        let wrapper = Wrapper(make_inner());
+        let _: FieldOffset<Wrapper, Inner, Aligned> = Wrapper::OFFSET_0;
+
+        let _: FieldOffset<Wrapper, u8, Aligned> = PUB_OFF!(Wrapper; a);
+        let _: FieldOffset<Wrapper, u32, Aligned> = PUB_OFF!(Wrapper; b.b);
+
+        assert_eq!(wrapper.f_get(pub_off!(a)), &3);
+        assert_eq!(wrapper.f_get(pub_off!(b.a)), &5);
+        assert_eq!(wrapper.f_get(pub_off!(b.b)), &8);
+        assert_eq!(wrapper.f_get(pub_off!(c)), &13);
+        assert_eq!(
+            PUB_OFF!(Wrapper; b.b).offset(),
+            PUB_OFF!(Inner; b.b).offset()
+        );
+        assert_eq!(PUB_OFF!(Wrapper; c).offset(), Inner::OFFSET_C.offset());
+
+        let generic = Generic {
+            inner: make_inner(),
+        };
+        let _: FieldOffset<Generic<Inner>, Inner, Aligned> = Generic::OFFSET_INNER;
+        assert_eq!(generic.f_get(pub_off!(b.b)), &8);
+        assert_eq!(generic.f_get(PUB_OFF!(Generic<Inner>; c)), &13);
+
+        let generic = Generic {
+            inner: Wrapper(make_inner()),
+        };
+        assert_eq!(generic.f_get(pub_off!(b.a)), &5);
+
+        let private = Private(make_inner());
+        let _: FieldOffset<Private, Inner, Aligned> = Private::OFFSET_0;
+        let _: FOWithVis<Private, repr_offset::privacy::IsPrivate, TS!(a), u8, Aligned> =
+            <Private as GetFieldOffset<TS!(a)>>::OFFSET_WITH_VIS;
+        let private_b_b = unsafe {
+            <Private as GetFieldOffset<TS!(b, b)>>::OFFSET_WITH_VIS.private_field_offset()
+        };
+        assert_eq!(private.f_get(private_b_b), &8);
+
+        let packed = Packed(make_inner());
+        let _: FieldOffset<Packed, Inner, Unaligned> = Packed::OFFSET_0;
+        let _: FieldOffset<Packed, u32, Unaligned> = PUB_OFF!(Packed; b.b);
+        assert_eq!(packed.f_get_copy(pub_off!(a)), 3);
+        assert_eq!(packed.f_get_copy(pub_off!(b.b)), 8);
+        assert_eq!(packed.f_get_copy(pub_off!(c)), 13);
+    }
+}
@@ -180,3 +180,137 @@ fn test_all_ext_ops_traits() {
         )
     });
 }
+
+#[test]
+fn write_through_maybe_uninit_ptr() {
+    use std::mem::MaybeUninit;
+
+    {
+        type This = ReprC<u8, String, u64, Vec<u8>>;
+
+        let mut this = MaybeUninit::<This>::uninit();
+        let ptr: *mut MaybeUninit<This> = &mut this;
+
+        let this = unsafe {
+            ptr.f_write(This::OFFSET_A.in_maybe_uninit(), 3);
+            ptr.f_write(This::OFFSET_B.in_maybe_uninit(), "5".to_string());
+            ptr.f_write(This::OFFSET_C.in_maybe_uninit(), 8);
+            ptr.f_write(This::OFFSET_D.in_maybe_uninit(), vec![13, 21]);
+
+            let ptr: *const MaybeUninit<This> = ptr;
+            assert_eq!(ptr.f_read_copy(This::OFFSET_A.in_maybe_uninit()), 3);
+            assert_eq!(ptr.f_read_copy(This::OFFSET_C.in_maybe_uninit()), 8);
+
+            this.assume_init()
+        };
+        assert_eq!(this.a, 3);
+        assert_eq!(this.b, "5");
+        assert_eq!(this.c, 8);
+        assert_eq!(this.d, [13, 21]);
+    }
+    {
+        type This = ReprPacked<u8, u64, u16, String>;
+
+        let mut this = MaybeUninit::<This>::uninit();
+        let ptr: *mut MaybeUninit<This> = &mut this;
+
+        let mut this = unsafe {
+            ptr.f_write(This::OFFSET_A.in_maybe_uninit(), 3);
+            ptr.f_write(This::OFFSET_B.in_maybe_uninit(), 5);
+            ptr.f_write(This::OFFSET_C.in_maybe_uninit(), 8);
+            ptr.f_write(This::OFFSET_D.in_maybe_uninit(), "13".to_string());
+            this.assume_init()
+        };
+        assert_eq!(This::OFFSET_A.get_copy(&this), 3);
+        assert_eq!(This::OFFSET_B.get_copy(&this), 5);
+        assert_eq!(This::OFFSET_C.get_copy(&this), 8);
+        assert_eq!(This::OFFSET_D.replace_mut(&mut this, String::new()), "13");
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn vec_field_push_pop() {
+    use repr_offset::off;
+
+    let mut aligned = ReprC {
+        a: 3u8,
+        b: vec![5u16],
+        c: Vec::<String>::new(),
+        d: (),
+    };
+    aligned.f_vec_push(off!(b), 8);
+    aligned.f_vec_push(off!(b), 13);
+    aligned.f_vec_push(off!(c), "foo".to_string());
+    assert_eq!(aligned.b, vec![5, 8, 13]);
+    assert_eq!(aligned.c, vec!["foo".to_string()]);
+
+    assert_eq!(aligned.f_vec_pop(off!(b)), Some(13));
+    assert_eq!(aligned.f_vec_pop(off!(c)), Some("foo".to_string()));
+    assert_eq!(aligned.f_vec_pop(off!(c)), None);
+    assert_eq!(aligned.b, vec![5, 8]);
+
+    let mut packed = ReprPacked {
+        a: 3u8,
+        b: vec![5u16],
+        c: Vec::<String>::new(),
+        d: (),
+    };
+    for i in 0..100 {
+        packed.f_vec_push(off!(b), i);
+    }
+    packed.f_vec_push(off!(c), "bar".to_string());
+    assert_eq!(packed.f_get_copy(off!(a)), 3);
+
+    assert_eq!(packed.f_vec_pop(off!(b)), Some(99));
+    assert_eq!(packed.f_vec_pop(off!(b)), Some(98));
+    assert_eq!(packed.f_vec_pop(off!(c)), Some("bar".to_string()));
+    assert_eq!(packed.f_vec_pop(off!(c)), None);
+    assert_eq!(
+        packed.f_replace(off!(b), Vec::new()),
+        (5..6).chain(0..98).collect::<Vec<u16>>()
+    );
+}
+
+#[test]
+fn get_mut_and_read() {
+    use repr_offset::off;
+
+    let mut this = ReprC {
+        a: 3u64,
+        b: 0u8,
+        c: 5u32,
+        d: (),
+    };
+    for value in [8, 13].iter().copied() {
+        let (a, c) = this.f_get_mut_and_read(off!(a), off!(c));
+        *a += u64::from(c);
+        this.c = value;
+    }
+    assert_eq!(this.a, 16);
+    assert_eq!(this.c, 13);
+
+    let (d, a) = this.f_get_mut_and_read(off!(d), off!(a));
+    assert_eq!(*d, ());
+    assert_eq!(a, 16);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic]
+fn get_mut_and_read_overlapping() {
+    use repr_offset::off;
+
+    let mut this = ReprC {
+        a: ReprC {
+            a: 3u8,
+            b: 5u8,
+            c: (),
+            d: (),
+        },
+        b: 0u8,
+        c: 0u8,
+        d: (),
+    };
+    let _ = this.f_get_mut_and_read(off!(a), off!(a.b));
+}
@@ -111,6 +111,81 @@ fn add_method() {
     }
 }
 
+#[test]
+fn is_first_method() {
+    assert!(Consts::OFFSET_A.is_first());
+    assert!(!Consts::OFFSET_B.is_first());
+    assert!(!Consts::OFFSET_C.is_first());
+    assert!(!Consts::OFFSET_D.is_first());
+
+    type Packed = StructPacked<(), (u8, u64, (), ()), (), ()>;
+    assert!(Packed::OFFSET_A.is_first());
+    assert!(!Packed::OFFSET_B.is_first());
+    assert!(!Packed::OFFSET_C.is_first());
+
+    type To = Transparent<StructReprC<u32, u32, u32, u32>>;
+    let in_wrapper = unsafe { Consts::OFFSET_A.cast_struct::<To>() };
+    assert!(in_wrapper.is_first());
+}
+
+#[test]
+fn offset_nonzero_method() {
+    use std::{mem::size_of, num::NonZeroUsize};
+
+    assert_eq!(Consts::OFFSET_A.offset_nonzero(), None);
+    assert_eq!(Consts::OFFSET_B.offset_nonzero(), NonZeroUsize::new(4));
+    assert_eq!(Consts::OFFSET_C.offset_nonzero(), NonZeroUsize::new(8));
+    assert_eq!(Consts::OFFSET_D.offset_nonzero(), NonZeroUsize::new(12));
+
+    type Packed = StructPacked<(), (u8, u64, (), ()), (), ()>;
+    assert_eq!(Packed::OFFSET_A.offset_nonzero(), None);
+    assert_eq!(Packed::OFFSET_B.offset_nonzero(), NonZeroUsize::new(1));
+    assert_eq!(Packed::OFFSET_C.offset_nonzero(), NonZeroUsize::new(9));
+    assert_eq!(Packed::OFFSET_D.offset_nonzero(), NonZeroUsize::new(9));
+
+    for offset in [Consts::OFFSET_B, Consts::OFFSET_C, Consts::OFFSET_D].iter() {
+        assert_eq!(
+            offset.offset_nonzero().map(NonZeroUsize::get),
+            Some(offset.offset())
+        );
+    }
+
+    assert_eq!(size_of::<Option<NonZeroUsize>>(), size_of::<usize>());
+}
+
+#[test]
+fn is_aligned_offset_method() {
+    use repr_offset::alignment::Alignment;
+
+    fn generic<S, F, A: Alignment>(offset: FieldOffset<S, F, A>) -> bool {
+        assert_eq!(offset.is_aligned_offset(), A::IS_ALIGNED);
+        offset.is_aligned_offset()
+    }
+
+    type This = StructReprC<(), (u8, u32, u16, u64), (), ()>;
+    type Packed = StructPacked<(), (u8, u32, u16, u64), (), ()>;
+
+    const ALIGNMENTS: [bool; 2] = [
+        <Aligned as Alignment>::IS_ALIGNED,
+        <Unaligned as Alignment>::IS_ALIGNED,
+    ];
+    assert_eq!(ALIGNMENTS, [true, false]);
+
+    assert!(This::OFFSET_A.is_aligned_offset());
+    assert!(This::OFFSET_D.is_aligned_offset());
+    assert!(generic(This::OFFSET_B));
+    assert!(generic(This::OFFSET_C));
+
+    assert!(!Packed::OFFSET_B.is_aligned_offset());
+    assert!(!Packed::OFFSET_D.is_aligned_offset());
+    assert!(!generic(Packed::OFFSET_B));
+    assert!(!generic(Packed::OFFSET_C));
+
+    // The alignment comes from the type parameter, not from the offset.
+    assert!(!generic(This::OFFSET_B.to_unaligned()));
+    assert!(generic(unsafe { Packed::OFFSET_B.to_aligned() }));
+}
+
 #[test]
 fn cast_struct_method() {
     let this = Transparent(StructReprC {
@@ -130,6 +205,48 @@ fn cast_struct_method() {
     }
 }
 
+#[test]
+fn cast_struct_generic_container() {
+    use std::mem::ManuallyDrop;
+
+    // Changes `S` while keeping `F` and `A`,
+    // the return type would fail to type check otherwise.
+    fn in_manually_drop<S, F, A>(
+        offset: FieldOffset<S, F, A>,
+    ) -> FieldOffset<ManuallyDrop<S>, F, A> {
+        unsafe { offset.cast_struct() }
+    }
+
+    let this = ManuallyDrop::new(StructReprC {
+        a: 3u8,
+        b: 5u64,
+        c: "8",
+        d: [13u16; 2],
+    });
+    type ThisC = StructReprC<(), (u8, u64, &'static str, [u16; 2]), (), ()>;
+
+    let off_a: FieldOffset<_, u8, Aligned> = in_manually_drop(ThisC::OFFSET_A);
+    let off_d: FieldOffset<_, [u16; 2], Aligned> = in_manually_drop(ThisC::OFFSET_D);
+    assert_eq!(off_a.get(&this), &3);
+    assert_eq!(in_manually_drop(ThisC::OFFSET_B).get(&this), &5);
+    assert_eq!(in_manually_drop(ThisC::OFFSET_C).get(&this), &"8");
+    assert_eq!(off_d.get(&this), &[13, 13]);
+    assert_eq!(off_d.offset(), ThisC::OFFSET_D.offset());
+
+    let packed = ManuallyDrop::new(StructPacked {
+        a: 3u8,
+        b: 5u64,
+        c: 8u32,
+        d: 13u16,
+    });
+    type ThisPacked = StructPacked<(), (u8, u64, u32, u16), (), ()>;
+
+    let off_b: FieldOffset<_, u64, Unaligned> = in_manually_drop(ThisPacked::OFFSET_B);
+    assert_eq!(off_b.get_copy(&packed), 5);
+    assert_eq!(in_manually_drop(ThisPacked::OFFSET_C).get_copy(&packed), 8);
+    assert_eq!(off_b.offset(), 1);
+}
+
 #[test]
 fn cast_field_method() {
     let this = StructReprC {
@@ -183,3 +300,2256 @@ fn cast_alignment() {
         assert_eq!(packed_d.to_aligned(), Consts::OFFSET_D);
     }
 }
+
+#[cfg(all(feature = "std", feature = "min_const_generics"))]
+#[test]
+fn get_cstr_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    let this = ReprC {
+        a: 3u8,
+        b: *b"hello\0\0\0",
+        c: *b"ab\0c",
+        d: [0u8; 0],
+    };
+    assert_eq!(
+        ReprC::OFFSET_B.get_cstr(&this).unwrap().to_bytes(),
+        b"hello"
+    );
+    assert_eq!(ReprC::OFFSET_C.get_cstr(&this).unwrap().to_bytes(), b"ab");
+    assert!(ReprC::OFFSET_D.get_cstr(&this).is_err());
+
+    let this = ReprC {
+        a: 3u8,
+        b: *b"\0nothing",
+        c: *b"full",
+        d: [0u8; 0],
+    };
+    assert_eq!(ReprC::OFFSET_B.get_cstr(&this).unwrap().to_bytes(), b"");
+    assert!(ReprC::OFFSET_C.get_cstr(&this).is_err());
+
+    let this = ReprPacked {
+        a: 3u8,
+        b: *b"packed\0",
+        c: *b"unterminated",
+        d: (),
+    };
+    assert_eq!(
+        ReprPacked::OFFSET_B.get_cstr(&this).unwrap().to_bytes(),
+        b"packed"
+    );
+    assert!(ReprPacked::OFFSET_C.get_cstr(&this).is_err());
+}
+
+#[test]
+fn read_zst_fields_from_dangling() {
+    use repr_offset::{
+        for_examples::{ReprC, ReprPacked},
+        ROExtRawOps,
+    };
+
+    use std::{marker::PhantomData, ptr::NonNull};
+
+    {
+        type This = ReprC<u8, (), u32, PhantomData<String>>;
+        let ptr = NonNull::<This>::dangling().as_ptr() as *const This;
+        unsafe {
+            assert_eq!(This::OFFSET_B.read_copy(ptr), ());
+            assert_eq!(This::OFFSET_B.read(ptr), ());
+            assert_eq!(This::OFFSET_D.read_copy(ptr), PhantomData);
+            assert_eq!(This::OFFSET_D.read(ptr), PhantomData);
+            assert_eq!(ptr.f_read_copy(This::OFFSET_B), ());
+            assert_eq!(ptr.f_read(This::OFFSET_D), PhantomData);
+        }
+    }
+    {
+        type This = ReprPacked<u8, [u64; 0], u32, ()>;
+        let ptr = NonNull::<This>::dangling().as_ptr() as *const This;
+        unsafe {
+            assert_eq!(This::OFFSET_B.read_copy(ptr), []);
+            assert_eq!(This::OFFSET_B.read(ptr), []);
+            assert_eq!(This::OFFSET_D.read_copy(ptr), ());
+            assert_eq!(This::OFFSET_D.read(ptr), ());
+            assert_eq!(ptr.f_read_copy(This::OFFSET_B), []);
+            assert_eq!(ptr.f_read(This::OFFSET_D), ());
+        }
+    }
+    {
+        // A struct where all the fields are zero-sized
+        type This = ReprC<(), [u16; 0], PhantomData<String>, ()>;
+        let ptr = NonNull::<This>::dangling().as_ptr() as *const This;
+        unsafe {
+            assert_eq!(This::OFFSET_A.read_copy(ptr), ());
+            assert_eq!(This::OFFSET_B.read(ptr), []);
+            assert_eq!(This::OFFSET_C.read(ptr), PhantomData);
+
+            let this = &*ptr;
+            assert_eq!(This::OFFSET_A.get_copy(this), ());
+            assert_eq!(This::OFFSET_B.get_copy(this), []);
+            assert_eq!(This::OFFSET_C.get_copy(this), PhantomData);
+            assert_eq!(This::OFFSET_D.get_copy(this), ());
+        }
+    }
+}
+
+// This test is mostly for Miri with `-Zmiri-strict-provenance`,
+// checking that field pointers are derived from the base pointer.
+#[test]
+fn nested_raw_pointer_access() {
+    use repr_offset::{
+        for_examples::{ReprC, ReprPacked},
+        ROExtRawMutOps, ROExtRawOps,
+    };
+
+    type Inner = ReprC<u8, u32, u16, u64>;
+    type Middle = ReprPacked<u8, Inner, u16, ()>;
+    type Outer = ReprC<u16, Middle, u8, ()>;
+
+    let mut this: Outer = ReprC {
+        a: 1,
+        b: ReprPacked {
+            a: 2,
+            b: ReprC {
+                a: 3,
+                b: 5,
+                c: 8,
+                d: 13,
+            },
+            c: 21,
+            d: (),
+        },
+        c: 34,
+        d: (),
+    };
+
+    let to_inner = Outer::OFFSET_B + Middle::OFFSET_B;
+    let ptr: *mut Outer = &mut this;
+    unsafe {
+        assert_eq!((to_inner + Inner::OFFSET_A).read_copy(ptr), 3);
+        assert_eq!((to_inner + Inner::OFFSET_B).read_copy(ptr), 5);
+        assert_eq!(ptr.f_read_copy(to_inner + Inner::OFFSET_C), 8);
+        assert_eq!(ptr.f_read_copy(to_inner + Inner::OFFSET_D), 13);
+
+        let inner_ptr = to_inner.raw_get_mut(ptr);
+        Inner::OFFSET_B.to_unaligned().write(inner_ptr, 55);
+        inner_ptr.f_write(Inner::OFFSET_D.to_unaligned(), 89);
+
+        let wrapping_ptr = (to_inner + Inner::OFFSET_C).wrapping_raw_get_mut(ptr);
+        wrapping_ptr.write_unaligned(144);
+
+        let middle_c = (Outer::OFFSET_B + Middle::OFFSET_C).raw_get(ptr);
+        assert_eq!(middle_c.read_unaligned(), 21);
+    }
+
+    assert_eq!((to_inner + Inner::OFFSET_B).get_copy(&this), 55);
+    assert_eq!((to_inner + Inner::OFFSET_C).get_copy(&this), 144);
+    assert_eq!((to_inner + Inner::OFFSET_D).get_copy(&this), 89);
+}
+
+#[cfg(feature = "min_const_generics")]
+#[test]
+fn as_byte_field_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    let mut this = ReprC {
+        a: 3u8,
+        b: 0xAABB_CCDDu32,
+        c: [5u16; 3],
+        d: (),
+    };
+    unsafe {
+        let b_bytes = ReprC::OFFSET_B.as_byte_field::<4>();
+        let c_bytes = ReprC::OFFSET_C.as_byte_field::<6>();
+        let d_bytes = ReprC::OFFSET_D.as_byte_field::<0>();
+
+        assert_eq!(b_bytes.offset(), 4);
+        assert_eq!(b_bytes.get(&this), &0xAABB_CCDDu32.to_ne_bytes());
+        assert_eq!(u32::from_ne_bytes(b_bytes.get_copy(&this)), 0xAABB_CCDD);
+        assert_eq!(d_bytes.get(&this), &[]);
+
+        b_bytes.get_mut(&mut this).reverse();
+        assert_eq!(this.b, 0xDDCC_BBAA);
+
+        *c_bytes.get_mut(&mut this) = [1, 2, 3, 4, 5, 6];
+        assert_eq!(
+            this.c,
+            [
+                u16::from_ne_bytes([1, 2]),
+                u16::from_ne_bytes([3, 4]),
+                u16::from_ne_bytes([5, 6]),
+            ]
+        );
+    }
+
+    let mut this = ReprPacked {
+        a: 3u8,
+        b: 0x1122_3344_5566_7788u64,
+        c: (),
+        d: (),
+    };
+    unsafe {
+        let b_bytes = ReprPacked::OFFSET_B.as_byte_field::<8>();
+        assert_eq!(b_bytes.offset(), 1);
+        assert_eq!(
+            b_bytes.get_copy(&this),
+            0x1122_3344_5566_7788u64.to_ne_bytes()
+        );
+
+        b_bytes.replace_mut(&mut this, 7u64.to_ne_bytes());
+        assert_eq!(ReprPacked::OFFSET_B.get_copy(&this), 7);
+    }
+}
+
+#[test]
+fn variant_field_offset() {
+    use repr_offset::VariantFieldOffset;
+
+    use std::mem;
+
+    #[derive(Debug, PartialEq)]
+    #[repr(C)]
+    enum Enum {
+        Empty,
+        Pair(u8, u32),
+        Single(u16),
+    }
+
+    let pair_tag = mem::discriminant(&Enum::Pair(0, 0));
+    let single_tag = mem::discriminant(&Enum::Single(0));
+
+    // `#[repr(C)]` enums are equivalent to a `#[repr(C)]` struct with the `c_int` tag,
+    // followed by a `#[repr(C)]` union of the fields of every variant.
+    let payload = mem::size_of::<std::os::raw::c_int>();
+    let pair_1: VariantFieldOffset<Enum, u32> =
+        unsafe { VariantFieldOffset::new(payload + 4, pair_tag) };
+    let single_0: VariantFieldOffset<Enum, u16> =
+        unsafe { VariantFieldOffset::new(payload, single_tag) };
+
+    assert_eq!(pair_1.offset(), payload + 4);
+    assert_eq!(pair_1.variant(), pair_tag);
+    assert_eq!(single_0.offset(), payload);
+    assert_eq!(single_0.variant(), single_tag);
+
+    let mut pair = Enum::Pair(3, 5);
+    let mut single = Enum::Single(8);
+    let mut empty = Enum::Empty;
+
+    assert_eq!(pair_1.project(&pair), Some(&5));
+    assert_eq!(single_0.project(&pair), None);
+    assert_eq!(pair_1.project(&single), None);
+    assert_eq!(single_0.project(&single), Some(&8));
+    assert_eq!(pair_1.project(&empty), None);
+    assert_eq!(single_0.project(&empty), None);
+
+    *pair_1.project_mut(&mut pair).unwrap() += 8;
+    *single_0.project_mut(&mut single).unwrap() += 13;
+    assert_eq!(pair, Enum::Pair(3, 13));
+    assert_eq!(single, Enum::Single(21));
+
+    assert_eq!(single_0.project_mut(&mut pair), None);
+    assert_eq!(pair_1.project_mut(&mut single), None);
+    assert_eq!(pair_1.project_mut(&mut empty), None);
+    assert_eq!(pair, Enum::Pair(3, 13));
+    assert_eq!(single, Enum::Single(21));
+}
+
+#[test]
+fn checked_add_bytes_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    type This = ReprC<u16, [u16; 3], u8, ()>;
+    type Packed = ReprPacked<u8, [u16; 3], (), ()>;
+
+    const ELEM_1: Option<FieldOffset<This, u16, Aligned>> =
+        unsafe { This::OFFSET_B.cast_field::<u16>().checked_add_bytes(2) };
+
+    unsafe {
+        assert_eq!(ELEM_1.map(|x| x.offset()), Some(4));
+
+        let this: This = ReprC {
+            a: 3,
+            b: [5, 8, 13],
+            c: 21,
+            d: (),
+        };
+        assert_eq!(ELEM_1.unwrap().get(&this), &8);
+
+        let elem_2 = Packed::OFFSET_B
+            .cast_field::<u16>()
+            .checked_add_bytes(4)
+            .unwrap();
+        assert_eq!(elem_2.offset(), 5);
+
+        let packed: Packed = ReprPacked {
+            a: 3,
+            b: [5, 8, 13],
+            c: (),
+            d: (),
+        };
+        assert_eq!(elem_2.get_copy(&packed), 13);
+
+        assert_eq!(This::OFFSET_A.checked_add_bytes(0), Some(This::OFFSET_A));
+
+        let near_max: FieldOffset<This, u8, Unaligned> = FieldOffset::new(usize::max_value() - 1);
+        assert_eq!(
+            near_max.checked_add_bytes(1).map(|x| x.offset()),
+            Some(usize::max_value())
+        );
+        assert_eq!(near_max.checked_add_bytes(2), None);
+        assert_eq!(near_max.checked_add_bytes(usize::max_value()), None);
+        assert_eq!(This::OFFSET_B.checked_add_bytes(usize::max_value()), None);
+    }
+}
+
+// Matching on an `Option` and panicking in constants requires Rust 1.57.0
+#[cfg(feature = "const_fns")]
+#[test]
+fn checked_add_bytes_const_match() {
+    use repr_offset::for_examples::ReprC;
+
+    type This = ReprC<u16, [u16; 3], u8, ()>;
+
+    const ELEM_2: FieldOffset<This, u16, Aligned> =
+        match unsafe { This::OFFSET_B.cast_field::<u16>().checked_add_bytes(4) } {
+            Some(x) => x,
+            None => panic!("the offset overflowed"),
+        };
+
+    assert_eq!(ELEM_2.offset(), 6);
+
+    let this: This = ReprC {
+        a: 3,
+        b: [5, 8, 13],
+        c: 21,
+        d: (),
+    };
+    assert_eq!(ELEM_2.get(&this), &13);
+}
+
+#[test]
+fn addr_range_methods() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    type Aligned = ReprC<u8, u64, [u16; 3], ()>;
+    type Packed = ReprPacked<u8, u64, [u16; 3], ()>;
+
+    let mut aligned: Aligned = ReprC {
+        a: 3,
+        b: 5,
+        c: [8, 13, 21],
+        d: (),
+    };
+    let base = &aligned as *const Aligned as usize;
+
+    let ranges = [
+        Aligned::OFFSET_A.addr_range(&aligned),
+        Aligned::OFFSET_B.addr_range(&aligned),
+        Aligned::OFFSET_C.addr_range(&aligned),
+        Aligned::OFFSET_D.addr_range(&aligned),
+    ];
+    let expected = [(0, 1), (8, 16), (16, 22), (22, 22)];
+    for (range, &(start, end)) in ranges.iter().zip(&expected) {
+        assert_eq!(range.start as usize, base + start);
+        assert_eq!(range.end as usize, base + end);
+    }
+
+    let range_mut = Aligned::OFFSET_C.addr_range_mut(&mut aligned);
+    assert_eq!(range_mut.start as usize, base + 16);
+    assert_eq!(range_mut.end as usize, base + 22);
+
+    let mut packed: Packed = ReprPacked {
+        a: 3,
+        b: 5,
+        c: [8, 13, 21],
+        d: (),
+    };
+    let base = &mut packed as *mut Packed as usize;
+
+    let range_b = Packed::OFFSET_B.addr_range_mut(&mut packed);
+    assert_eq!(range_b.start as usize, base + 1);
+    assert_eq!(range_b.end as usize, base + 9);
+
+    let range_c = Packed::OFFSET_C.addr_range(&packed);
+    assert_eq!(range_c.start as usize, base + 9);
+    assert_eq!(range_c.end as usize, base + 15);
+
+    // The base pointer doesn't need to be valid
+    let range_null = Packed::OFFSET_C.addr_range(std::ptr::null());
+    assert_eq!(range_null.start as usize, 9);
+    assert_eq!(range_null.end as usize, 15);
+}
+
+#[test]
+fn cell_field_methods() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    use std::cell::{Cell, UnsafeCell};
+
+    type This = ReprC<u8, u32, u16, ()>;
+
+    let mut this: This = ReprC {
+        a: 3,
+        b: 5,
+        c: 8,
+        d: (),
+    };
+    unsafe {
+        let off_b = This::OFFSET_B.as_cell_field();
+        let _: FieldOffset<This, Cell<u32>, Aligned> = off_b;
+        assert_eq!(off_b.offset(), This::OFFSET_B.offset());
+
+        let cell: &Cell<u32> = off_b.get_mut(&mut this);
+        let cells = [cell, cell];
+        cells[0].set(13);
+        cells[1].set(cells[1].get() + 8);
+    }
+    assert_eq!(this.b, 21);
+
+    unsafe {
+        let off_c = This::OFFSET_C.as_unsafe_cell_field();
+        let _: FieldOffset<This, UnsafeCell<u16>, Aligned> = off_c;
+        *off_c.get_mut(&mut this).get() = 34;
+    }
+    assert_eq!(this.c, 34);
+
+    // The field is never referenced, it's only copied out of the packed struct
+    type Packed = ReprPacked<u8, u32, (), ()>;
+    let mut packed: Packed = ReprPacked {
+        a: 3,
+        b: 5,
+        c: (),
+        d: (),
+    };
+    unsafe {
+        let off_b = Packed::OFFSET_B.as_cell_field();
+        let _: FieldOffset<Packed, Cell<u32>, Unaligned> = off_b;
+        let cell = off_b.replace_mut(&mut packed, Cell::new(55));
+        assert_eq!(cell.get(), 5);
+        assert_eq!(off_b.read(&packed).get(), 55);
+    }
+    assert_eq!(Packed::OFFSET_B.get_copy(&packed), 55);
+}
+
+#[test]
+fn refcell_borrow_field_methods() {
+    use std::cell::RefCell;
+
+    type This = StructReprC<(), (u8, String, u32, Vec<u16>), (), ()>;
+
+    let cell = RefCell::new(StructReprC {
+        a: 3u8,
+        b: String::from("foo"),
+        c: 8u32,
+        d: vec![13u16],
+    });
+
+    {
+        let a = This::OFFSET_A.borrow_field(&cell);
+        let b = This::OFFSET_B.borrow_field(&cell);
+        assert_eq!(*a, 3);
+        assert_eq!(*b, "foo");
+        assert!(cell.try_borrow_mut().is_err());
+    }
+
+    {
+        let mut b = This::OFFSET_B.borrow_field_mut(&cell);
+        b.push_str("bar");
+        assert!(cell.try_borrow().is_err());
+    }
+    *This::OFFSET_C.borrow_field_mut(&cell) += 5;
+    This::OFFSET_D.borrow_field_mut(&cell).push(21);
+
+    assert_eq!(*This::OFFSET_B.borrow_field(&cell), "foobar");
+    assert_eq!(*This::OFFSET_C.borrow_field(&cell), 13);
+    assert_eq!(*This::OFFSET_D.borrow_field(&cell), [13, 21]);
+
+    let this = cell.into_inner();
+    assert_eq!(this.a, 3);
+    assert_eq!(this.b, "foobar");
+    assert_eq!(this.c, 13);
+    assert_eq!(this.d, [13, 21]);
+}
+
+#[test]
+#[should_panic]
+fn refcell_borrow_field_mut_while_borrowed() {
+    use std::cell::RefCell;
+
+    type This = StructReprC<(), (u8, u16, u32, u64), (), ()>;
+
+    let cell = RefCell::new(StructReprC {
+        a: 3u8,
+        b: 5u16,
+        c: 8u32,
+        d: 13u64,
+    });
+
+    let _a = This::OFFSET_A.borrow_field(&cell);
+    let _b = This::OFFSET_B.borrow_field_mut(&cell);
+}
+
+#[test]
+fn read_int_from_bytes() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    // An IPv4-like header
+    type Header = ReprC<u16, u16, u32, i64>;
+    type PackedHeader = ReprPacked<u8, u32, i16, ()>;
+
+    let mut bytes = vec![0x45, 0x00, 0x00, 0x54];
+    bytes.extend_from_slice(&0xC0A8_0001u32.to_be_bytes());
+    bytes.extend_from_slice(&(-3i64).to_be_bytes());
+
+    assert_eq!(Header::OFFSET_A.read_int_be(&bytes), Some(0x4500));
+    assert_eq!(Header::OFFSET_B.read_int_be(&bytes), Some(0x54));
+    assert_eq!(Header::OFFSET_C.read_int_be(&bytes), Some(0xC0A8_0001));
+    assert_eq!(Header::OFFSET_D.read_int_be(&bytes), Some(-3));
+
+    assert_eq!(Header::OFFSET_A.read_int_le(&bytes), Some(0x45));
+    assert_eq!(Header::OFFSET_C.read_int_le(&bytes), Some(0x0100_A8C0));
+
+    // Too short
+    assert_eq!(Header::OFFSET_C.read_int_be(&bytes[..7]), None);
+    assert_eq!(Header::OFFSET_C.read_int_be(&bytes[..8]), Some(0xC0A8_0001));
+    assert_eq!(Header::OFFSET_D.read_int_be(&bytes[..15]), None);
+    assert_eq!(Header::OFFSET_A.read_int_be(&[]), None);
+
+    let bytes = [7, 0xDE, 0xAD, 0xBE, 0xEF, 0xFF, 0xFE];
+    assert_eq!(PackedHeader::OFFSET_A.read_int_be(&bytes), Some(7));
+    assert_eq!(
+        PackedHeader::OFFSET_B.read_int_be(&bytes),
+        Some(0xDEAD_BEEF)
+    );
+    assert_eq!(
+        PackedHeader::OFFSET_B.read_int_le(&bytes),
+        Some(0xEFBE_ADDE)
+    );
+    assert_eq!(PackedHeader::OFFSET_C.read_int_be(&bytes), Some(-2));
+
+    // An offset that overflows when adding the size of the field
+    let huge: FieldOffset<Header, u32, Unaligned> =
+        unsafe { FieldOffset::new(usize::max_value() - 1) };
+    assert_eq!(huge.read_int_be(&bytes), None);
+}
+
+#[cfg(feature = "min_const_generics")]
+#[test]
+fn in_array_methods() {
+    use repr_offset::for_examples::ReprC;
+
+    let mut array = [
+        ReprC {
+            a: 1u32,
+            b: "foo",
+            c: (),
+            d: 10u8,
+        },
+        ReprC {
+            a: 2,
+            b: "bar",
+            c: (),
+            d: 20,
+        },
+        ReprC {
+            a: 3,
+            b: "baz",
+            c: (),
+            d: 30,
+        },
+        ReprC {
+            a: 4,
+            b: "qux",
+            c: (),
+            d: 40,
+        },
+    ];
+
+    for i in 0..4 {
+        *ReprC::OFFSET_A.get_mut_in_array(&mut array, i) *= 10;
+    }
+    assert_eq!(
+        array.iter().map(|x| x.a).collect::<Vec<_>>(),
+        [10, 20, 30, 40]
+    );
+
+    for i in 0..4 {
+        assert_eq!(ReprC::OFFSET_A.get_in_array(&array, i), &array[i].a);
+        assert_eq!(ReprC::OFFSET_D.get_in_array(&array, i), &array[i].d);
+    }
+    assert_eq!(ReprC::OFFSET_B.get_in_array(&array, 2), &"baz");
+    assert_eq!(ReprC::OFFSET_C.get_in_array(&array, 3), &());
+
+    let out_of_bounds = std::panic::catch_unwind(|| {
+        let _ = ReprC::OFFSET_A.get_in_array(&array, 4);
+    });
+    assert!(out_of_bounds.is_err());
+
+    let mut copy = array;
+    let out_of_bounds = std::panic::catch_unwind(move || {
+        let _ = ReprC::OFFSET_A.get_mut_in_array(&mut copy, usize::max_value());
+    });
+    assert!(out_of_bounds.is_err());
+}
+
+#[test]
+fn compare_field_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    use std::cmp::Ordering;
+
+    let mut list = [
+        ReprC {
+            a: 0u8,
+            b: 30u32,
+            c: "c",
+            d: (),
+        },
+        ReprC {
+            a: 1,
+            b: 10,
+            c: "a",
+            d: (),
+        },
+        ReprC {
+            a: 2,
+            b: 20,
+            c: "b",
+            d: (),
+        },
+        ReprC {
+            a: 3,
+            b: 10,
+            c: "d",
+            d: (),
+        },
+    ];
+
+    assert_eq!(
+        ReprC::OFFSET_B.compare_field(&list[0], &list[1]),
+        Ordering::Greater
+    );
+    assert_eq!(
+        ReprC::OFFSET_B.compare_field(&list[1], &list[3]),
+        Ordering::Equal
+    );
+    assert_eq!(
+        ReprC::OFFSET_D.compare_field(&list[1], &list[3]),
+        Ordering::Equal
+    );
+
+    // Stable sort, so the elements with equal `b` keep their order
+    list.sort_by(|l, r| ReprC::OFFSET_B.compare_field(l, r));
+    assert_eq!(
+        list.iter().map(|x| (x.a, x.b)).collect::<Vec<_>>(),
+        [(1, 10), (3, 10), (2, 20), (0, 30)]
+    );
+
+    list.sort_by(|l, r| ReprC::OFFSET_C.compare_field(r, l));
+    assert_eq!(
+        list.iter().map(|x| x.c).collect::<Vec<_>>(),
+        ["d", "c", "b", "a"]
+    );
+
+    let mut packed = [
+        ReprPacked {
+            a: 0u8,
+            b: 30u64,
+            c: (),
+            d: (),
+        },
+        ReprPacked {
+            a: 1,
+            b: 10,
+            c: (),
+            d: (),
+        },
+        ReprPacked {
+            a: 2,
+            b: 20,
+            c: (),
+            d: (),
+        },
+    ];
+
+    assert_eq!(
+        ReprPacked::OFFSET_B.compare_field(&packed[1], &packed[2]),
+        Ordering::Less
+    );
+
+    packed.sort_by(|l, r| ReprPacked::OFFSET_B.compare_field(l, r));
+    assert_eq!(
+        packed
+            .iter()
+            .map(|x| ReprPacked::OFFSET_A.get_copy(x))
+            .collect::<Vec<_>>(),
+        [1, 2, 0]
+    );
+}
+
+#[test]
+fn setter_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    {
+        type This = ReprC<u8, u64, Rc<()>, &'static str>;
+
+        let mut this: This = ReprC {
+            a: 3,
+            b: 5,
+            c: rc.clone(),
+            d: "foo",
+        };
+
+        let set_a = This::OFFSET_A.setter();
+        let set_b = This::OFFSET_B.setter();
+        let set_c = This::OFFSET_C.setter();
+        let set_d = This::OFFSET_D.setter();
+
+        set_a(&mut this, 13);
+        set_b(&mut this, 21);
+        set_d(&mut this, "bar");
+        assert_eq!((this.a, this.b, this.d), (13, 21, "bar"));
+
+        assert_eq!(Rc::strong_count(&rc), 2);
+        set_c(&mut this, Rc::new(()));
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        set_c(&mut this, rc.clone());
+        assert!(Rc::ptr_eq(&this.c, &rc));
+        assert_eq!(Rc::strong_count(&rc), 2);
+    }
+    assert_eq!(Rc::strong_count(&rc), 1);
+    {
+        type This = ReprPacked<u8, u64, Rc<()>, &'static str>;
+
+        let mut this: This = ReprPacked {
+            a: 3,
+            b: 5,
+            c: rc.clone(),
+            d: "foo",
+        };
+
+        let setters = [This::OFFSET_B.setter(), This::OFFSET_B.setter()];
+        for (i, set) in setters.iter().enumerate() {
+            set(&mut this, i as u64 + 100);
+        }
+        This::OFFSET_A.setter()(&mut this, 13);
+        This::OFFSET_D.setter()(&mut this, "bar");
+
+        assert_eq!(This::OFFSET_A.get_copy(&this), 13);
+        assert_eq!(This::OFFSET_B.get_copy(&this), 101);
+        assert_eq!(This::OFFSET_D.get_copy(&this), "bar");
+
+        let set_c = This::OFFSET_C.setter();
+        assert_eq!(Rc::strong_count(&rc), 2);
+        set_c(&mut this, Rc::new(()));
+        assert_eq!(Rc::strong_count(&rc), 1);
+        set_c(&mut this, rc.clone());
+        assert_eq!(Rc::strong_count(&rc), 2);
+    }
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn to_aligned_if_align_one_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    type This = ReprPacked<u32, u8, i8, bool>;
+
+    let mut this: This = ReprPacked {
+        a: 3,
+        b: 5,
+        c: -8,
+        d: false,
+    };
+
+    let offset_b: FieldOffset<This, u8, Aligned> = This::OFFSET_B.to_aligned_if_align_one();
+    let offset_c: FieldOffset<This, i8, Aligned> = This::OFFSET_C.to_aligned_if_align_one();
+    let offset_d: FieldOffset<This, bool, Aligned> = This::OFFSET_D.to_aligned_if_align_one();
+
+    assert_eq!(offset_b.offset(), 4);
+    assert_eq!(offset_c.offset(), 5);
+    assert_eq!(offset_d.offset(), 6);
+
+    assert_eq!(offset_b.get(&this), &5);
+    assert_eq!(offset_c.get(&this), &-8);
+    assert_eq!(offset_d.get(&this), &false);
+
+    *offset_b.get_mut(&mut this) += 8;
+    *offset_c.get_mut(&mut this) -= 5;
+    *offset_d.get_mut(&mut this) = true;
+
+    assert_eq!(This::OFFSET_B.get_copy(&this), 13);
+    assert_eq!(This::OFFSET_C.get_copy(&this), -13);
+    assert!(This::OFFSET_D.get_copy(&this));
+    assert_eq!(This::OFFSET_A.get_copy(&this), 3);
+
+    // Already aligned offsets work too
+    let aligned = ReprC::<u32, u8, (), ()>::OFFSET_B.to_aligned_if_align_one();
+    assert_eq!(aligned, ReprC::<u32, u8, (), ()>::OFFSET_B);
+}
+
+#[cfg(feature = "min_const_generics")]
+#[test]
+fn to_aligned_if_align_one_array() {
+    use repr_offset::for_examples::ReprPacked;
+
+    type This = ReprPacked<u16, [u8; 3], [[bool; 2]; 2], ()>;
+
+    let mut this: This = ReprPacked {
+        a: 3,
+        b: [5, 8, 13],
+        c: [[false, true], [true, false]],
+        d: (),
+    };
+
+    let offset_b = This::OFFSET_B.to_aligned_if_align_one();
+    let offset_c = This::OFFSET_C.to_aligned_if_align_one();
+
+    offset_b.get_mut(&mut this).reverse();
+    assert_eq!(offset_b.get(&this), &[13, 8, 5]);
+    assert_eq!(offset_c.get(&this), &[[false, true], [true, false]]);
+}
+
+#[test]
+fn offset_misalignment_method() {
+    use repr_offset::for_examples::{ReprAlign4, ReprC, ReprPacked};
+
+    type This = ReprC<u8, u32, u64, [u64; 3]>;
+    assert_eq!(This::OFFSET_D.offset(), 16);
+
+    let misalignments = |align: usize| {
+        [
+            This::OFFSET_A.offset_misalignment(align),
+            This::OFFSET_B.offset_misalignment(align),
+            This::OFFSET_C.offset_misalignment(align),
+            This::OFFSET_D.offset_misalignment(align),
+        ]
+    };
+    assert_eq!(misalignments(4), [0, 0, 0, 0]);
+    assert_eq!(misalignments(8), [0, 4, 0, 0]);
+    assert_eq!(misalignments(16), [0, 4, 8, 0]);
+
+    type Packed = ReprPacked<u8, u32, u64, [u64; 3]>;
+    let misalignments = |align: usize| {
+        [
+            Packed::OFFSET_A.offset_misalignment(align),
+            Packed::OFFSET_B.offset_misalignment(align),
+            Packed::OFFSET_C.offset_misalignment(align),
+            Packed::OFFSET_D.offset_misalignment(align),
+        ]
+    };
+    assert_eq!(misalignments(4), [0, 1, 1, 1]);
+    assert_eq!(misalignments(8), [0, 1, 5, 5]);
+    assert_eq!(misalignments(16), [0, 1, 5, 13]);
+
+    const MISALIGNMENT: usize = ReprAlign4::<u8, u8, (), ()>::OFFSET_B.offset_misalignment(4);
+    assert_eq!(MISALIGNMENT, 1);
+
+    let zero_align = std::panic::catch_unwind(|| This::OFFSET_B.offset_misalignment(0));
+    assert!(zero_align.is_err());
+}
+
+#[test]
+fn read_from_uninit_bytes_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    use std::mem::{self, MaybeUninit};
+
+    fn write_bytes(buffer: &mut [MaybeUninit<u8>], start: usize, bytes: &[u8]) {
+        for (dst, src) in buffer[start..].iter_mut().zip(bytes) {
+            *dst = MaybeUninit::new(*src);
+        }
+    }
+
+    type This = ReprC<u8, u16, u64, ()>;
+
+    // One extra byte to read from a misaligned position
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 1 + mem::size_of::<This>()];
+
+    write_bytes(&mut buffer, 1, &[3]);
+    write_bytes(&mut buffer, 1 + 2, &0x0102u16.to_ne_bytes());
+    write_bytes(&mut buffer, 1 + 8, &u64::max_value().to_ne_bytes());
+
+    unsafe {
+        let bytes = &buffer[1..];
+        assert_eq!(This::OFFSET_A.read_from_uninit_bytes(bytes), 3);
+        assert_eq!(This::OFFSET_B.read_from_uninit_bytes(bytes), 0x0102);
+        assert_eq!(
+            This::OFFSET_C.read_from_uninit_bytes(bytes),
+            u64::max_value()
+        );
+        This::OFFSET_D.read_from_uninit_bytes(bytes);
+    }
+
+    type Packed = ReprPacked<u8, i32, (), ()>;
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 5];
+    write_bytes(&mut buffer, 1, &(-100i32).to_ne_bytes());
+    unsafe {
+        assert_eq!(Packed::OFFSET_B.read_from_uninit_bytes(&buffer), -100);
+    }
+
+    #[cfg(debug_assertions)]
+    {
+        let too_short = std::panic::catch_unwind(|| unsafe {
+            Packed::OFFSET_B.read_from_uninit_bytes(&buffer[..4])
+        });
+        assert!(too_short.is_err());
+    }
+}
+
+#[test]
+fn read_write_swapped_methods() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    let mut this = ReprC {
+        a: 0x0102u16,
+        b: 0x0102_0304u32,
+        c: 0x0102_0304_0506_0708u64,
+        d: -2i16,
+    };
+    let mut packed = ReprPacked {
+        a: 0x0102u16,
+        b: 0x0102_0304u32,
+        c: 0x0102_0304_0506_0708u64,
+        d: -2i16,
+    };
+
+    unsafe {
+        macro_rules! read_all {
+            ($struct:ident, $this:expr) => {
+                assert_eq!($struct::OFFSET_A.read_swapped($this), 0x0201);
+                assert_eq!($struct::OFFSET_B.read_swapped($this), 0x0403_0201);
+                assert_eq!($struct::OFFSET_C.read_swapped($this), 0x0807_0605_0403_0201);
+                assert_eq!($struct::OFFSET_D.read_swapped($this), -257);
+            };
+        }
+        read_all!(ReprC, &this);
+        read_all!(ReprPacked, &packed);
+
+        macro_rules! write_all {
+            ($struct:ident, $this:expr) => {
+                $struct::OFFSET_A.write_swapped($this, 0xAABB);
+                $struct::OFFSET_B.write_swapped($this, 0xAABB_CCDD);
+                $struct::OFFSET_C.write_swapped($this, 0xAABB_CCDD_EEFF_0011);
+                $struct::OFFSET_D.write_swapped($this, 1);
+            };
+        }
+        write_all!(ReprC, &mut this);
+        write_all!(ReprPacked, &mut packed);
+    }
+
+    assert_eq!(this.a, 0xBBAA);
+    assert_eq!(this.b, 0xDDCC_BBAA);
+    assert_eq!(this.c, 0x1100_FFEE_DDCC_BBAA);
+    assert_eq!(this.d, 256);
+
+    assert_eq!({ packed.a }, 0xBBAA);
+    assert_eq!({ packed.b }, 0xDDCC_BBAA);
+    assert_eq!({ packed.c }, 0x1100_FFEE_DDCC_BBAA);
+    assert_eq!({ packed.d }, 256);
+}
+
+#[test]
+fn read_sentinel_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    macro_rules! test_struct {
+        ($struct:ident) => {{
+            let absent = $struct {
+                a: -1i32,
+                b: u32::max_value(),
+                c: 0u8,
+                d: std::ptr::null::<u8>(),
+            };
+            let present = $struct {
+                a: 5i32,
+                b: 8u32,
+                c: 13u8,
+                d: &21u8 as *const u8,
+            };
+            unsafe {
+                assert_eq!($struct::OFFSET_A.read_sentinel(&absent, -1), None);
+                assert_eq!(
+                    $struct::OFFSET_B.read_sentinel(&absent, u32::max_value()),
+                    None
+                );
+                assert_eq!($struct::OFFSET_C.read_sentinel(&absent, 0), None);
+                assert_eq!(
+                    $struct::OFFSET_D.read_sentinel(&absent, std::ptr::null()),
+                    None
+                );
+
+                assert_eq!($struct::OFFSET_A.read_sentinel(&present, -1), Some(5));
+                assert_eq!(
+                    $struct::OFFSET_B.read_sentinel(&present, u32::max_value()),
+                    Some(8)
+                );
+                assert_eq!($struct::OFFSET_C.read_sentinel(&present, 0), Some(13));
+                let ptr = $struct::OFFSET_D.read_sentinel(&present, std::ptr::null());
+                assert_eq!(ptr.map(|x| *x), Some(21));
+
+                // The sentinel is only compared with the field being read
+                assert_eq!($struct::OFFSET_C.read_sentinel(&absent, 13), Some(0));
+            }
+        }};
+    }
+
+    test_struct!(ReprC);
+    test_struct!(ReprPacked);
+}
+
+#[test]
+fn raw_get_checked_methods() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    {
+        let mut this = ReprC {
+            a: 3u8,
+            b: 5u64,
+            c: (),
+            d: [8u16; 2],
+        };
+        let base = &this as *const _ as usize;
+
+        assert_eq!(ReprC::OFFSET_A.raw_get_checked(&this) as usize, base);
+        assert_eq!(ReprC::OFFSET_B.raw_get_checked(&this) as usize, base + 8);
+        assert_eq!(ReprC::OFFSET_D.raw_get_checked(&this) as usize, base + 16);
+
+        unsafe {
+            *ReprC::OFFSET_B.raw_get_checked_mut(&mut this) = 13;
+        }
+        assert_eq!(this.b, 13);
+    }
+    {
+        let mut this = ReprPacked {
+            a: 3u8,
+            b: 5u64,
+            c: (),
+            d: [8u16; 2],
+        };
+        let base = &this as *const _ as usize;
+
+        assert_eq!(
+            ReprPacked::OFFSET_B.raw_get_checked(&this) as usize,
+            base + 1
+        );
+        assert_eq!(
+            ReprPacked::OFFSET_D.raw_get_checked(&this) as usize,
+            base + 9
+        );
+
+        unsafe {
+            ReprPacked::OFFSET_D
+                .raw_get_checked_mut(&mut this)
+                .write_unaligned([21, 34]);
+        }
+        assert_eq!({ this.d }, [21, 34]);
+    }
+
+    #[cfg(debug_assertions)]
+    {
+        use std::panic::catch_unwind;
+
+        type This = ReprC<u8, u32, (), ()>;
+
+        let this = ReprC {
+            a: 3u8,
+            b: 5u32,
+            c: (),
+            d: (),
+        };
+        let ptr = &this as *const This as usize;
+
+        // The field ends exactly at the end of the struct
+        unsafe {
+            let end = FieldOffset::<This, u32, Aligned>::new(4);
+            end.raw_get_checked(ptr as *const This);
+        }
+
+        let oversized = unsafe { FieldOffset::<This, u32, Aligned>::new(5) };
+        assert!(catch_unwind(|| oversized.raw_get_checked(ptr as *const This)).is_err());
+        assert!(catch_unwind(|| oversized.raw_get_checked_mut(ptr as *mut This)).is_err());
+
+        let overflowing = unsafe { FieldOffset::<This, u32, Aligned>::new(usize::max_value()) };
+        assert!(catch_unwind(|| overflowing.raw_get_checked(ptr as *const This)).is_err());
+    }
+}
+
+#[test]
+fn in_array_element_method() {
+    use repr_offset::for_examples::{ReprAlign4, ReprC, ReprPacked};
+
+    fn addr_offset<T, F>(array: &[T], field: *const F) -> usize {
+        field as usize - (array.as_ptr() as usize)
+    }
+
+    {
+        type This = ReprC<u8, u32, u16, ()>;
+        let array = [ReprC {
+            a: 0u8,
+            b: 0u32,
+            c: 0u16,
+            d: (),
+        }; 4];
+
+        for (i, elem) in array.iter().enumerate() {
+            assert_eq!(This::OFFSET_A.in_array_element(i), i * 12);
+            assert_eq!(This::OFFSET_B.in_array_element(i), i * 12 + 4);
+            assert_eq!(This::OFFSET_C.in_array_element(i), i * 12 + 8);
+            assert_eq!(
+                This::OFFSET_C.in_array_element(i),
+                addr_offset(&array, &elem.c)
+            );
+        }
+    }
+    {
+        type This = ReprPacked<u8, u32, u16, ()>;
+        for i in 0..4 {
+            assert_eq!(This::OFFSET_A.in_array_element(i), i * 7);
+            assert_eq!(This::OFFSET_B.in_array_element(i), i * 7 + 1);
+            assert_eq!(This::OFFSET_C.in_array_element(i), i * 7 + 5);
+        }
+    }
+    {
+        type This = ReprAlign4<u8, u8, (), ()>;
+        for i in 0..4 {
+            assert_eq!(This::OFFSET_A.in_array_element(i), i * 4);
+            assert_eq!(This::OFFSET_B.in_array_element(i), i * 4 + 1);
+        }
+    }
+
+    const OFFSET: usize = ReprC::<u64, u8, (), ()>::OFFSET_B.in_array_element(3);
+    assert_eq!(OFFSET, 3 * 16 + 8);
+}
+
+#[test]
+fn flat_offset_methods() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    {
+        type This = ReprC<u8, u32, u16, ()>;
+        for i in 0..4 {
+            assert_eq!(
+                This::OFFSET_B.flat_offset(i),
+                This::OFFSET_B.in_array_element(i)
+            );
+            assert_eq!(This::OFFSET_C.flat_offset(i), i * 12 + 8);
+            assert_eq!(This::OFFSET_C.flat_offset_checked(i, 4), Some(i * 12 + 8));
+        }
+        assert_eq!(This::OFFSET_C.flat_offset_checked(4, 4), None);
+        assert_eq!(This::OFFSET_C.flat_offset_checked(5, 4), None);
+        assert_eq!(This::OFFSET_A.flat_offset_checked(0, 0), None);
+    }
+    {
+        type This = ReprPacked<u8, u32, u16, ()>;
+        assert_eq!(This::OFFSET_B.flat_offset(3), 22);
+        assert_eq!(This::OFFSET_B.flat_offset_checked(3, 4), Some(22));
+        assert_eq!(This::OFFSET_B.flat_offset_checked(4, 4), None);
+    }
+    {
+        // The offset doesn't fit in a usize
+        type This = ReprC<u8, [u8; 255], (), ()>;
+        let max = usize::max_value();
+        let last = max / 256;
+        assert_eq!(This::OFFSET_B.flat_offset_checked(max - 1, max), None);
+        assert_eq!(This::OFFSET_B.flat_offset_checked(last + 1, max), None);
+        assert_eq!(
+            This::OFFSET_B.flat_offset_checked(last, max),
+            Some(last * 256 + 1)
+        );
+    }
+
+    const OFFSET: usize = ReprC::<u64, u8, (), ()>::OFFSET_B.flat_offset(3);
+    assert_eq!(OFFSET, 3 * 16 + 8);
+}
+
+#[cfg(feature = "const_fns")]
+#[test]
+fn get_copy_const_ptr_method() {
+    use repr_offset::for_examples::{ReprAlign4, ReprC};
+
+    type This = ReprC<u8, u64, (), [u16; 3]>;
+    const THIS: This = ReprC {
+        a: 3,
+        b: u64::max_value(),
+        c: (),
+        d: [5, 8, 13],
+    };
+
+    const A: u8 = unsafe { This::OFFSET_A.get_copy_const_ptr(&THIS) };
+    const B: u64 = unsafe { This::OFFSET_B.get_copy_const_ptr(&THIS) };
+    const C: () = unsafe { This::OFFSET_C.get_copy_const_ptr(&THIS) };
+    const D: [u16; 3] = unsafe { This::OFFSET_D.get_copy_const_ptr(&THIS) };
+    assert_eq!(A, 3);
+    assert_eq!(B, u64::max_value());
+    assert_eq!(C, ());
+    assert_eq!(D, [5, 8, 13]);
+
+    type Nested = ReprAlign4<u8, This, u16, ()>;
+    const NESTED: Nested = ReprAlign4 {
+        a: 21,
+        b: THIS,
+        c: 34,
+        d: (),
+    };
+    const NESTED_D: [u16; 3] = unsafe {
+        Nested::OFFSET_B
+            .add(This::OFFSET_D)
+            .get_copy_const_ptr(&NESTED)
+    };
+    const NESTED_C: u16 = unsafe { Nested::OFFSET_C.get_copy_const_ptr(&NESTED) };
+    assert_eq!(NESTED_D, [5, 8, 13]);
+    assert_eq!(NESTED_C, 34);
+
+    // It can also be used at runtime
+    let this = ReprC {
+        a: 55u8,
+        b: 89u64,
+        c: (),
+        d: [0u16; 3],
+    };
+    assert_eq!(unsafe { This::OFFSET_B.get_copy_const_ptr(&this) }, 89);
+}
+
+#[test]
+fn at_method() {
+    use repr_offset::{
+        for_examples::{ReprC, ReprPacked},
+        FieldReader,
+    };
+
+    use std::mem::ManuallyDrop;
+
+    {
+        let this = ManuallyDrop::new(ReprC {
+            a: 3u8,
+            b: 5u64,
+            c: "8".to_string(),
+            d: [13u16; 2],
+        });
+        let base: *const _ = &*this;
+
+        let reader: FieldReader<_, u64, Aligned> = ReprC::OFFSET_B.at(base);
+        assert_eq!(reader.ptr(), &this.b as *const u64);
+        assert_eq!(ReprC::OFFSET_D.at(base).ptr(), &this.d as *const [u16; 2]);
+
+        unsafe {
+            assert_eq!(ReprC::OFFSET_A.at(base).copy(), 3);
+            assert_eq!(reader.copy(), 5);
+            assert_eq!(ReprC::OFFSET_D.at(base).copy(), [13, 13]);
+            assert_eq!(ReprC::OFFSET_C.at(base).read(), "8");
+        }
+    }
+    {
+        let this = ManuallyDrop::new(ReprPacked {
+            a: 3u8,
+            b: 5u64,
+            c: "8".to_string(),
+            d: [13u16; 2],
+        });
+        let base: *const _ = &*this;
+        let base_addr = base as usize;
+
+        let reader: FieldReader<_, u64, Unaligned> = ReprPacked::OFFSET_B.at(base);
+        assert_eq!(reader.ptr() as usize, base_addr + 1);
+        assert_eq!(ReprPacked::OFFSET_C.at(base).ptr() as usize, base_addr + 9);
+
+        unsafe {
+            assert_eq!(ReprPacked::OFFSET_A.at(base).copy(), 3);
+            assert_eq!(reader.copy(), 5);
+            assert_eq!(reader.ptr().read_unaligned(), 5);
+            assert_eq!(ReprPacked::OFFSET_D.at(base).copy(), [13, 13]);
+            assert_eq!(ReprPacked::OFFSET_C.at(base).read(), "8");
+        }
+    }
+}
+
+#[test]
+fn cast_field_prefix_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    let mut bytes = [0u8; 16];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = i as u8 + 1;
+    }
+    let prefix = u32::from_ne_bytes([1, 2, 3, 4]);
+
+    {
+        let mut this = ReprC {
+            a: 0u32,
+            b: bytes,
+            c: (),
+            d: (),
+        };
+        let header = unsafe { ReprC::OFFSET_B.cast_field_prefix::<u32>() };
+        let _: FieldOffset<_, u32, Aligned> = header;
+        assert_eq!(header.offset(), 4);
+        assert_eq!(header.get_copy(&this), prefix);
+
+        *header.get_mut(&mut this) = u32::max_value();
+        assert_eq!(this.b[..4], [255; 4]);
+        assert_eq!(this.b[4..], bytes[4..]);
+
+        // The same size as the field
+        let whole = unsafe { ReprC::OFFSET_B.cast_field_prefix::<[u32; 4]>() };
+        assert_eq!(whole.get(&this)[0], u32::max_value());
+        assert_eq!(whole.get(&this)[1], u32::from_ne_bytes([5, 6, 7, 8]));
+
+        let zst = unsafe { ReprC::OFFSET_B.cast_field_prefix::<()>() };
+        assert_eq!(zst.get_copy(&this), ());
+    }
+    {
+        let mut this = ReprPacked {
+            a: 0u8,
+            b: bytes,
+            c: (),
+            d: (),
+        };
+        let header = unsafe { ReprPacked::OFFSET_B.cast_field_prefix::<u32>() };
+        let _: FieldOffset<_, u32, Unaligned> = header;
+        assert_eq!(header.offset(), 1);
+        assert_eq!(header.get_copy(&this), prefix);
+
+        header.replace_mut(&mut this, 0);
+        assert_eq!({ this.b }[..4], [0; 4]);
+        assert_eq!({ this.b }[4..], bytes[4..]);
+    }
+}
+
+#[cfg(feature = "bitflags")]
+#[test]
+fn read_flags_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    bitflags::bitflags! {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        struct Flags: u32 {
+            const A = 1;
+            const B = 1 << 8;
+            const C = 1 << 31;
+        }
+    }
+
+    let this = ReprC {
+        a: 0u8,
+        b: 1u32 | (1 << 31),
+        c: Flags::B,
+        d: 0b10u32,
+    };
+    unsafe {
+        assert_eq!(
+            ReprC::OFFSET_B.read_flags::<Flags>(&this),
+            Flags::A | Flags::C
+        );
+
+        // Bits that aren't flags are preserved
+        let undefined = ReprC::OFFSET_D.read_flags::<Flags>(&this);
+        assert_eq!(undefined & Flags::all(), Flags::empty());
+        assert_eq!(undefined.bits(), 0b10);
+    }
+    // Fields of flags types work with the regular methods
+    assert_eq!(ReprC::OFFSET_C.get_copy(&this), Flags::B);
+
+    let packed = ReprPacked {
+        a: 0u8,
+        b: 1u32 << 8,
+        c: Flags::A | Flags::B,
+        d: (),
+    };
+    unsafe {
+        assert_eq!(ReprPacked::OFFSET_B.read_flags::<Flags>(&packed), Flags::B);
+    }
+    assert_eq!(ReprPacked::OFFSET_C.get_copy(&packed), Flags::A | Flags::B);
+}
+
+#[test]
+fn trailing_padding_method() {
+    use repr_offset::for_examples::{ReprAlign4, ReprC, ReprPacked};
+
+    type This = ReprC<u8, u64, u16, u8>;
+    assert_eq!(This::OFFSET_D.trailing_padding(), 5);
+    assert_eq!(This::OFFSET_C.trailing_padding(), 6);
+    assert_eq!(This::OFFSET_B.trailing_padding(), 8);
+    assert_eq!(This::OFFSET_A.trailing_padding(), 23);
+
+    type Packed = ReprPacked<u8, u64, u16, u8>;
+    assert_eq!(Packed::OFFSET_D.trailing_padding(), 0);
+    assert_eq!(Packed::OFFSET_C.trailing_padding(), 1);
+
+    type Align = ReprAlign4<u8, (), (), ()>;
+    assert_eq!(Align::OFFSET_A.trailing_padding(), 3);
+    assert_eq!(Align::OFFSET_D.trailing_padding(), 3);
+
+    const PADDING: usize = ReprC::<u32, u8, (), ()>::OFFSET_B.trailing_padding();
+    assert_eq!(PADDING, 3);
+}
+
+#[test]
+fn field_layout_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    use std::alloc::Layout;
+
+    type This = ReprC<u8, u64, [u16; 3], String>;
+    assert_eq!(This::OFFSET_A.field_layout(), Layout::new::<u8>());
+    assert_eq!(This::OFFSET_B.field_layout(), Layout::new::<u64>());
+    assert_eq!(This::OFFSET_C.field_layout(), Layout::new::<[u16; 3]>());
+    assert_eq!(This::OFFSET_D.field_layout(), Layout::new::<String>());
+
+    type Packed = ReprPacked<(), u64, ReprC<u8, u32>, [u128; 2]>;
+    assert_eq!(Packed::OFFSET_A.field_layout(), Layout::new::<()>());
+    assert_eq!(Packed::OFFSET_B.field_layout(), Layout::new::<u64>());
+    assert_eq!(
+        Packed::OFFSET_C.field_layout(),
+        Layout::new::<ReprC<u8, u32>>()
+    );
+    assert_eq!(Packed::OFFSET_D.field_layout(), Layout::new::<[u128; 2]>());
+}
+
+#[test]
+fn field_align_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    use std::mem::align_of;
+
+    type This = ReprC<u8, u64, [u16; 3], String>;
+    assert_eq!(This::OFFSET_A.field_align(), 1);
+    assert_eq!(This::OFFSET_B.field_align(), align_of::<u64>());
+    assert_eq!(This::OFFSET_C.field_align(), 2);
+    assert_eq!(This::OFFSET_D.field_align(), align_of::<String>());
+
+    type Packed = ReprPacked<(), u64, ReprC<u8, u32>, [u128; 2]>;
+    assert_eq!(Packed::OFFSET_A.field_align(), 1);
+    assert_eq!(Packed::OFFSET_B.field_align(), align_of::<u64>());
+    assert_eq!(Packed::OFFSET_C.field_align(), 4);
+    assert_eq!(Packed::OFFSET_D.field_align(), align_of::<u128>());
+
+    const ALIGN: usize = ReprC::<u8, u32, (), ()>::OFFSET_B.field_align();
+    assert_eq!(ALIGN, 4);
+}
+
+#[cfg(feature = "ptr_alignment")]
+#[test]
+fn field_alignment_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    use std::mem::Alignment;
+
+    type This = ReprC<u8, u64, [u16; 3], String>;
+    assert_eq!(This::OFFSET_A.field_alignment(), Alignment::of::<u8>());
+    assert_eq!(This::OFFSET_B.field_alignment(), Alignment::of::<u64>());
+    assert_eq!(
+        This::OFFSET_C.field_alignment(),
+        Alignment::of::<[u16; 3]>()
+    );
+    assert_eq!(This::OFFSET_D.field_alignment(), Alignment::of::<String>());
+
+    type Packed = ReprPacked<(), u64, ReprC<u8, u32>, [u128; 2]>;
+    assert_eq!(Packed::OFFSET_A.field_alignment(), Alignment::of::<()>());
+    assert_eq!(Packed::OFFSET_B.field_alignment(), Alignment::of::<u64>());
+    assert_eq!(
+        Packed::OFFSET_D.field_alignment().as_usize(),
+        Packed::OFFSET_D.field_align()
+    );
+
+    const ALIGNMENT: Alignment = ReprC::<u8, u32, (), ()>::OFFSET_B.field_alignment();
+    assert_eq!(ALIGNMENT.as_usize(), 4);
+}
+
+#[test]
+fn replace_into_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+    use repr_offset::utils::moved;
+
+    use std::{mem::MaybeUninit, rc::Rc};
+
+    let old_rc = Rc::new(3u32);
+    let new_rc = Rc::new(5u32);
+
+    {
+        let mut this = ReprC {
+            a: 0u8,
+            b: old_rc.clone(),
+            c: [0u64; 16],
+            d: (),
+        };
+        let mut out = MaybeUninit::uninit();
+        let old = unsafe {
+            ReprC::OFFSET_B.replace_into(&mut this, new_rc.clone(), &mut out);
+            out.assume_init()
+        };
+        assert!(Rc::ptr_eq(&old, &old_rc));
+        assert!(Rc::ptr_eq(&this.b, &new_rc));
+        assert_eq!(Rc::strong_count(&old_rc), 2);
+        assert_eq!(Rc::strong_count(&new_rc), 2);
+
+        let mut out = MaybeUninit::uninit();
+        let old_array = unsafe {
+            ReprC::OFFSET_C.replace_into(&mut this, [7u64; 16], &mut out);
+            out.assume_init()
+        };
+        assert_eq!(old_array, [0u64; 16]);
+        assert_eq!(this.c, [7u64; 16]);
+    }
+    assert_eq!(Rc::strong_count(&old_rc), 1);
+    assert_eq!(Rc::strong_count(&new_rc), 1);
+
+    {
+        let mut this = ReprPacked {
+            a: 0u8,
+            b: old_rc.clone(),
+            c: (),
+            d: (),
+        };
+        let mut out = MaybeUninit::uninit();
+        let old = unsafe {
+            ReprPacked::OFFSET_B.replace_into(&mut this, new_rc.clone(), &mut out);
+            out.assume_init()
+        };
+        assert!(Rc::ptr_eq(&old, &old_rc));
+
+        let mut out = MaybeUninit::uninit();
+        unsafe {
+            ReprPacked::OFFSET_C.replace_into(&mut this, (), &mut out);
+        }
+
+        assert!(Rc::ptr_eq(&moved(this.b), &new_rc));
+    }
+    assert_eq!(Rc::strong_count(&old_rc), 1);
+    assert_eq!(Rc::strong_count(&new_rc), 1);
+}
+
+#[cfg(feature = "atomic_cas")]
+#[test]
+fn atomic_compare_exchange_method() {
+    use repr_offset::for_examples::ReprC;
+
+    use std::{
+        ptr,
+        sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+        sync::Arc,
+        thread,
+    };
+
+    type Counter = ReprC<u8, AtomicUsize, AtomicBool, AtomicPtr<u32>>;
+
+    let mut number = 3u32;
+    let this = Counter {
+        a: 1,
+        b: AtomicUsize::new(0),
+        c: AtomicBool::new(false),
+        d: AtomicPtr::new(ptr::null_mut()),
+    };
+    let base: *const Counter = &this;
+
+    unsafe {
+        let off = Counter::OFFSET_B;
+        assert_eq!(
+            off.compare_exchange(base, 0, 10, Ordering::SeqCst, Ordering::SeqCst),
+            Ok(0)
+        );
+        assert_eq!(
+            off.compare_exchange(base, 0, 20, Ordering::SeqCst, Ordering::SeqCst),
+            Err(10)
+        );
+        assert_eq!(off.load(base, Ordering::SeqCst), 10);
+
+        let off = Counter::OFFSET_C;
+        assert_eq!(
+            off.compare_exchange(base, false, true, Ordering::SeqCst, Ordering::Relaxed),
+            Ok(false)
+        );
+        assert!(off.load(base, Ordering::SeqCst));
+
+        let off = Counter::OFFSET_D;
+        assert_eq!(
+            off.compare_exchange(
+                base,
+                ptr::null_mut(),
+                &mut number,
+                Ordering::SeqCst,
+                Ordering::Relaxed
+            ),
+            Ok(ptr::null_mut())
+        );
+        assert_eq!(off.load(base, Ordering::SeqCst), &mut number as *mut u32);
+    }
+    assert_eq!(this.b.load(Ordering::SeqCst), 10);
+    assert!(this.c.load(Ordering::SeqCst));
+
+    // Incrementing the field from multiple threads with a CAS loop.
+    type Shared = ReprC<u64, AtomicUsize, (), ()>;
+    let shared = Arc::new(Shared {
+        a: 0,
+        b: AtomicUsize::new(0),
+        c: (),
+        d: (),
+    });
+    let handles = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                let base: *const Shared = &*shared;
+                for _ in 0..1000 {
+                    let mut current = unsafe { Shared::OFFSET_B.load(base, Ordering::Relaxed) };
+                    loop {
+                        let res = unsafe {
+                            Shared::OFFSET_B.compare_exchange(
+                                base,
+                                current,
+                                current + 1,
+                                Ordering::AcqRel,
+                                Ordering::Relaxed,
+                            )
+                        };
+                        match res {
+                            Ok(_) => break,
+                            Err(x) => current = x,
+                        }
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(shared.b.load(Ordering::SeqCst), 4000);
+}
+
+#[test]
+fn field_non_null_mut_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+    use repr_offset::utils::moved;
+
+    use std::ptr::NonNull;
+
+    // Every pointer comes from a different struct,
+    // since getting a pointer to another field of the same struct
+    // (with a new `&mut` to it) would invalidate the previous one.
+    struct FieldPtrs<A, B> {
+        a: NonNull<A>,
+        b: NonNull<B>,
+    }
+
+    {
+        type This = ReprC<u8, Vec<u32>, (), ()>;
+        let new = |a: u8| -> Box<This> {
+            Box::new(ReprC {
+                a,
+                b: vec![5, 8],
+                c: (),
+                d: (),
+            })
+        };
+        let mut left = new(3);
+        let mut right = new(21);
+        let right_addr = &*right as *const This as usize;
+
+        let ptrs = FieldPtrs {
+            a: This::OFFSET_A.field_non_null_mut(&mut left),
+            b: This::OFFSET_B.field_non_null_mut(&mut right),
+        };
+        assert_eq!(
+            ptrs.b.as_ptr() as usize,
+            right_addr + This::OFFSET_B.offset()
+        );
+        unsafe {
+            *ptrs.a.as_ptr() += 10;
+            (*ptrs.b.as_ptr()).push(13);
+            assert_eq!(*ptrs.a.as_ref(), 13);
+            assert_eq!(ptrs.b.as_ref(), &[5, 8, 13]);
+        }
+        assert_eq!(left.a, 13);
+        assert_eq!(left.b, [5, 8]);
+        assert_eq!(right.a, 21);
+        assert_eq!(right.b, [5, 8, 13]);
+    }
+    {
+        type This = ReprPacked<u8, u64, u16, ()>;
+        let new = |b: u64| This {
+            a: 3,
+            b,
+            c: 8,
+            d: (),
+        };
+        let mut left = new(5);
+        let mut right = new(34);
+        let left_addr = &left as *const This as usize;
+        let right_addr = &right as *const This as usize;
+
+        let ptrs = FieldPtrs {
+            a: This::OFFSET_B.field_non_null_mut(&mut left),
+            b: This::OFFSET_C.field_non_null_mut(&mut right),
+        };
+        assert_eq!(ptrs.a.as_ptr() as usize, left_addr + 1);
+        assert_eq!(ptrs.b.as_ptr() as usize, right_addr + 9);
+        unsafe {
+            let a = ptrs.a.as_ptr();
+            a.write_unaligned(a.read_unaligned() + 100);
+            ptrs.b.as_ptr().write_unaligned(21);
+        }
+        assert_eq!(moved(left.b), 105);
+        assert_eq!(moved(left.c), 8);
+        assert_eq!(moved(right.b), 34);
+        assert_eq!(moved(right.c), 21);
+    }
+}
+
+#[cfg(feature = "min_const_generics")]
+#[test]
+fn get_array_mut_slice_method() {
+    use repr_offset::for_examples::ReprC;
+
+    type This = ReprC<u8, [u32; 8], (), [u16; 0]>;
+
+    let mut this: This = ReprC {
+        a: 3,
+        b: [0, 1, 2, 3, 4, 5, 6, 7],
+        c: (),
+        d: [],
+    };
+
+    {
+        let slice: &mut [u32] = This::OFFSET_B.get_array_mut_slice(&mut this);
+        assert_eq!(slice.len(), 8);
+        for (i, elem) in slice.iter_mut().enumerate() {
+            *elem += i as u32 * 10;
+        }
+        slice[1..4].reverse();
+        slice[7] = 1000;
+    }
+    assert_eq!(this.a, 3);
+    assert_eq!(this.b, [0, 33, 22, 11, 44, 55, 66, 1000]);
+
+    assert_eq!(
+        This::OFFSET_B.get_array_slice(&this),
+        &[0, 33, 22, 11, 44, 55, 66, 1000][..]
+    );
+
+    assert_eq!(This::OFFSET_D.get_array_mut_slice(&mut this).len(), 0);
+    assert_eq!(This::OFFSET_D.get_array_slice(&this), &[][..]);
+}
+
+#[test]
+fn hash_field_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+
+    {
+        type This = ReprC<u8, u64, String, ()>;
+
+        let hash_ac = |this: &This| {
+            let mut hasher = DefaultHasher::new();
+            This::OFFSET_A.hash_field(this, &mut hasher);
+            This::OFFSET_C.hash_field(this, &mut hasher);
+            hasher.finish()
+        };
+
+        let mut this = ReprC {
+            a: 3,
+            b: 5,
+            c: "foo".to_string(),
+            d: (),
+        };
+        let hash = hash_ac(&this);
+
+        this.b = 8;
+        assert_eq!(hash_ac(&this), hash);
+        this.b = u64::max_value();
+        assert_eq!(hash_ac(&this), hash);
+
+        this.a = 4;
+        assert_ne!(hash_ac(&this), hash);
+        this.a = 3;
+        assert_eq!(hash_ac(&this), hash);
+
+        this.c.push('!');
+        assert_ne!(hash_ac(&this), hash);
+    }
+    {
+        type This = ReprPacked<u8, u64, u16, ()>;
+
+        let hash_ac = |this: &This| {
+            let mut hasher = DefaultHasher::new();
+            This::OFFSET_A.hash_field(this, &mut hasher);
+            This::OFFSET_C.hash_field(this, &mut hasher);
+            hasher.finish()
+        };
+
+        let mut this = ReprPacked {
+            a: 3,
+            b: 5,
+            c: 8,
+            d: (),
+        };
+        let hash = hash_ac(&this);
+
+        this.b = 13;
+        assert_eq!(hash_ac(&this), hash);
+        this.b = u64::max_value();
+        assert_eq!(hash_ac(&this), hash);
+
+        this.a = 4;
+        assert_ne!(hash_ac(&this), hash);
+        this.a = 3;
+        assert_eq!(hash_ac(&this), hash);
+
+        this.c = 21;
+        assert_ne!(hash_ac(&this), hash);
+    }
+}
+
+#[test]
+fn read_manually_drop_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    use std::{mem::ManuallyDrop, rc::Rc};
+
+    let left = Rc::new(3);
+    let right = Rc::new(5);
+    {
+        type This = ReprC<Rc<u32>, u8, Rc<u32>, ()>;
+        let mut this: This = ReprC {
+            a: left.clone(),
+            b: 8,
+            c: right.clone(),
+            d: (),
+        };
+        let ptr: *mut This = &mut this;
+        unsafe {
+            let a = This::OFFSET_A.read_manually_drop(ptr);
+            let c = This::OFFSET_C.read_manually_drop(ptr);
+            This::OFFSET_A.write(ptr, ManuallyDrop::into_inner(c));
+            This::OFFSET_C.write(ptr, ManuallyDrop::into_inner(a));
+        }
+        assert_eq!((*this.a, this.b, *this.c), (5, 8, 3));
+        assert!(Rc::ptr_eq(&this.a, &right));
+        assert!(Rc::ptr_eq(&this.c, &left));
+        assert_eq!(Rc::strong_count(&left), 2);
+        assert_eq!(Rc::strong_count(&right), 2);
+    }
+    assert_eq!(Rc::strong_count(&left), 1);
+    assert_eq!(Rc::strong_count(&right), 1);
+    {
+        type This = ReprPacked<u8, Rc<u32>, Rc<u32>, ()>;
+        let mut this: This = ReprPacked {
+            a: 8,
+            b: left.clone(),
+            c: right.clone(),
+            d: (),
+        };
+        let ptr: *mut This = &mut this;
+        unsafe {
+            let b = This::OFFSET_B.read_manually_drop(ptr);
+            let c = This::OFFSET_C.read_manually_drop(ptr);
+            This::OFFSET_B.write(ptr, ManuallyDrop::into_inner(c));
+            This::OFFSET_C.write(ptr, ManuallyDrop::into_inner(b));
+        }
+        assert_eq!(This::OFFSET_A.get_copy(&this), 8);
+        let b = This::OFFSET_B.replace_mut(&mut this, Rc::new(0));
+        let c = This::OFFSET_C.replace_mut(&mut this, Rc::new(0));
+        assert!(Rc::ptr_eq(&b, &right));
+        assert!(Rc::ptr_eq(&c, &left));
+        assert_eq!(Rc::strong_count(&left), 2);
+        assert_eq!(Rc::strong_count(&right), 2);
+    }
+    assert_eq!(Rc::strong_count(&left), 1);
+    assert_eq!(Rc::strong_count(&right), 1);
+
+    // Dropping the copy explicitly, after the field was overwritten without dropping it.
+    {
+        type This = ReprC<Rc<u32>, (), (), ()>;
+        let mut this: This = ReprC {
+            a: left.clone(),
+            b: (),
+            c: (),
+            d: (),
+        };
+        unsafe {
+            let mut copy = This::OFFSET_A.read_manually_drop(&this);
+            assert_eq!(Rc::strong_count(&left), 2);
+            This::OFFSET_A.write(&mut this, right.clone());
+            ManuallyDrop::drop(&mut copy);
+        }
+        assert_eq!(Rc::strong_count(&left), 1);
+        assert_eq!(Rc::strong_count(&right), 2);
+    }
+    assert_eq!(Rc::strong_count(&right), 1);
+}
+
+#[test]
+fn with_loaded_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    use std::{
+        ptr,
+        sync::{
+            atomic::{AtomicPtr, Ordering},
+            Arc,
+        },
+        thread,
+    };
+
+    {
+        type Node = ReprC<u8, u64, [u16; 3], ()>;
+
+        let atomic = Arc::new(AtomicPtr::new(ptr::null_mut::<Node>()));
+
+        let read_b = |atomic: &AtomicPtr<Node>| unsafe {
+            Node::OFFSET_B.with_loaded(atomic, Ordering::Acquire, |b| *b)
+        };
+        assert_eq!(read_b(&atomic), None);
+
+        let node = Box::into_raw(Box::new(ReprC {
+            a: 3,
+            b: 5,
+            c: [8, 13, 21],
+            d: (),
+        }));
+        atomic.store(node, Ordering::Release);
+
+        let threads = (0..4)
+            .map(|_| {
+                let atomic = atomic.clone();
+                thread::spawn(move || unsafe {
+                    let b = Node::OFFSET_B.with_loaded(&atomic, Ordering::Acquire, |b| *b);
+                    let c = Node::OFFSET_C.with_loaded(&atomic, Ordering::Acquire, |c| *c);
+                    (b, c)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), (Some(5), Some([8, 13, 21])));
+        }
+
+        let field_addr =
+            unsafe { Node::OFFSET_C.with_loaded(&atomic, Ordering::Acquire, |c| c as usize) };
+        assert_eq!(field_addr, Some(node as usize + 16));
+
+        let node = atomic.swap(ptr::null_mut(), Ordering::AcqRel);
+        assert_eq!(read_b(&atomic), None);
+        drop(unsafe { Box::from_raw(node) });
+    }
+    {
+        type Node = ReprPacked<u8, u64, u16, ()>;
+
+        let node = Box::into_raw(Box::new(ReprPacked {
+            a: 3,
+            b: 5,
+            c: 8,
+            d: (),
+        }));
+        let atomic = AtomicPtr::new(node);
+
+        unsafe {
+            let read = Node::OFFSET_B.with_loaded(&atomic, Ordering::Acquire, |b| {
+                assert_eq!(b as usize, node as usize + 1);
+                b.read_unaligned()
+            });
+            assert_eq!(read, Some(5));
+            assert_eq!(
+                Node::OFFSET_C.with_loaded(&atomic, Ordering::Relaxed, |c| c.read_unaligned()),
+                Some(8)
+            );
+
+            drop(Box::from_raw(node));
+        }
+    }
+}
+
+#[cfg(feature = "min_const_generics")]
+#[test]
+fn find_byte_methods() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    {
+        type This = ReprC<u8, [u8; 12], [u8; 0], [u8; 4]>;
+        let this: This = ReprC {
+            a: b'/',
+            b: *b"GET /a/b/c\r\n",
+            c: [],
+            d: *b"////",
+        };
+
+        assert_eq!(This::OFFSET_B.find_byte(&this, b'/'), Some(4));
+        assert_eq!(This::OFFSET_B.rfind_byte(&this, b'/'), Some(8));
+        assert_eq!(This::OFFSET_B.find_byte(&this, b'G'), Some(0));
+        assert_eq!(This::OFFSET_B.rfind_byte(&this, b'G'), Some(0));
+        assert_eq!(This::OFFSET_B.find_byte(&this, b'\n'), Some(11));
+        assert_eq!(This::OFFSET_B.rfind_byte(&this, b'\n'), Some(11));
+
+        // Bytes outside of the field are not found
+        assert_eq!(This::OFFSET_B.find_byte(&this, b'?'), None);
+        assert_eq!(This::OFFSET_B.rfind_byte(&this, b'?'), None);
+
+        assert_eq!(This::OFFSET_C.find_byte(&this, b'/'), None);
+        assert_eq!(This::OFFSET_C.rfind_byte(&this, b'/'), None);
+
+        assert_eq!(This::OFFSET_D.find_byte(&this, b'/'), Some(0));
+        assert_eq!(This::OFFSET_D.rfind_byte(&this, b'/'), Some(3));
+    }
+    {
+        type This = ReprPacked<u8, [u8; 7], u64, ()>;
+        let this: This = ReprPacked {
+            a: b':',
+            b: *b"foo:bar",
+            c: u64::from_ne_bytes(*b"::::::::"),
+            d: (),
+        };
+
+        assert_eq!(This::OFFSET_B.find_byte(&this, b':'), Some(3));
+        assert_eq!(This::OFFSET_B.rfind_byte(&this, b':'), Some(3));
+        assert_eq!(This::OFFSET_B.find_byte(&this, b'o'), Some(1));
+        assert_eq!(This::OFFSET_B.rfind_byte(&this, b'o'), Some(2));
+        assert_eq!(This::OFFSET_B.find_byte(&this, b'\0'), None);
+        assert_eq!(This::OFFSET_B.rfind_byte(&this, b'\0'), None);
+    }
+}
+
+#[test]
+fn split_field_ptr_method() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    {
+        type This = ReprC<u8, u32, [u16; 3], u64>;
+        let this: This = ReprC {
+            a: 3,
+            b: 5,
+            c: [8, 13, 21],
+            d: 34,
+        };
+        let base = &this as *const This as *const u8;
+
+        unsafe {
+            let (a, after_a) = This::OFFSET_A.split_field_ptr(&this);
+            assert_eq!(*a, 3);
+            assert_eq!(after_a, base.add(1));
+
+            let (b, after_b) = This::OFFSET_B.split_field_ptr(&this);
+            assert_eq!(*b, 5);
+            assert_eq!(after_b, base.add(8));
+            assert_eq!(after_b, This::OFFSET_C.get_ptr(&this) as *const u8);
+
+            let (c, after_c) = This::OFFSET_C.split_field_ptr(&this);
+            assert_eq!(*c, [8, 13, 21]);
+            assert_eq!(after_c, base.add(14));
+
+            // There's padding between `c` and `d`
+            let (d, after_d) = This::OFFSET_D.split_field_ptr(&this);
+            assert_eq!(d as *const u8, base.add(16));
+            assert_eq!(*d, 34);
+            assert_eq!(after_d, base.add(std::mem::size_of::<This>()));
+        }
+    }
+    {
+        // Parsing a sequence of headers laid out back to back in a buffer.
+        type Header = ReprPacked<u8, u16, [u8; 2], ()>;
+
+        let buffer: [u8; 10] = [3, 5, 0, b'a', b'b', 8, 13, 0, b'c', b'd'];
+        let mut cursor = buffer.as_ptr();
+        let mut parsed = Vec::new();
+        unsafe {
+            while cursor < buffer.as_ptr().add(buffer.len()) {
+                let header = cursor as *const Header;
+                let (kind, _) = Header::OFFSET_A.split_field_ptr(header);
+                let (len, after_len) = Header::OFFSET_B.split_field_ptr(header);
+                let (name, after_name) = Header::OFFSET_C.split_field_ptr(header);
+                assert_eq!(after_len, name as *const u8);
+                parsed.push((*kind, len.read_unaligned(), *name));
+                cursor = after_name;
+            }
+        }
+        assert_eq!(cursor, buffer.as_ptr().wrapping_add(10));
+        assert_eq!(
+            parsed,
+            vec![
+                (3, u16::from_ne_bytes([5, 0]), *b"ab"),
+                (8, u16::from_ne_bytes([13, 0]), *b"cd"),
+            ]
+        );
+    }
+}
+
+#[test]
+fn field_min_max_methods() {
+    use repr_offset::for_examples::{ReprC, ReprPacked};
+
+    {
+        type This = ReprC<u8, u32, (), u16>;
+        let new = |a: u8, b: u32| ReprC { a, b, c: (), d: 0 };
+        let list: [This; 5] = [new(0, 8), new(1, 3), new(2, 13), new(3, 3), new(4, 13)];
+
+        let min = This::OFFSET_B.field_min(&list).unwrap();
+        let max = This::OFFSET_B.field_max(&list).unwrap();
+        assert_eq!((*min, *max), (3, 13));
+
+        // Ties return the first minimum and the last maximum
+        assert!(std::ptr::eq(min, &list[1].b));
+        assert!(std::ptr::eq(max, &list[4].b));
+
+        assert_eq!(This::OFFSET_A.field_min(&list), Some(&0));
+        assert_eq!(This::OFFSET_A.field_max(&list), Some(&4));
+
+        assert_eq!(This::OFFSET_B.field_min(&list[..1]), Some(&8));
+        assert_eq!(This::OFFSET_B.field_max(&list[..1]), Some(&8));
+
+        assert_eq!(This::OFFSET_B.field_min(&[]), None);
+        assert_eq!(This::OFFSET_B.field_max(&[]), None);
+    }
+    {
+        type This = ReprPacked<u8, u32, (), i16>;
+        let new = |b: u32, d: i16| ReprPacked { a: 0, b, c: (), d };
+        let list: [This; 4] = [new(8, -5), new(3, 8), new(13, -13), new(5, 0)];
+
+        assert_eq!(This::OFFSET_B.field_min(&list), Some(3));
+        assert_eq!(This::OFFSET_B.field_max(&list), Some(13));
+        assert_eq!(This::OFFSET_D.field_min(&list), Some(-13));
+        assert_eq!(This::OFFSET_D.field_max(&list), Some(8));
+
+        assert_eq!(This::OFFSET_B.field_min(&[]), None);
+        assert_eq!(This::OFFSET_B.field_max(&[]), None);
+    }
+}
+
+#[test]
+fn field_type_id_and_downcast() {
+    use repr_offset::FieldOffsetDyn;
+    use std::any::TypeId;
+
+    type This = StructReprC<(), (u8, u32, String, [u16; 2]), (), ()>;
+    type Packed = StructPacked<(), (u8, u32, String, [u16; 2]), (), ()>;
+
+    assert_eq!(This::OFFSET_A.field_type_id(), TypeId::of::<u8>());
+    assert_eq!(This::OFFSET_B.field_type_id(), TypeId::of::<u32>());
+    assert_eq!(This::OFFSET_C.field_type_id(), TypeId::of::<String>());
+    assert_eq!(Packed::OFFSET_D.field_type_id(), TypeId::of::<[u16; 2]>());
+    assert_ne!(This::OFFSET_B.field_type_id(), TypeId::of::<i32>());
+
+    let offsets: [FieldOffsetDyn<_>; 4] = [
+        This::OFFSET_A.to_dyn_typed(),
+        This::OFFSET_B.to_dyn_typed(),
+        This::OFFSET_C.to_dyn_typed(),
+        This::OFFSET_D.to_dyn_typed(),
+    ];
+    for (typed, untyped) in offsets.iter().zip(&[
+        This::OFFSET_A.to_dyn(),
+        This::OFFSET_B.to_dyn(),
+        This::OFFSET_C.to_dyn(),
+        This::OFFSET_D.to_dyn(),
+    ]) {
+        assert!(typed.is_typed());
+        assert!(!untyped.is_typed());
+        assert_eq!(typed, untyped);
+        assert_eq!(typed.offset(), untyped.offset());
+        assert_eq!(typed.size(), untyped.size());
+    }
+
+    assert_eq!(offsets[0].downcast::<u8, Aligned>(), Some(This::OFFSET_A));
+    assert_eq!(
+        offsets[2].downcast::<String, Aligned>(),
+        Some(This::OFFSET_C)
+    );
+    assert_eq!(
+        offsets[3].downcast::<[u16; 2], Aligned>(),
+        Some(This::OFFSET_D)
+    );
+
+    // Wrong field type
+    assert_eq!(offsets[0].downcast::<i8, Aligned>(), None);
+    assert_eq!(offsets[1].downcast::<u8, Aligned>(), None);
+    assert_eq!(offsets[3].downcast::<[u16; 3], Aligned>(), None);
+    // Wrong alignment
+    assert_eq!(offsets[1].downcast::<u32, Unaligned>(), None);
+    // Untyped offsets never downcast
+    assert_eq!(This::OFFSET_B.to_dyn().downcast::<u32, Aligned>(), None);
+
+    let packed_b = Packed::OFFSET_B.to_dyn_typed();
+    assert_eq!(packed_b.downcast::<u32, Aligned>(), None);
+    let packed_b = packed_b.downcast::<u32, Unaligned>().unwrap();
+    assert_eq!(packed_b, Packed::OFFSET_B);
+    assert_eq!(packed_b.offset(), 1);
+}
+
+#[test]
+fn unsized_struct_methods() {
+    use std::fmt::Debug;
+
+    #[repr(C)]
+    struct Message<T: ?Sized> {
+        kind: u16,
+        len: u32,
+        bytes: T,
+    }
+
+    unsafe {
+        let kind = FieldOffset::<Message<[u8]>, u16, Aligned>::new(0);
+        let len = FieldOffset::<Message<[u8]>, u32, Aligned>::new(4);
+
+        let mut message = Message {
+            kind: 3,
+            len: 5,
+            bytes: [8u8, 13, 21],
+        };
+        let message: &mut Message<[u8]> = &mut message;
+
+        assert_eq!(kind.get(message), &3);
+        assert_eq!(len.get(message), &5);
+        assert_eq!(*len.raw_get(message), 5);
+        assert_eq!(len.get_ptr(message), &message.len as *const u32);
+        assert_eq!(len.read(message), 5);
+        assert_eq!(len.read_copy(message), 5);
+
+        *kind.get_mut(message) += 10;
+        len.write(message, 100);
+        assert_eq!(kind.replace(message, 55), 13);
+        assert_eq!(kind.replace_mut(message, 89), 55);
+        *len.raw_get_mut(message) += 1;
+        assert_eq!(message.kind, 89);
+        assert_eq!(message.len, 101);
+        assert_eq!(message.bytes, [8, 13, 21]);
+    }
+
+    unsafe {
+        let kind = FieldOffset::<Message<dyn Debug>, u16, Aligned>::new(0);
+        let len = FieldOffset::<Message<dyn Debug>, u32, Aligned>::new(4);
+
+        let mut message = Message {
+            kind: 3,
+            len: 5,
+            bytes: "hello",
+        };
+        let message: &mut Message<dyn Debug> = &mut message;
+
+        assert_eq!(kind.get(message), &3);
+        *len.get_mut(message) *= 2;
+        assert_eq!(len.read(message), 10);
+        assert_eq!(format!("{:?}", &message.bytes), "\"hello\"");
+    }
+
+    unsafe {
+        // Treating the field as unaligned, to test the `Unaligned` methods.
+        let len = FieldOffset::<Message<[u8]>, u32, Unaligned>::new(4);
+
+        let mut message = Message {
+            kind: 3,
+            len: 5,
+            bytes: [8u8, 13],
+        };
+        let message: &mut Message<[u8]> = &mut message;
+
+        assert_eq!(len.get_copy(message), 5);
+        assert_eq!(len.read(message), 5);
+        len.write(message, 21);
+        assert_eq!(len.replace_mut(message, 34), 21);
+        assert_eq!(len.read_copy(message), 34);
+        assert_eq!(message.len, 34);
+    }
+}
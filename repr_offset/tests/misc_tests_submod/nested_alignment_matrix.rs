@@ -0,0 +1,218 @@
+//! Tests composing offsets through 3 levels of nested structs,
+//! with every combination of aligned and packed structs at each level.
+
+use repr_offset::{
+    for_examples::{ReprAlign16, ReprAlign4, ReprC, ReprPacked, ReprPacked2},
+    Aligned, FieldOffset, OFF, PUB_OFF,
+};
+
+use std::any::TypeId;
+
+/// The layout attributes of a struct, used to compute the expected offsets of fields
+/// independently of the library.
+trait LayoutAttrs {
+    /// `Some(n)` for `#[repr(packed(n))]` structs.
+    const PACK: Option<usize>;
+    /// The `n` in `#[repr(align(n))]`, 1 if there's no `align` attribute.
+    const MIN_ALIGN: usize;
+}
+
+macro_rules! impl_layout_attrs {
+    ($($struct:ident = ($pack:expr, $min_align:expr),)*) => {
+        $(
+            impl<A, B, C, D> LayoutAttrs for $struct<A, B, C, D> {
+                const PACK: Option<usize> = $pack;
+                const MIN_ALIGN: usize = $min_align;
+            }
+        )*
+    };
+}
+
+impl_layout_attrs! {
+    ReprC = (None, 1),
+    ReprAlign4 = (None, 4),
+    ReprAlign16 = (None, 16),
+    ReprPacked = (Some(1), 1),
+    ReprPacked2 = (Some(2), 1),
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Layout {
+    size: usize,
+    align: usize,
+}
+
+impl Layout {
+    fn of<T>() -> Self {
+        Self {
+            size: std::mem::size_of::<T>(),
+            align: std::mem::align_of::<T>(),
+        }
+    }
+}
+
+/// Computes the offsets of the fields, and the layout, of a `#[repr(C)]` struct.
+fn repr_c_layout<S: LayoutAttrs>(fields: &[Layout]) -> (Vec<usize>, Layout) {
+    let field_align = |field: &Layout| match S::PACK {
+        Some(pack) => field.align.min(pack),
+        None => field.align,
+    };
+    let round_up = |x: usize, align: usize| (x + align - 1) & !(align - 1);
+
+    let mut offsets = Vec::new();
+    let mut end = 0;
+    let mut align = S::MIN_ALIGN;
+    for field in fields {
+        let offset = round_up(end, field_align(field));
+        offsets.push(offset);
+        end = offset + field.size;
+        align = align.max(field_align(field));
+    }
+    let size = round_up(end, align);
+    (offsets, Layout { size, align })
+}
+
+fn is_aligned<S, F, A: 'static>(_: FieldOffset<S, F, A>) -> bool {
+    TypeId::of::<A>() == TypeId::of::<Aligned>()
+}
+
+macro_rules! test_matrix {
+    (levels $levels:tt) => {
+        test_matrix!(@outer $levels $levels $levels)
+    };
+    (@outer [$($outer:ident)*] $middles:tt $inners:tt) => {
+        $( test_matrix!(@middle $outer $middles $inners); )*
+    };
+    (@middle $outer:ident [$($middle:ident)*] $inners:tt) => {
+        $( test_matrix!(@inner $outer $middle $inners); )*
+    };
+    (@inner $outer:ident $middle:ident [$($inner:ident)*]) => {
+        $( test_combination!($outer, $middle, $inner); )*
+    };
+}
+
+macro_rules! test_combination {
+    ($outer:ident, $middle:ident, $inner:ident) => {{
+        type Inner = $inner<u8, u32, u16, u64>;
+        type Middle = $middle<u8, Inner, u16, u8>;
+        type Outer = $outer<u16, Middle, u8, u32>;
+
+        let context = (
+            stringify!($outer),
+            stringify!($middle),
+            stringify!($inner),
+        );
+
+        let (inner_offs, inner_layout) = repr_c_layout::<Inner>(&[
+            Layout::of::<u8>(),
+            Layout::of::<u32>(),
+            Layout::of::<u16>(),
+            Layout::of::<u64>(),
+        ]);
+        let (middle_offs, middle_layout) = repr_c_layout::<Middle>(&[
+            Layout::of::<u8>(),
+            inner_layout,
+            Layout::of::<u16>(),
+            Layout::of::<u8>(),
+        ]);
+        let (outer_offs, outer_layout) = repr_c_layout::<Outer>(&[
+            Layout::of::<u16>(),
+            middle_layout,
+            Layout::of::<u8>(),
+            Layout::of::<u32>(),
+        ]);
+        assert_eq!(inner_layout.size, std::mem::size_of::<Inner>(), "{:?}", context);
+        assert_eq!(middle_layout.size, std::mem::size_of::<Middle>(), "{:?}", context);
+        assert_eq!(outer_layout.size, std::mem::size_of::<Outer>(), "{:?}", context);
+
+        let outer_aligned = <Outer as LayoutAttrs>::PACK.is_none();
+        let middle_aligned = <Middle as LayoutAttrs>::PACK.is_none();
+        let inner_aligned = <Inner as LayoutAttrs>::PACK.is_none();
+
+        let to_middle = Outer::OFFSET_B;
+        let to_inner = to_middle + Middle::OFFSET_B;
+
+        assert_eq!(to_middle.offset(), outer_offs[1], "{:?}", context);
+        assert_eq!(to_inner.offset(), outer_offs[1] + middle_offs[1], "{:?}", context);
+        assert_eq!(is_aligned(to_middle), outer_aligned, "{:?}", context);
+        assert_eq!(
+            is_aligned(to_inner),
+            outer_aligned && middle_aligned,
+            "{:?}",
+            context
+        );
+
+        let all_aligned = outer_aligned && middle_aligned && inner_aligned;
+        let leaf_a = to_inner + Inner::OFFSET_A;
+        let leaf_b = to_inner + Inner::OFFSET_B;
+        let leaf_c = to_inner + Inner::OFFSET_C;
+        let leaf_d = to_inner + Inner::OFFSET_D;
+
+        let base = outer_offs[1] + middle_offs[1];
+        assert_eq!(leaf_a.offset(), base + inner_offs[0], "{:?}", context);
+        assert_eq!(leaf_b.offset(), base + inner_offs[1], "{:?}", context);
+        assert_eq!(leaf_c.offset(), base + inner_offs[2], "{:?}", context);
+        assert_eq!(leaf_d.offset(), base + inner_offs[3], "{:?}", context);
+
+        assert_eq!(is_aligned(leaf_a), all_aligned, "{:?}", context);
+        assert_eq!(is_aligned(leaf_b), all_aligned, "{:?}", context);
+        assert_eq!(is_aligned(leaf_c), all_aligned, "{:?}", context);
+        assert_eq!(is_aligned(leaf_d), all_aligned, "{:?}", context);
+
+        // The macros must produce the same offsets as composing them by hand.
+        assert_eq!(OFF!(Outer; b.b.a), leaf_a, "{:?}", context);
+        assert_eq!(OFF!(Outer; b.b.b), leaf_b, "{:?}", context);
+        assert_eq!(PUB_OFF!(Outer; b.b.c), leaf_c, "{:?}", context);
+        assert_eq!(PUB_OFF!(Outer; b.b.d), leaf_d, "{:?}", context);
+        assert_eq!(is_aligned(OFF!(Outer; b.b.d)), all_aligned, "{:?}", context);
+        assert_eq!(is_aligned(PUB_OFF!(Outer; b.c)), outer_aligned && middle_aligned);
+
+        let this: Outer = $outer {
+            a: 1,
+            b: $middle {
+                a: 2,
+                b: $inner {
+                    a: 3,
+                    b: 5,
+                    c: 8,
+                    d: 13,
+                },
+                c: 21,
+                d: 34,
+            },
+            c: 55,
+            d: 89,
+        };
+        assert_eq!(leaf_a.get_copy(&this), 3, "{:?}", context);
+        assert_eq!(leaf_b.get_copy(&this), 5, "{:?}", context);
+        assert_eq!(leaf_c.get_copy(&this), 8, "{:?}", context);
+        assert_eq!(leaf_d.get_copy(&this), 13, "{:?}", context);
+        assert_eq!((to_middle + Middle::OFFSET_C).get_copy(&this), 21, "{:?}", context);
+        assert_eq!((to_middle + Middle::OFFSET_D).get_copy(&this), 34, "{:?}", context);
+    }};
+}
+
+#[test]
+fn nested_alignment_matrix() {
+    test_matrix! {
+        levels[ReprC ReprAlign4 ReprPacked ReprPacked2]
+    }
+}
+
+// `#[repr(packed)]` structs can't contain `#[repr(align(N))]` structs,
+// so `ReprAlign16` is only tested in levels that don't have a packed struct above.
+#[test]
+fn nested_alignment_matrix_align16() {
+    test_matrix! {
+        @outer
+        [ReprC ReprAlign4 ReprAlign16]
+        [ReprC ReprAlign4 ReprAlign16]
+        [ReprC ReprAlign4 ReprAlign16 ReprPacked ReprPacked2]
+    }
+    test_matrix! {
+        @outer
+        [ReprC ReprAlign4 ReprAlign16]
+        [ReprPacked ReprPacked2]
+        [ReprC ReprAlign4 ReprPacked ReprPacked2]
+    }
+}
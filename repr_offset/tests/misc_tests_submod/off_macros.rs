@@ -46,6 +46,76 @@ const _CONST_EXECUTABLE: () = {
     let _: FieldOffset<ReprC, (), Aligned> = PUB_OFF!(ReprC; b);
 };
 
+mod nested_const {
+    use super::*;
+
+    use repr_offset::{for_examples::ReprPacked, Unaligned};
+
+    type Leaf = ReprC<u8, u64, u16, ()>;
+    type Inner = ReprC<u32, u8, Leaf, ()>;
+    type Middle = ReprC<Inner, u16, (), ()>;
+    type Outer = ReprC<u8, u32, Middle, ()>;
+
+    const OFF_NESTED: FieldOffset<Outer, u16, Aligned> = OFF!(Outer; c.a.c.c);
+    const PUB_OFF_NESTED: FieldOffset<Outer, u16, Aligned> = PUB_OFF!(Outer; c.a.c.c);
+    const ADDED: FieldOffset<Outer, u16, Aligned> = Outer::OFFSET_C
+        .add(Middle::OFFSET_A)
+        .add(Inner::OFFSET_C)
+        .add(Leaf::OFFSET_C);
+
+    type PLeaf = ReprPacked<u8, u64, u16, ()>;
+    type PInner = ReprC<u32, u8, PLeaf, ()>;
+    type PMiddle = ReprC<PInner, u16, (), ()>;
+    type POuter = ReprC<u8, u32, PMiddle, ()>;
+
+    const PACKED_NESTED: FieldOffset<POuter, u64, Unaligned> = OFF!(POuter; c.a.c.b);
+    const PACKED_ADDED: FieldOffset<POuter, u64, Unaligned> = POuter::OFFSET_C
+        .add(PMiddle::OFFSET_A)
+        .add(PInner::OFFSET_C)
+        .add(PLeaf::OFFSET_B);
+
+    // Using the nested offsets at compile-time
+    const BYTES: [u8; OFF_NESTED.offset()] = [0; OFF_NESTED.offset()];
+    const PACKED_BYTES: [u8; PACKED_NESTED.offset()] = [0; PACKED_ADDED.offset()];
+
+    #[test]
+    fn nested_const_offsets() {
+        assert_eq!(OFF_NESTED, ADDED);
+        assert_eq!(PUB_OFF_NESTED, ADDED);
+        assert_eq!(OFF_NESTED.offset(), 8 + 8 + 16);
+        assert_eq!(BYTES.len(), 32);
+
+        assert_eq!(PACKED_NESTED, PACKED_ADDED);
+        assert_eq!(PACKED_NESTED.offset(), 8 + 5 + 1);
+        assert_eq!(PACKED_BYTES.len(), 14);
+
+        let mut this: POuter = ReprC {
+            a: 0,
+            b: 0,
+            c: ReprC {
+                a: ReprC {
+                    a: 0,
+                    b: 0,
+                    c: ReprPacked {
+                        a: 0,
+                        b: 3,
+                        c: 0,
+                        d: (),
+                    },
+                    d: (),
+                },
+                b: 0,
+                c: (),
+                d: (),
+            },
+            d: (),
+        };
+        assert_eq!(PACKED_NESTED.get_copy(&this), 3);
+        unsafe { PACKED_NESTED.write(&mut this, 5) };
+        assert_eq!({ this.c.a.c.b }, 5);
+    }
+}
+
 #[test]
 fn capitalized_off_macro() {
     {
@@ -85,3 +155,228 @@ fn capitalized_off_macro() {
         assert_eq!(foo.f_get(PUB_OFF!(ReprC; d)), &Some(13));
     }
 }
+
+// Passing types with defaulted type parameters as `Type<>`,
+// which doesn't need the type parameters to be inferred from context.
+#[test]
+fn defaulted_type_parameters() {
+    type Unit = ReprC<(), (), (), ()>;
+
+    let off_a = OFF!(ReprC<>; a);
+    let _: FieldOffset<Unit, (), Aligned> = off_a;
+    assert_eq!(off_a.offset(), 0);
+    assert_eq!(PUB_OFF!(ReprC<>; d).offset(), 0);
+
+    assert_eq!(OFF!(repr_offset::for_examples::ReprC<>; b), OFF!(Unit; b));
+    assert_eq!(
+        PUB_OFF!(::repr_offset::for_examples::ReprC<>; c),
+        PUB_OFF!(Unit; c)
+    );
+
+    // Only some of the type parameters are passed,
+    // the rest use their defaults.
+    {
+        let off_b = OFF!(ReprC<u64, u8>; b);
+        let _: FieldOffset<ReprC<u64, u8, (), ()>, u8, Aligned> = off_b;
+        assert_eq!(off_b.offset(), 8);
+
+        let off_c = PUB_OFF!(ReprC<u64, u8>; c);
+        let _: FieldOffset<ReprC<u64, u8, (), ()>, (), Aligned> = off_c;
+        assert_eq!(off_c.offset(), 9);
+    }
+
+    // Nested fields
+    {
+        let off = OFF!(ReprC<u8, RFooInner>; b.d);
+        let _: FieldOffset<ReprC<u8, RFooInner, (), ()>, i128, Aligned> = off;
+        assert_eq!(off.offset(), OFF!(RFoo; b.d).offset());
+        assert_eq!(off, PUB_OFF!(ReprC<u8, RFooInner>; b.d));
+    }
+}
+
+// Accessing the fields of a type parameter through a (possibly nested) field
+// of a concrete generic struct.
+#[test]
+fn nested_fields_of_type_parameter() {
+    use repr_offset::{
+        alignment::Alignment, for_examples::ReprPacked, tstr::TS, GetPubFieldOffset, ROExtOps,
+    };
+
+    type Inner = ReprC<u8, u32, u16, ()>;
+
+    fn one_level<T, A>(this: &ReprC<u64, T, (), ()>) -> u32
+    where
+        T: GetPubFieldOffset<TS!(b), Type = u32, Alignment = A>,
+        A: Alignment,
+        ReprC<u64, T, (), ()>: ROExtOps<A>,
+    {
+        let offset = PUB_OFF!(ReprC<u64, T, (), ()>; b.b);
+        assert_eq!(
+            offset.offset(),
+            8 + <T as GetPubFieldOffset<TS!(b)>>::OFFSET.offset()
+        );
+        assert_eq!(offset, pub_off!(*this; b.b));
+        this.f_get_copy(offset)
+    }
+
+    fn two_levels<T, A>(this: &ReprC<u8, ReprC<u16, T, (), ()>, (), ()>) -> u32
+    where
+        T: GetPubFieldOffset<TS!(b), Type = u32, Alignment = A>,
+        A: Alignment,
+        ReprC<u8, ReprC<u16, T, (), ()>, (), ()>: ROExtOps<A>,
+    {
+        this.f_get_copy(PUB_OFF!(ReprC<u8, ReprC<u16, T, (), ()>, (), ()>; b.b.b))
+    }
+
+    // The field is always unaligned inside the packed struct,
+    // but the alignment of `T`'s field still needs to be bounded by `Alignment`.
+    fn in_packed<T, A>(this: &ReprPacked<u8, T, (), ()>) -> u32
+    where
+        T: GetPubFieldOffset<TS!(b), Type = u32, Alignment = A>,
+        A: Alignment,
+    {
+        let offset = pub_off!(*this; b.b);
+        assert_eq!(
+            offset.offset(),
+            1 + <T as GetPubFieldOffset<TS!(b)>>::OFFSET.offset()
+        );
+        this.f_get_copy(offset)
+    }
+
+    let inner: Inner = ReprC {
+        a: 3,
+        b: 5,
+        c: 8,
+        d: (),
+    };
+
+    let this = ReprC {
+        a: 13u64,
+        b: inner,
+        c: (),
+        d: (),
+    };
+    assert_eq!(one_level(&this), 5);
+
+    let this = ReprC {
+        a: 21u8,
+        b: ReprC {
+            a: 34u16,
+            b: inner,
+            c: (),
+            d: (),
+        },
+        c: (),
+        d: (),
+    };
+    assert_eq!(two_levels(&this), 5);
+
+    let this = ReprPacked {
+        a: 55u8,
+        b: inner,
+        c: (),
+        d: (),
+    };
+    assert_eq!(in_packed(&this), 5);
+}
+
+mod reference_fields {
+    use super::*;
+
+    use repr_offset::{
+        for_examples::ReprPacked, get_field_offset::GetFieldOffset, tstr::TS, GetPubFieldOffset,
+        ROExtOps,
+    };
+
+    type Refs<'a, 'b> = ReprC<&'a u32, &'b mut u32, ReprC<u8, &'a str, (), ()>, ()>;
+
+    fn get_ref<'a>(this: &Refs<'a, '_>) -> &'a u32 {
+        this.f_get_copy(off!(*this; a))
+    }
+
+    fn get_ref_inferred<'a>(this: &Refs<'a, '_>) -> &'a u32 {
+        this.f_get_copy(off!(a))
+    }
+
+    fn get_nested<'a>(this: &Refs<'a, '_>) -> &'a str {
+        let _: FieldOffset<Refs<'a, '_>, &'a str, Aligned> = OFF!(Refs<'a, '_>; c.b);
+        let _: FieldOffset<Refs<'a, '_>, &'a str, Aligned> = PUB_OFF!(ReprC; c.b);
+        this.f_get_copy(off!(*this; c.b))
+    }
+
+    fn increment<'a, 'b>(this: &mut Refs<'a, 'b>) -> &'a u32
+    where
+        'a: 'b,
+    {
+        **this.f_get_mut(off!(b)) += 1;
+        **this.f_get_mut(pub_off!(*this; b)) += 1;
+        this.f_replace(off!(*this; a), this.a)
+    }
+
+    fn offsets<'a, 'b>() -> (
+        FieldOffset<Refs<'a, 'b>, &'a u32, Aligned>,
+        FieldOffset<Refs<'a, 'b>, &'b mut u32, Aligned>,
+    ) {
+        (off!(a), pub_off!(b))
+    }
+
+    fn generic<'a, T, A>(this: &T) -> &'a u32
+    where
+        T: GetPubFieldOffset<TS!(a), Type = &'a u32, Alignment = A> + ROExtOps<A>,
+    {
+        this.f_get_copy(pub_off!(a))
+    }
+
+    fn packed<'a>(this: &ReprPacked<&'a u32, u8, &'a str, ()>) -> (&'a u32, &'a str) {
+        (this.f_get_copy(off!(a)), this.f_get_copy(off!(*this; c)))
+    }
+
+    fn associated_type<'a, 'b>(
+        this: Refs<'a, 'b>,
+    ) -> <Refs<'a, 'b> as GetFieldOffset<TS!(a)>>::Type {
+        this.a
+    }
+
+    #[test]
+    fn reference_fields_test() {
+        let number = 3u32;
+        let mut other = 5u32;
+        let string = String::from("hello");
+
+        let a: &u32;
+        {
+            let mut this: Refs<'_, '_> = ReprC {
+                a: &number,
+                b: &mut other,
+                c: ReprC {
+                    a: 0,
+                    b: &string[1..],
+                    c: (),
+                    d: (),
+                },
+                d: (),
+            };
+            assert_eq!(get_ref(&this), &3);
+            assert_eq!(get_ref_inferred(&this), &3);
+            assert_eq!(get_nested(&this), "ello");
+            assert_eq!(generic(&this), &3);
+
+            assert_eq!(increment(&mut this), &3);
+            a = associated_type(this);
+        }
+        assert_eq!(a, &3);
+        assert_eq!(other, 7);
+
+        let (off_a, off_b) = offsets();
+        assert_eq!(off_a.offset(), 0);
+        assert_eq!(off_b.offset(), std::mem::size_of::<&u32>());
+
+        let packed_ = ReprPacked {
+            a: &number,
+            b: 0,
+            c: &string[..2],
+            d: (),
+        };
+        assert_eq!(packed(&packed_), (&3, "he"));
+    }
+}
@@ -1,4 +1,4 @@
-use repr_offset::{unsafe_struct_field_offsets, Aligned};
+use repr_offset::{unsafe_struct_field_offsets, Aligned, Unaligned};
 
 #[repr(C)]
 pub struct Foo {
@@ -88,3 +88,212 @@ fn offsets_macro_params() {
     assert_eq!(AttributeConsts::OFFSET_BAR, Foo::OFFSET_BAR,);
     assert_eq!(AttributeConsts::OFFSET_BAZ, "nope");
 }
+
+#[repr(C, packed(2))]
+pub struct Packed2 {
+    pub a: u8,
+    pub b: u32,
+    pub c: u16,
+    pub d: u64,
+}
+
+unsafe_struct_field_offsets! {
+    alignment =  Unaligned,
+    packing = 2,
+
+    impl[] Packed2 {
+        pub const OFFSET_A, a: u8;
+        pub const OFFSET_B, b: u32;
+        pub const OFFSET_C, c: u16;
+        pub const OFFSET_D, d: u64;
+    }
+}
+
+#[repr(C, packed(4))]
+pub struct Packed4 {
+    pub a: u8,
+    pub b: u64,
+    pub c: u16,
+    pub d: u32,
+    pub e: u8,
+}
+
+unsafe_struct_field_offsets! {
+    alignment =  Unaligned,
+    packing = 4,
+    usize_offsets = true,
+
+    impl[] Packed4 {
+        pub const OFFSET_A, a: u8;
+        pub const OFFSET_B, b: u64;
+        pub const OFFSET_C, c: u16;
+        pub const OFFSET_D, d: u32;
+        pub const OFFSET_E, e: u8;
+    }
+}
+
+#[repr(C, align(8))]
+pub struct Wrapper {
+    pub pad: u16,
+    pub inner: Packed2,
+}
+
+unsafe_struct_field_offsets! {
+    alignment =  Aligned,
+
+    impl[] Wrapper {
+        pub const OFFSET_PAD, pad: u16;
+        pub const OFFSET_INNER, inner: Packed2;
+    }
+}
+
+pub struct InWrapperConsts;
+
+// The offsets of the fields in `Packed2`, relative to the start of `Wrapper`.
+unsafe_struct_field_offsets! {
+    Self = Wrapper,
+    alignment =  Unaligned,
+    packing = 2,
+    starting_offset = Wrapper::OFFSET_INNER.offset(),
+
+    impl[] InWrapperConsts {
+        pub const OFFSET_A, a: u8;
+        pub const OFFSET_B, b: u32;
+        pub const OFFSET_C, c: u16;
+        pub const OFFSET_D, d: u64;
+    }
+}
+
+pub struct InWrapperUsizeConsts;
+
+unsafe_struct_field_offsets! {
+    Self = Wrapper,
+    alignment =  Unaligned,
+    packing = 2,
+    starting_offset = 2,
+    usize_offsets = true,
+
+    impl[] InWrapperUsizeConsts {
+        pub const OFFSET_A, a: u8;
+        pub const OFFSET_B, b: u32;
+        pub const OFFSET_C, c: u16;
+        pub const OFFSET_D, d: u64;
+    }
+}
+
+#[test]
+fn packing_param() {
+    assert_eq!(Packed2::OFFSET_A.offset(), 0);
+    assert_eq!(Packed2::OFFSET_B.offset(), 2);
+    assert_eq!(Packed2::OFFSET_C.offset(), 6);
+    assert_eq!(Packed2::OFFSET_D.offset(), 8);
+    assert_eq!(core::mem::size_of::<Packed2>(), 16);
+
+    assert_eq!(Packed4::OFFSET_A, 0);
+    assert_eq!(Packed4::OFFSET_B, 4);
+    assert_eq!(Packed4::OFFSET_C, 12);
+    assert_eq!(Packed4::OFFSET_D, 16);
+    assert_eq!(Packed4::OFFSET_E, 20);
+    assert_eq!(core::mem::size_of::<Packed4>(), 24);
+
+    let mut this = Packed2 {
+        a: 3,
+        b: 5,
+        c: 8,
+        d: 13,
+    };
+    assert_eq!(Packed2::OFFSET_A.get_copy(&this), 3);
+    assert_eq!(Packed2::OFFSET_B.get_copy(&this), 5);
+    assert_eq!(Packed2::OFFSET_C.get_copy(&this), 8);
+    assert_eq!(Packed2::OFFSET_D.get_copy(&this), 13);
+
+    Packed2::OFFSET_D.replace_mut(&mut this, 21);
+    assert_eq!({ this.d }, 21);
+}
+
+#[test]
+fn starting_offset_param() {
+    let nested_offsets = [
+        (Wrapper::OFFSET_INNER + Packed2::OFFSET_A).offset(),
+        (Wrapper::OFFSET_INNER + Packed2::OFFSET_B).offset(),
+        (Wrapper::OFFSET_INNER + Packed2::OFFSET_C).offset(),
+        (Wrapper::OFFSET_INNER + Packed2::OFFSET_D).offset(),
+    ];
+    assert_eq!(nested_offsets, [2, 4, 8, 10]);
+
+    assert_eq!(
+        [
+            InWrapperConsts::OFFSET_A.offset(),
+            InWrapperConsts::OFFSET_B.offset(),
+            InWrapperConsts::OFFSET_C.offset(),
+            InWrapperConsts::OFFSET_D.offset(),
+        ],
+        nested_offsets,
+    );
+    assert_eq!(
+        [
+            InWrapperUsizeConsts::OFFSET_A,
+            InWrapperUsizeConsts::OFFSET_B,
+            InWrapperUsizeConsts::OFFSET_C,
+            InWrapperUsizeConsts::OFFSET_D,
+        ],
+        nested_offsets,
+    );
+
+    let this = Wrapper {
+        pad: 0,
+        inner: Packed2 {
+            a: 3,
+            b: 5,
+            c: 8,
+            d: 13,
+        },
+    };
+    assert_eq!(InWrapperConsts::OFFSET_A.get_copy(&this), 3);
+    assert_eq!(InWrapperConsts::OFFSET_B.get_copy(&this), 5);
+    assert_eq!(InWrapperConsts::OFFSET_C.get_copy(&this), 8);
+    assert_eq!(InWrapperConsts::OFFSET_D.get_copy(&this), 13);
+}
+
+#[cfg(feature = "priv_offset_of")]
+#[test]
+fn packing_and_starting_offset_match_offset_of() {
+    use core::mem::offset_of;
+
+    assert_eq!(Packed2::OFFSET_A.offset(), offset_of!(Packed2, a));
+    assert_eq!(Packed2::OFFSET_B.offset(), offset_of!(Packed2, b));
+    assert_eq!(Packed2::OFFSET_C.offset(), offset_of!(Packed2, c));
+    assert_eq!(Packed2::OFFSET_D.offset(), offset_of!(Packed2, d));
+
+    assert_eq!(Packed4::OFFSET_A, offset_of!(Packed4, a));
+    assert_eq!(Packed4::OFFSET_B, offset_of!(Packed4, b));
+    assert_eq!(Packed4::OFFSET_C, offset_of!(Packed4, c));
+    assert_eq!(Packed4::OFFSET_D, offset_of!(Packed4, d));
+    assert_eq!(Packed4::OFFSET_E, offset_of!(Packed4, e));
+
+    let inner = offset_of!(Wrapper, inner);
+    let nested_offsets = [
+        inner + offset_of!(Packed2, a),
+        inner + offset_of!(Packed2, b),
+        inner + offset_of!(Packed2, c),
+        inner + offset_of!(Packed2, d),
+    ];
+    assert_eq!(
+        [
+            InWrapperConsts::OFFSET_A.offset(),
+            InWrapperConsts::OFFSET_B.offset(),
+            InWrapperConsts::OFFSET_C.offset(),
+            InWrapperConsts::OFFSET_D.offset(),
+        ],
+        nested_offsets,
+    );
+    assert_eq!(
+        [
+            InWrapperUsizeConsts::OFFSET_A,
+            InWrapperUsizeConsts::OFFSET_B,
+            InWrapperUsizeConsts::OFFSET_C,
+            InWrapperUsizeConsts::OFFSET_D,
+        ],
+        nested_offsets,
+    );
+}
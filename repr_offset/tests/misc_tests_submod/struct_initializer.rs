@@ -0,0 +1,244 @@
+use repr_offset::{
+    for_examples::{ReprAlign4, ReprC, ReprPacked},
+    StructInitializer,
+};
+
+#[test]
+fn initialize_all_fields() {
+    {
+        let mut init = StructInitializer::<ReprC<u8, u64, String, Vec<u8>>>::new();
+        init.write(ReprC::OFFSET_A, 3)
+            .write(ReprC::OFFSET_B, 5)
+            .write(ReprC::OFFSET_C, "8".to_string())
+            .write(ReprC::OFFSET_D, vec![13, 21]);
+
+        let this = unsafe { init.assume_init() };
+        assert_eq!(this.a, 3);
+        assert_eq!(this.b, 5);
+        assert_eq!(this.c, "8");
+        assert_eq!(this.d, [13, 21]);
+    }
+    {
+        // Writing the fields out of order
+        let mut init = StructInitializer::<ReprC<u16, u8, u32, u8>>::default();
+        init.write(ReprC::OFFSET_D, 34)
+            .write(ReprC::OFFSET_B, 55)
+            .write(ReprC::OFFSET_C, 89)
+            .write(ReprC::OFFSET_A, 144);
+
+        let this = unsafe { init.assume_init() };
+        assert_eq!((this.a, this.b, this.c, this.d), (144, 55, 89, 34));
+    }
+    {
+        let mut init = StructInitializer::<ReprPacked<u8, u64, u16, u32>>::new();
+        init.write(ReprPacked::OFFSET_A, 3)
+            .write(ReprPacked::OFFSET_B, 5)
+            .write(ReprPacked::OFFSET_C, 8)
+            .write(ReprPacked::OFFSET_D, 13);
+
+        let this = unsafe { init.assume_init() };
+        assert_eq!(ReprPacked::OFFSET_A.get_copy(&this), 3);
+        assert_eq!(ReprPacked::OFFSET_B.get_copy(&this), 5);
+        assert_eq!(ReprPacked::OFFSET_C.get_copy(&this), 8);
+        assert_eq!(ReprPacked::OFFSET_D.get_copy(&this), 13);
+    }
+    {
+        let mut init = StructInitializer::<ReprAlign4<u8, (), u8, ()>>::new();
+        init.write(ReprAlign4::OFFSET_A, 3)
+            .write(ReprAlign4::OFFSET_B, ())
+            .write(ReprAlign4::OFFSET_C, 5)
+            .write(ReprAlign4::OFFSET_D, ());
+
+        let this = unsafe { init.assume_init() };
+        assert_eq!((this.a, this.c), (3, 5));
+    }
+}
+
+#[test]
+fn initialize_nested_fields() {
+    type Inner = ReprC<u32, u16, (), ()>;
+
+    let mut init = StructInitializer::<ReprC<u8, Inner, u64, ()>>::new();
+    init.write(ReprC::OFFSET_A, 3)
+        .write(ReprC::OFFSET_B + Inner::OFFSET_A, 5)
+        .write(ReprC::OFFSET_B + Inner::OFFSET_B, 8)
+        .write(ReprC::OFFSET_B + Inner::OFFSET_C, ())
+        .write(ReprC::OFFSET_B + Inner::OFFSET_D, ())
+        .write(ReprC::OFFSET_C, 13)
+        .write(ReprC::OFFSET_D, ());
+
+    let this = unsafe { init.assume_init() };
+    assert_eq!(this.a, 3);
+    assert_eq!((this.b.a, this.b.b), (5, 8));
+    assert_eq!(this.c, 13);
+}
+
+#[cfg(feature = "debug_offsets")]
+#[test]
+#[should_panic]
+fn missing_field() {
+    let mut init = StructInitializer::<ReprC<u8, u64, u32, u32>>::new();
+    init.write(ReprC::OFFSET_A, 3)
+        .write(ReprC::OFFSET_C, 8)
+        .write(ReprC::OFFSET_D, 13);
+
+    let _ = unsafe { init.assume_init() };
+}
+
+#[cfg(feature = "debug_offsets")]
+#[test]
+#[should_panic]
+fn missing_trailing_field() {
+    let mut init = StructInitializer::<ReprPacked<u8, u8, u8, u8>>::new();
+    init.write(ReprPacked::OFFSET_A, 3)
+        .write(ReprPacked::OFFSET_B, 5)
+        .write(ReprPacked::OFFSET_C, 8);
+
+    let _ = unsafe { init.assume_init() };
+}
+
+#[cfg(feature = "debug_offsets")]
+#[test]
+#[should_panic]
+fn field_written_twice() {
+    let mut init = StructInitializer::<ReprC<u8, u8, u8, u8>>::new();
+    init.write(ReprC::OFFSET_A, 3).write(ReprC::OFFSET_A, 5);
+}
+
+#[test]
+fn drops_written_fields_on_panic() {
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    let rc = Rc::new(());
+    let count = || Rc::strong_count(&rc);
+
+    {
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            let mut init = StructInitializer::<ReprC<Rc<()>, u64, Rc<()>, Rc<()>>>::new();
+            init.write(ReprC::OFFSET_A, rc.clone())
+                .write(ReprC::OFFSET_B, 5)
+                .write(ReprC::OFFSET_D, rc.clone());
+            assert_eq!(count(), 3);
+
+            let make_c = || -> Rc<()> { panic!("initialization failed") };
+            init.write(ReprC::OFFSET_C, make_c());
+            unsafe { init.assume_init() }
+        }));
+        assert!(res.is_err());
+        assert_eq!(count(), 1);
+    }
+    {
+        // Unaligned fields
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            let mut init = StructInitializer::<ReprPacked<u8, Rc<()>, Vec<Rc<()>>, u64>>::new();
+            init.write(ReprPacked::OFFSET_A, 3)
+                .write(ReprPacked::OFFSET_B, rc.clone())
+                .write(ReprPacked::OFFSET_C, vec![rc.clone(), rc.clone()]);
+            assert_eq!(count(), 4);
+
+            panic!("initialization failed");
+        }));
+        assert!(res.is_err());
+        assert_eq!(count(), 1);
+    }
+}
+
+#[test]
+fn drop_tracking() {
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let count = || Rc::strong_count(&rc);
+
+    {
+        // Dropping the initializer without calling `assume_init`
+        let mut init = StructInitializer::<ReprC<Rc<()>, Rc<()>, (), ()>>::new();
+        init.write(ReprC::OFFSET_B, rc.clone());
+        assert_eq!(count(), 2);
+        drop(init);
+        assert_eq!(count(), 1);
+    }
+    {
+        // `assume_init` doesn't drop the fields
+        let mut init = StructInitializer::<ReprC<Rc<()>, Rc<()>, (), ()>>::new();
+        init.write(ReprC::OFFSET_A, rc.clone())
+            .write(ReprC::OFFSET_B, rc.clone())
+            .write(ReprC::OFFSET_C, ())
+            .write(ReprC::OFFSET_D, ());
+        let this = unsafe { init.assume_init() };
+        assert_eq!(count(), 3);
+        drop(this);
+        assert_eq!(count(), 1);
+    }
+    #[cfg(not(feature = "debug_offsets"))]
+    {
+        // Overwriting a field leaks its previous value,
+        // and only the last value is dropped.
+        let leaked = Rc::new(());
+        let mut init = StructInitializer::<ReprC<Rc<()>, (), (), ()>>::new();
+        init.write(ReprC::OFFSET_A, leaked.clone())
+            .write(ReprC::OFFSET_A, rc.clone());
+        drop(init);
+        assert_eq!(count(), 1);
+        assert_eq!(Rc::strong_count(&leaked), 2);
+    }
+    #[cfg(not(feature = "debug_offsets"))]
+    {
+        // Overwriting a nested field forgets the field that contained it.
+        type Inner = ReprC<Rc<()>, Rc<()>, (), ()>;
+
+        let leaked = Rc::new(());
+        let mut init = StructInitializer::<ReprC<u8, Inner, (), ()>>::new();
+        let inner = ReprC {
+            a: leaked.clone(),
+            b: leaked.clone(),
+            c: (),
+            d: (),
+        };
+        init.write(ReprC::OFFSET_B, inner)
+            .write(ReprC::OFFSET_B + Inner::OFFSET_A, rc.clone());
+        drop(init);
+        assert_eq!(count(), 1);
+        assert_eq!(Rc::strong_count(&leaked), 3);
+    }
+    {
+        // Writing more than 16 fields that need dropping panics,
+        // dropping the fields that were written and the value that wasn't.
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        type Fields = [Rc<()>; 20];
+
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            let mut init = StructInitializer::<ReprC<Fields, (), (), ()>>::new();
+            for i in 0..20 {
+                let offset = unsafe { ReprC::OFFSET_A.cast_field::<Rc<()>>() };
+                let offset = unsafe { offset.checked_add_bytes(i * 8).unwrap() };
+                init.write(offset, rc.clone());
+                assert_eq!(count(), i + 2);
+            }
+        }));
+        assert!(res.is_err());
+        assert_eq!(count(), 1);
+    }
+    #[cfg(not(feature = "debug_offsets"))]
+    {
+        // Overwriting a tracked field when 16 fields are tracked doesn't panic,
+        // it leaks the previous value of the field.
+        type Fields = [Rc<()>; 16];
+
+        let mut init = StructInitializer::<ReprC<Fields, (), (), ()>>::new();
+        for i in 0..16 {
+            let offset = unsafe { ReprC::OFFSET_A.cast_field::<Rc<()>>() };
+            let offset = unsafe { offset.checked_add_bytes(i * 8).unwrap() };
+            init.write(offset, rc.clone());
+        }
+        let offset = unsafe { ReprC::OFFSET_A.cast_field::<Rc<()>>() };
+        init.write(offset, rc.clone());
+        assert_eq!(count(), 18);
+        drop(init);
+        assert_eq!(count(), 2);
+    }
+}
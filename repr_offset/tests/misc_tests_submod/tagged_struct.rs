@@ -0,0 +1,152 @@
+//! Tests the pattern of a `#[repr(C)]` struct with a tag field,
+//! and a union payload that the tag determines the active field of.
+
+use repr_offset::{Aligned, FieldOffset, Unaligned};
+
+#[cfg(feature = "derive")]
+use repr_offset::ReprOffset;
+
+#[cfg(not(feature = "derive"))]
+use repr_offset_derive::ReprOffset;
+
+const CIRCLE_TAG: u8 = 0;
+const RECT_TAG: u8 = 1;
+
+#[repr(C)]
+#[derive(ReprOffset)]
+struct Shape {
+    tag: u8,
+    payload: ShapePayload,
+}
+
+#[repr(C, packed)]
+#[derive(ReprOffset)]
+struct PackedShape {
+    tag: u8,
+    payload: ShapePayload,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+union ShapePayload {
+    circle: Circle,
+    rect: Rect,
+}
+
+impl ShapePayload {
+    // All the fields of a union are at offset 0.
+    const OFFSET_CIRCLE: FieldOffset<Self, Circle, Aligned> = unsafe { FieldOffset::new(0) };
+    const OFFSET_RECT: FieldOffset<Self, Rect, Aligned> = unsafe { FieldOffset::new(0) };
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, ReprOffset)]
+struct Circle {
+    radius: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, ReprOffset)]
+struct Rect {
+    width: u16,
+    height: u16,
+}
+
+const CIRCLE_RADIUS: FieldOffset<Shape, u32, Aligned> = Shape::OFFSET_PAYLOAD
+    .add(ShapePayload::OFFSET_CIRCLE)
+    .add(Circle::OFFSET_RADIUS);
+
+const RECT: FieldOffset<Shape, Rect, Aligned> =
+    Shape::OFFSET_PAYLOAD.add(ShapePayload::OFFSET_RECT);
+
+const RECT_HEIGHT: FieldOffset<Shape, u16, Aligned> = RECT.add(Rect::OFFSET_HEIGHT);
+
+const PACKED_RECT_HEIGHT: FieldOffset<PackedShape, u16, Unaligned> = PackedShape::OFFSET_PAYLOAD
+    .add(ShapePayload::OFFSET_RECT)
+    .add(Rect::OFFSET_HEIGHT);
+
+fn circle_radius(shape: &Shape) -> Option<u32> {
+    if Shape::OFFSET_TAG.get_copy(shape) == CIRCLE_TAG {
+        // Safety: the tag says that the `circle` field of the payload is initialized.
+        Some(unsafe { CIRCLE_RADIUS.read(shape) })
+    } else {
+        None
+    }
+}
+
+fn rect_height_mut(shape: &mut Shape) -> Option<&mut u16> {
+    if Shape::OFFSET_TAG.get_copy(shape) == RECT_TAG {
+        // Safety: the tag says that the `rect` field of the payload is initialized.
+        Some(unsafe { &mut *RECT_HEIGHT.get_mut_ptr(shape) })
+    } else {
+        None
+    }
+}
+
+fn packed_rect_height(shape: &PackedShape) -> Option<u16> {
+    if PackedShape::OFFSET_TAG.get_copy(shape) == RECT_TAG {
+        // Safety: the tag says that the `rect` field of the payload is initialized.
+        Some(unsafe { PACKED_RECT_HEIGHT.read(shape) })
+    } else {
+        None
+    }
+}
+
+#[test]
+fn tagged_struct_offsets() {
+    assert_eq!(Shape::OFFSET_PAYLOAD.offset(), 4);
+    assert_eq!(CIRCLE_RADIUS.offset(), 4);
+    assert_eq!(RECT_HEIGHT.offset(), 6);
+    assert_eq!(PackedShape::OFFSET_PAYLOAD.offset(), 1);
+    assert_eq!(PACKED_RECT_HEIGHT.offset(), 3);
+}
+
+#[test]
+fn tagged_struct_access() {
+    let mut circle = Shape {
+        tag: CIRCLE_TAG,
+        payload: ShapePayload {
+            circle: Circle { radius: 3 },
+        },
+    };
+    let mut rect = Shape {
+        tag: RECT_TAG,
+        payload: ShapePayload {
+            rect: Rect {
+                width: 5,
+                height: 8,
+            },
+        },
+    };
+
+    assert_eq!(circle_radius(&circle), Some(3));
+    assert_eq!(circle_radius(&rect), None);
+
+    assert_eq!(rect_height_mut(&mut circle), None);
+    *rect_height_mut(&mut rect).unwrap() += 5;
+    assert_eq!(
+        unsafe { RECT.read(&rect) },
+        Rect {
+            width: 5,
+            height: 13
+        }
+    );
+
+    let packed_rect = PackedShape {
+        tag: RECT_TAG,
+        payload: ShapePayload {
+            rect: Rect {
+                width: 21,
+                height: 34,
+            },
+        },
+    };
+    let packed_circle = PackedShape {
+        tag: CIRCLE_TAG,
+        payload: ShapePayload {
+            circle: Circle { radius: 55 },
+        },
+    };
+    assert_eq!(packed_rect_height(&packed_rect), Some(34));
+    assert_eq!(packed_rect_height(&packed_circle), None);
+}
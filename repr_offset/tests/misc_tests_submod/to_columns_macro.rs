@@ -0,0 +1,114 @@
+use repr_offset::{
+    for_examples::{ReprC, ReprPacked},
+    to_columns,
+};
+
+use std::rc::Rc;
+
+#[test]
+fn to_columns_test() {
+    {
+        type This = ReprC<u8, String, Vec<u32>, u64>;
+        let new = |a: u8, b: &str, c: &[u32], d: u64| ReprC {
+            a,
+            b: b.to_string(),
+            c: c.to_vec(),
+            d,
+        };
+        let list = || -> Vec<This> {
+            vec![
+                new(3, "foo", &[5], 8),
+                new(13, "bar", &[21, 34], 55),
+                new(89, "baz", &[], 144),
+            ]
+        };
+
+        let (a, b, c, d) = to_columns!(list(); a, b, c, d);
+        assert_eq!(a, [3, 13, 89]);
+        assert_eq!(b, ["foo", "bar", "baz"]);
+        assert_eq!(c, [vec![5], vec![21, 34], vec![]]);
+        assert_eq!(d, [8, 55, 144]);
+
+        let (d, b) = to_columns!(list(); d, b,);
+        assert_eq!(d, [8, 55, 144]);
+        assert_eq!(b, ["foo", "bar", "baz"]);
+
+        let (c,) = to_columns!(list(); c);
+        assert_eq!(c, [vec![5], vec![21, 34], vec![]]);
+    }
+    {
+        // Empty `Vec`s produce empty columns
+        let list = Vec::<ReprC<u8, u16, u32, u64>>::new();
+        let (a, d) = to_columns!(list; a, d);
+        assert!(a.is_empty());
+        assert!(d.is_empty());
+    }
+    {
+        // Nested fields
+        type Inner = ReprC<u16, String, (), ()>;
+        let new = |a: u8, b_a: u16, b_b: &str| ReprC {
+            a,
+            b: Inner {
+                a: b_a,
+                b: b_b.to_string(),
+                c: (),
+                d: (),
+            },
+            c: (),
+            d: (),
+        };
+        let list = vec![new(3, 5, "foo"), new(8, 13, "bar")];
+        let (a, b_a, b_b) = to_columns!(list; a, b.a, b.b);
+        assert_eq!(a, [3, 8]);
+        assert_eq!(b_a, [5, 13]);
+        assert_eq!(b_b, ["foo", "bar"]);
+    }
+    {
+        // Packed structs
+        type This = ReprPacked<u8, u64, String, u16>;
+        let list: Vec<This> = vec![
+            ReprPacked {
+                a: 3,
+                b: 5,
+                c: "foo".to_string(),
+                d: 8,
+            },
+            ReprPacked {
+                a: 13,
+                b: 21,
+                c: "bar".to_string(),
+                d: 34,
+            },
+        ];
+        let (b, c, d) = to_columns!(list; b, c, d);
+        assert_eq!(b, [5, 21]);
+        assert_eq!(c, ["foo", "bar"]);
+        assert_eq!(d, [8, 34]);
+    }
+    {
+        // The fields that aren't passed are dropped
+        let rc = Rc::new(());
+        let list = vec![
+            ReprC {
+                a: rc.clone(),
+                b: 3u8,
+                c: rc.clone(),
+                d: (),
+            },
+            ReprC {
+                a: rc.clone(),
+                b: 5u8,
+                c: rc.clone(),
+                d: (),
+            },
+        ];
+        assert_eq!(Rc::strong_count(&rc), 5);
+
+        let (b, c) = to_columns!(list; b, c);
+        assert_eq!(b, [3, 5]);
+        assert_eq!(Rc::strong_count(&rc), 3);
+
+        drop(c);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+}
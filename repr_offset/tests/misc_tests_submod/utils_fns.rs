@@ -0,0 +1,168 @@
+use repr_offset::{
+    for_examples::{ReprC, ReprPacked},
+    utils::{accumulate_fields, moved, project, project_mut, swap_field_in_slice},
+};
+
+use std::{
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
+
+type This = ReprC<u8, u64, String, Vec<u8>>;
+
+fn make_this() -> This {
+    ReprC {
+        a: 3,
+        b: 5,
+        c: "8".to_string(),
+        d: vec![13, 21],
+    }
+}
+
+struct Custom<T>(T);
+
+impl<T> Deref for Custom<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Custom<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[test]
+fn project_fn() {
+    let boxed = Box::new(make_this());
+    assert_eq!(project(&boxed, This::OFFSET_A), &3);
+    assert_eq!(project(&boxed, This::OFFSET_B), &5);
+    assert_eq!(project(&boxed, This::OFFSET_C), "8");
+    assert_eq!(project(&boxed, This::OFFSET_D), &[13, 21]);
+
+    let rc = Rc::new(make_this());
+    assert_eq!(project(&rc, This::OFFSET_A), &3);
+    assert_eq!(project(&rc, This::OFFSET_B), &5);
+    assert_eq!(project(&rc, This::OFFSET_C), "8");
+    assert_eq!(project(&rc, This::OFFSET_D), &[13, 21]);
+
+    let custom = Custom(make_this());
+    assert_eq!(project(&custom, This::OFFSET_A), &3);
+    assert_eq!(project(&custom, This::OFFSET_B), &5);
+    assert_eq!(project(&custom, This::OFFSET_C), "8");
+    assert_eq!(project(&custom, This::OFFSET_D), &[13, 21]);
+}
+
+#[test]
+fn project_mut_fn() {
+    let mut boxed = Box::new(make_this());
+    *project_mut(&mut boxed, This::OFFSET_A) += 100;
+    project_mut(&mut boxed, This::OFFSET_C).push('9');
+    assert_eq!(boxed.a, 103);
+    assert_eq!(boxed.c, "89");
+
+    let mut custom = Custom(make_this());
+    *project_mut(&mut custom, This::OFFSET_B) *= 2;
+    project_mut(&mut custom, This::OFFSET_D).push(34);
+    assert_eq!(custom.b, 10);
+    assert_eq!(custom.d, [13, 21, 34]);
+}
+
+#[test]
+fn swap_field_in_slice_fn() {
+    let mut list = [make_this(), make_this(), make_this()];
+    list[1].b = 100;
+    list[2].b = 200;
+    list[2].c = "foo".to_string();
+
+    swap_field_in_slice(This::OFFSET_B, &mut list, 0, 2);
+    assert_eq!(list.iter().map(|x| x.b).collect::<Vec<_>>(), [200, 100, 5]);
+
+    swap_field_in_slice(This::OFFSET_B, &mut list, 1, 1);
+    assert_eq!(list.iter().map(|x| x.b).collect::<Vec<_>>(), [200, 100, 5]);
+
+    // The other fields are untouched
+    for x in &list[..2] {
+        assert_eq!((x.a, &*x.c, &*x.d), (3, "8", &[13, 21][..]));
+    }
+    assert_eq!((list[2].a, &*list[2].c), (3, "foo"));
+
+    swap_field_in_slice(This::OFFSET_C, &mut list, 2, 1);
+    assert_eq!(
+        list.iter().map(|x| &*x.c).collect::<Vec<_>>(),
+        ["8", "foo", "8"]
+    );
+
+    let mut packed = [
+        ReprPacked {
+            a: 'a',
+            b: 3u64,
+            c: (),
+            d: 'x',
+        },
+        ReprPacked {
+            a: 'b',
+            b: 5u64,
+            c: (),
+            d: 'y',
+        },
+    ];
+    swap_field_in_slice(ReprPacked::OFFSET_B, &mut packed, 1, 0);
+    assert_eq!(
+        packed
+            .iter()
+            .map(|x| (moved(x.a), moved(x.b), moved(x.d)))
+            .collect::<Vec<_>>(),
+        [('a', 5, 'x'), ('b', 3, 'y')]
+    );
+    swap_field_in_slice(ReprPacked::OFFSET_C, &mut packed, 1, 0);
+
+    let out_of_bounds = std::panic::catch_unwind(move || {
+        swap_field_in_slice(ReprPacked::OFFSET_B, &mut packed, 0, 2);
+    });
+    assert!(out_of_bounds.is_err());
+
+    let out_of_bounds = std::panic::catch_unwind(move || {
+        swap_field_in_slice(This::OFFSET_A, &mut [], 0, 0);
+    });
+    assert!(out_of_bounds.is_err());
+}
+
+#[test]
+fn accumulate_fields_fn() {
+    type Packed = ReprPacked<u8, u32, [u8; 3], u16>;
+    let packed: Packed = ReprPacked {
+        a: 3,
+        b: 0x0102_0304,
+        c: [5, 8, 13],
+        d: 0x1522,
+    };
+
+    let mut accumulated = Vec::new();
+    unsafe {
+        accumulate_fields(
+            &packed,
+            &[Packed::OFFSET_D.to_dyn(), Packed::OFFSET_B.to_dyn()],
+            &mut |bytes| accumulated.extend_from_slice(bytes),
+        );
+    }
+    let mut expected = Vec::new();
+    expected.extend_from_slice(&0x1522u16.to_ne_bytes());
+    expected.extend_from_slice(&0x0102_0304u32.to_ne_bytes());
+    assert_eq!(accumulated, expected);
+
+    let this = make_this();
+    let mut chunks = Vec::new();
+    unsafe {
+        accumulate_fields(
+            &this,
+            &[This::OFFSET_A.to_dyn(), This::OFFSET_B.to_dyn()],
+            &mut |bytes| chunks.push(bytes.to_vec()),
+        );
+        accumulate_fields(&this, &[], &mut |_| panic!("no fields were passed"));
+    }
+    assert_eq!(chunks, [vec![3], 5u64.to_ne_bytes().to_vec()]);
+}
@@ -0,0 +1,65 @@
+use repr_offset::{unsafe_struct_field_offsets, verify_offsets, Aligned, Unaligned};
+
+#[repr(C)]
+pub struct Struct {
+    pub a: u8,
+    pub b: u64,
+    c: &'static str,
+    pub d: [u16; 3],
+}
+
+unsafe_struct_field_offsets! {
+    alignment =  Aligned,
+
+    impl[] Struct {
+        pub const OFFSET_A, a: u8;
+        pub const OFFSET_B, b: u64;
+        const OFFSET_C, c: &'static str;
+        pub const OFFSET_D, d: [u16; 3];
+    }
+}
+
+verify_offsets!(Struct; a, b, c, d);
+
+#[repr(C, packed)]
+pub struct Packed(pub u8, pub u32, pub Option<u16>);
+
+unsafe_struct_field_offsets! {
+    alignment =  Unaligned,
+
+    impl[] Packed {
+        pub const OFFSET_0, 0: u8;
+        pub const OFFSET_1, 1: u32;
+        pub const OFFSET_2, 2: Option<u16>;
+    }
+}
+
+verify_offsets!(Packed; 0, 1, 2,);
+
+#[repr(C)]
+pub struct Generic<T> {
+    pub a: u8,
+    pub b: T,
+}
+
+unsafe_struct_field_offsets! {
+    alignment =  Aligned,
+
+    impl[T] Generic<T> {
+        pub const OFFSET_A, a: u8;
+        pub const OFFSET_B, b: T;
+    }
+}
+
+verify_offsets!(Generic<u32>; a, b);
+verify_offsets!(Generic<(u8, u64)>; b);
+
+#[test]
+fn verify_offsets_inside_fn() {
+    // This is checked at compile-time, the test only exists to show that
+    // the macro can be used inside functions.
+    verify_offsets!(Struct; d, a);
+
+    assert_eq!(Struct::OFFSET_D.offset(), 32);
+    assert_eq!(Packed::OFFSET_2.offset(), 5);
+}
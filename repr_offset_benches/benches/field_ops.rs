@@ -0,0 +1,207 @@
+//! Benchmarks for the `FieldOffset` methods used in hot (de)serialization paths,
+//! comparing aligned and unaligned fields against accessing the fields directly.
+//!
+//! Every benchmark operates on a slice of `LEN` structs,
+//! so that the per-call overhead of criterion doesn't dominate the measurements.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use repr_offset::{
+    for_examples::{ReprC, ReprPacked},
+    utils::moved,
+};
+
+use std::ptr;
+
+const LEN: usize = 1024;
+
+type Aligned = ReprC<u8, u32, u16, u64>;
+type Packed = ReprPacked<u8, u32, u16, u64>;
+
+fn aligned_values() -> Vec<Aligned> {
+    (0..LEN)
+        .map(|i| ReprC {
+            a: i as u8,
+            b: i as u32,
+            c: i as u16,
+            d: i as u64,
+        })
+        .collect()
+}
+
+fn packed_values() -> Vec<Packed> {
+    (0..LEN)
+        .map(|i| ReprPacked {
+            a: i as u8,
+            b: i as u32,
+            c: i as u16,
+            d: i as u64,
+        })
+        .collect()
+}
+
+fn bench_get_copy(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_copy");
+
+    let aligned = aligned_values();
+    group.bench_function("aligned/direct", |b| {
+        b.iter(|| black_box(&aligned).iter().map(|x| x.d).sum::<u64>())
+    });
+    group.bench_function("aligned/field_offset", |b| {
+        b.iter(|| {
+            black_box(&aligned)
+                .iter()
+                .map(|x| Aligned::OFFSET_D.get_copy(x))
+                .sum::<u64>()
+        })
+    });
+
+    let packed = packed_values();
+    group.bench_function("unaligned/direct", |b| {
+        b.iter(|| black_box(&packed).iter().map(|x| moved(x.d)).sum::<u64>())
+    });
+    group.bench_function("unaligned/field_offset", |b| {
+        b.iter(|| {
+            black_box(&packed)
+                .iter()
+                .map(|x| Packed::OFFSET_D.get_copy(x))
+                .sum::<u64>()
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_read_copy(c: &mut Criterion) {
+    let mut group = c.benchmark_group("read_copy");
+
+    let aligned = aligned_values();
+    group.bench_function("aligned/direct", |b| {
+        b.iter(|| {
+            let ptr = black_box(&aligned).as_ptr();
+            (0..LEN).map(|i| unsafe { (*ptr.add(i)).d }).sum::<u64>()
+        })
+    });
+    group.bench_function("aligned/field_offset", |b| {
+        b.iter(|| {
+            let ptr = black_box(&aligned).as_ptr();
+            (0..LEN)
+                .map(|i| unsafe { Aligned::OFFSET_D.read_copy(ptr.add(i)) })
+                .sum::<u64>()
+        })
+    });
+
+    let packed = packed_values();
+    group.bench_function("unaligned/direct", |b| {
+        b.iter(|| {
+            let ptr = black_box(&packed).as_ptr();
+            (0..LEN)
+                .map(|i| unsafe { ptr::addr_of!((*ptr.add(i)).d).read_unaligned() })
+                .sum::<u64>()
+        })
+    });
+    group.bench_function("unaligned/field_offset", |b| {
+        b.iter(|| {
+            let ptr = black_box(&packed).as_ptr();
+            (0..LEN)
+                .map(|i| unsafe { Packed::OFFSET_D.read_copy(ptr.add(i)) })
+                .sum::<u64>()
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_write(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write");
+
+    let mut aligned = aligned_values();
+    group.bench_function("aligned/direct", |b| {
+        b.iter(|| {
+            for (i, x) in black_box(&mut aligned).iter_mut().enumerate() {
+                x.d = i as u64;
+            }
+        })
+    });
+    group.bench_function("aligned/field_offset", |b| {
+        b.iter(|| {
+            for (i, x) in black_box(&mut aligned).iter_mut().enumerate() {
+                unsafe { Aligned::OFFSET_D.write(x, i as u64) }
+            }
+        })
+    });
+
+    let mut packed = packed_values();
+    group.bench_function("unaligned/direct", |b| {
+        b.iter(|| {
+            for (i, x) in black_box(&mut packed).iter_mut().enumerate() {
+                x.d = i as u64;
+            }
+        })
+    });
+    group.bench_function("unaligned/field_offset", |b| {
+        b.iter(|| {
+            for (i, x) in black_box(&mut packed).iter_mut().enumerate() {
+                unsafe { Packed::OFFSET_D.write(x, i as u64) }
+            }
+        })
+    });
+
+    group.finish();
+}
+
+// `mem::swap` can't be used to swap the fields of packed structs,
+// because it takes references.
+#[allow(clippy::manual_swap)]
+fn bench_swap(c: &mut Criterion) {
+    let mut group = c.benchmark_group("swap");
+
+    let mut aligned = aligned_values();
+    group.bench_function("aligned/direct", |b| {
+        b.iter(|| {
+            let (left, right) = black_box(&mut aligned).split_at_mut(LEN / 2);
+            for (l, r) in left.iter_mut().zip(right) {
+                std::mem::swap(&mut l.d, &mut r.d);
+            }
+        })
+    });
+    group.bench_function("aligned/field_offset", |b| {
+        b.iter(|| {
+            let (left, right) = black_box(&mut aligned).split_at_mut(LEN / 2);
+            for (l, r) in left.iter_mut().zip(right) {
+                Aligned::OFFSET_D.swap_mut(l, r);
+            }
+        })
+    });
+
+    let mut packed = packed_values();
+    group.bench_function("unaligned/direct", |b| {
+        b.iter(|| {
+            let (left, right) = black_box(&mut packed).split_at_mut(LEN / 2);
+            for (l, r) in left.iter_mut().zip(right) {
+                let tmp = l.d;
+                l.d = r.d;
+                r.d = tmp;
+            }
+        })
+    });
+    group.bench_function("unaligned/field_offset", |b| {
+        b.iter(|| {
+            let (left, right) = black_box(&mut packed).split_at_mut(LEN / 2);
+            for (l, r) in left.iter_mut().zip(right) {
+                Packed::OFFSET_D.swap_mut(l, r);
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_get_copy,
+    bench_read_copy,
+    bench_write,
+    bench_swap
+);
+criterion_main!(benches);
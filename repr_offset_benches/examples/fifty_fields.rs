@@ -0,0 +1,187 @@
+//! Measures the compile-time cost of the `GetFieldOffset` impls of a 50-field struct.
+//!
+//! Building this with the `no_get_field_offset` feature disables the impls,
+//! see the README of this crate for how to compare the compile times.
+
+use repr_offset::{unsafe_struct_field_offsets, Aligned};
+
+// This expands to the same code as `#[derive(ReprOffset)]` for this struct,
+// with `#[roff(impl_GetFieldOffset = false)]` when `$impl_gfo` is `false`.
+macro_rules! declare_struct {
+    ($impl_gfo:ident) => {
+        #[repr(C)]
+        pub struct FiftyFields {
+            pub f0: u8,
+            pub f1: u16,
+            pub f2: u32,
+            pub f3: u64,
+            pub f4: u8,
+            pub f5: u16,
+            pub f6: u32,
+            pub f7: u64,
+            pub f8: u8,
+            pub f9: u16,
+            pub f10: u32,
+            pub f11: u64,
+            pub f12: u8,
+            pub f13: u16,
+            pub f14: u32,
+            pub f15: u64,
+            pub f16: u8,
+            pub f17: u16,
+            pub f18: u32,
+            pub f19: u64,
+            pub f20: u8,
+            pub f21: u16,
+            pub f22: u32,
+            pub f23: u64,
+            pub f24: u8,
+            pub f25: u16,
+            pub f26: u32,
+            pub f27: u64,
+            pub f28: u8,
+            pub f29: u16,
+            pub f30: u32,
+            pub f31: u64,
+            pub f32: u8,
+            pub f33: u16,
+            pub f34: u32,
+            pub f35: u64,
+            pub f36: u8,
+            pub f37: u16,
+            pub f38: u32,
+            pub f39: u64,
+            pub f40: u8,
+            pub f41: u16,
+            pub f42: u32,
+            pub f43: u64,
+            pub f44: u8,
+            pub f45: u16,
+            pub f46: u32,
+            pub f47: u64,
+            pub f48: u8,
+            pub f49: u16,
+        }
+
+        unsafe_struct_field_offsets! {
+            alignment = Aligned,
+            impl_GetFieldOffset = $impl_gfo,
+
+            impl[] FiftyFields {
+                pub const OFFSET_F0, f0: u8;
+                pub const OFFSET_F1, f1: u16;
+                pub const OFFSET_F2, f2: u32;
+                pub const OFFSET_F3, f3: u64;
+                pub const OFFSET_F4, f4: u8;
+                pub const OFFSET_F5, f5: u16;
+                pub const OFFSET_F6, f6: u32;
+                pub const OFFSET_F7, f7: u64;
+                pub const OFFSET_F8, f8: u8;
+                pub const OFFSET_F9, f9: u16;
+                pub const OFFSET_F10, f10: u32;
+                pub const OFFSET_F11, f11: u64;
+                pub const OFFSET_F12, f12: u8;
+                pub const OFFSET_F13, f13: u16;
+                pub const OFFSET_F14, f14: u32;
+                pub const OFFSET_F15, f15: u64;
+                pub const OFFSET_F16, f16: u8;
+                pub const OFFSET_F17, f17: u16;
+                pub const OFFSET_F18, f18: u32;
+                pub const OFFSET_F19, f19: u64;
+                pub const OFFSET_F20, f20: u8;
+                pub const OFFSET_F21, f21: u16;
+                pub const OFFSET_F22, f22: u32;
+                pub const OFFSET_F23, f23: u64;
+                pub const OFFSET_F24, f24: u8;
+                pub const OFFSET_F25, f25: u16;
+                pub const OFFSET_F26, f26: u32;
+                pub const OFFSET_F27, f27: u64;
+                pub const OFFSET_F28, f28: u8;
+                pub const OFFSET_F29, f29: u16;
+                pub const OFFSET_F30, f30: u32;
+                pub const OFFSET_F31, f31: u64;
+                pub const OFFSET_F32, f32: u8;
+                pub const OFFSET_F33, f33: u16;
+                pub const OFFSET_F34, f34: u32;
+                pub const OFFSET_F35, f35: u64;
+                pub const OFFSET_F36, f36: u8;
+                pub const OFFSET_F37, f37: u16;
+                pub const OFFSET_F38, f38: u32;
+                pub const OFFSET_F39, f39: u64;
+                pub const OFFSET_F40, f40: u8;
+                pub const OFFSET_F41, f41: u16;
+                pub const OFFSET_F42, f42: u32;
+                pub const OFFSET_F43, f43: u64;
+                pub const OFFSET_F44, f44: u8;
+                pub const OFFSET_F45, f45: u16;
+                pub const OFFSET_F46, f46: u32;
+                pub const OFFSET_F47, f47: u64;
+                pub const OFFSET_F48, f48: u8;
+                pub const OFFSET_F49, f49: u16;
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "no_get_field_offset"))]
+declare_struct! {true}
+
+#[cfg(feature = "no_get_field_offset")]
+declare_struct! {false}
+
+fn main() {
+    let offsets = [
+        FiftyFields::OFFSET_F0.offset(),
+        FiftyFields::OFFSET_F1.offset(),
+        FiftyFields::OFFSET_F2.offset(),
+        FiftyFields::OFFSET_F3.offset(),
+        FiftyFields::OFFSET_F4.offset(),
+        FiftyFields::OFFSET_F5.offset(),
+        FiftyFields::OFFSET_F6.offset(),
+        FiftyFields::OFFSET_F7.offset(),
+        FiftyFields::OFFSET_F8.offset(),
+        FiftyFields::OFFSET_F9.offset(),
+        FiftyFields::OFFSET_F10.offset(),
+        FiftyFields::OFFSET_F11.offset(),
+        FiftyFields::OFFSET_F12.offset(),
+        FiftyFields::OFFSET_F13.offset(),
+        FiftyFields::OFFSET_F14.offset(),
+        FiftyFields::OFFSET_F15.offset(),
+        FiftyFields::OFFSET_F16.offset(),
+        FiftyFields::OFFSET_F17.offset(),
+        FiftyFields::OFFSET_F18.offset(),
+        FiftyFields::OFFSET_F19.offset(),
+        FiftyFields::OFFSET_F20.offset(),
+        FiftyFields::OFFSET_F21.offset(),
+        FiftyFields::OFFSET_F22.offset(),
+        FiftyFields::OFFSET_F23.offset(),
+        FiftyFields::OFFSET_F24.offset(),
+        FiftyFields::OFFSET_F25.offset(),
+        FiftyFields::OFFSET_F26.offset(),
+        FiftyFields::OFFSET_F27.offset(),
+        FiftyFields::OFFSET_F28.offset(),
+        FiftyFields::OFFSET_F29.offset(),
+        FiftyFields::OFFSET_F30.offset(),
+        FiftyFields::OFFSET_F31.offset(),
+        FiftyFields::OFFSET_F32.offset(),
+        FiftyFields::OFFSET_F33.offset(),
+        FiftyFields::OFFSET_F34.offset(),
+        FiftyFields::OFFSET_F35.offset(),
+        FiftyFields::OFFSET_F36.offset(),
+        FiftyFields::OFFSET_F37.offset(),
+        FiftyFields::OFFSET_F38.offset(),
+        FiftyFields::OFFSET_F39.offset(),
+        FiftyFields::OFFSET_F40.offset(),
+        FiftyFields::OFFSET_F41.offset(),
+        FiftyFields::OFFSET_F42.offset(),
+        FiftyFields::OFFSET_F43.offset(),
+        FiftyFields::OFFSET_F44.offset(),
+        FiftyFields::OFFSET_F45.offset(),
+        FiftyFields::OFFSET_F46.offset(),
+        FiftyFields::OFFSET_F47.offset(),
+        FiftyFields::OFFSET_F48.offset(),
+        FiftyFields::OFFSET_F49.offset(),
+    ];
+
+    println!("{:?}", offsets);
+}
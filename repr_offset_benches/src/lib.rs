@@ -0,0 +1 @@
+//! This crate only has benchmarks, in the `benches` directory.
@@ -1,14 +1,16 @@
 use as_derive_utils::{
-    datastructure::{DataStructure, DataVariant, FieldIdent},
+    datastructure::{DataStructure, DataVariant, Field, FieldIdent},
     gen_params_in::{GenParamsIn, InWhat},
-    return_syn_err, ToTokenFnMut,
+    return_spanned_err, return_syn_err, ToTokenFnMut,
 };
 
+use core_extensions::matches;
+
 use proc_macro2::{Span, TokenStream as TokenStream2};
 
 use quote::{quote, ToTokens};
 
-use syn::{DeriveInput, Ident};
+use syn::{spanned::Spanned, DeriveInput, Ident, LitStr, Member, Type};
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -33,6 +35,62 @@ pub(crate) fn derive(data: DeriveInput) -> Result<TokenStream2, syn::Error> {
     }
 
     let options = attribute_parsing::parse_attrs_for_derive(ds)?;
+    if options.delegate_fields && ds.variants[0].fields.len() != 1 {
+        return_syn_err!(
+            Span::call_site(),
+            "The `delegate_fields` attribute requires the struct to have exactly one field."
+        )
+    }
+    if options.layout_guard && !ds.generics.params.is_empty() {
+        return_syn_err!(
+            Span::call_site(),
+            "The `layout_guard` attribute can't be used with generic structs."
+        )
+    }
+    let has_unsafe_offset = ds.variants[0]
+        .fields
+        .iter()
+        .any(|field| options.field_map[field.index].unsafe_offset.is_some());
+    if has_unsafe_offset
+        && (options.refs_view
+            || options.into_fields
+            || options.write_all
+            || options.clone_via_fields
+            || options.write_defaults)
+    {
+        return_syn_err!(
+            Span::call_site(),
+            "The `unsafe_offset` attribute can't be used with \
+             the `refs_view`, `into_fields`, `write_all`, `clone_via_fields`, \
+             or `write_defaults` attributes."
+        )
+    }
+    if options.tuple_index
+        && !ds.variants[0]
+            .fields
+            .iter()
+            .all(|field| matches!(FieldIdent::Index(..) = field.ident))
+    {
+        return_syn_err!(
+            Span::call_site(),
+            "The `tuple_index` attribute can only be used with tuple structs."
+        )
+    }
+    for nested in &options.nested_consts {
+        if field_of_member(ds, &nested.path[0]).is_none() {
+            return_spanned_err!(
+                nested.lit,
+                "`{}` is not a field of this struct",
+                member_name(&nested.path[0]),
+            )
+        }
+    }
+    if options.c_accessors.is_some() && !ds.generics.params.is_empty() {
+        return_syn_err!(
+            Span::call_site(),
+            "The `c_accessors` attribute can't be used with generic structs."
+        )
+    }
     let output = derive_inner(&ds, &options);
     if options.debug_print {
         panic!("\n\n\n{}\n\n\n", output);
@@ -48,7 +106,9 @@ fn derive_inner(ds: &DataStructure<'_>, options: &ReprOffsetConfig<'_>) -> Token
     };
 
     let usize_offsets = options.use_usize_offsets;
-    let impl_getfieldoffset = options.impl_getfieldoffset;
+    // The `GetFieldOffset` impl generated by `delegate_fields` would overlap with
+    // the ones for the fields of this struct.
+    let impl_getfieldoffset = options.impl_getfieldoffset && !options.delegate_fields;
 
     let impl_generics = GenParamsIn::new(ds.generics, InWhat::ImplHeader);
 
@@ -73,22 +133,379 @@ fn derive_inner(ds: &DataStructure<'_>, options: &ReprOffsetConfig<'_>) -> Token
             String::new()
         }
     });
-    let offset_name = struct_.fields.iter().map(|field| {
-        ToTokenFnMut::new(move |ts| {
-            let f_conf = &options.field_map[field.index];
-            match &f_conf.offset_name {
-                None => concat_field_ident(&options.offset_prefix, &field.ident).to_tokens(ts),
-                Some(OffsetIdent::Prefix(prefix)) => {
-                    concat_field_ident(prefix, &field.ident).to_tokens(ts)
-                }
-                Some(OffsetIdent::Full(full)) => full.to_tokens(ts),
+    let offset_name = struct_
+        .fields
+        .iter()
+        .map(|field| offset_ident(options, field))
+        .collect::<Vec<Ident>>();
+    // The offsets of the fields in the struct's actual layout,
+    // which differ from `offset_name` for fields with a `#[roff(unsafe_offset = ...)]`,
+    // since those have a hidden constant for the computed offset.
+    let computed_offset_name = struct_
+        .fields
+        .iter()
+        .zip(&offset_name)
+        .map(|(field, offset_name)| {
+            if options.field_map[field.index].unsafe_offset.is_some() {
+                Ident::new(
+                    &format!("__ROFF_COMPUTED_{}", offset_name),
+                    offset_name.span(),
+                )
+            } else {
+                offset_name.clone()
             }
         })
+        .collect::<Vec<Ident>>();
+    let hidden_attr = struct_.fields.iter().map(|field| {
+        if options.no_offset_consts || options.field_map[field.index].unsafe_offset.is_some() {
+            quote!(#[doc(hidden)])
+        } else {
+            quote!()
+        }
     });
     let field_names = struct_.fields.iter().map(|x| &x.ident);
     let field_tys = struct_.fields.iter().map(|x| x.ty);
 
-    let extra_bounds = options.extra_bounds.iter();
+    let extra_bounds = options.extra_bounds.iter().collect::<Vec<_>>();
+
+    let field_enum = ToTokenFnMut::new(|ts| {
+        if !options.field_enum {
+            return;
+        }
+
+        let struct_vis = ds.vis;
+        let enum_name = Ident::new(&format!("{}Field", name), name.span());
+        let enum_doc = format!("Refers to a field of [`{0}`](./struct.{0}.html).", name);
+
+        let variant_doc = struct_
+            .fields
+            .iter()
+            .map(|field| format!("The `{}` field.", field.ident()));
+        let variant_name = struct_
+            .fields
+            .iter()
+            .map(field_variant_ident)
+            .collect::<Vec<Ident>>();
+        let struct_where_preds = ds.generics.where_clause.as_ref().map(|x| &x.predicates);
+
+        // Fields of packed structs can't be borrowed,
+        // so `get_dyn` is only generated for non-packed structs.
+        //
+        // `get_dyn` is allowed to be unused because it's generated
+        // whether or not the user wants it.
+        let get_dyn = if options.is_packed {
+            TokenStream2::new()
+        } else {
+            let any_field_tys = struct_.fields.iter().map(|x| x.ty);
+            quote! {
+                impl<#impl_generics> #name #ty_generics
+                where
+                    #( #extra_bounds , )*
+                    #struct_where_preds
+                {
+                    /// Gets a reference to the field that `field` refers to,
+                    /// as a `&dyn Any` that can be downcasted to the type of the field.
+                    #[allow(dead_code)]
+                    #struct_vis fn get_dyn(&self, field: #enum_name) -> &dyn ::core::any::Any
+                    where
+                        #( #any_field_tys: 'static, )*
+                    {
+                        match field {
+                            #(
+                                #enum_name::#variant_name => Self::#offset_name.get(self),
+                            )*
+                        }
+                    }
+                }
+            }
+        };
+
+        let const_offset_fn = if options.const_offset_fn {
+            quote! {
+                impl<#impl_generics> #name #ty_generics
+                where
+                    #( #extra_bounds , )*
+                    #struct_where_preds
+                {
+                    /// Gets the offset of the field that `field` refers to,
+                    /// usable in const contexts.
+                    #[allow(dead_code)]
+                    #struct_vis const fn offset_of_field(field: #enum_name) -> usize {
+                        match field {
+                            #(
+                                #enum_name::#variant_name => Self::#offset_name.offset(),
+                            )*
+                        }
+                    }
+                }
+            }
+        } else {
+            TokenStream2::new()
+        };
+
+        let tokens = quote! {
+            #[doc = #enum_doc]
+            #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #struct_vis enum #enum_name {
+                #(
+                    #[doc = #variant_doc]
+                    #variant_name,
+                )*
+            }
+
+            impl #enum_name {
+                /// Gets the type-erased offset of the field.
+                #struct_vis fn offset_dyn<#impl_generics>(
+                    self
+                ) -> ::repr_offset::FieldOffsetDyn<#name #ty_generics>
+                where
+                    #( #extra_bounds , )*
+                    #struct_where_preds
+                {
+                    match self {
+                        #(
+                            #enum_name::#variant_name =>
+                                <#name #ty_generics>::#offset_name.to_dyn(),
+                        )*
+                    }
+                }
+            }
+
+            #get_dyn
+
+            #const_offset_fn
+        };
+        tokens.to_tokens(ts);
+    });
+
+    let refs_view = if options.refs_view {
+        refs_view_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let offset_debug = ToTokenFnMut::new(|ts| {
+        if !options.derive_offset_debug {
+            return;
+        }
+
+        let struct_vis = ds.vis;
+        let field_name_strs = struct_.fields.iter().map(|field| {
+            let field_name = field.ident.to_string();
+            field_name.trim_start_matches("r#").to_string()
+        });
+        let to_usize = if usize_offsets {
+            quote!()
+        } else {
+            quote!(.offset())
+        };
+        let debug_where_preds = ds.generics.where_clause.as_ref().map(|x| &x.predicates);
+
+        let tokens = quote! {
+            impl<#impl_generics> #name #ty_generics
+            where
+                #( #extra_bounds , )*
+                #debug_where_preds
+            {
+                #[doc(hidden)]
+                const __ROFF_DEBUG_OFFSETS: &'static [(&'static str, usize)] = &[
+                    #( (#field_name_strs, Self::#offset_name #to_usize), )*
+                ];
+
+                /// Gets the name and offset of every field, in declaration order.
+                #struct_vis const fn debug_offsets() -> &'static [(&'static str, usize)] {
+                    Self::__ROFF_DEBUG_OFFSETS
+                }
+            }
+        };
+        tokens.to_tokens(ts);
+    });
+
+    let fields_proxy = if options.fields_proxy {
+        fields_proxy_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let clone_via_fields = if options.clone_via_fields {
+        clone_via_fields_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let write_defaults = if options.write_defaults {
+        write_defaults_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let try_from_bytes = if options.try_from_bytes {
+        try_from_bytes_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let eq_by_fields = if options.eq_by_fields {
+        eq_by_fields_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let derive_eq = if options.derive_eq {
+        derive_eq_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let derive_hash = if options.derive_hash {
+        derive_hash_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let read_from_unaligned = ToTokenFnMut::new(|ts| {
+        if !options.read_from_unaligned {
+            return;
+        }
+
+        let struct_vis = ds.vis;
+        let read_field_names = struct_.fields.iter().map(|x| &x.ident);
+        let read_where_preds = ds.generics.where_clause.as_ref().map(|x| &x.predicates);
+
+        let tokens = quote! {
+            impl<#impl_generics> #name #ty_generics
+            where
+                #( #extra_bounds , )*
+                #read_where_preds
+            {
+                /// Reads this struct from the passed pointer,
+                /// reading each field with unaligned reads.
+                ///
+                /// # Safety
+                ///
+                /// The pointer must point to at least `size_of::<Self>()` readable bytes,
+                /// which must be valid bit patterns for the fields of this struct
+                /// at the offset of each field.
+                ///
+                /// Padding bytes are not read.
+                #struct_vis unsafe fn read_from_unaligned(__roff_ptr: *const u8) -> Self {
+                    let __roff_ptr = __roff_ptr as *const Self;
+                    Self {
+                        #(
+                            #read_field_names:
+                                Self::#offset_name.to_unaligned().read(__roff_ptr),
+                        )*
+                    }
+                }
+            }
+        };
+        tokens.to_tokens(ts);
+    });
+
+    let into_fields = if options.into_fields {
+        into_fields_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let write_all = if options.write_all {
+        write_all_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let dyn_offsets = if options.dyn_offsets {
+        dyn_offsets_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let dyn_set = if options.dyn_set {
+        dyn_set_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let tuple_index = if options.tuple_index {
+        tuple_index_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let nested_consts = if options.nested_consts.is_empty() {
+        TokenStream2::new()
+    } else {
+        nested_consts_tokens(ds, options, &offset_name)
+    };
+
+    let padding_consts = if options.padding_consts {
+        padding_consts_tokens(ds, options, &computed_offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let offsets_array = if options.offsets_array {
+        offsets_array_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let layout_guard = ToTokenFnMut::new(|ts| {
+        if !options.layout_guard {
+            return;
+        }
+
+        let guard_field_names = struct_.fields.iter().map(|x| &x.ident);
+        let offsets = computed_offset_name.iter().map(|computed| {
+            if options.use_usize_offsets {
+                quote!(#name::#computed)
+            } else {
+                quote!(#name::#computed.offset())
+            }
+        });
+        let messages = struct_.fields.iter().map(|field| {
+            format!(
+                "The offset of the `{}` field of `{}` differs from the one computed by rustc.",
+                field.ident(),
+                name,
+            )
+        });
+
+        let tokens = quote! {
+            const _: () = {
+                #(
+                    ::core::assert!(
+                        #offsets == ::core::mem::offset_of!(#name, #guard_field_names),
+                        #messages
+                    );
+                )*
+            };
+        };
+        tokens.to_tokens(ts);
+    });
+
+    let delegate_fields = if options.delegate_fields {
+        delegate_fields_tokens(ds, options)
+    } else {
+        TokenStream2::new()
+    };
+
+    let serialize_via_fields = if options.serialize_via_fields {
+        serialize_via_fields_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let unsafe_offsets = if computed_offset_name != offset_name {
+        unsafe_offsets_tokens(ds, options, &offset_name)
+    } else {
+        TokenStream2::new()
+    };
+
+    let c_accessors = match &options.c_accessors {
+        Some(prefix) => c_accessors_tokens(ds, options, prefix, &offset_name),
+        None => TokenStream2::new(),
+    };
 
     quote! {
         ::repr_offset::unsafe_struct_field_offsets!{
@@ -103,13 +520,1415 @@ fn derive_inner(ds: &DataStructure<'_>, options: &ReprOffsetConfig<'_>) -> Token
             ]{
                 #(
                     #[doc = #offset_doc]
-                    #vis const #offset_name, #field_names: #field_tys;
+                    #hidden_attr
+                    #vis const #computed_offset_name, #field_names: #field_tys;
+                )*
+            }
+        }
+
+        #field_enum
+
+        #refs_view
+
+        #fields_proxy
+
+        #offset_debug
+
+        #clone_via_fields
+
+        #read_from_unaligned
+
+        #write_defaults
+
+        #try_from_bytes
+
+        #eq_by_fields
+
+        #derive_eq
+
+        #derive_hash
+
+        #into_fields
+
+        #write_all
+
+        #dyn_offsets
+
+        #dyn_set
+
+        #tuple_index
+
+        #nested_consts
+
+        #padding_consts
+
+        #offsets_array
+
+        #serialize_via_fields
+
+        #layout_guard
+
+        #unsafe_offsets
+
+        #delegate_fields
+
+        #c_accessors
+    }
+}
+
+fn refs_view_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let struct_ = &ds.variants[0];
+
+    let field_vis = struct_.fields.iter().map(|x| x.vis).collect::<Vec<_>>();
+    let field_names = struct_.fields.iter().map(|x| &x.ident).collect::<Vec<_>>();
+    let field_tys = struct_.fields.iter().map(|x| x.ty).collect::<Vec<_>>();
+    let is_tuple = struct_
+        .fields
+        .iter()
+        .any(|x| matches!(FieldIdent::Index { .. } = x.ident));
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let refs_name = Ident::new(&format!("{}Refs", name), name.span());
+
+    // Declares a struct named `$struct_name` with a field for each field of the derive input,
+    // with `$field_ty` as the type of every field.
+    macro_rules! declare_view {
+        ($doc:expr, $generics:expr, $struct_name:ident, |$ty:ident| $field_ty:expr) => {{
+            let doc = $doc;
+            let (decl_generics, _, decl_where) = $generics.split_for_impl();
+            let field_doc = struct_
+                .fields
+                .iter()
+                .map(|field| format!("The `{}` field.", field.ident()));
+            let field_ty = field_tys.iter().map(|$ty| $field_ty);
+            if is_tuple {
+                quote! {
+                    #[doc = #doc]
+                    #struct_vis struct #$struct_name #decl_generics (
+                        #( #[doc = #field_doc] #field_vis #field_ty, )*
+                    ) #decl_where;
+                }
+            } else {
+                quote! {
+                    #[doc = #doc]
+                    #struct_vis struct #$struct_name #decl_generics #decl_where {
+                        #( #[doc = #field_doc] #field_vis #field_names: #field_ty, )*
+                    }
+                }
+            }
+        }};
+    }
+
+    if options.is_packed {
+        let refs_decl = declare_view!(
+            format!("Copies of the fields of `{}`.", name),
+            ds.generics,
+            refs_name,
+            |ty| quote!(#ty)
+        );
+
+        quote! {
+            #refs_decl
+
+            impl #impl_generics #name #ty_generics
+            where
+                #( #extra_bounds, )*
+                #where_preds
+            {
+                /// Copies all the fields of this struct,
+                /// since references to fields of packed structs can be unaligned.
+                #struct_vis fn as_refs(&self) -> #refs_name #ty_generics {
+                    #refs_name {
+                        #( #field_names: Self::#offset_names.get_copy(self), )*
+                    }
+                }
+            }
+        }
+    } else {
+        let mut refs_generics = ds.generics.clone();
+        refs_generics
+            .params
+            .insert(0, syn::parse_quote!('__roff_refs));
+        let (_, refs_ty_generics, _) = refs_generics.split_for_impl();
+
+        let refs_mut_name = Ident::new(&format!("{}RefsMut", name), name.span());
+
+        let refs_decl = declare_view!(
+            format!("References to all the fields of `{}`.", name),
+            refs_generics,
+            refs_name,
+            |ty| quote!(&'__roff_refs #ty)
+        );
+        let refs_mut_decl = declare_view!(
+            format!("Mutable references to all the fields of `{}`.", name),
+            refs_generics,
+            refs_mut_name,
+            |ty| quote!(&'__roff_refs mut #ty)
+        );
+
+        quote! {
+            #refs_decl
+
+            #refs_mut_decl
+
+            impl #impl_generics #name #ty_generics
+            where
+                #( #extra_bounds, )*
+                #where_preds
+            {
+                /// Gets references to all the fields of this struct.
+                #struct_vis fn as_refs<'__roff_refs>(
+                    &'__roff_refs self,
+                ) -> #refs_name #refs_ty_generics {
+                    #refs_name {
+                        #( #field_names: Self::#offset_names.get(self), )*
+                    }
+                }
+
+                /// Gets mutable references to all the fields of this struct.
+                #struct_vis fn as_refs_mut<'__roff_refs>(
+                    &'__roff_refs mut self,
+                ) -> #refs_mut_name #refs_ty_generics {
+                    // Prefixed to not collide with constants that the struct is declared next to,
+                    // which would make this `let` a refutable pattern.
+                    let __roff_ptr: *mut Self = self;
+                    // safety: every reference is to a different field,
+                    // and the fields are aligned because this is not a packed struct.
+                    unsafe {
+                        #refs_mut_name {
+                            #( #field_names: &mut *Self::#offset_names.raw_get_mut(__roff_ptr), )*
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn fields_proxy_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let struct_ = &ds.variants[0];
+
+    let field_vis = struct_.fields.iter().map(|x| x.vis).collect::<Vec<_>>();
+    let field_tys = struct_.fields.iter().map(|x| x.ty).collect::<Vec<_>>();
+    let method_names = struct_
+        .fields
+        .iter()
+        .map(|field| match &field.ident {
+            FieldIdent::Index(index, ident) => Ident::new(&format!("f{}", index), ident.span()),
+            FieldIdent::Named(ident) => (*ident).clone(),
+        })
+        .collect::<Vec<Ident>>();
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let mut proxy_generics = ds.generics.clone();
+    proxy_generics
+        .params
+        .insert(0, syn::parse_quote!('__roff_fields));
+    let (proxy_impl_generics, proxy_ty_generics, proxy_where) = proxy_generics.split_for_impl();
+
+    let proxy_name = Ident::new(&format!("{}Fields", name), name.span());
+    let proxy_doc = format!(
+        "Accessors for the fields of `{}`, returned by its `fields` method.",
+        name
+    );
+
+    let accessors = if options.is_packed {
+        let method_docs = struct_
+            .fields
+            .iter()
+            .map(|field| format!("Gets a copy of the `{}` field.", field.ident()));
+
+        // The higher-ranked bound allows declaring the method for fields that aren't `Copy`,
+        // instead of erroring because the bound is always false.
+        quote! {
+            #(
+                #[doc = #method_docs]
+                #field_vis fn #method_names(&self) -> #field_tys
+                where
+                    for<'__roff_copy> #field_tys: Copy,
+                {
+                    <#name #ty_generics>::#offset_names.get_copy(self.this)
+                }
+            )*
+        }
+    } else {
+        let method_docs = struct_
+            .fields
+            .iter()
+            .map(|field| format!("Gets a reference to the `{}` field.", field.ident()));
+
+        quote! {
+            #(
+                #[doc = #method_docs]
+                #field_vis fn #method_names(&self) -> &'__roff_fields #field_tys {
+                    <#name #ty_generics>::#offset_names.get(self.this)
+                }
+            )*
+        }
+    };
+
+    quote! {
+        #[doc = #proxy_doc]
+        #struct_vis struct #proxy_name #proxy_impl_generics #proxy_where {
+            this: &'__roff_fields #name #ty_generics,
+        }
+
+        impl #proxy_impl_generics Clone for #proxy_name #proxy_ty_generics
+        #proxy_where
+        {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #proxy_impl_generics Copy for #proxy_name #proxy_ty_generics
+        #proxy_where
+        {}
+
+        impl #proxy_impl_generics #proxy_name #proxy_ty_generics
+        where
+            #( #extra_bounds, )*
+            #where_preds
+        {
+            #accessors
+        }
+
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #where_preds
+        {
+            /// Gets a proxy with a method to access each field of this struct.
+            #struct_vis fn fields<'__roff_fields>(
+                &'__roff_fields self,
+            ) -> #proxy_name #proxy_ty_generics {
+                #proxy_name { this: self }
+            }
+        }
+    }
+}
+
+fn clone_via_fields_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let struct_ = &ds.variants[0];
+
+    let field_tys = struct_.fields.iter().map(|x| x.ty).collect::<Vec<_>>();
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let packed_copy_bounds = packed_copy_bounds(&field_tys, options);
+
+    // Fields of packed structs can't be borrowed,
+    // so they're cloned from a copy of the field.
+    let cloned_fields = field_tys.iter().zip(offset_names).map(|(ty, offset_name)| {
+        if options.is_packed {
+            quote!(<#ty as ::core::clone::Clone>::clone(&Self::#offset_name.get_copy(self)))
+        } else {
+            quote!(<#ty as ::core::clone::Clone>::clone(Self::#offset_name.get(self)))
+        }
+    });
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #( #field_tys: ::core::clone::Clone, )*
+            #packed_copy_bounds
+            #where_preds
+        {
+            /// Clones this struct by cloning each field,
+            /// requiring only that every field implements `Clone`.
+            #struct_vis fn clone_via_fields(&self) -> Self {
+                let mut __roff_out = ::repr_offset::pmr::MaybeUninit::<Self>::uninit();
+                let __roff_ptr: *mut Self = __roff_out.as_mut_ptr();
+                // safety: every field is written before the struct is assumed initialized,
+                // and `read`/`write` do unaligned accesses for fields of packed structs.
+                unsafe {
+                    #( Self::#offset_names.write(__roff_ptr, #cloned_fields); )*
+                    __roff_out.assume_init()
+                }
+            }
+        }
+    }
+}
+
+// The `Copy` bounds for the fields of packed structs,
+// required by the impls that copy the fields out of the struct instead of borrowing them,
+// the same bounds that the built-in derives require for packed structs.
+//
+// Bitwise copies of non-`Copy` fields would allow safe code to observe
+// aliased owned values (eg: two `Box`es pointing to the same allocation).
+fn packed_copy_bounds(field_tys: &[&Type], options: &ReprOffsetConfig<'_>) -> TokenStream2 {
+    if options.is_packed {
+        quote!( #( #field_tys: ::core::marker::Copy, )* )
+    } else {
+        TokenStream2::new()
+    }
+}
+
+fn eq_by_fields_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let struct_ = &ds.variants[0];
+
+    let field_tys = struct_.fields.iter().map(|x| x.ty).collect::<Vec<_>>();
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let compared_fields = compared_fields_tokens(&field_tys, options, offset_names);
+    let packed_copy_bounds = packed_copy_bounds(&field_tys, options);
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #( #field_tys: ::core::cmp::PartialEq, )*
+            #packed_copy_bounds
+            #where_preds
+        {
+            /// Compares this struct with `other` field by field,
+            /// requiring only that every field implements `PartialEq`.
+            ///
+            /// Padding bytes are not compared.
+            #struct_vis fn eq_by_fields(&self, other: &Self) -> bool {
+                true #( && #compared_fields )*
+            }
+        }
+    }
+}
+
+fn write_defaults_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let struct_ = &ds.variants[0];
+
+    let field_tys = struct_.fields.iter().map(|x| x.ty).collect::<Vec<_>>();
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #( #field_tys: ::core::default::Default, )*
+            #where_preds
+        {
+            /// Writes the default value of every field into the struct pointed to by `ptr`,
+            /// without reading or dropping the previous values of the fields.
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must be valid for writes of `size_of::<Self>()` bytes,
+            /// and be aligned to `align_of::<Self>()`.
+            ///
+            /// The previous values of the fields are overwritten without being dropped.
+            #struct_vis unsafe fn write_defaults(__roff_ptr: *mut Self) {
+                #(
+                    Self::#offset_names.write(
+                        __roff_ptr,
+                        <#field_tys as ::core::default::Default>::default(),
+                    );
                 )*
             }
         }
     }
 }
 
+fn try_from_bytes_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_ = &ds.variants[0];
+
+    let field_tys = struct_.fields.iter().map(|x| x.ty).collect::<Vec<_>>();
+    let field_names = struct_.fields.iter().map(|x| &x.ident);
+
+    let impl_generics = GenParamsIn::new(ds.generics, InWhat::ImplHeader);
+    let (_, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    quote! {
+        unsafe impl<#impl_generics> ::repr_offset::utils::ValidBitPattern for #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #( #field_tys: ::repr_offset::utils::ValidBitPattern, )*
+            #where_preds
+        {
+            fn is_valid_bit_pattern(__roff_bytes: &[u8]) -> bool {
+                true #(
+                    && <#field_tys as ::repr_offset::utils::ValidBitPattern>::is_valid_bit_pattern(
+                        &__roff_bytes[Self::#offset_names.offset()..]
+                            [..::core::mem::size_of::<#field_tys>()]
+                    )
+                )*
+            }
+        }
+
+        impl<'__roff_a, #impl_generics> ::core::convert::TryFrom<&'__roff_a [u8]> for #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #( #field_tys: ::repr_offset::utils::ValidBitPattern, )*
+            #where_preds
+        {
+            type Error = ::repr_offset::utils::FromBytesError;
+
+            fn try_from(
+                __roff_bytes: &'__roff_a [u8],
+            ) -> ::core::result::Result<Self, ::repr_offset::utils::FromBytesError> {
+                let __roff_size = ::core::mem::size_of::<Self>();
+                if __roff_bytes.len() < __roff_size {
+                    return ::core::result::Result::Err(
+                        ::repr_offset::utils::FromBytesError::TooShort {
+                            expected: __roff_size,
+                            found: __roff_bytes.len(),
+                        }
+                    );
+                }
+                if !<Self as ::repr_offset::utils::ValidBitPattern>::is_valid_bit_pattern(
+                    &__roff_bytes[..__roff_size]
+                ) {
+                    return ::core::result::Result::Err(
+                        ::repr_offset::utils::FromBytesError::InvalidBitPattern
+                    );
+                }
+
+                let __roff_ptr = __roff_bytes.as_ptr() as *const Self;
+                // safety: `__roff_bytes` is at least as large as `Self`,
+                // and the bytes of every field are a valid value of the field's type.
+                unsafe {
+                    ::core::result::Result::Ok(Self {
+                        #(
+                            #field_names: Self::#offset_names.to_unaligned().read(__roff_ptr),
+                        )*
+                    })
+                }
+            }
+        }
+    }
+}
+
+fn derive_eq_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_ = &ds.variants[0];
+
+    let field_tys = struct_.fields.iter().map(|x| x.ty).collect::<Vec<_>>();
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let compared_fields = compared_fields_tokens(&field_tys, options, offset_names);
+    let packed_copy_bounds = packed_copy_bounds(&field_tys, options);
+
+    let eq_impl = if options.derive_total_eq {
+        quote! {
+            impl #impl_generics ::core::cmp::Eq for #name #ty_generics
+            where
+                #( #extra_bounds, )*
+                #( #field_tys: ::core::cmp::Eq, )*
+                #packed_copy_bounds
+                #where_preds
+            {}
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    quote! {
+        impl #impl_generics ::core::cmp::PartialEq for #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #( #field_tys: ::core::cmp::PartialEq, )*
+            #packed_copy_bounds
+            #where_preds
+        {
+            fn eq(&self, other: &Self) -> bool {
+                true #( && #compared_fields )*
+            }
+        }
+
+        #eq_impl
+    }
+}
+
+fn derive_hash_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_ = &ds.variants[0];
+
+    let field_tys = struct_.fields.iter().map(|x| x.ty).collect::<Vec<_>>();
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let packed_copy_bounds = packed_copy_bounds(&field_tys, options);
+
+    // Fields of packed structs are hashed through copies of them,
+    // the same way that `derive_eq` compares them.
+    let hashed_fields = field_tys.iter().zip(offset_names).map(|(ty, offset_name)| {
+        if options.is_packed {
+            quote! {
+                <#ty as ::core::hash::Hash>::hash(&Self::#offset_name.get_copy(self), __roff_state);
+            }
+        } else {
+            quote! {
+                <#ty as ::core::hash::Hash>::hash(Self::#offset_name.get(self), __roff_state);
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::core::hash::Hash for #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #( #field_tys: ::core::hash::Hash, )*
+            #packed_copy_bounds
+            #where_preds
+        {
+            fn hash<__H>(&self, __roff_state: &mut __H)
+            where
+                __H: ::core::hash::Hasher,
+            {
+                #( #hashed_fields )*
+            }
+        }
+    }
+}
+
+// The expressions that compare each field of `self` and `other` for equality.
+//
+// Fields of packed structs can't be borrowed,
+// so they're compared through copies of them,
+// which requires the `packed_copy_bounds` bounds.
+fn compared_fields_tokens(
+    field_tys: &[&Type],
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> Vec<TokenStream2> {
+    field_tys
+        .iter()
+        .zip(offset_names)
+        .map(|(ty, offset_name)| {
+            if options.is_packed {
+                quote! {
+                    <#ty as ::core::cmp::PartialEq>::eq(
+                        &Self::#offset_name.get_copy(self),
+                        &Self::#offset_name.get_copy(other),
+                    )
+                }
+            } else {
+                quote! {
+                    <#ty as ::core::cmp::PartialEq>::eq(
+                        Self::#offset_name.get(self),
+                        Self::#offset_name.get(other),
+                    )
+                }
+            }
+        })
+        .collect()
+}
+
+fn serialize_via_fields_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let name_str = name.to_string();
+    let struct_ = &ds.variants[0];
+
+    let field_tys = struct_.fields.iter().map(|x| x.ty).collect::<Vec<_>>();
+    let field_count = field_tys.len();
+    let is_tuple = struct_
+        .fields
+        .iter()
+        .any(|x| matches!(FieldIdent::Index { .. } = x.ident));
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let packed_copy_bounds = packed_copy_bounds(&field_tys, options);
+
+    // Fields of packed structs can't be borrowed,
+    // so they're serialized through copies of them.
+    let field_refs = offset_names.iter().map(|offset_name| {
+        if options.is_packed {
+            quote!(&Self::#offset_name.get_copy(self))
+        } else {
+            quote!(Self::#offset_name.get(self))
+        }
+    });
+
+    let (serialize_method, serialize_trait, fields) = if is_tuple {
+        (
+            quote!(serialize_tuple_struct),
+            quote!(SerializeTupleStruct),
+            quote!( #( __roff_state.serialize_field(#field_refs)?; )* ),
+        )
+    } else {
+        let field_name_strs = struct_.fields.iter().map(|field| {
+            let field_name = field.ident().to_string();
+            field_name.trim_start_matches("r#").to_string()
+        });
+        (
+            quote!(serialize_struct),
+            quote!(SerializeStruct),
+            quote!( #( __roff_state.serialize_field(#field_name_strs, #field_refs)?; )* ),
+        )
+    };
+
+    quote! {
+        impl #impl_generics ::repr_offset::pmr::serde::Serialize for #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #( #field_tys: ::repr_offset::pmr::serde::Serialize, )*
+            #packed_copy_bounds
+            #where_preds
+        {
+            fn serialize<__S>(&self, __roff_serializer: __S) -> Result<__S::Ok, __S::Error>
+            where
+                __S: ::repr_offset::pmr::serde::Serializer,
+            {
+                use ::repr_offset::pmr::serde::ser::#serialize_trait;
+
+                let mut __roff_state =
+                    __roff_serializer.#serialize_method(#name_str, #field_count)?;
+                #fields
+                __roff_state.end()
+            }
+        }
+    }
+}
+
+fn into_fields_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let struct_ = &ds.variants[0];
+
+    let field_tys = struct_.fields.iter().map(|x| x.ty);
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #where_preds
+        {
+            /// Moves every field out of this struct, returning them in a tuple,
+            /// in declaration order.
+            ///
+            /// This doesn't run the destructor of this struct,
+            /// only the destructors of the returned fields run when they're dropped.
+            #struct_vis fn into_fields(self) -> ( #( #field_tys, )* ) {
+                let __roff_this = ::repr_offset::pmr::ManuallyDrop::new(self);
+                let __roff_ptr: *const Self = &*__roff_this;
+                // safety: every field is read exactly once,
+                // and the struct is never dropped, so the fields are moved out of it.
+                unsafe {
+                    ( #( Self::#offset_names.read(__roff_ptr), )* )
+                }
+            }
+        }
+    }
+}
+
+fn write_all_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let struct_ = &ds.variants[0];
+
+    let field_tys = struct_.fields.iter().map(|x| x.ty);
+    let indices = (0..struct_.fields.len()).map(syn::Index::from);
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #where_preds
+        {
+            /// Moves every element of `values` into the field of the same position
+            /// (in declaration order) of the struct pointed to by `ptr`,
+            /// without reading or dropping the previous values of the fields.
+            ///
+            /// This is the inverse of `into_fields`,
+            /// and can be used to initialize a `MaybeUninit<Self>`.
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must be valid for writes of `size_of::<Self>()` bytes,
+            /// and be aligned to `align_of::<Self>()`.
+            ///
+            /// The previous values of the fields are overwritten without being dropped.
+            #struct_vis unsafe fn write_all_from_tuple(
+                __roff_ptr: *mut Self,
+                __roff_values: ( #( #field_tys, )* ),
+            ) {
+                #(
+                    Self::#offset_names.write(__roff_ptr, __roff_values.#indices);
+                )*
+            }
+        }
+    }
+}
+
+fn dyn_offsets_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let struct_ = &ds.variants[0];
+
+    let field_name_strs = struct_.fields.iter().map(|field| {
+        let field_name = field.ident.to_string();
+        field_name.trim_start_matches("r#").to_string()
+    });
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #where_preds
+        {
+            /// Gets the type-erased offset of the field named `name`,
+            /// returning `None` if there's no field with that name.
+            ///
+            /// Tuple struct fields are named with their position, eg: `"0"`.
+            #struct_vis fn field_offset_dyn(
+                name: &str,
+            ) -> ::core::option::Option<::repr_offset::FieldOffsetDyn<Self>> {
+                match name {
+                    #(
+                        #field_name_strs => ::core::option::Option::Some(
+                            Self::#offset_names.to_dyn()
+                        ),
+                    )*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    }
+}
+
+fn dyn_set_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let struct_ = &ds.variants[0];
+
+    let field_name_strs = struct_.fields.iter().map(|field| {
+        let field_name = field.ident.to_string();
+        field_name.trim_start_matches("r#").to_string()
+    });
+    let field_tys = struct_.fields.iter().map(|x| x.ty).collect::<Vec<_>>();
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #where_preds
+        {
+            /// Sets the field named `name` to `value`,
+            /// dropping the previous value of the field.
+            ///
+            /// Tuple struct fields are named with their position, eg: `"0"`.
+            ///
+            /// # Errors
+            ///
+            /// This returns a `SetFieldError::UnknownField` if there's no field named `name`,
+            /// and a `SetFieldError::TypeMismatch` containing `value`
+            /// if it isn't of the type of the field.
+            #struct_vis fn set_field_dyn(
+                &mut self,
+                name: &str,
+                value: ::repr_offset::pmr::Box<dyn ::core::any::Any>,
+            ) -> ::core::result::Result<(), ::repr_offset::utils::SetFieldError>
+            where
+                #( #field_tys: 'static, )*
+            {
+                match name {
+                    #(
+                        #field_name_strs => {
+                            let value = ::repr_offset::pmr::Box::<dyn ::core::any::Any>::downcast::<
+                                #field_tys
+                            >(value)
+                            .map_err(::repr_offset::utils::SetFieldError::TypeMismatch)?;
+                            Self::#offset_names.replace_mut(self, *value);
+                            ::core::result::Result::Ok(())
+                        }
+                    )*
+                    _ => ::core::result::Result::Err(
+                        ::repr_offset::utils::SetFieldError::UnknownField
+                    ),
+                }
+            }
+        }
+    }
+}
+
+fn tuple_index_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let struct_ = &ds.variants[0];
+
+    let indices = 0..struct_.fields.len();
+    let field_tys = struct_.fields.iter().map(|x| x.ty);
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #where_preds
+        {
+            /// Gets a reference to the field at the `index` position,
+            /// as a `&dyn Any` that can be downcasted to the type of the field,
+            /// returning `None` if `index` is out of bounds.
+            #struct_vis fn field_ref(&self, index: usize) -> ::core::option::Option<&dyn ::core::any::Any>
+            where
+                #( #field_tys: 'static, )*
+            {
+                match index {
+                    #(
+                        #indices => ::core::option::Option::Some(Self::#offset_names.get(self)),
+                    )*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    }
+}
+
+fn nested_consts_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let consts = options.nested_consts.iter().map(|nested| {
+        let path = &nested.path;
+        let first = field_of_member(ds, &path[0]).expect("checked in `derive`");
+        let first_offset = &offset_names[first.index.pos];
+        let first_ty = first.ty;
+        let vis = first.vis;
+        let rest = &path[1..];
+
+        let const_name = path
+            .iter()
+            .fold(options.offset_prefix.to_string(), |mut acc, x| {
+                if !acc.ends_with('_') && !acc.is_empty() {
+                    acc.push('_');
+                }
+                acc.push_str(&member_name(x).to_uppercase());
+                acc
+            });
+        let const_name = Ident::new(&const_name, nested.lit.span());
+        let doc = format!("The offset of the `{}` nested field.", nested.lit.value());
+
+        quote! {
+            #[doc = #doc]
+            #vis const #const_name: ::repr_offset::FieldOffset<
+                Self,
+                ::repr_offset::get_field_offset::PrivFieldType<
+                    Self,
+                    ::repr_offset::tstr::TS!( #(#path),* ),
+                >,
+                ::repr_offset::get_field_offset::PrivFieldAlignment<
+                    Self,
+                    ::repr_offset::tstr::TS!( #(#path),* ),
+                >,
+            > = Self::#first_offset.add(::repr_offset::OFF!(#first_ty; #(#rest).*));
+        }
+    });
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #where_preds
+        {
+            #( #consts )*
+        }
+    }
+}
+
+// Finds the field of the struct that `member` refers to.
+fn field_of_member<'a>(ds: &'a DataStructure<'a>, member: &Member) -> Option<&'a Field<'a>> {
+    ds.variants[0]
+        .fields
+        .iter()
+        .find(|field| match (&field.ident, member) {
+            (FieldIdent::Index(index, _), Member::Unnamed(x)) => *index == x.index as usize,
+            (FieldIdent::Named(ident), Member::Named(x)) => *ident == x,
+            _ => false,
+        })
+}
+
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string().trim_start_matches("r#").to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+fn padding_consts_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_ = &ds.variants[0];
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let offsets = offset_names
+        .iter()
+        .map(|offset_name| {
+            if options.use_usize_offsets {
+                quote!(Self::#offset_name)
+            } else {
+                quote!(Self::#offset_name.offset())
+            }
+        })
+        .collect::<Vec<TokenStream2>>();
+
+    let consts = struct_.fields.iter().enumerate().map(|(i, field)| {
+        let vis = field.vis;
+        let ty = field.ty;
+        let offset = &offsets[i];
+        let field_name = field.ident.to_string();
+        let const_name = Ident::new(
+            &format!(
+                "PADDING_AFTER_{}",
+                field_name.trim_start_matches("r#").to_uppercase()
+            ),
+            field_ident_span(&field.ident),
+        );
+        // The padding after the last field is the trailing padding of the struct.
+        let next_offset = match offsets.get(i + 1) {
+            Some(next_offset) => next_offset.clone(),
+            None => quote!(::core::mem::size_of::<Self>()),
+        };
+        let doc = format!(
+            "The amount of padding bytes after the `{}` field.",
+            field_name
+        );
+
+        quote! {
+            #[doc = #doc]
+            #vis const #const_name: usize =
+                #next_offset - (#offset + ::core::mem::size_of::<#ty>());
+        }
+    });
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #where_preds
+        {
+            #( #consts )*
+        }
+    }
+}
+
+fn offsets_array_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let field_count = offset_names.len();
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let offsets = offset_names.iter().map(|offset_name| {
+        if options.use_usize_offsets {
+            quote!(Self::#offset_name)
+        } else {
+            quote!(Self::#offset_name.offset())
+        }
+    });
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #where_preds
+        {
+            /// The offsets of all the fields, in declaration order.
+            #struct_vis const OFFSETS: [usize; #field_count] = [ #( #offsets, )* ];
+        }
+    }
+}
+
+fn delegate_fields_tokens(ds: &DataStructure<'_>, options: &ReprOffsetConfig<'_>) -> TokenStream2 {
+    let name = ds.name;
+    let field = &ds.variants[0].fields[0];
+    let field_ty = field.ty;
+
+    let impl_generics =
+        GenParamsIn::with_after_types(ds.generics, InWhat::ImplHeader, quote!(__RoffFN,));
+    let (_, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let inner_gfo = quote!(<#field_ty as ::repr_offset::pmr::GetFieldOffset<__RoffFN>>);
+
+    // Fields of packed structs can be unaligned, regardless of their alignment in `#field_ty`.
+    let alignment = if options.is_packed {
+        quote!(::repr_offset::Unaligned)
+    } else {
+        quote!(#inner_gfo::Alignment)
+    };
+
+    let privacy = if field.is_public() {
+        quote!(#inner_gfo::Privacy)
+    } else {
+        quote!(::repr_offset::privacy::IsPrivate)
+    };
+
+    quote! {
+        // safety: the only field of this struct is at offset 0,
+        // so the fields of `#field_ty` are at the same offsets in this struct.
+        unsafe impl<#impl_generics> ::repr_offset::pmr::GetFieldOffset<__RoffFN>
+        for #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #field_ty: ::repr_offset::pmr::GetFieldOffset<__RoffFN>,
+            #where_preds
+        {
+            type Type = #inner_gfo::Type;
+            type Alignment = #alignment;
+            type Privacy = #privacy;
+
+            const OFFSET_WITH_VIS: ::repr_offset::pmr::FieldOffsetWithVis<
+                Self,
+                Self::Privacy,
+                __RoffFN,
+                Self::Type,
+                Self::Alignment,
+            > = unsafe {
+                ::repr_offset::pmr::FieldOffsetWithVis::new(
+                    #inner_gfo::OFFSET_WITH_VIS.private_field_offset().offset()
+                )
+            };
+        }
+    }
+}
+
+fn offset_ident(options: &ReprOffsetConfig<'_>, field: &Field<'_>) -> Ident {
+    let f_conf = &options.field_map[field.index];
+    let ident = match &f_conf.offset_name {
+        None => concat_field_ident(&options.offset_prefix, &field.ident),
+        Some(OffsetIdent::Prefix(prefix)) => concat_field_ident(prefix, &field.ident),
+        Some(OffsetIdent::Full(full)) => full.clone(),
+    };
+    // The constants are still needed to implement `GetFieldOffset`,
+    // and by the code generated by the other attributes,
+    // so `#[roff(no_offset_consts)]` only hides them.
+    if options.no_offset_consts {
+        Ident::new(&format!("__ROFF_{}", ident), ident.span())
+    } else {
+        ident
+    }
+}
+
+// Converts the name of a field to the name of its `#[roff(field_enum)]` variant,
+// `foo_bar` becomes `FooBar`, and `0` becomes `F0`.
+fn field_variant_ident(field: &Field<'_>) -> Ident {
+    let mut variant = String::new();
+    match &field.ident {
+        FieldIdent::Index(index, _) => {
+            variant.push('F');
+            variant.push_str(&index.to_string());
+        }
+        FieldIdent::Named(ident) => {
+            let ident = ident.to_string();
+            let ident = ident.trim_start_matches("r#");
+            for word in ident.split('_').filter(|w| !w.is_empty()) {
+                let mut chars = word.chars();
+                variant.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+                variant.extend(chars);
+            }
+        }
+    }
+    Ident::new(&variant, field_ident_span(&field.ident))
+}
+
+fn unsafe_offsets_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_ = &ds.variants[0];
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let where_preds = where_clause.map(|x| &x.predicates);
+    let extra_bounds = &options.extra_bounds;
+
+    let alignment = if options.is_packed {
+        quote!(::repr_offset::Unaligned)
+    } else {
+        quote!(::repr_offset::Aligned)
+    };
+
+    let consts = struct_
+        .fields
+        .iter()
+        .zip(offset_names)
+        .filter_map(|(field, offset_name)| {
+            let offset = options.field_map[field.index].unsafe_offset.as_ref()?;
+            let vis = field.vis;
+            let ty = field.ty;
+            let doc = if field.is_public() {
+                format!(
+                    "The offset of the `{}` field, \
+                     forced with the `#[roff(unsafe_offset = ...)]` attribute.",
+                    field.ident()
+                )
+            } else {
+                String::new()
+            };
+
+            let hidden_attr = if options.no_offset_consts {
+                quote!(#[doc(hidden)])
+            } else {
+                quote!()
+            };
+
+            Some(if options.use_usize_offsets {
+                quote! {
+                    #[doc = #doc]
+                    #hidden_attr
+                    #vis const #offset_name: usize = #offset;
+                }
+            } else {
+                quote! {
+                    #[doc = #doc]
+                    #hidden_attr
+                    #vis const #offset_name: ::repr_offset::FieldOffset<Self, #ty, #alignment> =
+                        unsafe { ::repr_offset::FieldOffset::new(#offset) };
+                }
+            })
+        });
+
+    quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #( #extra_bounds, )*
+            #where_preds
+        {
+            #( #consts )*
+        }
+    }
+}
+
+fn c_accessors_tokens(
+    ds: &DataStructure<'_>,
+    options: &ReprOffsetConfig<'_>,
+    prefix: &LitStr,
+    offset_names: &[Ident],
+) -> TokenStream2 {
+    let name = ds.name;
+    let struct_vis = ds.vis;
+    let struct_ = &ds.variants[0];
+
+    let accessors = struct_
+        .fields
+        .iter()
+        .zip(offset_names)
+        .map(|(field, offset_name)| {
+            let ty = field.ty;
+            let field_name = field.ident.to_string();
+            let field_name = field_name.trim_start_matches("r#");
+
+            // Proc macros can't emit warnings on stable Rust,
+            // so this uses a deprecated item to emit one.
+            if !is_ffi_safe_type(ty) {
+                let note = format!(
+                    "The `{}` field of `{}` doesn't have an FFI-safe type, \
+                 so no C accessor was generated for it.",
+                    field_name, name,
+                );
+                return quote::quote_spanned! {ty.span()=>
+                    const _: () = {
+                        #[deprecated(note = #note)]
+                        struct NotFfiSafeField;
+                        let _ = NotFfiSafeField;
+                    };
+                };
+            }
+
+            let fn_name = Ident::new(
+                &format!("{}get_{}", prefix.value(), field_name),
+                prefix.span(),
+            );
+            let doc = format!(
+                "Reads the `{}` field of the `{}` that `this` points to.\n\n\
+             # Safety\n\n\
+             `this` must point to a `{}` that is valid for reads.",
+                field_name, name, name,
+            );
+
+            let no_mangle = if options.c_accessors_no_mangle {
+                quote!(#[no_mangle])
+            } else {
+                TokenStream2::new()
+            };
+
+            quote! {
+                #[doc = #doc]
+                #no_mangle
+                #struct_vis unsafe extern "C" fn #fn_name(this: *const #name) -> #ty {
+                    #name::#offset_name.read_copy(this)
+                }
+            }
+        });
+
+    quote!( #( #accessors )* )
+}
+
+// Whether the type is one of the primitive types that can be passed through FFI,
+// or a raw pointer.
+//
+// Type aliases can't be detected, so fields that use them are skipped.
+fn is_ffi_safe_type(ty: &Type) -> bool {
+    const FFI_SAFE: &[&str] = &[
+        "u8",
+        "i8",
+        "u16",
+        "i16",
+        "u32",
+        "i32",
+        "u64",
+        "i64",
+        "usize",
+        "isize",
+        "f32",
+        "f64",
+        "bool",
+        "c_char",
+        "c_schar",
+        "c_uchar",
+        "c_short",
+        "c_ushort",
+        "c_int",
+        "c_uint",
+        "c_long",
+        "c_ulong",
+        "c_longlong",
+        "c_ulonglong",
+        "c_float",
+        "c_double",
+    ];
+
+    match ty {
+        Type::Ptr { .. } => true,
+        Type::Paren(x) => is_ffi_safe_type(&x.elem),
+        Type::Group(x) => is_ffi_safe_type(&x.elem),
+        Type::Path(x) if x.qself.is_none() => match x.path.segments.last() {
+            Some(seg) if seg.arguments.is_empty() => FFI_SAFE.iter().any(|n| seg.ident == n),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 fn concat_field_ident(prefix: &Ident, field_name: &FieldIdent<'_>) -> Ident {
     Ident::new(
         &format!("{}{}", prefix, field_name.to_string().to_uppercase()),
@@ -11,7 +11,9 @@ use proc_macro2::Span;
 
 use quote::ToTokens;
 
-use syn::{Attribute, Ident, Meta, MetaList, MetaNameValue, WherePredicate};
+use syn::{
+    Attribute, Ident, LitStr, Member, Meta, MetaList, MetaNameValue, NestedMeta, WherePredicate,
+};
 
 use std::marker::PhantomData;
 
@@ -21,6 +23,33 @@ pub(crate) struct ReprOffsetConfig<'a> {
     pub(crate) is_packed: bool,
     pub(crate) use_usize_offsets: bool,
     pub(crate) impl_getfieldoffset: bool,
+    pub(crate) no_offset_consts: bool,
+    pub(crate) field_enum: bool,
+    pub(crate) const_offset_fn: bool,
+    pub(crate) refs_view: bool,
+    pub(crate) fields_proxy: bool,
+    pub(crate) derive_offset_debug: bool,
+    pub(crate) clone_via_fields: bool,
+    pub(crate) read_from_unaligned: bool,
+    pub(crate) write_defaults: bool,
+    pub(crate) try_from_bytes: bool,
+    pub(crate) eq_by_fields: bool,
+    pub(crate) derive_eq: bool,
+    pub(crate) derive_total_eq: bool,
+    pub(crate) derive_hash: bool,
+    pub(crate) layout_guard: bool,
+    pub(crate) into_fields: bool,
+    pub(crate) write_all: bool,
+    pub(crate) dyn_offsets: bool,
+    pub(crate) dyn_set: bool,
+    pub(crate) tuple_index: bool,
+    pub(crate) padding_consts: bool,
+    pub(crate) offsets_array: bool,
+    pub(crate) delegate_fields: bool,
+    pub(crate) serialize_via_fields: bool,
+    pub(crate) c_accessors: Option<LitStr>,
+    pub(crate) c_accessors_no_mangle: bool,
+    pub(crate) nested_consts: Vec<NestedConst>,
     pub(crate) offset_prefix: Ident,
     pub(crate) field_map: FieldMap<FieldConfig>,
     pub(crate) extra_bounds: Vec<WherePredicate>,
@@ -36,6 +65,33 @@ impl<'a> ReprOffsetConfig<'a> {
             is_repr_stable,
             use_usize_offsets,
             impl_getfieldoffset,
+            no_offset_consts,
+            field_enum,
+            const_offset_fn,
+            refs_view,
+            fields_proxy,
+            derive_offset_debug,
+            clone_via_fields,
+            read_from_unaligned,
+            write_defaults,
+            try_from_bytes,
+            eq_by_fields,
+            derive_eq,
+            derive_total_eq,
+            derive_hash,
+            layout_guard,
+            into_fields,
+            write_all,
+            dyn_offsets,
+            dyn_set,
+            tuple_index,
+            padding_consts,
+            offsets_array,
+            delegate_fields,
+            serialize_via_fields,
+            c_accessors,
+            c_accessors_no_mangle,
+            nested_consts,
             offset_prefix,
             field_map,
             extra_bounds,
@@ -50,11 +106,191 @@ impl<'a> ReprOffsetConfig<'a> {
             }
         }
 
+        if no_offset_consts && !impl_getfieldoffset {
+            return_syn_err! {
+                Span::call_site(),
+                "The `no_offset_consts` attribute can't be used with `impl_GetFieldOffset = false`."
+            }
+        }
+
+        if field_enum && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `field_enum` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if const_offset_fn && !field_enum {
+            return_syn_err! {
+                Span::call_site(),
+                "The `const_offset_fn` attribute requires the `field_enum` attribute."
+            }
+        }
+
+        if refs_view && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `refs_view` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if fields_proxy && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `fields_proxy` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if clone_via_fields && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `clone_via_fields` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if read_from_unaligned && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `read_from_unaligned` attribute can't be used with `usize_offsets`."
+            }
+        }
+        if write_defaults && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `write_defaults` attribute can't be used with `usize_offsets`."
+            }
+        }
+        if try_from_bytes && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `try_from_bytes` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if eq_by_fields && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `eq_by_fields` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if derive_eq && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `derive_eq` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if derive_hash && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `derive_hash` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if into_fields && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `into_fields` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if write_all && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `write_all` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if dyn_offsets && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `dyn_offsets` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if dyn_set && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `dyn_set` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if tuple_index && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `tuple_index` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if tuple_index && is_packed {
+            return_syn_err! {
+                Span::call_site(),
+                "The `tuple_index` attribute can't be used with packed structs, \
+                 since their fields can't be borrowed."
+            }
+        }
+
+        if !nested_consts.is_empty() && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `nested_consts` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if serialize_via_fields && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `serialize_via_fields` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if c_accessors.is_some() && use_usize_offsets {
+            return_syn_err! {
+                Span::call_site(),
+                "The `c_accessors` attribute can't be used with `usize_offsets`."
+            }
+        }
+
+        if c_accessors_no_mangle && c_accessors.is_none() {
+            return_syn_err! {
+                Span::call_site(),
+                "The `c_accessors_no_mangle` attribute requires the `c_accessors` attribute."
+            }
+        }
+
         Ok(Self {
             debug_print,
             is_packed,
             use_usize_offsets,
             impl_getfieldoffset,
+            no_offset_consts,
+            field_enum,
+            const_offset_fn,
+            refs_view,
+            fields_proxy,
+            derive_offset_debug,
+            clone_via_fields,
+            read_from_unaligned,
+            write_defaults,
+            try_from_bytes,
+            eq_by_fields,
+            derive_eq,
+            derive_total_eq,
+            derive_hash,
+            layout_guard,
+            into_fields,
+            write_all,
+            dyn_offsets,
+            dyn_set,
+            tuple_index,
+            padding_consts,
+            offsets_array,
+            delegate_fields,
+            serialize_via_fields,
+            c_accessors,
+            c_accessors_no_mangle,
+            nested_consts,
             offset_prefix,
             field_map,
             extra_bounds,
@@ -71,6 +307,34 @@ struct ReprOffsetAttrs<'a> {
     is_repr_stable: bool,
     use_usize_offsets: bool,
     impl_getfieldoffset: bool,
+    no_offset_consts: bool,
+    field_enum: bool,
+    const_offset_fn: bool,
+    refs_view: bool,
+    fields_proxy: bool,
+    derive_offset_debug: bool,
+    clone_via_fields: bool,
+    read_from_unaligned: bool,
+    write_defaults: bool,
+    try_from_bytes: bool,
+    eq_by_fields: bool,
+    derive_eq: bool,
+    // If there was a `#[roff(derive_eq(Eq))]` attribute
+    derive_total_eq: bool,
+    derive_hash: bool,
+    layout_guard: bool,
+    into_fields: bool,
+    write_all: bool,
+    dyn_offsets: bool,
+    dyn_set: bool,
+    tuple_index: bool,
+    padding_consts: bool,
+    offsets_array: bool,
+    delegate_fields: bool,
+    serialize_via_fields: bool,
+    c_accessors: Option<LitStr>,
+    c_accessors_no_mangle: bool,
+    nested_consts: Vec<NestedConst>,
     offset_prefix: Ident,
     field_map: FieldMap<FieldConfig>,
     extra_bounds: Vec<WherePredicate>,
@@ -80,6 +344,15 @@ struct ReprOffsetAttrs<'a> {
 
 pub(crate) struct FieldConfig {
     pub(crate) offset_name: Option<OffsetIdent>,
+    // The offset that the user forced this field to be at,
+    // with the `#[roff(unsafe_offset = ...)]` attribute.
+    pub(crate) unsafe_offset: Option<syn::Expr>,
+}
+
+// A nested field path passed to the `#[roff(nested_consts(...))]` attribute.
+pub(crate) struct NestedConst {
+    pub(crate) lit: LitStr,
+    pub(crate) path: Vec<Member>,
 }
 
 pub(crate) enum OffsetIdent {
@@ -104,8 +377,38 @@ pub(crate) fn parse_attrs_for_derive<'a>(
         is_repr_stable: false,
         use_usize_offsets: false,
         impl_getfieldoffset: true,
+        no_offset_consts: false,
+        field_enum: false,
+        const_offset_fn: false,
+        refs_view: false,
+        fields_proxy: false,
+        derive_offset_debug: false,
+        clone_via_fields: false,
+        read_from_unaligned: false,
+        write_defaults: false,
+        try_from_bytes: false,
+        eq_by_fields: false,
+        derive_eq: false,
+        derive_total_eq: false,
+        derive_hash: false,
+        layout_guard: false,
+        into_fields: false,
+        write_all: false,
+        dyn_offsets: false,
+        dyn_set: false,
+        tuple_index: false,
+        padding_consts: false,
+        offsets_array: false,
+        delegate_fields: false,
+        serialize_via_fields: false,
+        c_accessors: None,
+        c_accessors_no_mangle: false,
+        nested_consts: vec![],
         offset_prefix: Ident::new("OFFSET_", Span::call_site()),
-        field_map: FieldMap::with(ds, |_| FieldConfig { offset_name: None }),
+        field_map: FieldMap::with(ds, |_| FieldConfig {
+            offset_name: None,
+            unsafe_offset: None,
+        }),
         extra_bounds: vec![],
         errors: LinearResult::ok(()),
         _marker: PhantomData,
@@ -187,6 +490,8 @@ fn parse_sabi_attr<'a>(
                 f_config.offset_name = Some(OffsetIdent::Full(parse_lit(&lit)?));
             } else if path.is_ident("offset_prefix") {
                 f_config.offset_name = Some(OffsetIdent::Prefix(parse_lit(&lit)?));
+            } else if path.is_ident("unsafe_offset") {
+                f_config.unsafe_offset = Some(parse_expr(lit)?);
             } else {
                 return Err(make_err(&path));
             }
@@ -196,10 +501,83 @@ fn parse_sabi_attr<'a>(
                 this.debug_print = true;
             } else if path.is_ident("usize_offsets") {
                 this.use_usize_offsets = true;
+            } else if path.is_ident("no_offset_consts") {
+                this.no_offset_consts = true;
+            } else if path.is_ident("field_enum") {
+                this.field_enum = true;
+            } else if path.is_ident("const_offset_fn") {
+                this.const_offset_fn = true;
+            } else if path.is_ident("refs_view") {
+                this.refs_view = true;
+            } else if path.is_ident("fields_proxy") {
+                this.fields_proxy = true;
+            } else if path.is_ident("derive_offset_debug") {
+                this.derive_offset_debug = true;
+            } else if path.is_ident("clone_via_fields") {
+                this.clone_via_fields = true;
+            } else if path.is_ident("read_from_unaligned") {
+                this.read_from_unaligned = true;
+            } else if path.is_ident("write_defaults") {
+                this.write_defaults = true;
+            } else if path.is_ident("try_from_bytes") {
+                this.try_from_bytes = true;
+            } else if path.is_ident("eq_by_fields") {
+                this.eq_by_fields = true;
+            } else if path.is_ident("derive_eq") {
+                this.derive_eq = true;
+            } else if path.is_ident("derive_hash") {
+                this.derive_hash = true;
+            } else if path.is_ident("layout_guard") {
+                this.layout_guard = true;
+            } else if path.is_ident("into_fields") {
+                this.into_fields = true;
+            } else if path.is_ident("write_all") {
+                this.write_all = true;
+            } else if path.is_ident("dyn_offsets") {
+                this.dyn_offsets = true;
+            } else if path.is_ident("dyn_set") {
+                this.dyn_set = true;
+            } else if path.is_ident("tuple_index") {
+                this.tuple_index = true;
+            } else if path.is_ident("c_accessors_no_mangle") {
+                this.c_accessors_no_mangle = true;
+            } else if path.is_ident("padding_consts") {
+                this.padding_consts = true;
+            } else if path.is_ident("offsets_array") {
+                this.offsets_array = true;
+            } else if path.is_ident("delegate_fields") {
+                this.delegate_fields = true;
+            } else if path.is_ident("serialize_via_fields") {
+                this.serialize_via_fields = true;
             } else {
                 return Err(make_err(&path));
             }
         }
+        (ParseContext::TypeAttr { .. }, Meta::List(MetaList { path, nested, .. }))
+            if path.is_ident("derive_eq") =>
+        {
+            this.derive_eq = true;
+            for trait_ in nested {
+                match trait_ {
+                    NestedMeta::Meta(Meta::Path(ref trait_path)) if trait_path.is_ident("Eq") => {
+                        this.derive_total_eq = true;
+                    }
+                    x => return Err(spanned_err!(x, "Expected `Eq`")),
+                }
+            }
+        }
+        (ParseContext::TypeAttr { .. }, Meta::List(MetaList { path, nested, .. }))
+            if path.is_ident("nested_consts") =>
+        {
+            for path_lit in nested {
+                match path_lit {
+                    NestedMeta::Lit(syn::Lit::Str(lit)) => {
+                        this.nested_consts.push(parse_nested_const(lit)?);
+                    }
+                    x => return Err(spanned_err!(x, "Expected a string literal")),
+                }
+            }
+        }
         (ParseContext::TypeAttr { .. }, Meta::NameValue(MetaNameValue { lit, path, .. })) => {
             let ident = path.get_ident().ok_or_else(|| make_err(&path))?;
 
@@ -209,6 +587,8 @@ fn parse_sabi_attr<'a>(
                 this.extra_bounds.push(parse_lit(&lit)?);
             } else if path.is_ident("impl_GetFieldOffset") {
                 this.impl_getfieldoffset = parse_bool(&lit)?;
+            } else if ident == "c_accessors" {
+                this.c_accessors = Some(parse_fn_prefix(&lit)?);
             } else {
                 return Err(make_err(&path));
             }
@@ -233,6 +613,33 @@ where
     }
 }
 
+fn parse_fn_prefix(lit: &syn::Lit) -> Result<LitStr, syn::Error> {
+    match lit {
+        syn::Lit::Str(x) => {
+            syn::parse_str::<Ident>(&format!("{}get", x.value()))
+                .map_err(|_| spanned_err!(x, "Expected a valid prefix for function names"))?;
+            Ok(x.clone())
+        }
+        _ => Err(spanned_err!(
+            lit,
+            "Expected string literal containing a function name prefix"
+        )),
+    }
+}
+
+fn parse_nested_const(lit: LitStr) -> Result<NestedConst, syn::Error> {
+    let value = lit.value();
+    let path = value
+        .split('.')
+        .map(|segment| syn::parse_str::<Member>(segment.trim()))
+        .collect::<Result<Vec<Member>, _>>()
+        .ok()
+        .filter(|path| path.len() >= 2)
+        .ok_or_else(|| spanned_err!(lit, "Expected a nested field path, eg: \"a.b\""))?;
+
+    Ok(NestedConst { lit, path })
+}
+
 fn parse_bool(lit: &syn::Lit) -> Result<bool, syn::Error> {
     match lit {
         syn::Lit::Bool(x) => Ok(x.value),
@@ -240,7 +647,6 @@ fn parse_bool(lit: &syn::Lit) -> Result<bool, syn::Error> {
     }
 }
 
-#[allow(dead_code)]
 fn parse_expr(lit: syn::Lit) -> Result<syn::Expr, syn::Error> {
     match lit {
         syn::Lit::Str(x) => x.parse(),